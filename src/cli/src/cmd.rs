@@ -15,6 +15,9 @@ pub use branch::BranchCmd;
 pub mod checkout;
 pub use checkout::CheckoutCmd;
 
+pub mod clean;
+pub use clean::CleanCmd;
+
 pub mod clone;
 pub use clone::CloneCmd;
 
@@ -63,6 +66,9 @@ pub use migrate::MigrateCmd;
 pub mod moo;
 pub use moo::MooCmd;
 
+pub mod mv;
+pub use mv::MvCmd;
+
 pub mod pull;
 pub use pull::PullCmd;
 
@@ -78,6 +84,9 @@ pub use rm::RmCmd;
 pub mod workspace;
 pub use workspace::WorkspaceCmd;
 
+pub mod worktree;
+pub use worktree::WorktreeCmd;
+
 pub mod restore;
 pub use restore::RestoreCmd;
 
@@ -90,12 +99,18 @@ pub use save::SaveCmd;
 pub mod schemas;
 pub use schemas::SchemasCmd;
 
+pub mod search;
+pub use search::SearchCmd;
+
 pub mod status;
 pub use status::StatusCmd;
 
 pub mod upload;
 pub use upload::UploadCmd;
 
+pub mod watch;
+pub use watch::WatchCmd;
+
 #[async_trait]
 pub trait RunCmd {
     fn name(&self) -> &str;
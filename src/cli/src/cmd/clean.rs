@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use bytesize::ByteSize;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::helpers::check_repo_migration_needed;
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::opts::CleanOpts;
+use std::env;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "clean";
+pub struct CleanCmd;
+
+#[async_trait]
+impl RunCmd for CleanCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Moves untracked files to the trash in .oxen, or purges caches that oxen can regenerate")
+            .arg(
+                Arg::new("dry-run")
+                    .long("dry-run")
+                    .short('n')
+                    .help("Only show what would be removed")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("dirs")
+                    .short('d')
+                    .help("Remove untracked directories as well as untracked files")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("caches")
+                    .long("caches")
+                    .help("Purge cached compares, DuckDB indices, resized images and decompressed versions instead of untracked files")
+                    .conflicts_with("dirs")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("undo")
+                    .long("undo")
+                    .help("Put back the files removed by the last clean")
+                    .exclusive(true)
+                    .action(clap::ArgAction::SetTrue),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let repo_dir = env::current_dir().unwrap();
+        let repository = LocalRepository::from_dir(&repo_dir)?;
+        check_repo_migration_needed(&repository)?;
+
+        if args.get_flag("undo") {
+            let paths = command::clean::undo(&repository)?;
+            for path in paths.iter() {
+                println!("Restored {}", path.display());
+            }
+            return Ok(());
+        }
+
+        let opts = CleanOpts {
+            dry_run: args.get_flag("dry-run"),
+            dirs: args.get_flag("dirs"),
+            caches: args.get_flag("caches"),
+        };
+        let result = command::clean(&repository, &opts)?;
+        if result.paths.is_empty() {
+            println!("Nothing to clean");
+            return Ok(());
+        }
+
+        let verb = if opts.dry_run {
+            "Would remove"
+        } else {
+            "Removing"
+        };
+        for path in result.paths.iter() {
+            println!("{verb} {}", path.display());
+        }
+
+        let size = ByteSize::b(result.bytes);
+        if opts.dry_run {
+            println!("\n{size} would be reclaimed");
+        } else if opts.caches {
+            println!("\nReclaimed {size}");
+        } else if let Some(trash_dir) = result.trash_dir {
+            println!(
+                "\nMoved {size} to {}, run `oxen clean --undo` to put it back",
+                trash_dir.display()
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -37,6 +37,12 @@ impl RunCmd for CloneCmd {
                     .help("This downloads the full commit history, all the data files, and all the commit databases. Useful if you want to have the entire history locally or push to a new remote.")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("link-checkout")
+                    .long("link-checkout")
+                    .help("Reflink or link the files in the working directory to their stored versions instead of copying them, so data is not on disk twice.")
+                    .action(clap::ArgAction::SetTrue),
+            )
             .arg(
                 Arg::new("branch")
                     .long("branch")
@@ -53,13 +59,14 @@ impl RunCmd for CloneCmd {
         let url = args.get_one::<String>("URL").expect("required");
         let shallow = args.get_flag("shallow");
         let all = args.get_flag("all");
+        let link_checkout = args.get_flag("link-checkout");
         let branch = args
             .get_one::<String>("branch")
             .expect("Must supply a branch");
 
         let dst = std::env::current_dir().expect("Could not get current working directory");
         // Get the name of the repo from the url
-        let name = url.split('/').last().unwrap();
+        let name = url.split('/').next_back().unwrap();
         let dst = dst.join(name);
 
         let opts = CloneOpts {
@@ -68,6 +75,7 @@ impl RunCmd for CloneCmd {
             shallow,
             all,
             branch: branch.to_string(),
+            link_checkout,
         };
 
         let host = api::remote::client::get_host_from_url(&opts.url)?;
@@ -1,12 +1,14 @@
 use std::path::PathBuf;
 
 use async_trait::async_trait;
-use clap::{arg, Command};
+use clap::{arg, Arg, Command};
 use liboxen::error::OxenError;
 
 use crate::cmd::RunCmd;
 use crate::helpers::{check_remote_version, get_host_or_default};
 use liboxen::command;
+use liboxen::util;
+use liboxen::util::hasher::HashAlgorithm;
 
 pub const INIT: &str = "init";
 
@@ -23,12 +25,35 @@ impl RunCmd for InitCmd {
         Command::new(INIT)
             .about("Initializes a local repository")
             .arg(arg!([PATH] "The directory to establish the repo in. Defaults to the current directory."))
+            .arg(
+                Arg::new("hash-algorithm")
+                    .long("hash-algorithm")
+                    .help("How to hash file contents, xxh3 or blake3. Defaults to xxh3.")
+                    .value_parser(["xxh3", "blake3"])
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("compress-versions")
+                    .long("compress-versions")
+                    .help("Store versions of files zstd compressed to save space")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("link-checkout")
+                    .long("link-checkout")
+                    .help("Reflink or link checked out files to their stored versions instead of copying them")
+                    .action(clap::ArgAction::SetTrue),
+            )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
         // Parse Args
         let default = String::from(".");
         let path = args.get_one::<String>("PATH").unwrap_or(&default);
+        let algorithm = match args.get_one::<String>("hash-algorithm") {
+            Some(algorithm) => algorithm.parse::<HashAlgorithm>()?,
+            None => HashAlgorithm::default(),
+        };
 
         // Make sure the remote version is compatible
         let host = get_host_or_default()?;
@@ -36,7 +61,14 @@ impl RunCmd for InitCmd {
 
         // Initialize the repository
         let directory = dunce::canonicalize(PathBuf::from(&path))?;
-        command::init(&directory)?;
+        let mut repo = command::init::init_with_hash_algorithm(&directory, algorithm)?;
+        if args.get_flag("compress-versions") {
+            repo.set_compress_versions(true);
+        }
+        if args.get_flag("link-checkout") {
+            repo.set_link_checkout(true);
+        }
+        repo.save(&util::fs::config_filepath(&repo.path))?;
         println!("🐂 repository initialized at: {directory:?}");
         Ok(())
     }
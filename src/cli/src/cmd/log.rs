@@ -3,6 +3,7 @@ use clap::{Arg, ArgMatches, Command};
 use colored::Colorize;
 use minus::Pager;
 use std::fmt::Write;
+use std::path::PathBuf;
 use time::format_description;
 
 use liboxen::command;
@@ -28,12 +29,26 @@ impl RunCmd for LogCmd {
     }
 
     fn args(&self) -> Command {
-        Command::new(NAME).about("See log of commits").arg(
-            Arg::new("revision")
-                .long("revision")
-                .help("The commit or branch id you want to get history from. Defaults to main.")
-                .action(clap::ArgAction::Set),
-        )
+        Command::new(NAME)
+            .about("See log of commits")
+            .arg(
+                Arg::new("revision")
+                    .long("revision")
+                    .help("The commit or branch id you want to get history from. Defaults to main.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("path")
+                    .help("Only show the commits that changed this path.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("follow")
+                    .long("follow")
+                    .help("Continue listing the history of the path across renames.")
+                    .requires("path")
+                    .action(clap::ArgAction::SetTrue),
+            )
     }
 
     async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
@@ -42,8 +57,13 @@ impl RunCmd for LogCmd {
 
         let revision = args.get_one::<String>("revision").map(String::from);
 
+        let path = args.get_one::<String>("path").map(PathBuf::from);
+        let follow = args.get_flag("follow");
+
         let opts = LogOpts {
             revision,
+            path,
+            follow,
             remote: false,
         };
 
@@ -4,8 +4,8 @@ use liboxen::error::OxenError;
 use std::path::Path;
 
 use liboxen::command::migrate::{
-    AddDirectoriesToCacheMigration, CacheDataFrameSizeMigration, CreateMerkleTreesMigration,
-    Migrate, PropagateSchemasMigration, UpdateVersionFilesMigration,
+    AddDirectoriesToCacheMigration, CacheDataFrameSizeMigration, CompressVersionFilesMigration,
+    CreateMerkleTreesMigration, Migrate, PropagateSchemasMigration, UpdateVersionFilesMigration,
 };
 
 use crate::cmd::RunCmd;
@@ -118,6 +118,24 @@ impl RunCmd for MigrateCmd {
                                 .action(clap::ArgAction::SetTrue),
                         ),
                     )
+                    .subcommand(
+                        Command::new(CompressVersionFilesMigration.name())
+                        .about("Compresses version files with zstd and compresses new ones from now on")
+                        .arg(
+                            Arg::new("PATH")
+                                .help("Directory in which to apply the migration")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("all")
+                                .long("all")
+                                .short('a')
+                                .help(
+                                    "Run the migration for all oxen repositories in this directory",
+                                )
+                                .action(clap::ArgAction::SetTrue),
+                        ),
+                    )
             )
             .subcommand(
                 Command::new("down")
@@ -213,6 +231,24 @@ impl RunCmd for MigrateCmd {
                                 .action(clap::ArgAction::SetTrue),
                         ),
                     )
+                    .subcommand(
+                        Command::new(CompressVersionFilesMigration.name())
+                        .about("Decompresses version files and stops compressing new ones")
+                        .arg(
+                            Arg::new("PATH")
+                                .help("Directory in which to apply the migration")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("all")
+                                .long("all")
+                                .short('a')
+                                .help(
+                                    "Run the migration for all oxen repositories in this directory",
+                                )
+                                .action(clap::ArgAction::SetTrue),
+                        ),
+                    )
                 )
     }
 
@@ -243,6 +279,12 @@ impl RunCmd for MigrateCmd {
                                 direction,
                                 args,
                             )?
+                        } else if migration == CompressVersionFilesMigration.name() {
+                            MigrateCmd::run_migration(
+                                &CompressVersionFilesMigration,
+                                direction,
+                                args,
+                            )?
                         } else {
                             return Err(OxenError::basic_str(format!(
                                 "Invalid migration: {}",
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::helpers::check_repo_migration_needed;
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use std::env;
+use std::path::PathBuf;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "mv";
+pub struct MvCmd;
+
+#[async_trait]
+impl RunCmd for MvCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Moves or renames a file or directory and stages the move")
+            .arg(
+                Arg::new("source")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("destination")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let source = args
+            .get_one::<String>("source")
+            .map(PathBuf::from)
+            .expect("Must supply source");
+        let destination = args
+            .get_one::<String>("destination")
+            .map(PathBuf::from)
+            .expect("Must supply destination");
+
+        let repo_dir = env::current_dir().unwrap();
+        let repository = LocalRepository::from_dir(&repo_dir)?;
+        check_repo_migration_needed(&repository)?;
+
+        let destination = command::mv(&repository, &source, &destination)?;
+        println!("{} -> {}", source.display(), destination.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use liboxen::command;
+use liboxen::constants::{DEFAULT_PAGE_NUM, DEFAULT_PAGE_SIZE};
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::opts::SearchOpts;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "search";
+pub struct SearchCmd;
+
+#[async_trait]
+impl RunCmd for SearchCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Search the text and tabular files in a revision")
+            .arg(
+                Arg::new("query")
+                    .required(true)
+                    .help("The words to search for. Every word must appear in a line or cell."),
+            )
+            .arg(
+                Arg::new("revision")
+                    .long("revision")
+                    .help("The commit or branch to search. Defaults to HEAD.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("path")
+                    .long("path")
+                    .short('p')
+                    .help("Only return results in this file or directory.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("remote")
+                    .long("remote")
+                    .help("Search the remote repository instead of the local one.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("page")
+                    .long("page")
+                    .help("Page number of the results. Default page = 1")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("page-size")
+                    .long("page-size")
+                    .help("Number of results per page. Default page-size = 100")
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let query = args.get_one::<String>("query").expect("Must supply query");
+
+        let opts = SearchOpts {
+            query: query.to_owned(),
+            revision: args.get_one::<String>("revision").map(String::from),
+            path: args.get_one::<String>("path").map(PathBuf::from),
+            page_num: args
+                .get_one::<String>("page")
+                .map(|x| x.parse::<usize>().expect("page must be valid int"))
+                .unwrap_or(DEFAULT_PAGE_NUM),
+            page_size: args
+                .get_one::<String>("page-size")
+                .map(|x| x.parse::<usize>().expect("page-size must be valid int"))
+                .unwrap_or(DEFAULT_PAGE_SIZE),
+            remote: args.get_flag("remote"),
+        };
+
+        let repo = LocalRepository::from_current_dir()?;
+        let (results, pagination) = command::search(&repo, &opts).await?;
+
+        for result in results {
+            let location = match (&result.column, result.row) {
+                (Some(column), Some(row)) => {
+                    format!("{}:{}:{}", result.path.display(), row, column)
+                }
+                (None, Some(row)) => format!("{}:{}", result.path.display(), row + 1),
+                _ => format!("{}", result.path.display()),
+            };
+            println!("{} {}", location.green(), result.text);
+        }
+        println!(
+            "\nPage {}/{} ({} results)",
+            pagination.page_number, pagination.total_pages, pagination.total_entries
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "watch";
+pub struct WatchCmd;
+
+#[async_trait]
+impl RunCmd for WatchCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Watch the working directory so `oxen status` and `oxen add` only look at files that changed. Runs until stopped.")
+            .arg(
+                Arg::new("stop")
+                    .long("stop")
+                    .help("Stop the watcher running on this repository")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("status")
+                    .long("status")
+                    .help("Check whether a watcher is running on this repository")
+                    .action(clap::ArgAction::SetTrue),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+
+        if args.get_flag("stop") {
+            if command::watch::stop(&repo)? {
+                println!("Stopped watching {:?}", repo.path);
+            } else {
+                println!("No watcher running on {:?}", repo.path);
+            }
+            return Ok(());
+        }
+
+        if args.get_flag("status") {
+            match command::watch::running(&repo) {
+                Some(state) => println!("Watching {:?} (pid {})", repo.path, state.pid),
+                None => println!("No watcher running on {:?}", repo.path),
+            }
+            return Ok(());
+        }
+
+        println!(
+            "Watching {:?} for changes, stop with `oxen watch --stop`",
+            repo.path
+        );
+        tokio::task::spawn_blocking(move || command::watch::watch(&repo))
+            .await
+            .map_err(|err| OxenError::basic_str(err.to_string()))?
+    }
+}
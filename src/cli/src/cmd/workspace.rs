@@ -19,6 +19,12 @@ pub use delete::WorkspaceDeleteCmd;
 pub mod list;
 pub use list::WorkspaceListCmd;
 
+pub mod refresh;
+pub use refresh::WorkspaceRefreshCmd;
+
+pub mod rename;
+pub use rename::WorkspaceRenameCmd;
+
 pub mod restore;
 pub use restore::WorkspaceRestoreCmd;
 
@@ -89,6 +95,8 @@ impl WorkspaceCmd {
             Box::new(WorkspaceDiffCmd),
             Box::new(WorkspaceDeleteCmd),
             Box::new(WorkspaceListCmd),
+            Box::new(WorkspaceRefreshCmd),
+            Box::new(WorkspaceRenameCmd),
             Box::new(WorkspaceRmCmd),
             Box::new(WorkspaceStatusCmd),
         ];
@@ -26,6 +26,26 @@ impl RunCmd for WorkspaceDfCmd {
                 .about("Index the data frame for querying.")
                 .arg(Arg::new("PATH").help("The path of the data frame file.")),
         )
+        .subcommand(
+            Command::new("upsert")
+                .about("Insert or update the rows of a local file, matched on the key columns.")
+                .arg(Arg::new("PATH").help("The path of the data frame file.").required(true))
+                .arg(Arg::new("FILE").help("The local csv, tsv, jsonl or parquet file with the rows.").required(true))
+                .arg(keys_arg()),
+        )
+        .subcommand(
+            Command::new("delete-rows")
+                .about("Delete the rows that match a local file on the key columns.")
+                .arg(Arg::new("PATH").help("The path of the data frame file.").required(true))
+                .arg(Arg::new("FILE").help("The local csv, tsv, jsonl or parquet file with the rows.").required(true))
+                .arg(keys_arg()),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Download the current state of the data frame, including the _oxen_diff_status of each row.")
+                .arg(Arg::new("PATH").help("The path of the data frame file.").required(true))
+                .arg(Arg::new("OUTPUT").help("Where to write the rows. The format is picked from the extension.").required(true)),
+        )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
@@ -45,6 +65,51 @@ impl RunCmd for WorkspaceDfCmd {
                         Err(e) => return Err(e),
                     }
                 }
+                ("upsert", sub_m) => {
+                    let (path, file, keys) = parse_bulk_args(sub_m)?;
+                    let repository = LocalRepository::from_current_dir()?;
+                    let summary = command::workspace::df::upsert_rows(
+                        &repository,
+                        workspace_id,
+                        path,
+                        file,
+                        &keys,
+                    )
+                    .await?;
+                    println!(
+                        "Added {} rows, updated {} rows",
+                        summary.added, summary.updated
+                    );
+                    return Ok(());
+                }
+                ("delete-rows", sub_m) => {
+                    let (path, file, keys) = parse_bulk_args(sub_m)?;
+                    let repository = LocalRepository::from_current_dir()?;
+                    let summary = command::workspace::df::delete_rows(
+                        &repository,
+                        workspace_id,
+                        path,
+                        file,
+                        &keys,
+                    )
+                    .await?;
+                    println!("Deleted {} rows", summary.deleted);
+                    return Ok(());
+                }
+                ("export", sub_m) => {
+                    let (Some(path), Some(output)) = (
+                        sub_m.get_one::<String>("PATH"),
+                        sub_m.get_one::<String>("OUTPUT"),
+                    ) else {
+                        return Err(OxenError::basic_str(
+                            "Must supply a path and an output file.",
+                        ));
+                    };
+                    let repository = LocalRepository::from_current_dir()?;
+                    command::workspace::df::export(&repository, workspace_id, path, output).await?;
+                    println!("Wrote {output}");
+                    return Ok(());
+                }
                 _ => return Err(OxenError::basic_str("Command not yet implemented.")),
             }
         }
@@ -52,3 +117,29 @@ impl RunCmd for WorkspaceDfCmd {
         return Err(OxenError::basic_str("Command not yet implemented."));
     }
 }
+
+fn keys_arg() -> Arg {
+    Arg::new("keys")
+        .long("keys")
+        .short('k')
+        .required(true)
+        .help("Comma separated list of the columns that identify a row. Ex) --keys file,label")
+}
+
+fn parse_bulk_args(args: &clap::ArgMatches) -> Result<(String, String, Vec<String>), OxenError> {
+    let (Some(path), Some(file), Some(keys)) = (
+        args.get_one::<String>("PATH"),
+        args.get_one::<String>("FILE"),
+        args.get_one::<String>("keys"),
+    ) else {
+        return Err(OxenError::basic_str(
+            "Must supply a path, a file and the --keys to match rows on.",
+        ));
+    };
+    let keys = keys
+        .split(',')
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+        .collect();
+    Ok((path.to_owned(), file.to_owned(), keys))
+}
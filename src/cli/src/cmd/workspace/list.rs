@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgMatches, Command};
 
 use liboxen::api;
 use liboxen::{error::OxenError, model::LocalRepository};
@@ -15,17 +15,36 @@ impl RunCmd for WorkspaceListCmd {
     }
 
     fn args(&self) -> Command {
-        Command::new(NAME).about("Lists all workspaces")
+        Command::new(NAME)
+            .about("Lists all workspaces")
+            .arg(
+                Arg::new("branch")
+                    .long("branch")
+                    .short('b')
+                    .help("Only list workspaces created from this branch"),
+            )
+            .arg(
+                Arg::new("creator")
+                    .long("creator")
+                    .short('c')
+                    .help("Only list workspaces created by this user"),
+            )
     }
 
-    async fn run(&self, _args: &ArgMatches) -> Result<(), OxenError> {
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
         let repository = LocalRepository::from_current_dir()?;
         let remote_repo = api::remote::repositories::get_default_remote(&repository).await?;
-        let workspaces = api::remote::workspaces::list(&remote_repo).await?;
+        let branch = args.get_one::<String>("branch").map(|s| s.as_str());
+        let creator = args.get_one::<String>("creator").map(|s| s.as_str());
+        let workspaces = api::remote::workspaces::list_by(&remote_repo, branch, creator).await?;
         for workspace in workspaces {
             println!(
-                "{}\t{}\t{}",
-                workspace.id, workspace.commit.id, workspace.commit.message
+                "{}\t{}\t{}\t{}\t{}",
+                workspace.id,
+                workspace.name.as_deref().unwrap_or(""),
+                workspace.branch_name.as_deref().unwrap_or(""),
+                workspace.commit.id,
+                workspace.commit.message
             );
         }
         Ok(())
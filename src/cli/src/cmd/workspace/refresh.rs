@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+
+use liboxen::command;
+use liboxen::constants::DEFAULT_BRANCH_NAME;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "refresh";
+pub struct WorkspaceRefreshCmd;
+
+#[async_trait]
+impl RunCmd for WorkspaceRefreshCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Moves a workspace onto the latest commit of a branch, replaying its changes")
+            .arg(
+                Arg::new("workspace_id")
+                    .long("workspace_id")
+                    .short('w')
+                    .required(true)
+                    .help("The workspace_id of the workspace to refresh"),
+            )
+            .arg(
+                Arg::new("branch")
+                    .long("branch")
+                    .short('b')
+                    .default_value(DEFAULT_BRANCH_NAME)
+                    .help("The branch to refresh the workspace onto"),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+
+        let Some(workspace_id) = args.get_one::<String>("workspace_id") else {
+            return Err(OxenError::basic_str("Must supply workspace_id"));
+        };
+
+        let Some(branch_name) = args.get_one::<String>("branch") else {
+            return Err(OxenError::basic_str("Must supply branch"));
+        };
+
+        let summary = command::workspace::refresh(&repo, workspace_id, branch_name).await?;
+
+        println!(
+            "Replayed {} row and {} column changes onto {}",
+            summary.replayed_rows, summary.replayed_columns, branch_name
+        );
+        if !summary.conflicts.is_empty() {
+            println!(
+                "\n{} changes could not be replayed:",
+                summary.conflicts.len()
+            );
+            for conflict in summary.conflicts {
+                let target = conflict.row_id.or(conflict.column).unwrap_or_default();
+                println!(
+                    "  {} {:?} {} - {}",
+                    conflict.operation, conflict.path, target, conflict.reason
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
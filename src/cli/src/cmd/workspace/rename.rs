@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "rename";
+pub struct WorkspaceRenameCmd;
+
+#[async_trait]
+impl RunCmd for WorkspaceRenameCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Renames a workspace")
+            .arg(
+                Arg::new("workspace_id")
+                    .long("workspace_id")
+                    .short('w')
+                    .required(true)
+                    .help("The workspace_id of the workspace to rename"),
+            )
+            .arg(
+                Arg::new("name")
+                    .required(true)
+                    .help("The new name of the workspace"),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+
+        let Some(workspace_id) = args.get_one::<String>("workspace_id") else {
+            return Err(OxenError::basic_str("Must supply workspace_id"));
+        };
+
+        let Some(name) = args.get_one::<String>("name") else {
+            return Err(OxenError::basic_str("Must supply name"));
+        };
+
+        command::workspace::rename(&repo, workspace_id, name).await?;
+
+        Ok(())
+    }
+}
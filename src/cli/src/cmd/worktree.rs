@@ -0,0 +1,74 @@
+pub mod add;
+pub use add::WorktreeAddCmd;
+
+pub mod list;
+pub use list::WorktreeListCmd;
+
+pub mod remove;
+pub use remove::WorktreeRemoveCmd;
+
+use async_trait::async_trait;
+use clap::Command;
+
+use liboxen::error::OxenError;
+use std::collections::HashMap;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "worktree";
+pub struct WorktreeCmd;
+
+#[async_trait]
+impl RunCmd for WorktreeCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        let mut command = Command::new(NAME)
+            .about("Manage extra working directories that share this repository")
+            .subcommand_required(true)
+            .arg_required_else_help(true);
+
+        // These are all the subcommands for the worktree command
+        // including `add`, `list`, and `remove`
+        let sub_commands = self.get_subcommands();
+        for cmd in sub_commands.values() {
+            command = command.subcommand(cmd.args());
+        }
+        command
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let sub_commands = self.get_subcommands();
+        if let Some((name, sub_matches)) = args.subcommand() {
+            let Some(cmd) = sub_commands.get(name) else {
+                eprintln!("Unknown worktree subcommand {name}");
+                return Err(OxenError::basic_str(format!(
+                    "Unknown worktree subcommand {name}"
+                )));
+            };
+
+            // Calling await within an await is making it complain?
+            tokio::task::block_in_place(|| {
+                tokio::runtime::Handle::current().block_on(cmd.run(sub_matches))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl WorktreeCmd {
+    fn get_subcommands(&self) -> HashMap<String, Box<dyn RunCmd>> {
+        let commands: Vec<Box<dyn RunCmd>> = vec![
+            Box::new(WorktreeAddCmd),
+            Box::new(WorktreeListCmd),
+            Box::new(WorktreeRemoveCmd),
+        ];
+        let mut runners: HashMap<String, Box<dyn RunCmd>> = HashMap::new();
+        for cmd in commands {
+            runners.insert(cmd.name().to_string(), cmd);
+        }
+        runners
+    }
+}
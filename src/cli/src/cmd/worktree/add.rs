@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+
+use liboxen::command;
+use liboxen::{error::OxenError, model::LocalRepository};
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "add";
+pub struct WorktreeAddCmd;
+
+#[async_trait]
+impl RunCmd for WorktreeAddCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Creates a worktree at the path with the branch checked out")
+            .arg(
+                Arg::new("path")
+                    .required(true)
+                    .help("Directory to create the worktree in"),
+            )
+            .arg(
+                Arg::new("branch")
+                    .required(true)
+                    .help("Branch to check out in the worktree"),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let repository = LocalRepository::from_current_dir()?;
+        let path = args.get_one::<String>("path").expect("Must supply path");
+        let branch = args
+            .get_one::<String>("branch")
+            .expect("Must supply branch");
+        let worktree = command::worktree::add(&repository, path, branch)?;
+        println!("Created worktree {}", worktree);
+        Ok(())
+    }
+}
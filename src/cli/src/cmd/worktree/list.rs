@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use clap::{ArgMatches, Command};
+
+use liboxen::command;
+use liboxen::{error::OxenError, model::LocalRepository};
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "list";
+pub struct WorktreeListCmd;
+
+#[async_trait]
+impl RunCmd for WorktreeListCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME).about("Lists the worktrees of the repository")
+    }
+
+    async fn run(&self, _args: &ArgMatches) -> Result<(), OxenError> {
+        let repository = LocalRepository::from_current_dir()?;
+        for worktree in command::worktree::list(&repository)? {
+            println!("{}", worktree);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+
+use liboxen::command;
+use liboxen::{error::OxenError, model::LocalRepository};
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "remove";
+pub struct WorktreeRemoveCmd;
+
+#[async_trait]
+impl RunCmd for WorktreeRemoveCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Deletes a worktree and its working directory")
+            .arg(
+                Arg::new("path")
+                    .required(true)
+                    .help("Path of the worktree to remove"),
+            )
+            .arg(
+                Arg::new("force")
+                    .long("force")
+                    .short('f')
+                    .help("Remove the worktree even if it has uncommitted changes")
+                    .action(clap::ArgAction::SetTrue),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let repository = LocalRepository::from_current_dir()?;
+        let path = args.get_one::<String>("path").expect("Must supply path");
+        let force = args.get_flag("force");
+        command::worktree::remove(&repository, path, force)?;
+        println!("Removed worktree {path}");
+        Ok(())
+    }
+}
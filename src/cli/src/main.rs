@@ -15,6 +15,7 @@ async fn main() -> ExitCode {
         Box::new(cmd::AddCmd),
         Box::new(cmd::BranchCmd),
         Box::new(cmd::CheckoutCmd),
+        Box::new(cmd::CleanCmd),
         Box::new(cmd::CloneCmd),
         Box::new(cmd::CommitCacheCmd),
         Box::new(cmd::CommitCmd),
@@ -32,6 +33,7 @@ async fn main() -> ExitCode {
         Box::new(cmd::MergeCmd),
         Box::new(cmd::MigrateCmd),
         Box::new(cmd::MooCmd),
+        Box::new(cmd::MvCmd),
         Box::new(cmd::PullCmd),
         Box::new(cmd::PushCmd),
         Box::new(cmd::RestoreCmd),
@@ -40,9 +42,12 @@ async fn main() -> ExitCode {
         Box::new(cmd::RmCmd),
         Box::new(cmd::SaveCmd),
         Box::new(cmd::SchemasCmd),
+        Box::new(cmd::SearchCmd),
         Box::new(cmd::StatusCmd),
         Box::new(cmd::UploadCmd),
+        Box::new(cmd::WatchCmd),
         Box::new(cmd::WorkspaceCmd),
+        Box::new(cmd::WorktreeCmd),
     ];
 
     let mut command = Command::new("oxen")
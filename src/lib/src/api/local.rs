@@ -8,7 +8,10 @@ pub mod entries;
 pub mod metadata;
 pub mod migrations;
 pub mod namespaces;
+pub mod quotas;
 pub mod repositories;
 pub mod resource;
 pub mod revisions;
 pub mod schemas;
+pub mod search;
+pub mod worktrees;
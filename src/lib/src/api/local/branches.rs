@@ -145,6 +145,9 @@ pub fn delete(repo: &LocalRepository, name: impl AsRef<str>) -> Result<Branch, O
             return Err(OxenError::basic_str(err));
         }
     }
+    let mut lock_file = api::local::worktrees::lock_file(repo)?;
+    let lock = lock_file.write()?;
+    api::local::worktrees::ensure_branch_available(repo, name, &lock)?;
 
     if branch_has_been_merged(repo, name)? {
         let ref_writer = RefWriter::new(repo)?;
@@ -165,6 +168,9 @@ pub fn force_delete(repo: &LocalRepository, name: impl AsRef<str>) -> Result<Bra
             return Err(OxenError::basic_str(err));
         }
     }
+    let mut lock_file = api::local::worktrees::lock_file(repo)?;
+    let lock = lock_file.write()?;
+    api::local::worktrees::ensure_branch_available(repo, name, &lock)?;
 
     let ref_writer = RefWriter::new(repo)?;
     ref_writer.delete_branch(name)
@@ -435,7 +441,7 @@ pub fn list_entry_versions_for_commit(
     let mut branch_commits = commit_reader.history_from_base_to_head(&root_commit.id, commit_id)?;
 
     // Sort on timestamp oldest to newest
-    branch_commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    branch_commits.sort_by_key(|commit| commit.timestamp);
 
     let mut result: Vec<(Commit, CommitEntry)> = Vec::new();
     let mut seen_hashes: HashSet<String> = HashSet::new();
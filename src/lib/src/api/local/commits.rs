@@ -318,10 +318,87 @@ pub async fn list_with_opts(
         } else {
             committer.history_from_head()?
         };
-        Ok(commits)
+        match &opts.path {
+            Some(path) => history_for_path(repo, commits, path, opts.follow),
+            None => Ok(commits),
+        }
     }
 }
 
+/// Filter a commit history down to the commits that changed `path`, the one that
+/// deleted it included. With `follow` the path is traced back across renames, matching
+/// the file by its content hash in the parent commit.
+pub fn history_for_path(
+    repo: &LocalRepository,
+    commits: Vec<Commit>,
+    path: &Path,
+    follow: bool,
+) -> Result<Vec<Commit>, OxenError> {
+    let mut path = path.to_path_buf();
+    let mut history: Vec<Commit> = vec![];
+    for commit in commits {
+        let entry_reader = CommitEntryReader::new(repo, &commit)?;
+        let entry = entry_reader.get_entry(&path)?;
+
+        let parent = match commit.parent_ids.first() {
+            Some(parent_id) => get_by_id(repo, parent_id)?,
+            None => None,
+        };
+        let Some(parent) = parent else {
+            if entry.is_some() {
+                history.push(commit);
+            }
+            continue;
+        };
+
+        let parent_reader = CommitEntryReader::new(repo, &parent)?;
+        match (entry, parent_reader.get_entry(&path)?) {
+            (None, None) => {}
+            (Some(entry), Some(parent_entry)) if parent_entry.hash == entry.hash => {}
+            (Some(entry), None) => {
+                history.push(commit);
+                if follow {
+                    if let Some(renamed_from) =
+                        find_rename_source(&parent_reader, &entry_reader, &entry)?
+                    {
+                        log::debug!(
+                            "history_for_path {:?} renamed from {:?}",
+                            path,
+                            renamed_from.path
+                        );
+                        path = renamed_from.path;
+                    }
+                }
+            }
+            _ => history.push(commit),
+        }
+    }
+    Ok(history)
+}
+
+// The file in the parent commit that `entry` was moved from. Only the directories that
+// changed between the two commits are searched.
+fn find_rename_source(
+    parent_reader: &CommitEntryReader,
+    entry_reader: &CommitEntryReader,
+    entry: &CommitEntry,
+) -> Result<Option<CommitEntry>, OxenError> {
+    for dir in parent_reader.list_dirs()? {
+        let parent_hash = parent_reader.get_dir_hash(&dir)?;
+        if parent_hash.is_some() && parent_hash == entry_reader.get_dir_hash(&dir)? {
+            continue;
+        }
+        let renamed_from = parent_reader
+            .list_dir_entries(&dir)?
+            .into_iter()
+            .find(|e| e.hash == entry.hash && !entry_reader.has_file(&e.path));
+        if renamed_from.is_some() {
+            return Ok(renamed_from);
+        }
+    }
+    Ok(None)
+}
+
 /// List the history for a specific branch or commit (revision)
 pub fn list_from(repo: &LocalRepository, revision: &str) -> Result<Vec<Commit>, OxenError> {
     log::debug!("list_from: {}", revision);
@@ -702,10 +779,14 @@ pub fn merge_objects_dbs(repo_objects_dir: &Path, tmp_objects_dir: &Path) -> Res
 }
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use crate::api;
     use crate::command;
     use crate::error::OxenError;
+    use crate::opts::LogOpts;
     use crate::test;
+    use crate::util;
 
     #[tokio::test]
     async fn test_commit_history_is_complete() -> Result<(), OxenError> {
@@ -781,4 +862,46 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_list_with_opts_follows_renames() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let old_path = Path::new("data.txt");
+            let new_path = Path::new("renamed.txt");
+            util::fs::write_to_path(repo.path.join(old_path), "first")?;
+            command::add(&repo, repo.path.join(old_path))?;
+            command::commit(&repo, "adding data")?;
+
+            util::fs::write_to_path(repo.path.join(old_path), "second")?;
+            command::add(&repo, repo.path.join(old_path))?;
+            command::commit(&repo, "modifying data")?;
+
+            command::mv(&repo, old_path, new_path)?;
+            command::commit(&repo, "renaming data")?;
+
+            let mut opts = LogOpts {
+                revision: None,
+                path: Some(new_path.to_path_buf()),
+                follow: false,
+                remote: false,
+            };
+            let commits = api::local::commits::list_with_opts(&repo, &opts).await?;
+            assert_eq!(commits.len(), 1);
+            assert_eq!(commits[0].message, "renaming data");
+
+            opts.follow = true;
+            let commits = api::local::commits::list_with_opts(&repo, &opts).await?;
+            assert_eq!(commits.len(), 3);
+
+            // The rename deleted the old path
+            opts.path = Some(old_path.to_path_buf());
+            opts.follow = false;
+            let commits = api::local::commits::list_with_opts(&repo, &opts).await?;
+            assert_eq!(commits.len(), 3);
+            assert_eq!(commits[0].message, "renaming data");
+
+            Ok(())
+        })
+        .await
+    }
 }
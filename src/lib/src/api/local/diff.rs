@@ -11,6 +11,7 @@ use rocksdb::{DBWithThreadMode, MultiThreaded};
 
 use crate::core::df::tabular;
 use crate::core::index::object_db_reader::{get_object_reader, ObjectDBReader};
+use crate::core::index::oxenattributes::FileAttributes;
 use crate::error::OxenError;
 use crate::model::diff::diff_entry_status::DiffEntryStatus;
 use crate::model::diff::tabular_diff::{
@@ -74,6 +75,39 @@ pub fn diff_files(
     }
 }
 
+/// Like `diff_files`, but treats the files the way `.oxenattributes` says to. The key
+/// columns from the attributes are used when no keys are passed in.
+pub fn diff_files_with_attributes(
+    file_1: impl AsRef<Path>,
+    file_2: impl AsRef<Path>,
+    attributes: &FileAttributes,
+    keys: Vec<String>,
+    targets: Vec<String>,
+    display: Vec<String>,
+) -> Result<DiffResult, OxenError> {
+    let file_1 = file_1.as_ref();
+    let file_2 = file_2.as_ref();
+    let keys = if keys.is_empty() {
+        attributes.keys.clone()
+    } else {
+        keys
+    };
+
+    if !attributes.is_binary() && attributes.is_tabular(file_1) && attributes.is_tabular(file_2) {
+        let df_1 = tabular::read_df(file_1, attributes.df_opts(file_1))?;
+        let df_2 = tabular::read_df(file_2, attributes.df_opts(file_2))?;
+        tabular_dfs(&df_1, &df_2, keys, targets, display)
+    } else if !attributes.is_binary() && attributes.is_text(file_1) && attributes.is_text(file_2) {
+        let result = utf8_diff::diff(file_1, file_2)?;
+        Ok(DiffResult::Text(result))
+    } else {
+        Err(OxenError::invalid_file_type(format!(
+            "Compare not supported for files, found {:?} and {:?}",
+            file_1, file_2
+        )))
+    }
+}
+
 pub fn tabular(
     file_1: impl AsRef<Path>,
     file_2: impl AsRef<Path>,
@@ -83,13 +117,22 @@ pub fn tabular(
 ) -> Result<DiffResult, OxenError> {
     let df_1 = tabular::read_df(file_1, DFOpts::empty())?;
     let df_2 = tabular::read_df(file_2, DFOpts::empty())?;
+    tabular_dfs(&df_1, &df_2, keys, targets, display)
+}
 
+fn tabular_dfs(
+    df_1: &DataFrame,
+    df_2: &DataFrame,
+    keys: Vec<String>,
+    targets: Vec<String>,
+    display: Vec<String>,
+) -> Result<DiffResult, OxenError> {
     let schema_1 = Schema::from_polars(&df_1.schema());
     let schema_2 = Schema::from_polars(&df_2.schema());
 
     validate_required_fields(schema_1, schema_2, keys.clone(), targets.clone())?;
 
-    diff_dfs(&df_1, &df_2, keys, targets, display)
+    diff_dfs(df_1, df_2, keys, targets, display)
 }
 
 fn validate_required_fields(
@@ -537,6 +580,13 @@ pub fn diff_entries(
         status = DiffEntryStatus::Removed;
     }
 
+    // Same content under a different path is a rename
+    if let (Some(base), Some(head)) = (&base_entry, &head_entry) {
+        if base.path != head.path && base.hash == head.hash {
+            status = DiffEntryStatus::Renamed;
+        }
+    }
+
     let should_do_full_diff = true;
 
     let entry = DiffEntry::from_commit_entry(
@@ -562,6 +612,7 @@ pub fn list_diff_entries_in_dir_top_level(
     head_commit: &Commit,
     page: usize,
     page_size: usize,
+    detect_renames: bool,
 ) -> Result<DiffEntriesCounts, OxenError> {
     log::debug!(
         "list_top_level_diff_entries base_commit: '{}', head_commit: '{}'",
@@ -628,16 +679,24 @@ pub fn list_diff_entries_in_dir_top_level(
     let mut modified_commit_entries: Vec<DiffCommitEntry> = vec![];
     collect_modified_entries(&base_entries, &head_entries, &mut modified_commit_entries)?;
 
+    let renamed_commit_entries = if detect_renames {
+        collect_renamed_entries(&mut added_commit_entries, &mut removed_commit_entries)
+    } else {
+        vec![]
+    };
+
     let counts = AddRemoveModifyCounts {
         added: added_commit_entries.len(),
         removed: removed_commit_entries.len(),
         modified: modified_commit_entries.len(),
+        renamed: renamed_commit_entries.len(),
     };
 
     let mut combined: Vec<_> = added_commit_entries
         .into_iter()
         .chain(removed_commit_entries)
         .chain(modified_commit_entries)
+        .chain(renamed_commit_entries)
         .collect();
 
     // Filter out the entries that are not direct children of the provided dir
@@ -1027,6 +1086,7 @@ pub fn list_diff_entries(
     dir: PathBuf,
     page: usize,
     page_size: usize,
+    detect_renames: bool,
 ) -> Result<DiffEntriesCounts, OxenError> {
     log::debug!(
         "list_diff_entries base_commit: '{}', head_commit: '{}'",
@@ -1125,15 +1185,23 @@ pub fn list_diff_entries(
         "Collected {} collect_modified_entries",
         modified_commit_entries.len()
     );
+    let renamed_commit_entries = if detect_renames {
+        collect_renamed_entries(&mut added_commit_entries, &mut removed_commit_entries)
+    } else {
+        vec![]
+    };
+
     let counts = AddRemoveModifyCounts {
         added: added_commit_entries.len(),
         removed: removed_commit_entries.len(),
         modified: modified_commit_entries.len(),
+        renamed: renamed_commit_entries.len(),
     };
     let mut combined: Vec<_> = added_commit_entries
         .into_iter()
         .chain(removed_commit_entries)
         .chain(modified_commit_entries)
+        .chain(renamed_commit_entries)
         .collect();
     combined.sort_by(|a, b| a.path.cmp(&b.path));
 
@@ -1345,11 +1413,54 @@ fn collect_modified_entries(
     Ok(())
 }
 
+// Pair up removed and added entries with the same content hash, these are renamed
+// rather than removed and added again
+fn collect_renamed_entries(
+    added_entries: &mut Vec<DiffCommitEntry>,
+    removed_entries: &mut Vec<DiffCommitEntry>,
+) -> Vec<DiffCommitEntry> {
+    let mut removed_by_hash: HashMap<String, Vec<CommitEntry>> = HashMap::new();
+    for entry in removed_entries.iter() {
+        if let Some(base_entry) = &entry.base_entry {
+            removed_by_hash
+                .entry(base_entry.hash.to_owned())
+                .or_default()
+                .push(base_entry.to_owned());
+        }
+    }
+
+    let mut renamed_entries: Vec<DiffCommitEntry> = vec![];
+    let mut renamed_from: HashSet<PathBuf> = HashSet::new();
+    added_entries.retain(|entry| {
+        let Some(head_entry) = &entry.head_entry else {
+            return true;
+        };
+        let Some(candidates) = removed_by_hash.get_mut(&head_entry.hash) else {
+            return true;
+        };
+        let Some(base_entry) = candidates.pop() else {
+            return true;
+        };
+        renamed_from.insert(base_entry.path.to_owned());
+        renamed_entries.push(DiffCommitEntry {
+            path: head_entry.path.to_owned(),
+            base_entry: Some(base_entry),
+            head_entry: Some(head_entry.to_owned()),
+            status: DiffEntryStatus::Renamed,
+        });
+        false
+    });
+    removed_entries.retain(|entry| !renamed_from.contains(&entry.path));
+
+    renamed_entries
+}
+
 // TODO: linear scan is not the most efficient way to do this
 pub fn get_add_remove_modify_counts(entries: &[DiffEntry]) -> AddRemoveModifyCounts {
     let mut added = 0;
     let mut removed = 0;
     let mut modified = 0;
+    let mut renamed = 0;
     for entry in entries {
         if entry.is_dir {
             continue;
@@ -1359,12 +1470,14 @@ pub fn get_add_remove_modify_counts(entries: &[DiffEntry]) -> AddRemoveModifyCou
             DiffEntryStatus::Added => added += 1,
             DiffEntryStatus::Removed => removed += 1,
             DiffEntryStatus::Modified => modified += 1,
+            DiffEntryStatus::Renamed => renamed += 1,
         }
     }
     AddRemoveModifyCounts {
         added,
         removed,
         modified,
+        renamed,
     }
 }
 
@@ -1377,7 +1490,9 @@ fn subset_dir_diffs_to_direct_children(
     for entry in entries {
         let status = DiffEntryStatus::from_str(&entry.status)?;
         let relevant_entry = match status {
-            DiffEntryStatus::Added | DiffEntryStatus::Modified => entry.head_entry.as_ref(),
+            DiffEntryStatus::Added | DiffEntryStatus::Modified | DiffEntryStatus::Renamed => {
+                entry.head_entry.as_ref()
+            }
             DiffEntryStatus::Removed => entry.base_entry.as_ref(),
         };
 
@@ -1402,7 +1517,9 @@ fn subset_file_diffs_to_direct_children(
 
     for entry in entries {
         let relevant_entry = match entry.status {
-            DiffEntryStatus::Added | DiffEntryStatus::Modified => entry.head_entry.as_ref(),
+            DiffEntryStatus::Added | DiffEntryStatus::Modified | DiffEntryStatus::Renamed => {
+                entry.head_entry.as_ref()
+            }
             DiffEntryStatus::Removed => entry.base_entry.as_ref(),
         };
 
@@ -1534,6 +1651,7 @@ mod tests {
                 PathBuf::from(""),
                 0,
                 10,
+                true,
             )?;
             let entries = entries.entries;
             assert_eq!(2, entries.len());
@@ -1544,6 +1662,52 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_diff_entries_renamed_file() -> Result<(), OxenError> {
+        test::run_bounding_box_csv_repo_test_fully_committed(|repo| {
+            let readme = PathBuf::from("README.md");
+            test::write_txt_file_to_path(repo.path.join(&readme), "Read me")?;
+            command::add(&repo, repo.path.join(&readme))?;
+            let base_commit = command::commit(&repo, "Adding a readme")?;
+
+            let moved = PathBuf::from("docs").join("README.md");
+            util::fs::create_dir_all(repo.path.join("docs"))?;
+            command::mv(&repo, &readme, &moved)?;
+            let head_commit = command::commit(&repo, "Moving the readme")?;
+
+            let entries = api::local::diff::list_diff_entries(
+                &repo,
+                &base_commit,
+                &head_commit,
+                PathBuf::from(""),
+                0,
+                10,
+                true,
+            )?;
+            assert_eq!(entries.counts.renamed, 1);
+            assert_eq!(entries.counts.added, 0);
+            assert_eq!(entries.counts.removed, 0);
+            let renamed = entries.entries.iter().find(|entry| !entry.is_dir).unwrap();
+            assert_eq!(DiffEntryStatus::Renamed.to_string(), renamed.status);
+
+            // Without rename detection it is removed and added again
+            let entries = api::local::diff::list_diff_entries(
+                &repo,
+                &base_commit,
+                &head_commit,
+                PathBuf::from(""),
+                0,
+                10,
+                false,
+            )?;
+            assert_eq!(entries.counts.renamed, 0);
+            assert_eq!(entries.counts.added, 1);
+            assert_eq!(entries.counts.removed, 1);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_diff_entries_modify_one_tabular() -> Result<(), OxenError> {
         test::run_bounding_box_csv_repo_test_fully_committed(|repo| {
@@ -1576,6 +1740,7 @@ train/cat_2.jpg,cat,30.5,44.0,333,396
                 PathBuf::from(""),
                 0,
                 10,
+                true,
             )?;
             let entries = entries.entries;
             // Recursively marks parent dirs as modified
@@ -1612,6 +1777,7 @@ train/cat_2.jpg,cat,30.5,44.0,333,396
                 PathBuf::from(""),
                 0,
                 10,
+                true,
             )?;
 
             let entries = entries.entries;
@@ -1667,6 +1833,7 @@ train/cat_2.jpg,cat,30.5,44.0,333,396
                 PathBuf::from(""),
                 0,
                 10,
+                true,
             )?;
             let entries = entries.entries;
             for entry in entries.iter().enumerate() {
@@ -1734,6 +1901,7 @@ train/cat_2.jpg,cat,30.5,44.0,333,396
                 &head_commit,
                 0,
                 10,
+                true,
             )?;
 
             let entries = entries.entries;
@@ -1745,6 +1913,7 @@ train/cat_2.jpg,cat,30.5,44.0,333,396
                 &head_commit,
                 0,
                 10,
+                true,
             )?;
 
             // We should have...
@@ -1806,6 +1975,7 @@ train/cat_2.jpg,cat,30.5,44.0,333,396
                 &head_commit,
                 0,
                 10,
+                true,
             )?;
 
             let entries = entries.entries;
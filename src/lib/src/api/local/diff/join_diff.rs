@@ -397,6 +397,7 @@ fn calculate_compare_mods(joined_df: &DataFrame) -> Result<AddRemoveModifyCounts
         added: added_rows,
         removed: removed_rows,
         modified: modified_rows,
+        renamed: 0,
     })
 }
 
@@ -36,7 +36,7 @@ pub fn get_meta_entry(
         CommitEntryReader::new_from_commit_id(repo, &commit.id, object_reader.clone())?;
     let commit_reader = CommitReader::new(repo)?;
     let mut commits = commit_reader.history_from_commit_id(&commit.id)?;
-    commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    commits.sort_by_key(|commit| commit.timestamp);
 
     // Check if the path is a dir or is the root
     if entry_reader.has_dir(path) || path == Path::new("") {
@@ -117,7 +117,7 @@ pub fn meta_entry_from_dir(
     let dir_metadata = api::local::entries::get_dir_entry_metadata(repo, commit, path)?;
 
     let base_name = path.file_name().unwrap_or(std::ffi::OsStr::new(""));
-    return Ok(MetadataEntry {
+    Ok(MetadataEntry {
         filename: String::from(base_name.to_string_lossy()),
         is_dir: true,
         size: total_size,
@@ -134,7 +134,7 @@ pub fn meta_entry_from_dir(
         }),
         metadata: Some(GenericMetadata::MetadataDir(dir_metadata)),
         is_queryable: None,
-    });
+    })
 }
 
 fn compute_latest_commit_for_dir(
@@ -230,7 +230,7 @@ pub fn get_commit_history_path(
         }
     }
 
-    history.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    history.sort_by_key(|commit| std::cmp::Reverse(commit.timestamp));
 
     Ok(history)
 }
@@ -310,7 +310,7 @@ pub fn meta_entry_from_commit_entry(
         None
     };
 
-    return Ok(MetadataEntry {
+    Ok(MetadataEntry {
         filename: String::from(base_name.to_string_lossy()),
         is_dir: false,
         size,
@@ -328,7 +328,7 @@ pub fn meta_entry_from_commit_entry(
         // Not applicable for files YET, but we will also compute this metadata
         metadata: None,
         is_queryable: is_indexed,
-    });
+    })
 }
 
 /// Commit entries are always files, not directories. Will return None if the path is a directory.
@@ -386,7 +386,7 @@ pub fn list_directory(
     let mut commits = commit_reader.history_from_commit_id(&commit.id)?;
 
     // Sort on timestamp oldest to newest
-    commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    commits.sort_by_key(|commit| commit.timestamp);
 
     let mut commit_entry_readers: Vec<(Commit, CommitDirEntryReader)> = Vec::new();
     for c in commits {
@@ -685,9 +685,7 @@ pub fn list_tabular_files_in_repo(
     let commits = commit_reader.list_all()?;
 
     for (path, _schema) in schemas.iter() {
-        let entry = entry_reader.get_entry(path)?;
-
-        if entry.is_some() {
+        if let Some(entry) = entry_reader.get_entry(path)? {
             let parent = path.parent().ok_or(OxenError::file_has_no_parent(path))?;
             let mut commit_entry_readers: Vec<(Commit, CommitDirEntryReader)> = Vec::new();
             for commit in &commits {
@@ -703,7 +701,7 @@ pub fn list_tabular_files_in_repo(
 
             let metadata = meta_entry_from_commit_entry(
                 local_repo,
-                &entry.unwrap(),
+                &entry,
                 &commit_entry_readers,
                 &commit.id,
             )?;
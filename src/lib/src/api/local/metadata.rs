@@ -74,6 +74,7 @@ pub fn from_commit_entry(
     commit: &Commit,
 ) -> Result<MetadataEntry, OxenError> {
     let path = util::fs::version_path(repo, entry);
+    let path = util::fs::readable_version_path(repo, &path)?;
     let base_name = entry
         .path
         .file_name()
@@ -110,7 +111,7 @@ pub fn get_cli(
         .file_name()
         .ok_or(OxenError::file_has_no_name(path))?;
     let size = get_file_size(path)?;
-    let hash = util::hasher::hash_file_contents(path)?;
+    let hash = util::hasher::hash_file_contents_with_algorithm(path, repo.hash_algorithm())?;
     let mime_type = util::fs::file_mime_type(path);
     let data_type = util::fs::datatype_from_mimetype(path, mime_type.as_str());
     let extension = util::fs::file_extension(path);
@@ -121,7 +122,7 @@ pub fn get_cli(
     let mut last_updated: Option<Commit> = None;
     // Sort commits by timestamp
     let mut commits = commit_reader.list_all()?;
-    commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    commits.sort_by_key(|commit| commit.timestamp);
 
     // Now that we know the commits are sorted, we can iterate through them and find when the file was last updated
     for commit in commits {
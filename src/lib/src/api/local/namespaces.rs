@@ -53,7 +53,8 @@ pub fn get(data_dir: &Path, name: &str) -> Result<Option<Namespace>, OxenError>
     Ok(Some(namespace))
 }
 
-fn get_storage_for_repo(repo: &LocalRepository) -> Result<u64, OxenError> {
+/// Size of the repo on disk as of its latest commit, as computed by the repo size cacher
+pub fn get_storage_for_repo(repo: &LocalRepository) -> Result<u64, OxenError> {
     let latest_commit = match api::local::commits::latest_commit(repo) {
         Ok(commit) => commit,
         Err(e) => {
@@ -0,0 +1,348 @@
+//! # Quotas - Storage limits for the namespaces and repositories on a server
+//!
+//! Quotas are read from `quotas.toml` at the root of the sync dir, anything without an entry
+//! is unlimited.
+//!
+//! ```toml
+//! [namespaces.ox]
+//! max_bytes = 10737418240
+//!
+//! [repos."ox/data"]
+//! max_bytes = 1073741824
+//! max_files = 100000
+//! ```
+//!
+//! Usage is cached per repository in `.oxen/.cache/usage.json`. Uploads the quota lets in are
+//! added to it as they are accepted, and the version files are walked again when it is older
+//! than `USAGE_MAX_AGE`, to catch up with what was deduplicated or removed.
+//!
+
+use jwalk::WalkDir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::api;
+use crate::constants::{
+    QUOTAS_FILENAME, QUOTAS_LOCK_FILENAME, TMP_DIR, USAGE_FILENAME, VERSIONS_DIR,
+};
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util;
+use crate::view::StorageUsage;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Quota {
+    pub max_bytes: Option<u64>,
+    pub max_files: Option<u64>,
+}
+
+impl Quota {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_bytes.is_none() && self.max_files.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct QuotasConfig {
+    /// Keyed by namespace
+    #[serde(default)]
+    pub namespaces: HashMap<String, Quota>,
+    /// Keyed by "namespace/repo_name"
+    #[serde(default)]
+    pub repos: HashMap<String, Quota>,
+}
+
+impl QuotasConfig {
+    pub fn from_sync_dir(sync_dir: &Path) -> Result<QuotasConfig, OxenError> {
+        let path = sync_dir.join(QUOTAS_FILENAME);
+        if !path.exists() {
+            return Ok(QuotasConfig::default());
+        }
+        let contents = util::fs::read_from_path(&path)?;
+        toml::from_str(&contents).map_err(|err| {
+            OxenError::basic_str(format!("Could not parse quotas file {path:?}: {err}"))
+        })
+    }
+
+    pub fn save(&self, sync_dir: &Path) -> Result<(), OxenError> {
+        let toml = toml::to_string(&self)?;
+        util::fs::write_to_path(sync_dir.join(QUOTAS_FILENAME), toml)?;
+        Ok(())
+    }
+
+    pub fn for_namespace(&self, namespace: &str) -> Quota {
+        self.namespaces.get(namespace).cloned().unwrap_or_default()
+    }
+
+    pub fn for_repo(&self, namespace: &str, repo_name: &str) -> Quota {
+        self.repos
+            .get(&format!("{namespace}/{repo_name}"))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// How long the cached usage of a repository is trusted before its version files are walked again
+const USAGE_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+// Serializes updates of the usage caches
+static USAGE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CachedUsage {
+    bytes: u64,
+    files: u64,
+    /// Seconds since the epoch when the version files were last walked
+    walked_at: u64,
+}
+
+impl CachedUsage {
+    fn is_fresh(&self) -> bool {
+        now_secs().saturating_sub(self.walked_at) < USAGE_MAX_AGE.as_secs()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn usage_path(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join(TMP_DIR)
+        .join(USAGE_FILENAME)
+}
+
+fn read_cached_usage(repo: &LocalRepository) -> Option<CachedUsage> {
+    let contents = std::fs::read_to_string(usage_path(repo)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_cached_usage(repo: &LocalRepository, usage: &CachedUsage) -> Result<(), OxenError> {
+    let path = usage_path(repo);
+    if let Some(parent) = path.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    util::fs::write_to_path(&path, serde_json::to_string(usage)?)?;
+    Ok(())
+}
+
+/// Bytes and number of version files stored for a repository, from the cache unless it is stale
+pub fn repo_usage(repo: &LocalRepository) -> Result<StorageUsage, OxenError> {
+    let _lock = USAGE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let cached = match read_cached_usage(repo) {
+        Some(cached) if cached.is_fresh() => cached,
+        _ => {
+            let walked = walk_repo_usage(repo)?;
+            let cached = CachedUsage {
+                bytes: walked.bytes,
+                files: walked.files,
+                walked_at: now_secs(),
+            };
+            write_cached_usage(repo, &cached)?;
+            cached
+        }
+    };
+    Ok(StorageUsage {
+        bytes: cached.bytes,
+        files: cached.files,
+        ..StorageUsage::default()
+    })
+}
+
+/// Adds an upload to the cached usage of a repository, until its version files are walked again
+pub fn add_repo_usage(repo: &LocalRepository, bytes: u64, files: u64) -> Result<(), OxenError> {
+    let _lock = USAGE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    // Without a cache the next read walks the version files, which already counts it
+    let Some(mut cached) = read_cached_usage(repo) else {
+        return Ok(());
+    };
+    cached.bytes = cached.bytes.saturating_add(bytes);
+    cached.files = cached.files.saturating_add(files);
+    write_cached_usage(repo, &cached)
+}
+
+fn walk_repo_usage(repo: &LocalRepository) -> Result<StorageUsage, OxenError> {
+    let versions_dir = util::fs::oxen_hidden_dir(&repo.path).join(VERSIONS_DIR);
+    let mut usage = StorageUsage::default();
+    if !versions_dir.exists() {
+        return Ok(usage);
+    }
+
+    for entry in WalkDir::new(&versions_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if entry.file_type().is_file() {
+            usage.files += 1;
+            usage.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+    Ok(usage)
+}
+
+/// Storage used by all the repositories in a namespace
+pub fn namespace_usage(sync_dir: &Path, namespace: &str) -> Result<StorageUsage, OxenError> {
+    let repos = api::local::repositories::list_repos_in_namespace(&sync_dir.join(namespace));
+    let mut usage = StorageUsage::default();
+    for repo in repos {
+        let repo_usage = repo_usage(&repo)?;
+        usage.bytes += repo_usage.bytes;
+        usage.files += repo_usage.files;
+    }
+    Ok(usage)
+}
+
+/// Usage of a namespace, and of one of its repositories if `repo_name` is given, with their quotas
+pub fn usage(
+    sync_dir: &Path,
+    namespace: &str,
+    repo_name: Option<&str>,
+) -> Result<(StorageUsage, Option<StorageUsage>), OxenError> {
+    let config = QuotasConfig::from_sync_dir(sync_dir)?;
+
+    let quota = config.for_namespace(namespace);
+    let namespace_usage = StorageUsage {
+        max_bytes: quota.max_bytes,
+        max_files: quota.max_files,
+        ..namespace_usage(sync_dir, namespace)?
+    };
+
+    let repo_usage = match repo_name {
+        Some(repo_name) => {
+            let repo = LocalRepository::from_dir(&sync_dir.join(namespace).join(repo_name))?;
+            let quota = config.for_repo(namespace, repo_name);
+            Some(StorageUsage {
+                max_bytes: quota.max_bytes,
+                max_files: quota.max_files,
+                ..repo_usage(&repo)?
+            })
+        }
+        None => None,
+    };
+
+    Ok((namespace_usage, repo_usage))
+}
+
+/// Errors with `OxenError::QuotaExceeded` if storing `bytes` more in `files` more files would put
+/// the repository or its namespace over quota
+pub fn check(
+    sync_dir: &Path,
+    namespace: &str,
+    repo_name: &str,
+    bytes: u64,
+    files: u64,
+) -> Result<(), OxenError> {
+    let config = QuotasConfig::from_sync_dir(sync_dir)?;
+    // Skip walking the versions dirs when there is nothing to enforce
+    if config.for_namespace(namespace).is_unlimited()
+        && config.for_repo(namespace, repo_name).is_unlimited()
+    {
+        return Ok(());
+    }
+
+    let (namespace_usage, repo_usage) = usage(sync_dir, namespace, Some(repo_name))?;
+    if let Some(repo_usage) = repo_usage {
+        repo_usage.check(&format!("repository {namespace}/{repo_name}"), bytes, files)?;
+    }
+    namespace_usage.check(&format!("namespace {namespace}"), bytes, files)
+}
+
+/// Checks an upload of `bytes` in `files` files against the quotas like `check`, and counts it
+/// in the usage of the repository once it is let in. The check and the count happen under a file
+/// lock in the sync dir, so uploads to other workers or processes of the server can't both slip
+/// under the same quota.
+pub fn reserve(
+    sync_dir: &Path,
+    namespace: &str,
+    repo_name: &str,
+    bytes: u64,
+    files: u64,
+) -> Result<(), OxenError> {
+    let lock_file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(sync_dir.join(QUOTAS_LOCK_FILENAME))?;
+    let mut lock_file = fd_lock::RwLock::new(lock_file);
+    let _lock = lock_file.write()?;
+
+    check(sync_dir, namespace, repo_name, bytes, files)?;
+    let repo = LocalRepository::from_dir(&sync_dir.join(namespace).join(repo_name))?;
+    add_repo_usage(&repo, bytes, files)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api;
+    use crate::api::local::quotas::{Quota, QuotasConfig};
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::test;
+
+    #[test]
+    fn test_quota_check_repo_and_namespace() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|sync_dir| {
+            let namespace = "ox";
+            let repo_dir = sync_dir.join(namespace).join("data");
+            let repo = command::init(&repo_dir)?;
+            test::write_txt_file_to_path(repo.path.join("hello.txt"), "hello world")?;
+            command::add(&repo, repo.path.join("hello.txt"))?;
+            command::commit(&repo, "adding hello")?;
+
+            // Nothing configured, anything goes
+            api::local::quotas::check(sync_dir, namespace, "data", u64::MAX, u64::MAX)?;
+
+            let usage = api::local::quotas::repo_usage(&repo)?;
+            assert!(usage.files >= 1);
+            assert!(usage.bytes > 0);
+
+            let mut config = QuotasConfig::default();
+            config.repos.insert(
+                "ox/data".to_string(),
+                Quota {
+                    max_bytes: None,
+                    max_files: Some(usage.files + 1),
+                },
+            );
+            config.save(sync_dir)?;
+
+            api::local::quotas::check(sync_dir, namespace, "data", 100, 1)?;
+            let result = api::local::quotas::check(sync_dir, namespace, "data", 100, 2);
+            assert!(matches!(result, Err(OxenError::QuotaExceeded(_))));
+
+            config.namespaces.insert(
+                namespace.to_string(),
+                Quota {
+                    max_bytes: Some(usage.bytes + 10),
+                    max_files: None,
+                },
+            );
+            config.save(sync_dir)?;
+            let result = api::local::quotas::check(sync_dir, namespace, "data", 11, 1);
+            assert!(matches!(result, Err(OxenError::QuotaExceeded(_))));
+
+            let (namespace_usage, repo_usage) =
+                api::local::quotas::usage(sync_dir, namespace, Some("data"))?;
+            assert_eq!(namespace_usage.max_bytes, Some(usage.bytes + 10));
+            assert_eq!(repo_usage.unwrap().max_files, Some(usage.files + 1));
+
+            // Accepted uploads count against the quota before the versions are walked again
+            api::local::quotas::reserve(sync_dir, namespace, "data", 5, 0)?;
+            let result = api::local::quotas::check(sync_dir, namespace, "data", 6, 0);
+            assert!(matches!(result, Err(OxenError::QuotaExceeded(_))));
+            assert_eq!(
+                api::local::quotas::repo_usage(&repo)?.bytes,
+                usage.bytes + 5
+            );
+
+            Ok(())
+        })
+    }
+}
@@ -2,6 +2,7 @@ use crate::api;
 use crate::command;
 use crate::constants;
 use crate::core::cache::commit_cacher;
+use crate::core::encryption;
 use crate::core::index::CommitEntryWriter;
 use crate::core::index::Stager;
 use crate::core::index::{CommitEntryReader, CommitWriter, RefWriter};
@@ -162,9 +163,19 @@ pub fn transfer_namespace(
         from_namespace,
         to_namespace
     );
+    rename(sync_dir, from_namespace, repo_name, to_namespace, repo_name)
+}
 
-    let repo_dir = sync_dir.join(from_namespace).join(repo_name);
-    let new_repo_dir = sync_dir.join(to_namespace).join(repo_name);
+/// Move a repository to a new namespace and/or name within the sync dir
+pub fn rename(
+    sync_dir: &Path,
+    namespace: &str,
+    name: &str,
+    new_namespace: &str,
+    new_name: &str,
+) -> Result<LocalRepository, OxenError> {
+    let repo_dir = sync_dir.join(namespace).join(name);
+    let new_repo_dir = sync_dir.join(new_namespace).join(new_name);
 
     if !repo_dir.exists() {
         log::debug!(
@@ -172,13 +183,18 @@ pub fn transfer_namespace(
             repo_dir
         );
         return Err(OxenError::repo_not_found(RepoNew::from_namespace_name(
-            from_namespace,
-            repo_name,
+            namespace, name,
         )));
     }
+    if new_repo_dir.exists() && util::fs::config_filepath(&new_repo_dir).exists() {
+        return Err(OxenError::repo_already_exists(
+            RepoNew::from_namespace_name(new_namespace, new_name),
+        ));
+    }
 
     util::fs::create_dir_all(&new_repo_dir)?;
     util::fs::rename(&repo_dir, &new_repo_dir)?;
+    encryption::forget(&repo_dir);
 
     // Update path in config
     let config_path = util::fs::config_filepath(&new_repo_dir);
@@ -186,7 +202,7 @@ pub fn transfer_namespace(
     repo.path = new_repo_dir;
     repo.save(&config_path)?;
 
-    let updated_repo = get_by_namespace_and_name(sync_dir, to_namespace, repo_name)?;
+    let updated_repo = get_by_namespace_and_name(sync_dir, new_namespace, new_name)?;
 
     match updated_repo {
         Some(new_repo) => Ok(new_repo),
@@ -219,9 +235,17 @@ pub fn create(root_dir: &Path, new_repo: RepoNew) -> Result<LocalRepository, Oxe
 
     // Create config file
     let config_path = util::fs::config_filepath(&repo_dir);
-    let local_repo = LocalRepository::new(&repo_dir)?;
+    let mut local_repo = LocalRepository::new(&repo_dir)?;
+    if let Some(hash_algorithm) = new_repo.hash_algorithm {
+        local_repo.set_hash_algorithm(hash_algorithm);
+    }
     local_repo.save(&config_path)?;
 
+    // Encrypt from the start if a key is configured for the repository or its namespace
+    if encryption::kms_for_repo(&repo_dir)?.is_some() {
+        encryption::enable(&repo_dir)?;
+    }
+
     // Create history dir
     let history_dir = util::fs::oxen_hidden_dir(&repo_dir).join(constants::HISTORY_DIR);
     std::fs::create_dir_all(history_dir)?;
@@ -329,6 +353,7 @@ pub fn delete(repo: LocalRepository) -> Result<LocalRepository, OxenError> {
 
     log::debug!("Deleting repo directory: {:?}", repo);
     util::fs::remove_dir_all(&repo.path)?;
+    encryption::forget(&repo.path);
     Ok(repo)
 }
 
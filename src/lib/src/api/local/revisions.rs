@@ -1,11 +1,12 @@
 //! Revisions can either be commits by id or head commits on branches by name
 
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 use crate::core::index::object_db_reader::get_object_reader;
 use crate::core::index::CommitDirEntryReader;
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
+use crate::util::fs::ReadableVersionPath;
 use crate::{api, util};
 
 /// Get a commit object from a commit id or branch name
@@ -28,7 +29,7 @@ pub fn get_version_file(
     repo: &LocalRepository,
     revision: impl AsRef<str>,
     path: impl AsRef<Path>,
-) -> Result<PathBuf, OxenError> {
+) -> Result<ReadableVersionPath, OxenError> {
     let commit_id = match get(repo, &revision)? {
         Some(commit) => commit.id,
         None => return Err(OxenError::commit_id_does_not_exist(revision.as_ref())),
@@ -42,7 +43,7 @@ pub fn get_version_file_from_commit_id(
     repo: &LocalRepository,
     commit_id: impl AsRef<str>,
     path: impl AsRef<Path>,
-) -> Result<PathBuf, OxenError> {
+) -> Result<ReadableVersionPath, OxenError> {
     let commit_id = commit_id.as_ref();
     let path = path.as_ref();
     let parent = match path.parent() {
@@ -66,5 +67,5 @@ pub fn get_version_file_from_commit_id(
         _ => return Err(OxenError::entry_does_not_exist_in_commit(path, commit_id)),
     };
 
-    Ok(util::fs::version_path(repo, &entry))
+    util::fs::readable_version_path(repo, &util::fs::version_path(repo, &entry))
 }
@@ -0,0 +1,288 @@
+//! # Local Search
+//!
+//! Full-text and nearest neighbor search over the files in a commit.
+//! The index is built lazily the first time a commit is searched,
+//! or ahead of time by the commit cachers on the server.
+//!
+
+use crate::core::cache::cachers::search_index;
+use crate::core::cache::cachers::search_index::{
+    DOCUMENTS_TABLE, EMBEDDINGS_TABLE, POSTINGS_TABLE,
+};
+use crate::core::db::data_frames::df_db;
+use crate::error::OxenError;
+use crate::model::{Commit, LocalRepository, SearchResult};
+use crate::opts::SearchOpts;
+use crate::view::Pagination;
+
+use duckdb::types::Value;
+use std::path::{Path, PathBuf};
+
+/// Build the search index for a commit if it does not exist yet
+pub fn index(repo: &LocalRepository, commit: &Commit) -> Result<(), OxenError> {
+    if !search_index::is_indexed(repo, commit) {
+        search_index::compute(repo, commit)?;
+    }
+    Ok(())
+}
+
+/// Search the files in the commit for rows or lines that contain every word in the query.
+/// Results are ordered by the number of times the words appear in the row.
+pub fn search(
+    repo: &LocalRepository,
+    commit: &Commit,
+    opts: &SearchOpts,
+) -> Result<(Vec<SearchResult>, Pagination), OxenError> {
+    let mut terms = search_index::tokenize(&opts.query);
+    terms.sort();
+    terms.dedup();
+    if terms.is_empty() {
+        return Err(OxenError::basic_str(
+            "Search query must contain at least one word",
+        ));
+    }
+
+    index(repo, commit)?;
+    let conn = df_db::get_connection(search_index::search_index_path(repo, commit))?;
+
+    let placeholders = vec!["?"; terms.len()].join(", ");
+    let mut values: Vec<Value> = terms.iter().map(|t| Value::Text(t.clone())).collect();
+    values.push(Value::BigInt(terms.len() as i64));
+
+    let path = opts.path.clone().unwrap_or_default();
+    let path_filter = path_filter("d.path", &path, &mut values);
+
+    let from = format!(
+        "FROM (SELECT doc_id, CAST(SUM(tf) AS DOUBLE) AS score, COUNT(DISTINCT term) AS matched
+               FROM {POSTINGS_TABLE} WHERE term IN ({placeholders}) GROUP BY doc_id) h
+         JOIN {DOCUMENTS_TABLE} d ON d.doc_id = h.doc_id
+         WHERE h.matched = ?{path_filter}"
+    );
+
+    let count_sql = format!("SELECT COUNT(*) {from}");
+    let total: i64 =
+        conn.query_row(&count_sql, duckdb::params_from_iter(values.iter()), |row| {
+            row.get(0)
+        })?;
+
+    let page_size = opts.page_size.max(1);
+    let page_num = opts.page_num.max(1);
+    let sql = format!(
+        "SELECT d.path, d.row_idx, d.column_name, d.text, h.score {from}
+         ORDER BY h.score DESC, d.path, d.row_idx, d.column_name
+         LIMIT {page_size} OFFSET {}",
+        (page_num - 1) * page_size
+    );
+    log::debug!("search sql: {}", sql);
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(duckdb::params_from_iter(values.iter()), |row| {
+        let path: String = row.get(0)?;
+        let row_idx: i64 = row.get(1)?;
+        let column: Option<String> = row.get(2)?;
+        let text: String = row.get(3)?;
+        let score: f64 = row.get(4)?;
+        Ok((path, row_idx, column, text, score))
+    })?;
+
+    let mut results = vec![];
+    for row in rows {
+        let (path, row_idx, column, text, score) = row?;
+        results.push(SearchResult {
+            path: PathBuf::from(path),
+            row: Some(row_idx as usize),
+            column,
+            text,
+            score,
+        });
+    }
+
+    let total = total as usize;
+    let pagination = Pagination {
+        page_size,
+        page_number: page_num,
+        total_pages: (total as f64 / page_size as f64).ceil() as usize,
+        total_entries: total,
+    };
+    Ok((results, pagination))
+}
+
+/// Find the rows under `path` whose embedding in `column` is closest to `vector` by cosine
+/// similarity. An empty path searches the whole commit.
+pub fn nearest(
+    repo: &LocalRepository,
+    commit: &Commit,
+    column: impl AsRef<str>,
+    vector: &[f64],
+    path: impl AsRef<Path>,
+    limit: usize,
+) -> Result<Vec<SearchResult>, OxenError> {
+    if vector.is_empty() {
+        return Err(OxenError::basic_str("Search vector must not be empty"));
+    }
+
+    index(repo, commit)?;
+    let conn = df_db::get_connection(search_index::search_index_path(repo, commit))?;
+
+    let vector = format!(
+        "[{}]",
+        vector
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    );
+    let mut values = vec![
+        Value::Text(vector.clone()),
+        Value::Text(column.as_ref().to_string()),
+        Value::Text(vector),
+    ];
+    // Filter before the limit, so rows from other paths don't take up the results
+    let path_filter = path_filter("path", path.as_ref(), &mut values);
+    let sql = format!(
+        "SELECT path, row_idx, column_name, score FROM (
+            SELECT path, row_idx, column_name,
+                   list_cosine_similarity(embedding, CAST(? AS DOUBLE[])) AS score
+            FROM {EMBEDDINGS_TABLE}
+            WHERE column_name = ? AND len(embedding) = len(CAST(? AS DOUBLE[])){path_filter})
+         WHERE score IS NOT NULL
+         ORDER BY score DESC, path, row_idx
+         LIMIT {limit}"
+    );
+    log::debug!("nearest sql: {}", sql);
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(duckdb::params_from_iter(values.iter()), |row| {
+        let path: String = row.get(0)?;
+        let row_idx: i64 = row.get(1)?;
+        let column: String = row.get(2)?;
+        let score: f64 = row.get(3)?;
+        Ok((path, row_idx, column, score))
+    })?;
+
+    let mut results = vec![];
+    for row in rows {
+        let (path, row_idx, column, score) = row?;
+        results.push(SearchResult {
+            path: PathBuf::from(path),
+            row: Some(row_idx as usize),
+            column: Some(column),
+            text: String::new(),
+            score,
+        });
+    }
+    Ok(results)
+}
+
+/// SQL restricting `column` to `path` and the files under it, the values are added to `values`
+fn path_filter(column: &str, path: &Path, values: &mut Vec<Value>) -> String {
+    let path = path.to_string_lossy().trim_end_matches('/').to_string();
+    if path.is_empty() {
+        return String::new();
+    }
+    values.push(Value::Text(path.clone()));
+    values.push(Value::Text(format!("{path}/%")));
+    format!(" AND ({column} = ? OR {column} LIKE ?)")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::api;
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::opts::SearchOpts;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_search_text_and_tabular_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let readme = repo.path.join("README.md");
+            util::fs::write_to_path(
+                &readme,
+                "# Cats\n\nA dataset of cats and dog pictures\nDog only",
+            )?;
+            let csv = repo.path.join("annotations.csv");
+            util::fs::write_to_path(&csv, "file,label\nimg1.jpg,cat\nimg2.jpg,dog\n")?;
+            command::add(&repo, &repo.path)?;
+            let commit = command::commit(&repo, "Adding data")?;
+
+            let opts = SearchOpts::from_query("Dog");
+            let (results, pagination) = api::local::search::search(&repo, &commit, &opts)?;
+            assert_eq!(pagination.total_entries, 3);
+            assert!(results
+                .iter()
+                .any(|r| r.path == Path::new("annotations.csv")
+                    && r.column == Some(String::from("label"))
+                    && r.row == Some(1)));
+
+            // every word has to match
+            let opts = SearchOpts::from_query("cats dog");
+            let (results, _) = api::local::search::search(&repo, &commit, &opts)?;
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].path, Path::new("README.md"));
+            assert_eq!(results[0].row, Some(2));
+
+            // filter by path
+            let mut opts = SearchOpts::from_query("dog");
+            opts.path = Some(Path::new("README.md").to_path_buf());
+            let (results, _) = api::local::search::search(&repo, &commit, &opts)?;
+            assert_eq!(results.len(), 2);
+            assert!(results.iter().all(|r| r.path == Path::new("README.md")));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_search_nearest_embeddings() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            // Embeddings are only indexed for the columns listed in .oxenattributes
+            let attributes = repo.path.join(".oxenattributes");
+            util::fs::write_to_path(&attributes, "*.jsonl embeddings=embedding\n")?;
+            let jsonl = repo.path.join("embeddings.jsonl");
+            util::fs::write_to_path(
+                &jsonl,
+                "{\"id\": \"a\", \"embedding\": [1.0, 0.0]}\n{\"id\": \"b\", \"embedding\": [0.0, 1.0]}\n{\"id\": \"c\", \"embedding\": [0.0, null]}\n",
+            )?;
+            let other = repo.path.join("other.jsonl");
+            util::fs::write_to_path(&other, "{\"id\": \"d\", \"embedding\": [0.1, 0.9]}\n")?;
+            command::add(&repo, &attributes)?;
+            command::add(&repo, &jsonl)?;
+            command::add(&repo, &other)?;
+            let commit = command::commit(&repo, "Adding embeddings")?;
+
+            let results =
+                api::local::search::nearest(&repo, &commit, "embedding", &[0.1, 0.9], "", 1)?;
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].path, Path::new("other.jsonl"));
+
+            // The path filter is applied before the limit
+            let results = api::local::search::nearest(
+                &repo,
+                &commit,
+                "embedding",
+                &[0.1, 0.9],
+                "embeddings.jsonl",
+                1,
+            )?;
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].row, Some(1));
+
+            // The vector with a null is not indexed
+            let results = api::local::search::nearest(
+                &repo,
+                &commit,
+                "embedding",
+                &[0.0, 1.0],
+                "embeddings.jsonl",
+                10,
+            )?;
+            assert_eq!(results.len(), 2);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,349 @@
+//! # Local Worktrees
+//!
+//! Check out more than one branch of a repository at a time.
+//!
+//! A worktree is an extra working directory with its own `.oxen` dir. That dir holds
+//! the state of its working directory: HEAD, the staging dbs, merge conflicts and the
+//! `oxen watch` journal. Everything else (version files, commit history, refs, objects
+//! and config) is a symlink into the `.oxen` dir of the main repository, so nothing is
+//! copied and a commit made in one worktree is visible from all of them.
+//!
+//! The main repository lists its worktrees in `.oxen/worktrees.toml`, and each worktree
+//! points back at the main repository with `.oxen/WORKTREE`. A branch can only be
+//! checked out in one worktree at a time.
+//!
+
+use rocksdb::{DBWithThreadMode, MultiThreaded};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use crate::constants::{
+    FS_JOURNAL_CHECKPOINT_FILE, FS_JOURNAL_FILE, FS_WATCHER_FILE, HEAD_FILE, HISTORY_DIR,
+    HOOKS_DIR, MERGE_DIR, MERGE_HEAD_FILE, OBJECTS_DIR, ORIG_HEAD_FILE, REFS_DIR,
+    REPOSITORY_LOCK_FILE, STAGED_DIR, TMP_DIR, TRASH_DIR, VERSIONS_DIR, WORKTREES_FILE,
+    WORKTREE_FILE,
+};
+use crate::core::db;
+use crate::core::index::{restore, CommitEntryReader, CommitEntryWriter};
+use crate::error::OxenError;
+use crate::model::{Commit, LocalRepository, Worktree};
+use crate::{api, command, util};
+
+// Entries of .oxen that describe a single working directory, these are never shared
+const WORKTREE_LOCAL_ENTRIES: [&str; 12] = [
+    HEAD_FILE,
+    STAGED_DIR,
+    MERGE_DIR,
+    MERGE_HEAD_FILE,
+    ORIG_HEAD_FILE,
+    FS_JOURNAL_FILE,
+    FS_WATCHER_FILE,
+    FS_JOURNAL_CHECKPOINT_FILE,
+    WORKTREES_FILE,
+    WORKTREE_FILE,
+    REPOSITORY_LOCK_FILE,
+    TRASH_DIR,
+];
+
+// Shared dirs that are created in the main repository first if they are missing,
+// so the worktree never ends up with a private copy of them. The tmp dir is shared on
+// purpose, it caches decompressed versions and commit trees that are the same for every
+// worktree, and `oxen clean` only looks in the main repository.
+const WORKTREE_SHARED_DIRS: [&str; 6] = [
+    REFS_DIR,
+    HISTORY_DIR,
+    VERSIONS_DIR,
+    OBJECTS_DIR,
+    HOOKS_DIR,
+    TMP_DIR,
+];
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct WorktreesConfig {
+    #[serde(default)]
+    worktrees: Vec<PathBuf>,
+}
+
+fn config_path(main_path: &Path) -> PathBuf {
+    util::fs::oxen_hidden_dir(main_path).join(WORKTREES_FILE)
+}
+
+fn read_config(main_path: &Path) -> Result<WorktreesConfig, OxenError> {
+    let path = config_path(main_path);
+    if !path.exists() {
+        return Ok(WorktreesConfig::default());
+    }
+    let contents = util::fs::read_from_path(&path)?;
+    Ok(toml::from_str(&contents)?)
+}
+
+fn write_config(main_path: &Path, config: &WorktreesConfig) -> Result<(), OxenError> {
+    let contents = toml::to_string(config)?;
+    util::fs::write_to_path(config_path(main_path), contents)
+}
+
+fn absolute_path(path: &Path) -> PathBuf {
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn read_head(path: &Path) -> Result<Option<String>, OxenError> {
+    let head_file = util::fs::oxen_hidden_dir(path).join(HEAD_FILE);
+    if head_file.exists() {
+        Ok(Some(
+            util::fs::read_from_path(&head_file)?.trim().to_string(),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+fn branch_in_use(branch: &str, path: &Path) -> OxenError {
+    let error = format!("Branch '{branch}' is already checked out in worktree {path:?}");
+    OxenError::basic_str(error)
+}
+
+/// Whether the repository is a worktree of another repository
+pub fn is_worktree(repo: &LocalRepository) -> bool {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join(WORKTREE_FILE)
+        .exists()
+}
+
+/// Path of the main repository, this is the repository itself unless it is a worktree
+pub fn main_repo_path(repo: &LocalRepository) -> Result<PathBuf, OxenError> {
+    let worktree_file = util::fs::oxen_hidden_dir(&repo.path).join(WORKTREE_FILE);
+    if worktree_file.exists() {
+        let path = util::fs::read_from_path(&worktree_file)?;
+        Ok(PathBuf::from(path.trim()))
+    } else {
+        Ok(absolute_path(&repo.path))
+    }
+}
+
+/// List the main repository followed by every worktree attached to it
+pub fn list(repo: &LocalRepository) -> Result<Vec<Worktree>, OxenError> {
+    let main_path = main_repo_path(repo)?;
+    let mut worktrees = vec![Worktree {
+        head: read_head(&main_path)?,
+        path: main_path.to_owned(),
+        is_main: true,
+    }];
+    for path in read_config(&main_path)?.worktrees {
+        worktrees.push(Worktree {
+            head: read_head(&path)?,
+            path,
+            is_main: false,
+        });
+    }
+    Ok(worktrees)
+}
+
+/// The lock of the main repository, which guards which branch each worktree has checked out
+pub fn lock_file(repo: &LocalRepository) -> Result<fd_lock::RwLock<File>, OxenError> {
+    let main_repo = LocalRepository::from_dir(&main_repo_path(repo)?)?;
+    Ok(api::local::repositories::get_lock_file(&main_repo)?)
+}
+
+/// Error if the branch is checked out in a worktree other than this one. Takes the write guard
+/// of `lock_file` so the answer holds until the caller is done moving or deleting the branch.
+pub fn ensure_branch_available(
+    repo: &LocalRepository,
+    branch: &str,
+    _lock: &fd_lock::RwLockWriteGuard<'_, File>,
+) -> Result<(), OxenError> {
+    let repo_path = absolute_path(&repo.path);
+    for worktree in list(repo)? {
+        if worktree.path != repo_path && worktree.head.as_deref() == Some(branch) {
+            return Err(branch_in_use(branch, &worktree.path));
+        }
+    }
+    Ok(())
+}
+
+/// # Add a worktree
+/// Creates a new working directory at `path` with `branch` checked out, sharing
+/// the version files and history of this repository
+pub fn add(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+    branch: &str,
+) -> Result<Worktree, OxenError> {
+    let path = path.as_ref();
+    let main_path = main_repo_path(repo)?;
+    let main_repo = LocalRepository::from_dir(&main_path)?;
+
+    // Hold the repository lock so two worktrees can't grab the same branch at once
+    let mut lock_file = api::local::repositories::get_lock_file(&main_repo)?;
+    let _lock = lock_file.write()?;
+
+    let Some(branch) = api::local::branches::get_by_name(&main_repo, branch)? else {
+        return Err(OxenError::local_branch_not_found(branch));
+    };
+    for worktree in list(&main_repo)? {
+        if worktree.head.as_deref() == Some(&branch.name) {
+            return Err(branch_in_use(&branch.name, &worktree.path));
+        }
+    }
+
+    if path.exists() && path.read_dir()?.next().is_some() {
+        let error = format!("Worktree path {path:?} already exists and is not empty");
+        return Err(OxenError::basic_str(error));
+    }
+    util::fs::create_dir_all(path)?;
+    let path = absolute_path(path);
+
+    let main_hidden_dir = util::fs::oxen_hidden_dir(&main_path);
+    let hidden_dir = util::fs::oxen_hidden_dir(&path);
+    util::fs::create_dir_all(&hidden_dir)?;
+    for dir in WORKTREE_SHARED_DIRS {
+        util::fs::create_dir_all(main_hidden_dir.join(dir))?;
+    }
+    for entry in std::fs::read_dir(&main_hidden_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let is_local = file_name
+            .to_str()
+            .map(|name| WORKTREE_LOCAL_ENTRIES.contains(&name))
+            .unwrap_or(false);
+        if !is_local {
+            util::fs::symlink(entry.path(), hidden_dir.join(&file_name))?;
+        }
+    }
+    util::fs::write_to_path(hidden_dir.join(WORKTREE_FILE), main_path.to_string_lossy())?;
+
+    let mut config = read_config(&main_path)?;
+    config.worktrees.push(path.to_owned());
+    write_config(&main_path, &config)?;
+
+    let worktree_repo = LocalRepository::from_dir(&path)?;
+    api::local::branches::set_head(&worktree_repo, &branch.name)?;
+    let commit = api::local::commits::get_by_id(&worktree_repo, &branch.commit_id)?
+        .ok_or(OxenError::commit_id_does_not_exist(&branch.commit_id))?;
+    checkout_files(&worktree_repo, &commit)?;
+
+    Ok(Worktree {
+        path,
+        head: Some(branch.name),
+        is_main: false,
+    })
+}
+
+fn checkout_files(repo: &LocalRepository, commit: &Commit) -> Result<(), OxenError> {
+    let entry_reader = CommitEntryReader::new(repo, commit)?;
+    let files_db_dir = CommitEntryWriter::files_db_dir(repo);
+    let files_db: DBWithThreadMode<MultiThreaded> = DBWithThreadMode::open(
+        &db::key_val::opts::default(),
+        dunce::simplified(&files_db_dir),
+    )?;
+
+    let entries = entry_reader.list_entries()?;
+    let bar = util::progress_bar::oxen_progress_bar_with_msg(
+        entries.len() as u64,
+        "Checking out worktree",
+    );
+    for entry in entries {
+        restore::restore_file(repo, &entry.path, &commit.id, &entry, &files_db)?;
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+    Ok(())
+}
+
+/// # Remove a worktree
+/// Deletes the working directory of the worktree, refusing to throw away
+/// uncommitted changes unless `force` is set
+pub fn remove(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+    force: bool,
+) -> Result<(), OxenError> {
+    let path = absolute_path(path.as_ref());
+    let main_path = main_repo_path(repo)?;
+    if path == main_path {
+        return Err(OxenError::basic_str("Cannot remove the main worktree"));
+    }
+    let main_repo = LocalRepository::from_dir(&main_path)?;
+
+    // Same lock as `add`, so the list of worktrees isn't rewritten by both at once
+    let mut lock_file = api::local::repositories::get_lock_file(&main_repo)?;
+    let _lock = lock_file.write()?;
+
+    let mut config = read_config(&main_path)?;
+    if !config.worktrees.contains(&path) {
+        let error = format!("{path:?} is not a worktree of {main_path:?}");
+        return Err(OxenError::basic_str(error));
+    }
+
+    if path.exists() {
+        if !force {
+            let worktree_repo = LocalRepository::from_dir(&path)?;
+            let status = command::status(&worktree_repo)?;
+            if !status.is_clean() {
+                let error = format!(
+                    "Worktree {path:?} has changes that are not committed, use --force to remove it anyway"
+                );
+                return Err(OxenError::basic_str(error));
+            }
+        }
+        util::fs::remove_dir_all(&path)?;
+    }
+
+    config.worktrees.retain(|worktree| worktree != &path);
+    write_config(&main_path, &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::api;
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::model::LocalRepository;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_worktree_add_commit_remove() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let file = Path::new("hello.txt");
+            util::fs::write_to_path(repo.path.join(file), "hello")?;
+            command::add(&repo, repo.path.join(file))?;
+            command::commit(&repo, "adding hello")?;
+            api::local::branches::create_from_head(&repo, "experiment")?;
+
+            let worktree_path = repo
+                .path
+                .with_file_name(format!("{}-worktree", repo.dirname()));
+            let worktree = api::local::worktrees::add(&repo, &worktree_path, "experiment")?;
+            assert!(worktree.path.join(file).exists());
+            assert_eq!(api::local::worktrees::list(&repo)?.len(), 2);
+
+            // Both sides refuse to check out a branch that is in use by the other
+            assert!(command::checkout(&repo, "experiment").await.is_err());
+            assert!(api::local::worktrees::add(
+                &repo,
+                repo.path.with_file_name("another-worktree"),
+                "experiment"
+            )
+            .is_err());
+
+            // Commits made in the worktree show up on the branch in the main repository
+            let worktree_repo = LocalRepository::from_dir(&worktree.path)?;
+            util::fs::write_to_path(worktree.path.join("world.txt"), "world")?;
+            command::add(&worktree_repo, worktree.path.join("world.txt"))?;
+            let commit = command::commit(&worktree_repo, "adding world")?;
+            let branch = api::local::branches::get_by_name(&repo, "experiment")?.unwrap();
+            assert_eq!(branch.commit_id, commit.id);
+            assert!(!repo.path.join("world.txt").exists());
+
+            api::local::worktrees::remove(&repo, &worktree.path, false)?;
+            assert!(!worktree.path.exists());
+            assert_eq!(api::local::worktrees::list(&repo)?.len(), 1);
+
+            Ok(())
+        })
+        .await
+    }
+}
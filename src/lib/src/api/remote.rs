@@ -13,6 +13,7 @@ pub mod merger;
 pub mod metadata;
 pub mod repositories;
 pub mod schemas;
+pub mod search;
 pub mod stats;
 pub mod version;
 pub mod workspaces;
@@ -3,11 +3,15 @@ use crate::error::OxenError;
 use crate::view::http;
 use crate::view::OxenResponse;
 
+use std::time::Duration;
+
 pub use reqwest::Url;
 use reqwest::{header, Client, ClientBuilder, IntoUrl};
 
 const VERSION: &str = crate::constants::OXEN_VERSION;
 const USER_AGENT: &str = "Oxen";
+// Longest we will wait when the server asks us to back off, so a bad Retry-After can't stall a push
+const MAX_RETRY_AFTER_SECS: u64 = 60;
 
 pub fn get_host_from_url<U: IntoUrl>(url: U) -> Result<String, OxenError> {
     let parsed_url = url.into_url()?;
@@ -90,6 +94,30 @@ fn builder_no_user_agent() -> ClientBuilder {
     Client::builder()
 }
 
+/// Errors with `OxenError::RateLimited` if the server turned the request away with a 429
+pub fn check_rate_limited(res: &reqwest::Response) -> Result<(), OxenError> {
+    if res.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Ok(());
+    }
+    let retry_after = res
+        .headers()
+        .get(header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(1)
+        .clamp(1, MAX_RETRY_AFTER_SECS);
+    Err(OxenError::RateLimited(retry_after))
+}
+
+/// How long the server asked us to wait, if the error is because we were rate limited, at most
+/// a minute. Retry loops sleep this long instead of their own back off.
+pub fn retry_after(err: &OxenError) -> Option<Duration> {
+    match err {
+        OxenError::RateLimited(seconds) => Some(Duration::from_secs(*seconds)),
+        _ => None,
+    }
+}
+
 /// Performs an extra parse to validate that the response is success
 pub async fn parse_json_body(url: &str, res: reqwest::Response) -> Result<String, OxenError> {
     let type_override = "unauthenticated";
@@ -116,6 +144,7 @@ pub async fn parse_json_body_with_err_msg(
     response_type: Option<&str>,
     response_msg_override: Option<&str>,
 ) -> Result<String, OxenError> {
+    check_rate_limited(&res)?;
     let status = res.status();
     let body = res.text().await?;
 
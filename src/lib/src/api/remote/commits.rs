@@ -361,11 +361,15 @@ pub async fn root_commit(remote_repo: &RemoteRepository) -> Result<Commit, OxenE
     }
 }
 
+/// Whether `local_head` can be merged into the remote branch, `bytes` and `files` are the size of
+/// the push for the remote to check against its quota
 pub async fn can_push(
     remote_repo: &RemoteRepository,
     remote_branch_name: &str,
     local_repo: &LocalRepository,
     local_head: &Commit,
+    bytes: u64,
+    files: u64,
 ) -> Result<bool, OxenError> {
     // Before we do this, need to ensure that we are working in the same repo
     // If we don't, downloading the commits db in the next step
@@ -435,7 +439,7 @@ pub async fn can_push(
     util::fs::remove_dir_all(&tmp_tree_path)?;
 
     let uri = format!(
-        "/commits/{}/can_push?remote_head={}&lca={}",
+        "/commits/{}/can_push?remote_head={}&lca={}&bytes={bytes}&files={files}",
         local_head.id, remote_head.id, lca.id
     );
 
@@ -472,7 +476,7 @@ pub async fn download_commits_db_to_path(
     let dst = dst.as_ref();
     let reader = res
         .bytes_stream()
-        .map_err(|e| futures::io::Error::new(futures::io::ErrorKind::Other, e))
+        .map_err(futures::io::Error::other)
         .into_async_read();
     let decoder = GzipDecoder::new(futures::io::BufReader::new(reader));
     let archive = Archive::new(decoder);
@@ -527,7 +531,7 @@ pub async fn download_objects_db_to_path(
     // Get the size of the archive
     let reader = res
         .bytes_stream()
-        .map_err(|e| futures::io::Error::new(futures::io::ErrorKind::Other, e))
+        .map_err(futures::io::Error::other)
         .into_async_read();
     let decoder = GzipDecoder::new(futures::io::BufReader::new(reader));
     let archive = Archive::new(decoder);
@@ -587,7 +591,7 @@ pub async fn download_commit_entries_db_to_path(
             let path = path.as_ref();
             let reader = res
                 .bytes_stream()
-                .map_err(|e| futures::io::Error::new(futures::io::ErrorKind::Other, e))
+                .map_err(futures::io::Error::other)
                 .into_async_read();
             let decoder = GzipDecoder::new(futures::io::BufReader::new(reader));
             let archive = Archive::new(decoder);
@@ -975,14 +979,15 @@ pub async fn upload_single_tarball_to_server_with_retry(
             }
             Err(err) => {
                 total_tries += 1;
-                // Exponentially back off
-                let sleep_time = total_tries * total_tries;
+                // Exponentially back off, unless the server told us how long to wait
+                let sleep_time = client::retry_after(&err)
+                    .unwrap_or(time::Duration::from_secs(total_tries * total_tries));
                 log::debug!(
-                    "upload_single_tarball_to_server_with_retry upload failed sleeping {}: {:?}",
+                    "upload_single_tarball_to_server_with_retry upload failed sleeping {:?}: {:?}",
                     sleep_time,
                     err
                 );
-                std::thread::sleep(std::time::Duration::from_secs(sleep_time));
+                std::thread::sleep(sleep_time);
             }
         }
     }
@@ -1108,15 +1113,16 @@ pub async fn upload_data_chunk_to_server_with_retry(
             }
             Err(err) => {
                 total_tries += 1;
-                // Exponentially back off
-                let sleep_time = total_tries * total_tries;
+                // Exponentially back off, unless the server told us how long to wait
+                let sleep_time = client::retry_after(&err)
+                    .unwrap_or(time::Duration::from_secs(total_tries * total_tries));
                 log::debug!(
-                    "upload_data_chunk_to_server_with_retry upload failed sleeping {}: {:?}",
+                    "upload_data_chunk_to_server_with_retry upload failed sleeping {:?}: {:?}",
                     sleep_time,
                     err
                 );
                 last_error = format!("{:?}", err);
-                std::thread::sleep(std::time::Duration::from_secs(sleep_time));
+                std::thread::sleep(sleep_time);
             }
         }
     }
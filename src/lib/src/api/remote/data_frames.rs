@@ -1,6 +1,9 @@
 use std::path::Path;
 
+use polars::frame::DataFrame;
+
 use crate::api;
+use crate::core::df::encoding::{self, DataFrameEncoding};
 use crate::error::OxenError;
 use crate::model::RemoteRepository;
 use crate::opts::DFOpts;
@@ -43,6 +46,44 @@ pub async fn get(
     }
 }
 
+/// Same rows as `get`, sent as Arrow or Parquet so the column types come through intact
+pub async fn get_df(
+    remote_repo: &RemoteRepository,
+    commit_or_branch: &str,
+    path: impl AsRef<Path>,
+    opts: DFOpts,
+    encoding: DataFrameEncoding,
+) -> Result<DataFrame, OxenError> {
+    let path_str = path.as_ref().to_str().unwrap();
+    let query_str = opts.to_http_query_params();
+    let uri = format!("/data_frames/{commit_or_branch}/{path_str}?{query_str}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    let res = client
+        .get(&url)
+        .header(reqwest::header::ACCEPT, encoding.mime_type())
+        .send()
+        .await?;
+
+    let content_type = res
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(DataFrameEncoding::from_mime_type);
+    if !res.status().is_success() || content_type != Some(encoding) {
+        // Errors come back as json
+        let body = client::parse_json_body(&url, res).await?;
+        return Err(OxenError::basic_str(format!(
+            "Expected {} from {url}, got: {body}",
+            encoding.mime_type()
+        )));
+    }
+
+    let bytes = res.bytes().await?;
+    encoding::decode(bytes.to_vec(), encoding)
+}
+
 pub async fn index(
     remote_repo: &RemoteRepository,
     commit_or_branch: &str,
@@ -82,12 +123,14 @@ mod tests {
     use crate::constants;
     use crate::constants::DEFAULT_BRANCH_NAME;
     use crate::constants::DEFAULT_REMOTE_NAME;
+    use crate::core::df::encoding::DataFrameEncoding;
     use crate::error::OxenError;
 
     use crate::opts::DFOpts;
     use crate::test;
     use crate::util;
 
+    use polars::prelude::DataType;
     use serde_json::json;
 
     #[tokio::test]
@@ -250,6 +293,49 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_get_df_arrow_and_parquet() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|mut local_repo| async move {
+            let repo_dir = &local_repo.path;
+            let large_dir = repo_dir.join("large_files");
+            std::fs::create_dir_all(&large_dir)?;
+            let csv_file = large_dir.join("test.csv");
+            let from_file = test::test_200k_csv();
+            util::fs::copy(from_file, &csv_file)?;
+
+            command::add(&local_repo, &csv_file)?;
+            command::commit(&local_repo, "add test.csv")?;
+
+            let remote = test::repo_remote_url_from(&local_repo.dirname());
+            command::config::set_remote(&mut local_repo, DEFAULT_REMOTE_NAME, &remote)?;
+            let remote_repo = test::create_remote_repo(&local_repo).await?;
+            command::push(&local_repo).await?;
+
+            for encoding in [DataFrameEncoding::ArrowStream, DataFrameEncoding::Parquet] {
+                let mut opts = DFOpts::empty();
+                opts.page = Some(2);
+                opts.page_size = Some(10);
+                let df = api::remote::data_frames::get_df(
+                    &remote_repo,
+                    DEFAULT_BRANCH_NAME,
+                    PathBuf::from("large_files").join("test.csv"),
+                    opts,
+                    encoding,
+                )
+                .await?;
+
+                assert_eq!(df.height(), 10);
+                assert_eq!(df.width(), 11);
+                // Numbers stay numbers instead of coming back as json values
+                assert_eq!(df.column("lefteye_x")?.dtype(), &DataType::Int64);
+                assert_eq!(df.column("image_id")?.str()?.get(0), Some("000011.jpg"));
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_paginate_df_page_1_page_size_20() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|mut local_repo| async move {
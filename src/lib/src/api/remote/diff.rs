@@ -16,7 +16,8 @@ pub async fn list_diff_entries(
 ) -> Result<CompareEntries, OxenError> {
     let base = base.as_ref();
     let head = head.as_ref();
-    let uri = format!("/compare/entries/{base}..{head}?page={page}&page_size={page_size}");
+    let uri =
+        format!("/compare/entries/{base}..{head}?page={page}&page_size={page_size}&renames=true");
     let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
 
     let client = client::new_for_url(&url)?;
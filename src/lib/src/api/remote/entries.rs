@@ -315,25 +315,48 @@ pub async fn download_large_entry(
         ));
     }
 
+    // The first chunk tells us which version of the file we are getting, the rest are only
+    // accepted from that same version in case the revision is a branch that moves under us
+    let mut tasks = tasks.into_iter();
+    let mut etag: Option<String> = None;
+    if let Some((remote_repo, remote_path, tmp_file, revision, chunk_start, chunk_size)) =
+        tasks.next()
+    {
+        etag = try_download_entry_chunk(
+            &remote_repo,
+            &remote_path,
+            &tmp_file,
+            &revision,
+            chunk_start,
+            chunk_size,
+            None,
+        )
+        .await?;
+        bar.inc(chunk_size);
+    }
+
     use futures::prelude::*;
     let num_workers = constants::DEFAULT_NUM_WORKERS;
     let bodies = stream::iter(tasks)
-        .map(|item| async move {
-            // log::debug!("Downloading chunk {:?} -> {:?}", remote_path, tmp_file);
-            let (remote_repo, remote_path, tmp_file, revision, chunk_start, chunk_size) = item;
-
-            match try_download_entry_chunk(
-                &remote_repo,
-                &remote_path,
-                &tmp_file, // local_path
-                &revision,
-                chunk_start,
-                chunk_size,
-            )
-            .await
-            {
-                Ok(_) => Ok(chunk_size),
-                Err(err) => Err(err),
+        .map(|item| {
+            let etag = etag.clone();
+            async move {
+                let (remote_repo, remote_path, tmp_file, revision, chunk_start, chunk_size) = item;
+
+                match try_download_entry_chunk(
+                    &remote_repo,
+                    &remote_path,
+                    &tmp_file, // local_path
+                    &revision,
+                    chunk_start,
+                    chunk_size,
+                    etag,
+                )
+                .await
+                {
+                    Ok(_) => Ok(chunk_size),
+                    Err(err) => Err(err),
+                }
             }
         })
         .buffer_unordered(num_workers);
@@ -346,7 +369,7 @@ pub async fn download_large_entry(
                     bar.inc(s);
                 }
                 Err(err) => {
-                    log::error!("Error uploading chunk: {:?}", err)
+                    log::error!("Error downloading chunk: {:?}", err)
                 }
             }
         })
@@ -411,7 +434,8 @@ async fn try_download_entry_chunk(
     revision: impl AsRef<str>,
     chunk_start: u64,
     chunk_size: u64,
-) -> Result<u64, OxenError> {
+    etag: Option<String>,
+) -> Result<Option<String>, OxenError> {
     let mut try_num = 0;
     while try_num < constants::NUM_HTTP_RETRIES {
         match download_entry_chunk(
@@ -421,25 +445,27 @@ async fn try_download_entry_chunk(
             &revision,
             chunk_start,
             chunk_size,
+            etag.as_deref(),
         )
         .await
         {
-            Ok(_) => {
+            Ok(etag) => {
                 log::debug!("Downloaded chunk {:?}", local_path.as_ref());
-                return Ok(chunk_size);
+                return Ok(etag);
             }
             Err(err) => {
                 log::error!("Error trying to download chunk: {}", err);
                 try_num += 1;
-                let sleep_time = try_num * try_num;
-                std::thread::sleep(std::time::Duration::from_secs(sleep_time));
+                let sleep_time = client::retry_after(&err)
+                    .unwrap_or(std::time::Duration::from_secs(try_num * try_num));
+                std::thread::sleep(sleep_time);
             }
         }
     }
     Err(OxenError::basic_str("Retry download chunk failed"))
 }
 
-/// Downloads a chunk of a file
+/// Downloads a chunk of a file with a Range request, returns the ETag of the file
 async fn download_entry_chunk(
     remote_repo: &RemoteRepository,
     remote_path: impl AsRef<Path>,
@@ -447,7 +473,8 @@ async fn download_entry_chunk(
     revision: impl AsRef<str>,
     chunk_start: u64,
     chunk_size: u64,
-) -> Result<(), OxenError> {
+    etag: Option<&str>,
+) -> Result<Option<String>, OxenError> {
     let remote_path = remote_path.as_ref();
     let local_path = local_path.as_ref();
     log::debug!(
@@ -457,39 +484,99 @@ async fn download_entry_chunk(
         local_path
     );
 
+    if let Some(parent) = local_path.parent() {
+        if !parent.exists() {
+            log::debug!("Create parent dir {:?}", parent);
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    // The last chunk is empty when the size is a multiple of the chunk size
+    if chunk_size == 0 {
+        util::fs::file_create(local_path)?;
+        return Ok(etag.map(String::from));
+    }
+
+    let (bytes, etag) = get_range(
+        remote_repo,
+        remote_path,
+        revision,
+        chunk_start,
+        chunk_size,
+        etag,
+    )
+    .await?;
+
+    // TODO: replace these with util::fs:: file functions for better error messages
+    // Copy to file
+    let mut dest = { fs::File::create(local_path)? };
+    let mut content = Cursor::new(bytes);
+    std::io::copy(&mut content, &mut dest)?;
+    Ok(etag)
+}
+
+/// Read `length` bytes starting at `start` of a file on the remote, without downloading the
+/// rest of it
+pub async fn read_range(
+    remote_repo: &RemoteRepository,
+    remote_path: impl AsRef<Path>,
+    revision: impl AsRef<str>,
+    start: u64,
+    length: u64,
+) -> Result<Vec<u8>, OxenError> {
+    if length == 0 {
+        return Ok(vec![]);
+    }
+    let (bytes, _etag) = get_range(remote_repo, remote_path, revision, start, length, None).await?;
+    Ok(bytes.to_vec())
+}
+
+/// GET a byte range of a file, only from the version with `if_range` as its ETag if given.
+/// Returns the bytes and the ETag of the file.
+async fn get_range(
+    remote_repo: &RemoteRepository,
+    remote_path: impl AsRef<Path>,
+    revision: impl AsRef<str>,
+    start: u64,
+    length: u64,
+    if_range: Option<&str>,
+) -> Result<(bytes::Bytes, Option<String>), OxenError> {
     let uri = format!(
-        "/chunk/{}/{}?chunk_start={}&chunk_size={}",
+        "/file/{}/{}",
         revision.as_ref(),
-        remote_path.to_string_lossy(),
-        chunk_start,
-        chunk_size
+        remote_path.as_ref().to_string_lossy()
     );
-
     let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
-
-    log::debug!("download_entry_chunk {}", url);
+    log::debug!("get_range {} bytes={}-{}", url, start, start + length - 1);
 
     let client = client::new_for_url(&url)?;
-    let response = client.get(&url).send().await?;
-
-    if let Some(parent) = local_path.parent() {
-        if !parent.exists() {
-            log::debug!("Create parent dir {:?}", parent);
-            std::fs::create_dir_all(parent)?;
-        }
+    let mut request = client.get(&url).header(
+        reqwest::header::RANGE,
+        format!("bytes={}-{}", start, start + length - 1),
+    );
+    if let Some(etag) = if_range {
+        request = request.header(reqwest::header::IF_RANGE, etag);
     }
+    let response = request.send().await?;
+    client::check_rate_limited(&response)?;
 
     let status = response.status();
-    if reqwest::StatusCode::OK == status {
-        // TODO: replace these with util::fs:: file functions for better error messages
-        // Copy to file
-        let mut dest = { fs::File::create(local_path)? };
-        let mut content = Cursor::new(response.bytes().await?);
-        std::io::copy(&mut content, &mut dest)?;
-        Ok(())
-    } else {
-        let err = format!("Could not download entry status: {status}");
-        Err(OxenError::basic_str(err))
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    match status {
+        reqwest::StatusCode::PARTIAL_CONTENT => Ok((response.bytes().await?, etag)),
+        // The server sends the whole file when the If-Range does not match
+        reqwest::StatusCode::OK if if_range.is_some() => Err(OxenError::basic_str(format!(
+            "{:?} changed on the remote while downloading it",
+            remote_path.as_ref()
+        ))),
+        _ => {
+            let err = format!("Could not download entry range status: {status}");
+            Err(OxenError::basic_str(err))
+        }
     }
 }
 
@@ -507,14 +594,15 @@ pub async fn download_data_from_version_paths(
             Err(OxenError::Authentication(val)) => return Err(OxenError::Authentication(val)),
             Err(err) => {
                 num_retries += 1;
-                // Exponentially back off
-                let sleep_time = num_retries * num_retries;
+                // Exponentially back off, unless the server told us how long to wait
+                let sleep_time = client::retry_after(&err)
+                    .unwrap_or(std::time::Duration::from_secs(num_retries * num_retries));
                 log::warn!(
-                    "Could not download content {:?} sleeping {}",
+                    "Could not download content {:?} sleeping {:?}",
                     err,
                     sleep_time
                 );
-                std::thread::sleep(std::time::Duration::from_secs(sleep_time));
+                std::thread::sleep(sleep_time);
             }
         }
     }
@@ -550,10 +638,11 @@ pub async fn try_download_data_from_version_paths(
             log::error!("{}", err);
             return Err(OxenError::authentication(err));
         }
+        client::check_rate_limited(&res)?;
 
         let reader = res
             .bytes_stream()
-            .map_err(|e| futures::io::Error::new(futures::io::ErrorKind::Other, e))
+            .map_err(futures::io::Error::other)
             .into_async_read();
         let decoder = GzipDecoder::new(futures::io::BufReader::new(reader));
         let archive = Archive::new(decoder);
@@ -638,6 +727,27 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_read_range() -> Result<(), OxenError> {
+        test::run_select_data_sync_remote("large_files", |local_repo, remote_repo| async move {
+            let remote_path = Path::new("large_files").join("test.csv");
+            let expected = std::fs::read(local_repo.path.join(&remote_path))?;
+
+            let bytes = api::remote::entries::read_range(
+                &remote_repo,
+                &remote_path,
+                DEFAULT_BRANCH_NAME,
+                100,
+                50,
+            )
+            .await?;
+            assert_eq!(bytes, expected[100..150]);
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_download_file_large_to_dir() -> Result<(), OxenError> {
         test::run_select_data_sync_remote("large_files", |local_repo, remote_repo| async move {
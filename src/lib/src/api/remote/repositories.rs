@@ -4,7 +4,10 @@ use crate::constants::{DEFAULT_HOST, DEFAULT_REMOTE_NAME};
 use crate::error::OxenError;
 use crate::model::{Branch, LocalRepository, Remote, RemoteRepository, RepoNew};
 use crate::view::repository::{RepositoryDataTypesResponse, RepositoryDataTypesView};
-use crate::view::{NamespaceView, RepositoryResponse, StatusMessage};
+use crate::view::{
+    ListNamespacesResponse, ListRepositoryResponse, NamespaceView, RepositoryResponse,
+    RepositoryView, StatusMessage, StorageUsageResponse,
+};
 use serde_json::json;
 use serde_json::value;
 use std::fmt;
@@ -257,6 +260,7 @@ pub async fn create_from_local(
     let url = api::endpoint::url_from_host(&host, "");
     let root_commit = api::local::commits::root_commit(repository)?;
     repo_new.root_commit = Some(root_commit);
+    repo_new.hash_algorithm = Some(repository.hash_algorithm());
 
     // convert repo_new to json with serde
     // let params = serde_json::to_string(&repo_new)?;
@@ -294,6 +298,102 @@ pub async fn create_from_local(
     }
 }
 
+/// Lists the namespaces hosted on a server
+pub async fn list_namespaces(host: &str) -> Result<Vec<String>, OxenError> {
+    let scheme = api::endpoint::get_scheme(host);
+    let url = format!("{scheme}://{host}/api/namespaces");
+    log::debug!("api::remote::repositories::list_namespaces {}", url);
+
+    let client = client::new_for_url(&url)?;
+    if let Ok(res) = client.get(&url).send().await {
+        let body = client::parse_json_body(&url, res).await?;
+        let response: Result<ListNamespacesResponse, serde_json::Error> =
+            serde_json::from_str(&body);
+        match response {
+            Ok(val) => Ok(val.namespaces.into_iter().map(|n| n.namespace).collect()),
+            Err(err) => Err(OxenError::basic_str(format!(
+                "api::repositories::list_namespaces() Could not deserialize response [{err}]\n{body}"
+            ))),
+        }
+    } else {
+        Err(OxenError::basic_str(format!(
+            "api::repositories::list_namespaces() Request failed at url {url}"
+        )))
+    }
+}
+
+/// Lists the repositories in a namespace on a server
+pub async fn list_in_namespace(
+    host: &str,
+    namespace: &str,
+) -> Result<Vec<RepositoryView>, OxenError> {
+    let url = api::endpoint::url_from_host(host, &format!("/{namespace}"));
+    log::debug!("api::remote::repositories::list_in_namespace {}", url);
+
+    let client = client::new_for_url(&url)?;
+    if let Ok(res) = client.get(&url).send().await {
+        let body = client::parse_json_body(&url, res).await?;
+        let response: Result<ListRepositoryResponse, serde_json::Error> =
+            serde_json::from_str(&body);
+        match response {
+            Ok(val) => Ok(val.repositories),
+            Err(err) => Err(OxenError::basic_str(format!(
+                "api::repositories::list_in_namespace() Could not deserialize response [{err}]\n{body}"
+            ))),
+        }
+    } else {
+        Err(OxenError::basic_str(format!(
+            "api::repositories::list_in_namespace() Request failed at url {url}"
+        )))
+    }
+}
+
+/// Storage used by the repository and its namespace, with their quotas. None if the server
+/// does not report usage.
+pub async fn get_usage(
+    repository: &RemoteRepository,
+) -> Result<Option<StorageUsageResponse>, OxenError> {
+    let url = api::endpoint::url_from_repo(repository, "/usage")?;
+    log::debug!("api::remote::repositories::get_usage {}", url);
+
+    let client = client::new_for_url(&url)?;
+    if let Ok(res) = client.get(&url).send().await {
+        if 404 == res.status() {
+            return Ok(None);
+        }
+        let body = client::parse_json_body(&url, res).await?;
+        let response: Result<StorageUsageResponse, serde_json::Error> = serde_json::from_str(&body);
+        match response {
+            Ok(val) => Ok(Some(val)),
+            Err(err) => Err(OxenError::basic_str(format!(
+                "api::repositories::get_usage() Could not deserialize response [{err}]\n{body}"
+            ))),
+        }
+    } else {
+        Err(OxenError::basic_str(format!(
+            "api::repositories::get_usage() Request failed at url {url}"
+        )))
+    }
+}
+
+/// Errors with `OxenError::QuotaExceeded` if sending `bytes` in `files` files would put the
+/// repository or its namespace over quota, so we can fail before transferring anything
+pub async fn check_quota(
+    repository: &RemoteRepository,
+    bytes: u64,
+    files: u64,
+) -> Result<(), OxenError> {
+    let Some(usage) = get_usage(repository).await? else {
+        return Ok(());
+    };
+    if let Some(repo_usage) = usage.repository {
+        let name = format!("repository {}/{}", repository.namespace, repository.name);
+        repo_usage.check(&name, bytes, files)?;
+    }
+    let name = format!("namespace {}", repository.namespace);
+    usage.namespace.check(&name, bytes, files)
+}
+
 pub async fn delete(repository: &RemoteRepository) -> Result<StatusMessage, OxenError> {
     let url = repository.api_url()?;
     log::debug!("Deleting repository: {}", url);
@@ -614,6 +714,29 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_list_namespaces_and_repositories() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|local_repo| async move {
+            let remote_repo = test::create_remote_repo(&local_repo).await?;
+
+            let namespaces = api::remote::repositories::list_namespaces(&test::test_host()).await?;
+            assert!(namespaces.contains(&remote_repo.namespace));
+
+            let repos = api::remote::repositories::list_in_namespace(
+                &test::test_host(),
+                &remote_repo.namespace,
+            )
+            .await?;
+            assert!(repos.iter().any(|r| r.name == remote_repo.name));
+
+            // cleanup
+            api::remote::repositories::delete(&remote_repo).await?;
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_delete_repository() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|local_repo| async move {
@@ -0,0 +1,115 @@
+//! # Remote Search
+//!
+//! Full-text and nearest neighbor search over a revision of a remote repository.
+//!
+
+use std::path::Path;
+
+use crate::api;
+use crate::api::remote::client;
+use crate::error::OxenError;
+use crate::model::{RemoteRepository, SearchResult};
+use crate::opts::SearchOpts;
+use crate::view::search::{
+    NearestNeighborsRequest, NearestNeighborsResponse, SearchResultsResponse,
+};
+
+pub async fn search(
+    remote_repo: &RemoteRepository,
+    revision: impl AsRef<str>,
+    opts: &SearchOpts,
+) -> Result<SearchResultsResponse, OxenError> {
+    let revision = revision.as_ref();
+    let path = opts
+        .path
+        .as_ref()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let query = urlencoding::encode(&opts.query);
+    let uri = format!(
+        "/search/{revision}/{path}?q={query}&page={}&page_size={}",
+        opts.page_num, opts.page_size
+    );
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            log::debug!("search got body: {}", body);
+            let response: Result<SearchResultsResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(val) => Ok(val),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "api::search::search error parsing response from {url}\n\nErr {err:?} \n\n{body}"
+                ))),
+            }
+        }
+        Err(err) => {
+            let err = format!("api::search::search Err {err:?} request failed: {url}");
+            Err(OxenError::basic_str(err))
+        }
+    }
+}
+
+pub async fn nearest(
+    remote_repo: &RemoteRepository,
+    revision: impl AsRef<str>,
+    path: impl AsRef<Path>,
+    request: &NearestNeighborsRequest,
+) -> Result<Vec<SearchResult>, OxenError> {
+    let revision = revision.as_ref();
+    let path = path.as_ref().to_string_lossy();
+    let uri = format!("/search/nearest/{revision}/{path}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    match client.post(&url).json(request).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            log::debug!("nearest got body: {}", body);
+            let response: Result<NearestNeighborsResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(val) => Ok(val.results),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "api::search::nearest error parsing response from {url}\n\nErr {err:?} \n\n{body}"
+                ))),
+            }
+        }
+        Err(err) => {
+            let err = format!("api::search::nearest Err {err:?} request failed: {url}");
+            Err(OxenError::basic_str(err))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::api;
+    use crate::constants::DEFAULT_BRANCH_NAME;
+    use crate::error::OxenError;
+    use crate::opts::SearchOpts;
+    use crate::test;
+
+    #[tokio::test]
+    async fn test_remote_search_annotations() -> Result<(), OxenError> {
+        test::run_remote_repo_test_bounding_box_csv_pushed(|remote_repo| async move {
+            let opts = SearchOpts::from_query("cat");
+            let response =
+                api::remote::search::search(&remote_repo, DEFAULT_BRANCH_NAME, &opts).await?;
+            // The file and label cells of both cat rows
+            assert_eq!(response.pagination.total_entries, 4);
+            assert!(response
+                .results
+                .iter()
+                .all(|r| r.path == Path::new("annotations/train/bounding_box.csv")));
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+}
@@ -9,14 +9,33 @@ pub use commits::commit;
 
 use crate::api;
 use crate::api::remote::client;
+use crate::config::UserConfig;
 use crate::error::OxenError;
 use crate::model::RemoteRepository;
 use crate::view::workspaces::ListWorkspaceResponseView;
-use crate::view::workspaces::{NewWorkspace, WorkspaceResponse};
+use crate::view::workspaces::{
+    NewWorkspace, RenameWorkspace, WorkspaceRefreshResponse, WorkspaceResponse,
+};
 use crate::view::WorkspaceResponseView;
 
 pub async fn list(remote_repo: &RemoteRepository) -> Result<Vec<WorkspaceResponse>, OxenError> {
-    let url = api::endpoint::url_from_repo(remote_repo, "/workspaces")?;
+    list_by(remote_repo, None, None).await
+}
+
+/// List the workspaces created from `branch_name` and by `creator`, a None filter matches everything
+pub async fn list_by(
+    remote_repo: &RemoteRepository,
+    branch_name: Option<&str>,
+    creator: Option<&str>,
+) -> Result<Vec<WorkspaceResponse>, OxenError> {
+    let mut url = url::Url::parse(&api::endpoint::url_from_repo(remote_repo, "/workspaces")?)?;
+    if let Some(branch_name) = branch_name {
+        url.query_pairs_mut().append_pair("branch", branch_name);
+    }
+    if let Some(creator) = creator {
+        url.query_pairs_mut().append_pair("creator", creator);
+    }
+    let url = url.to_string();
     let client = client::new_for_url(&url)?;
     let res = client.get(&url).send().await?;
     let body = client::parse_json_body(&url, res).await?;
@@ -56,6 +75,9 @@ pub async fn create_with_path(
         // These two are needed for the oxen hub right now, ignored by the server
         resource_path: Some(path.to_str().unwrap().to_string()),
         entity_type: Some("user".to_string()),
+        name: None,
+        creator: UserConfig::get().ok().map(|user| user.email),
+        ttl_secs: None,
     };
 
     let client = client::new_for_url(&url)?;
@@ -72,6 +94,59 @@ pub async fn create_with_path(
     }
 }
 
+/// Change the display name of a workspace
+pub async fn rename(
+    remote_repo: &RemoteRepository,
+    workspace_id: impl AsRef<str>,
+    name: impl AsRef<str>,
+) -> Result<WorkspaceResponse, OxenError> {
+    let workspace_id = workspace_id.as_ref();
+    let url = api::endpoint::url_from_repo(remote_repo, &format!("/workspaces/{workspace_id}"))?;
+    log::debug!("rename workspace {}\n", url);
+
+    let body = RenameWorkspace {
+        name: name.as_ref().to_string(),
+    };
+    let client = client::new_for_url(&url)?;
+    let res = client.put(&url).json(&body).send().await?;
+
+    let body = client::parse_json_body(&url, res).await?;
+    let response: Result<WorkspaceResponseView, serde_json::Error> = serde_json::from_str(&body);
+    match response {
+        Ok(val) => Ok(val.workspace),
+        Err(err) => Err(OxenError::basic_str(format!(
+            "error parsing response from {url}\n\nErr {err:?} \n\n{body}"
+        ))),
+    }
+}
+
+/// Move the workspace onto the latest commit of `branch_name`, replaying its staged edits
+pub async fn refresh(
+    remote_repo: &RemoteRepository,
+    workspace_id: impl AsRef<str>,
+    branch_name: impl AsRef<str>,
+) -> Result<WorkspaceRefreshResponse, OxenError> {
+    let workspace_id = workspace_id.as_ref();
+    let branch_name = branch_name.as_ref();
+    let url = api::endpoint::url_from_repo(
+        remote_repo,
+        &format!("/workspaces/{workspace_id}/refresh/{branch_name}"),
+    )?;
+    log::debug!("refresh workspace {}\n", url);
+
+    let client = client::new_for_url(&url)?;
+    let res = client.post(&url).send().await?;
+
+    let body = client::parse_json_body(&url, res).await?;
+    let response: Result<WorkspaceRefreshResponse, serde_json::Error> = serde_json::from_str(&body);
+    match response {
+        Ok(val) => Ok(val),
+        Err(err) => Err(OxenError::basic_str(format!(
+            "error parsing response from {url}\n\nErr {err:?} \n\n{body}"
+        ))),
+    }
+}
+
 pub async fn delete(
     remote_repo: &RemoteRepository,
     workspace_id: impl AsRef<str>,
@@ -158,4 +233,38 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_rename_and_list_workspaces_by_branch() -> Result<(), OxenError> {
+        test::run_empty_remote_repo_test(|_local_repo, remote_repo| async move {
+            create(&remote_repo, "main", "test_workspace_id").await?;
+
+            let workspace = rename(&remote_repo, "test_workspace_id", "Labeling pass").await?;
+            assert_eq!(workspace.id, "test_workspace_id");
+            assert_eq!(workspace.name, Some(String::from("Labeling pass")));
+
+            let workspaces = list_by(&remote_repo, Some("main"), None).await?;
+            assert_eq!(workspaces.len(), 1);
+            let workspaces = list_by(&remote_repo, Some("no-such-branch"), None).await?;
+            assert_eq!(workspaces.len(), 0);
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_refresh_workspace_already_up_to_date() -> Result<(), OxenError> {
+        test::run_empty_remote_repo_test(|_local_repo, remote_repo| async move {
+            create(&remote_repo, "main", "test_workspace_id").await?;
+
+            let response = refresh(&remote_repo, "test_workspace_id", "main").await?;
+            assert_eq!(response.workspace.id, "test_workspace_id");
+            assert_eq!(response.summary.replayed_rows, 0);
+            assert!(response.summary.conflicts.is_empty());
+
+            Ok(remote_repo)
+        })
+        .await
+    }
 }
@@ -1,23 +1,19 @@
-use serde::{Deserialize, Serialize};
-
 use crate::api;
 use crate::api::remote::client;
 use crate::error::OxenError;
 use crate::opts::DFOpts;
+use crate::util;
+use crate::view::data_frames::DataFrameChangesResponse;
 use crate::view::entry::PaginatedMetadataEntriesResponse;
 use crate::view::json_data_frame_view::WorkspaceJsonDataFrameViewResponse;
+use std::io::Cursor;
 use std::path::Path;
 
-use crate::model::RemoteRepository;
+use crate::model::{BulkFileFormat, RemoteRepository};
 use crate::view::{JsonDataFrameViewResponse, JsonDataFrameViews, StatusMessage};
 
 pub mod rows;
 
-#[derive(Serialize, Deserialize)]
-struct PutParam {
-    is_indexed: bool,
-}
-
 pub async fn get(
     remote_repo: &RemoteRepository,
     workspace_id: impl AsRef<str>,
@@ -58,6 +54,96 @@ pub async fn get(
     }
 }
 
+/// Download the current state of the workspace data frame, including the `_oxen_diff_status`
+/// of each row. The format is picked from the extension of `output`.
+pub async fn export(
+    remote_repo: &RemoteRepository,
+    workspace_id: impl AsRef<str>,
+    path: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<(), OxenError> {
+    let workspace_id = workspace_id.as_ref();
+    let path = path.as_ref();
+    let output = output.as_ref();
+    let Some(file_path_str) = path.to_str() else {
+        return Err(OxenError::basic_str(format!(
+            "Path must be a string: {:?}",
+            path
+        )));
+    };
+    let format = BulkFileFormat::from_path(output)?;
+    let uri = format!(
+        "/workspaces/{workspace_id}/data_frames/export/{file_path_str}?format={}",
+        format.extension()
+    );
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|err| OxenError::basic_str(format!("Request failed: {url}\n\nErr {err:?}")))?;
+
+    let status = response.status();
+    if reqwest::StatusCode::OK != status {
+        let body = response.text().await.unwrap_or_default();
+        let err = format!("workspaces::data_frames::export failed with status {status}\n\n{body}");
+        return Err(OxenError::basic_str(err));
+    }
+
+    if let Some(parent) = output.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            util::fs::create_dir_all(parent)?;
+        }
+    }
+    let mut dest_file = util::fs::file_create(output)?;
+    let mut content = Cursor::new(response.bytes().await?);
+    std::io::copy(&mut content, &mut dest_file)?;
+    Ok(())
+}
+
+/// Fetch the row edits made to the data frame after the `since` sequence number
+pub async fn changes(
+    remote_repo: &RemoteRepository,
+    workspace_id: impl AsRef<str>,
+    path: impl AsRef<Path>,
+    since: i64,
+) -> Result<DataFrameChangesResponse, OxenError> {
+    let workspace_id = workspace_id.as_ref();
+    let path = path.as_ref();
+    let Some(file_path_str) = path.to_str() else {
+        return Err(OxenError::basic_str(format!(
+            "Path must be a string: {:?}",
+            path
+        )));
+    };
+    let uri =
+        format!("/workspaces/{workspace_id}/data_frames/changes/{file_path_str}?since={since}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<DataFrameChangesResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(response) => Ok(response),
+                Err(err) => {
+                    let err = format!("workspaces::data_frames::changes error parsing from {url}\n\nErr {err:?} \n\n{body}");
+                    Err(OxenError::basic_str(err))
+                }
+            }
+        }
+        Err(err) => {
+            let err =
+                format!("workspaces::data_frames::changes Request failed: {url}\n\nErr {err:?}");
+            Err(OxenError::basic_str(err))
+        }
+    }
+}
+
 pub async fn is_indexed(
     remote_repo: &RemoteRepository,
     workspace_id: &str,
@@ -5,9 +5,10 @@ use polars::frame::DataFrame;
 use crate::api;
 use crate::api::remote::client;
 use crate::error::OxenError;
+use crate::view::data_frames::{DataFrameBulkRowsResponse, DataFrameBulkRowsSummary};
 use crate::view::json_data_frame_view::JsonDataFrameRowResponse;
 
-use crate::model::RemoteRepository;
+use crate::model::{BulkFileFormat, RemoteRepository};
 
 pub async fn get(
     remote_repo: &RemoteRepository,
@@ -220,6 +221,80 @@ pub async fn restore_row(
     }
 }
 
+/// Upload a csv, jsonl, parquet... file and insert or update each of its rows,
+/// matching existing rows on the `keys` columns.
+pub async fn upsert(
+    remote_repo: &RemoteRepository,
+    workspace_id: &str,
+    path: &Path,
+    file: &Path,
+    keys: &[String],
+) -> Result<DataFrameBulkRowsSummary, OxenError> {
+    bulk_rows(remote_repo, workspace_id, path, file, keys, "upsert").await
+}
+
+/// Upload a csv, jsonl, parquet... file and delete every row that matches it on the `keys` columns.
+pub async fn delete_many(
+    remote_repo: &RemoteRepository,
+    workspace_id: &str,
+    path: &Path,
+    file: &Path,
+    keys: &[String],
+) -> Result<DataFrameBulkRowsSummary, OxenError> {
+    bulk_rows(remote_repo, workspace_id, path, file, keys, "delete").await
+}
+
+async fn bulk_rows(
+    remote_repo: &RemoteRepository,
+    workspace_id: &str,
+    path: &Path,
+    file: &Path,
+    keys: &[String],
+    operation: &str,
+) -> Result<DataFrameBulkRowsSummary, OxenError> {
+    let Some(file_path_str) = path.to_str() else {
+        return Err(OxenError::basic_str(format!(
+            "Path must be a string: {:?}",
+            path
+        )));
+    };
+    let format = BulkFileFormat::from_path(file)?;
+    let keys = urlencoding::encode(&keys.join(",")).to_string();
+    let uri = format!(
+        "/workspaces/{workspace_id}/data_frames/rows/{operation}/resource/{file_path_str}?keys={keys}&format={}",
+        format.extension()
+    );
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    log::debug!("bulk_rows {url}");
+
+    let Ok(data) = std::fs::read(file) else {
+        let err = format!("Error reading file at path: {file:?}");
+        return Err(OxenError::basic_str(err));
+    };
+
+    let client = client::new_for_url(&url)?;
+    match client.post(&url).body(data).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<DataFrameBulkRowsResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(val) => Ok(val.summary),
+                Err(err) => {
+                    let err = format!("api::workspaces::data_frames::rows::{operation} error parsing response from {url}\n\nErr {err:?} \n\n{body}");
+                    Err(OxenError::basic_str(err))
+                }
+            }
+        }
+        Err(err) => {
+            let err = format!(
+                "api::workspaces::data_frames::rows::{operation} Request failed: {url}\n\nErr {err:?}"
+            );
+            Err(OxenError::basic_str(err))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -232,6 +307,7 @@ mod tests {
     use crate::error::OxenError;
     use crate::opts::DFOpts;
     use crate::test;
+    use crate::util;
     use crate::view::json_data_frame_view::JsonDataFrameRowResponse;
 
     use std::path::Path;
@@ -579,4 +655,53 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_upsert_and_export_rows_from_file() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_remote_repo_test_bounding_box_csv_pushed(|remote_repo| async move {
+            let workspace_id = UserConfig::identifier()?;
+            api::remote::workspaces::create(&remote_repo, DEFAULT_BRANCH_NAME, &workspace_id)
+                .await?;
+
+            let path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            api::remote::workspaces::data_frames::index(&remote_repo, &workspace_id, &path).await?;
+
+            let upload_dir = test::test_run_dir().join(uuid::Uuid::new_v4().to_string());
+            util::fs::create_dir_all(&upload_dir)?;
+            let upload = upload_dir.join("predictions.csv");
+            util::fs::write_to_path(
+                &upload,
+                "file,label,min_x,min_y,width,height\ntrain/dog_2.jpg,cat,7.0,29.5,246,247\ntrain/new.jpg,dog,1.0,2.0,10,10\n",
+            )?;
+            let keys = vec![String::from("file"), String::from("min_x")];
+            let summary = api::remote::workspaces::data_frames::rows::upsert(
+                &remote_repo,
+                &workspace_id,
+                &path,
+                &upload,
+                &keys,
+            )
+            .await?;
+            assert_eq!(summary.added, 1);
+            assert_eq!(summary.updated, 1);
+
+            let output = upload_dir.join("export.jsonl");
+            api::remote::workspaces::data_frames::export(&remote_repo, &workspace_id, &path, &output)
+                .await?;
+            let df = crate::core::df::tabular::read_df(&output, DFOpts::empty())?;
+            assert_eq!(df.height(), 7);
+            assert!(df.get_column_names().contains(&constants::DIFF_STATUS_COL));
+
+            util::fs::remove_dir_all(upload_dir)?;
+
+            Ok(remote_repo)
+        })
+        .await
+    }
 }
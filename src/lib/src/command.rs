@@ -6,6 +6,7 @@
 pub mod add;
 pub mod branch;
 pub mod checkout;
+pub mod clean;
 pub mod clone;
 pub mod commit;
 pub mod commit_cache;
@@ -22,20 +23,25 @@ pub mod load;
 pub mod log;
 pub mod merge;
 pub mod migrate;
+pub mod mv;
 pub mod pull;
 pub mod push;
 pub mod restore;
 pub mod rm;
 pub mod save;
 pub mod schemas;
+pub mod search;
 pub mod status;
+pub mod watch;
 pub mod workspace;
+pub mod worktree;
 
 pub use crate::command::add::add;
 pub use crate::command::branch::unlock;
 pub use crate::command::checkout::{
     checkout, checkout_combine, checkout_ours, checkout_theirs, create_checkout,
 };
+pub use crate::command::clean::clean;
 pub use crate::command::clone::{clone, clone_url, deep_clone_url, shallow_clone_url};
 pub use crate::command::commit::commit;
 pub use crate::command::df::{df, schema};
@@ -47,9 +53,11 @@ pub use crate::command::init::init;
 pub use crate::command::load::load;
 pub use crate::command::log::log_commits;
 pub use crate::command::merge::merge;
+pub use crate::command::mv::mv;
 pub use crate::command::pull::{pull, pull_all, pull_remote_branch, pull_shallow};
 pub use crate::command::push::{push, push_remote_branch, push_remote_repo_branch_name};
 pub use crate::command::restore::restore;
 pub use crate::command::rm::rm;
 pub use crate::command::save::save;
+pub use crate::command::search::search;
 pub use crate::command::status::{status, status_from_dir};
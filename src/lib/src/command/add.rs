@@ -8,8 +8,9 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use super::helpers;
+use crate::core::hooks::{self, Hook, HookInput};
 use crate::core::index::{oxenignore, CommitEntryReader, SchemaReader, Stager};
-use crate::{api, error::OxenError, model::LocalRepository};
+use crate::{api, command, error::OxenError, model::LocalRepository, util};
 /// # Stage files into repository
 ///
 /// ```
@@ -38,11 +39,7 @@ use crate::{api, error::OxenError, model::LocalRepository};
 /// # }
 /// ```
 pub fn add<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Result<(), OxenError> {
-    let stager = Stager::new_with_merge(repo)?;
     let commit = api::local::commits::head_commit(repo)?;
-    let reader = CommitEntryReader::new(repo, &commit)?;
-    let schema_reader = SchemaReader::new(repo, &commit.id)?;
-    let ignore = oxenignore::create(repo);
     log::debug!("---START--- oxen add: {:?}", path.as_ref());
 
     // Collect paths that match the glob pattern either:
@@ -64,12 +61,35 @@ pub fn add<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Result<(), OxenEr
         }
     }
 
+    add_paths(repo, paths)?;
+
+    log::debug!("---END--- oxen add: {:?}", path.as_ref());
+    Ok(())
+}
+
+/// Stages each of the paths, running the pre-add hook on them first
+pub(crate) fn add_paths(repo: &LocalRepository, paths: HashSet<PathBuf>) -> Result<(), OxenError> {
+    let stager = Stager::new_with_merge(repo)?;
+    let commit = api::local::commits::head_commit(repo)?;
+    let reader = CommitEntryReader::new(repo, &commit)?;
+    let schema_reader = SchemaReader::new(repo, &commit.id)?;
+    let ignore = oxenignore::create(repo);
+
+    if hooks::is_installed(repo, Hook::PreAdd) {
+        let status = command::status::status_without_untracked(repo)?;
+        let mut input = HookInput::new(repo, Hook::PreAdd)?.with_staged_data(&status);
+        input.paths = paths
+            .iter()
+            .map(|path| util::fs::path_relative_to_dir(path, &repo.path).unwrap_or(path.to_owned()))
+            .collect();
+        input.paths.sort();
+        hooks::run(repo, &input)?;
+    }
+
     // Get all entries in the head commit
     for path in paths {
         stager.add(path.as_ref(), &reader, &schema_reader, &ignore)?;
     }
-
-    log::debug!("---END--- oxen add: {:?}", path.as_ref());
     Ok(())
 }
 
@@ -27,6 +27,10 @@ pub async fn checkout(
             return api::local::branches::get_by_name(repo, value);
         }
 
+        let mut lock_file = api::local::worktrees::lock_file(repo)?;
+        let lock = lock_file.write()?;
+        api::local::worktrees::ensure_branch_available(repo, value, &lock)?;
+
         println!("Checkout branch: {value}");
         api::local::branches::set_working_branch(repo, value).await?;
         api::local::branches::set_head(repo, value)?;
@@ -121,8 +125,10 @@ pub fn checkout_combine<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Resu
     {
         if util::fs::is_tabular(&conflict.base_entry.path) {
             let df_base_path = util::fs::version_path(repo, &conflict.base_entry);
+            let df_base_path = util::fs::readable_version_path(repo, &df_base_path)?;
             let df_base = tabular::read_df(df_base_path, DFOpts::empty())?;
             let df_merge_path = util::fs::version_path(repo, &conflict.merge_entry);
+            let df_merge_path = util::fs::readable_version_path(repo, &df_merge_path)?;
             let df_merge = tabular::read_df(df_merge_path, DFOpts::empty())?;
 
             log::debug!("GOT DF HEAD {}", df_base);
@@ -135,6 +141,9 @@ pub fn checkout_combine<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Resu
                         Ok(mut uniq) => {
                             log::debug!("GOT DF COMBINED UNIQUE {}", uniq);
                             let output_path = repo.path.join(&conflict.base_entry.path);
+                            if util::fs::is_linked_file(&output_path) {
+                                util::fs::remove_file(&output_path)?;
+                            }
                             tabular::write_df(&mut uniq, &output_path)
                         }
                         _ => Err(OxenError::basic_str("Could not uniq data")),
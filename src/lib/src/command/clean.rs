@@ -0,0 +1,462 @@
+//! # oxen clean
+//!
+//! Remove untracked files from the working directory, or purge the caches under `.oxen`
+//!
+
+use ignore::gitignore::Gitignore;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use time::OffsetDateTime;
+
+use crate::constants::{
+    CACHE_DIR, COMPARES_DIR, HISTORY_DIR, TMP_DIR, TRASH_DIR, VERSIONS_DIR, VERSION_FILE_NAME,
+};
+use crate::core::index::oxenignore;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::opts::CleanOpts;
+use crate::{api, command, util};
+
+/// Batches of cleaned files kept in `.oxen/trash` before the oldest are deleted
+const MAX_TRASH_BATCHES: usize = 10;
+/// Batches older than this are deleted on the next clean, 30 days
+const MAX_TRASH_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+#[derive(Debug, Clone, Default)]
+pub struct CleanResult {
+    /// Untracked paths relative to the repository, or the cache paths under `.oxen`
+    pub paths: Vec<PathBuf>,
+    /// Size of everything in `paths`
+    pub bytes: u64,
+    /// Where the untracked files were moved to, `None` for a dry run or when cleaning caches
+    pub trash_dir: Option<PathBuf>,
+}
+
+/// # oxen clean
+///
+/// Untracked files (and the files in untracked directories with `opts.dirs`) are not deleted,
+/// they are moved to `.oxen/trash/` so `undo` can put them back. Paths matched by `.oxenignore`
+/// are never touched. Each clean keeps the last 10 batches in the trash that are less than 30
+/// days old and deletes the rest.
+///
+/// With `opts.caches` it purges the data under `.oxen` that oxen regenerates when it needs it
+/// instead: decompressed version files, cached compares, the DuckDB indices of commits and
+/// resized images. It refuses to while another oxen process holds the repository lock.
+pub fn clean(repo: &LocalRepository, opts: &CleanOpts) -> Result<CleanResult, OxenError> {
+    if opts.caches {
+        return clean_caches(repo, opts);
+    }
+
+    let (paths, dirs) = untracked_paths(repo, opts)?;
+    let bytes = paths
+        .iter()
+        .map(|path| disk_usage(&repo.path.join(path)))
+        .sum();
+
+    let mut result = CleanResult {
+        paths,
+        bytes,
+        trash_dir: None,
+    };
+    if opts.dry_run || result.paths.is_empty() {
+        return Ok(result);
+    }
+
+    let batch = OffsetDateTime::now_utc().unix_timestamp_nanos().to_string();
+    let trash_dir = trash_root(repo).join(batch);
+    for path in &result.paths {
+        let dst = trash_dir.join(path);
+        if let Some(parent) = dst.parent() {
+            util::fs::create_dir_all(parent)?;
+        }
+        util::fs::rename(repo.path.join(path), &dst)?;
+    }
+    // Ignored files keep their directory, the ones left empty go
+    for dir in dirs {
+        remove_empty_dirs(&repo.path.join(dir))?;
+    }
+    result.trash_dir = Some(trash_dir);
+
+    prune_trash(repo)?;
+    Ok(result)
+}
+
+fn clean_caches(repo: &LocalRepository, opts: &CleanOpts) -> Result<CleanResult, OxenError> {
+    // Pushes, pulls and migrations read the caches while they hold the lock
+    let main_repo = LocalRepository::from_dir(&api::local::worktrees::main_repo_path(repo)?)?;
+    let mut lock_file = api::local::repositories::get_lock_file(&main_repo)?;
+    let Ok(_lock) = lock_file.try_write() else {
+        return Err(OxenError::basic_str(
+            "Skipping the caches, another oxen process holds the repository lock",
+        ));
+    };
+
+    let paths = cache_paths(repo)?;
+    let bytes = paths.iter().map(|path| disk_usage(path)).sum();
+    if !opts.dry_run {
+        for path in &paths {
+            if path.is_dir() {
+                util::fs::remove_dir_all(path)?;
+            } else {
+                util::fs::remove_file(path)?;
+            }
+        }
+    }
+    Ok(CleanResult {
+        paths,
+        bytes,
+        trash_dir: None,
+    })
+}
+
+/// Puts back the untracked files moved to the trash by the last `oxen clean`, returns their paths
+pub fn undo(repo: &LocalRepository) -> Result<Vec<PathBuf>, OxenError> {
+    let Some(trash_dir) = latest_trash_dir(repo)? else {
+        return Err(OxenError::basic_str("Nothing to undo, the trash is empty"));
+    };
+
+    let mut paths = vec![];
+    for file in util::fs::rlist_files_in_dir(&trash_dir) {
+        paths.push(util::fs::path_relative_to_dir(&file, &trash_dir)?);
+    }
+    paths.sort();
+
+    // Check everything first so we never restore half of the files
+    for path in &paths {
+        if repo.path.join(path).exists() {
+            let error = format!("Cannot undo clean, {path:?} would be overwritten");
+            return Err(OxenError::basic_str(error));
+        }
+    }
+    for path in &paths {
+        let dst = repo.path.join(path);
+        if let Some(parent) = dst.parent() {
+            util::fs::create_dir_all(parent)?;
+        }
+        util::fs::rename(trash_dir.join(path), &dst)?;
+    }
+    util::fs::remove_dir_all(&trash_dir)?;
+
+    Ok(paths)
+}
+
+fn trash_root(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path).join(TRASH_DIR)
+}
+
+fn latest_trash_dir(repo: &LocalRepository) -> Result<Option<PathBuf>, OxenError> {
+    Ok(trash_batches(repo)?.pop().map(|(_, path)| path))
+}
+
+/// The batches in the trash, oldest first, with the time in nanoseconds they were cleaned at
+fn trash_batches(repo: &LocalRepository) -> Result<Vec<(i128, PathBuf)>, OxenError> {
+    let trash_root = trash_root(repo);
+    if !trash_root.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut batches = vec![];
+    for entry in std::fs::read_dir(&trash_root)? {
+        let path = entry?.path();
+        let Some(timestamp) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<i128>().ok())
+        else {
+            continue;
+        };
+        if path.is_dir() {
+            batches.push((timestamp, path));
+        }
+    }
+    batches.sort();
+    Ok(batches)
+}
+
+fn prune_trash(repo: &LocalRepository) -> Result<(), OxenError> {
+    let batches = trash_batches(repo)?;
+    let oldest_kept = SystemTime::now() - MAX_TRASH_AGE;
+    let oldest_kept = OffsetDateTime::from(oldest_kept).unix_timestamp_nanos();
+    let num_over = batches.len().saturating_sub(MAX_TRASH_BATCHES);
+    for (i, (timestamp, path)) in batches.iter().enumerate() {
+        if i < num_over || *timestamp < oldest_kept {
+            log::debug!("clean pruning trash batch {:?}", path);
+            util::fs::remove_dir_all(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// The untracked files to move to the trash, and the untracked dirs they were found in
+fn untracked_paths(
+    repo: &LocalRepository,
+    opts: &CleanOpts,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>), OxenError> {
+    let status = command::status(repo)?;
+    let mut paths = status.untracked_files;
+    let mut dirs = vec![];
+    if opts.dirs {
+        for (dir, _) in status.untracked_dirs {
+            for file in util::fs::rlist_files_in_dir(&repo.path.join(&dir)) {
+                paths.push(util::fs::path_relative_to_dir(&file, &repo.path)?);
+            }
+            dirs.push(dir);
+        }
+    }
+
+    // Status already skips ignored paths, but deleting is not the place to rely on that
+    let ignore = oxenignore::create(repo);
+    paths.retain(|path| !is_ignored(repo, &ignore, path));
+    paths.sort();
+    paths.dedup();
+    Ok((paths, dirs))
+}
+
+/// Removes the directories under `dir` that have no files left, and `dir` itself if it is empty
+fn remove_empty_dirs(dir: &Path) -> Result<bool, OxenError> {
+    if !dir.is_dir() {
+        return Ok(false);
+    }
+    let mut is_empty = true;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !(path.is_dir() && remove_empty_dirs(&path)?) {
+            is_empty = false;
+        }
+    }
+    if is_empty {
+        std::fs::remove_dir(dir)?;
+    }
+    Ok(is_empty)
+}
+
+fn is_ignored(repo: &LocalRepository, ignore: &Option<Gitignore>, path: &Path) -> bool {
+    let Some(ignore) = ignore else {
+        return false;
+    };
+    let is_dir = repo.path.join(path).is_dir();
+    ignore.matched_path_or_any_parents(path, is_dir).is_ignore()
+}
+
+fn cache_paths(repo: &LocalRepository) -> Result<Vec<PathBuf>, OxenError> {
+    // Worktrees share the caches of the main repository
+    let main_path = api::local::worktrees::main_repo_path(repo)?;
+    let hidden_dir = util::fs::oxen_hidden_dir(main_path);
+    let mut paths = vec![];
+
+    // Decompressed version files and commit trees kept around while pushing
+    let tmp_dir = hidden_dir.join(TMP_DIR);
+    if tmp_dir.exists() {
+        paths.push(tmp_dir);
+    }
+
+    let compares_dir = hidden_dir.join(CACHE_DIR).join(COMPARES_DIR);
+    if compares_dir.exists() {
+        paths.push(compares_dir);
+    }
+
+    let history_dir = hidden_dir.join(HISTORY_DIR);
+    if history_dir.exists() {
+        for entry in std::fs::read_dir(&history_dir)? {
+            let cache_dir = entry?.path().join(CACHE_DIR);
+            for file in util::fs::rlist_files_in_dir(&cache_dir) {
+                if is_duckdb_file(&file) {
+                    paths.push(file);
+                }
+            }
+        }
+    }
+
+    for file in util::fs::rlist_files_in_dir(&hidden_dir.join(VERSIONS_DIR)) {
+        if is_resized_image(&file) {
+            paths.push(file);
+        }
+    }
+
+    Ok(paths)
+}
+
+fn is_duckdb_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    name.ends_with(".duckdb") || name.ends_with(".duckdb.wal")
+}
+
+// Resized images are saved next to the version file as `{width}x{height}.{ext}`
+fn is_resized_image(path: &Path) -> bool {
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return false;
+    };
+    if stem == VERSION_FILE_NAME {
+        return false;
+    }
+    match stem.split_once('x') {
+        Some((width, height)) => {
+            width.chars().all(|c| c.is_ascii_digit()) && height.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+fn disk_usage(path: &Path) -> u64 {
+    if path.is_dir() {
+        util::fs::rlist_files_in_dir(path)
+            .iter()
+            .filter_map(|file| std::fs::symlink_metadata(file).ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    } else {
+        std::fs::symlink_metadata(path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use time::OffsetDateTime;
+
+    use crate::api;
+    use crate::command;
+    use crate::constants;
+    use crate::error::OxenError;
+    use crate::opts::CleanOpts;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_clean_moves_untracked_to_trash_and_undo() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(repo.path.join("hello.txt"), "hello")?;
+            command::add(&repo, repo.path.join("hello.txt"))?;
+            command::commit(&repo, "adding hello")?;
+
+            util::fs::write_to_path(repo.path.join(".oxenignore"), "*.log\n")?;
+            util::fs::write_to_path(repo.path.join("scratch.txt"), "scratch")?;
+            util::fs::write_to_path(repo.path.join("debug.log"), "debug")?;
+            util::fs::create_dir_all(repo.path.join("tmp").join("nested"))?;
+            util::fs::write_to_path(repo.path.join("tmp").join("a.txt"), "a")?;
+            util::fs::write_to_path(repo.path.join("tmp").join("nested").join("b.txt"), "b")?;
+            util::fs::create_dir_all(repo.path.join("logs"))?;
+            util::fs::write_to_path(repo.path.join("logs").join("c.txt"), "c")?;
+            util::fs::write_to_path(repo.path.join("logs").join("run.log"), "run")?;
+
+            // A dry run reports without touching anything
+            let opts = CleanOpts {
+                dry_run: true,
+                ..CleanOpts::default()
+            };
+            let result = command::clean(&repo, &opts)?;
+            assert!(result
+                .paths
+                .contains(&Path::new("scratch.txt").to_path_buf()));
+            assert!(!result.paths.contains(&Path::new("tmp/a.txt").to_path_buf()));
+            assert!(result.trash_dir.is_none());
+            assert!(repo.path.join("scratch.txt").exists());
+
+            let opts = CleanOpts {
+                dirs: true,
+                ..CleanOpts::default()
+            };
+            let result = command::clean(&repo, &opts)?;
+            assert!(result.paths.contains(&Path::new("tmp/a.txt").to_path_buf()));
+            assert!(!repo.path.join("scratch.txt").exists());
+            assert!(!repo.path.join("tmp").exists());
+            assert!(repo.path.join("debug.log").exists());
+            // Ignored files in untracked dirs stay where they are
+            assert!(!repo.path.join("logs").join("c.txt").exists());
+            assert!(repo.path.join("logs").join("run.log").exists());
+            assert!(repo.path.join("hello.txt").exists());
+            assert!(result.trash_dir.unwrap().join("scratch.txt").exists());
+
+            command::clean::undo(&repo)?;
+            assert!(repo.path.join("scratch.txt").exists());
+            assert!(repo.path.join("tmp").join("a.txt").exists());
+            assert!(repo.path.join("tmp").join("nested").join("b.txt").exists());
+            assert!(repo.path.join("logs").join("c.txt").exists());
+            assert!(command::clean::undo(&repo).is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_clean_caches_removes_resized_images() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let versions_dir = util::fs::oxen_hidden_dir(&repo.path)
+                .join("versions")
+                .join("files")
+                .join("ab")
+                .join("cdef");
+            util::fs::create_dir_all(&versions_dir)?;
+            util::fs::write_to_path(versions_dir.join("data.jpg"), "image")?;
+            util::fs::write_to_path(versions_dir.join("64x64.jpg"), "small")?;
+
+            let opts = CleanOpts {
+                caches: true,
+                ..CleanOpts::default()
+            };
+            let result = command::clean(&repo, &opts)?;
+            assert!(result
+                .paths
+                .iter()
+                .any(|path| path.ends_with("cdef/64x64.jpg")));
+            assert!(result.bytes >= 5);
+            assert!(!versions_dir.join("64x64.jpg").exists());
+            assert!(versions_dir.join("data.jpg").exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_clean_caches_skips_while_repo_is_locked() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let tmp_dir = util::fs::oxen_hidden_dir(&repo.path).join(constants::TMP_DIR);
+            util::fs::create_dir_all(&tmp_dir)?;
+            util::fs::write_to_path(tmp_dir.join("data.csv"), "a,b")?;
+
+            let opts = CleanOpts {
+                caches: true,
+                ..CleanOpts::default()
+            };
+            let mut lock_file = api::local::repositories::get_lock_file(&repo)?;
+            let lock = api::local::repositories::get_exclusive_lock(&mut lock_file)?;
+            assert!(command::clean(&repo, &opts).is_err());
+            assert!(tmp_dir.join("data.csv").exists());
+
+            drop(lock);
+            command::clean(&repo, &opts)?;
+            assert!(!tmp_dir.exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_clean_prunes_old_trash_batches() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let trash_dir = util::fs::oxen_hidden_dir(&repo.path).join("trash");
+            let now = OffsetDateTime::now_utc().unix_timestamp_nanos();
+            // One batch from a year ago and a dozen recent ones
+            let year = 365 * 24 * 60 * 60 * 1_000_000_000_i128;
+            util::fs::create_dir_all(trash_dir.join((now - year).to_string()))?;
+            for i in 0..12 {
+                util::fs::create_dir_all(trash_dir.join((now - 12 + i).to_string()))?;
+            }
+
+            util::fs::write_to_path(repo.path.join("scratch.txt"), "scratch")?;
+            let result = command::clean(&repo, &CleanOpts::default())?;
+
+            let batches = std::fs::read_dir(&trash_dir)?.count();
+            assert_eq!(batches, 10);
+            assert!(result.trash_dir.unwrap().join("scratch.txt").exists());
+            assert!(!trash_dir.join((now - year).to_string()).exists());
+
+            Ok(())
+        })
+    }
+}
@@ -61,6 +61,7 @@ async fn _clone(
         shallow,
         all,
         branch: DEFAULT_BRANCH_NAME.to_string(),
+        link_checkout: false,
     };
     clone(&opts).await
 }
@@ -110,11 +111,24 @@ async fn clone_repo(
     let mut local_repo = LocalRepository::from_remote(remote_repo.clone(), repo_path)?;
     repo_path.clone_into(&mut local_repo.path);
     local_repo.set_remote(DEFAULT_REMOTE_NAME, &remote_repo.remote.url);
+    // Hash new files the way the remote does
+    let hash_algorithm = api::remote::repositories::get_repo_data_by_remote(&remote_repo.remote)
+        .await?
+        .and_then(|view| view.hash_algorithm);
+    if let Some(hash_algorithm) = hash_algorithm {
+        local_repo.set_hash_algorithm(hash_algorithm);
+    }
+    if opts.link_checkout {
+        local_repo.set_link_checkout(true);
+    }
 
     // Save remote config in .oxen/config.toml
     let remote_cfg = RemoteConfig {
         remote_name: Some(DEFAULT_REMOTE_NAME.to_string()),
         remotes: vec![remote_repo.remote.clone()],
+        hash_algorithm,
+        compress_versions: None,
+        link_checkout: opts.link_checkout.then_some(true),
     };
 
     let toml = toml::to_string(&remote_cfg)?;
@@ -5,6 +5,7 @@
 
 use crate::api;
 use crate::command;
+use crate::core::hooks::{self, Hook, HookInput};
 use crate::core::index::CommitEntryWriter;
 use crate::error;
 use crate::error::OxenError;
@@ -51,7 +52,20 @@ Stage a file or directory with `oxen add <file>`"
         ));
     }
 
-    let commit = api::local::commits::commit(repo, &status, message)?;
+    if hooks::is_installed(repo, Hook::PreCommit) {
+        let mut input = HookInput::new(repo, Hook::PreCommit)?.with_staged_data(&status);
+        input.message = Some(message.to_string());
+        hooks::run(repo, &input)?;
+    }
+    let message = if hooks::is_installed(repo, Hook::CommitMsg) {
+        let mut input = HookInput::new(repo, Hook::CommitMsg)?.with_staged_data(&status);
+        input.message = Some(message.to_string());
+        hooks::run_commit_msg(repo, &input, message)?
+    } else {
+        message.to_string()
+    };
+
+    let commit = api::local::commits::commit(repo, &status, &message)?;
     // Open then close commit entry writer to force indexing on rocksbds
     {
         // Get time here
@@ -60,6 +74,13 @@ Stage a file or directory with `oxen add <file>`"
         let _elapsed = start.elapsed();
     }
     log::info!("DONE COMMITTING in command::commit {}", commit);
+
+    if hooks::is_installed(repo, Hook::PostCommit) {
+        let mut input = HookInput::new(repo, Hook::PostCommit)?.with_staged_data(&status);
+        input.message = Some(message);
+        input.commit = Some(commit.to_owned());
+        hooks::run(repo, &input)?;
+    }
     Ok(commit)
 }
 
@@ -51,6 +51,8 @@ pub async fn compute_cache(
     let commits = if let Some(revision) = revision {
         let opts = LogOpts {
             revision: Some(revision),
+            path: None,
+            follow: false,
             remote: false,
         };
         api::local::commits::list_with_opts(repo, &opts).await?
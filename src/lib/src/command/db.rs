@@ -3,10 +3,11 @@
 //! Print out values from a rocksdb key value database
 //!
 
+use crate::core::db::key_val::kv_db;
 use crate::error::OxenError;
 
 use bytevec::ByteDecodable;
-use rocksdb::{IteratorMode, LogLevel, Options, DB};
+use rocksdb::{LogLevel, Options, DB};
 use std::path::Path;
 use std::str;
 
@@ -19,7 +20,7 @@ pub fn list(path: impl AsRef<Path>) -> Result<Vec<(String, String)>, OxenError>
     let mut result: Vec<(String, String)> = Vec::new();
 
     let db = DB::open_for_read_only(&opts, dunce::simplified(path), false)?;
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(&db);
     for item in iter {
         match item {
             Ok((key, value)) => {
@@ -50,7 +51,7 @@ pub fn get(path: impl AsRef<Path>, key: impl AsRef<str>) -> Result<String, OxenE
     opts.set_log_level(LogLevel::Fatal);
 
     let db = DB::open_for_read_only(&opts, dunce::simplified(path), false)?;
-    if let Some(value) = db.get(key)? {
+    if let Some(value) = kv_db::get_bytes(&db, key)? {
         Ok(String::from_utf8(value)?)
     } else {
         Err(OxenError::basic_str(format!("Key {} not found", key)))
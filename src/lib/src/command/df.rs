@@ -6,12 +6,28 @@
 use std::path::Path;
 
 use crate::core::df::tabular;
+use crate::core::index::oxenattributes::{FileAttributes, OxenAttributes};
 use crate::error::OxenError;
+use crate::model::LocalRepository;
 use crate::opts::DFOpts;
 use crate::util;
 
+/// The `.oxenattributes` of a file, if it is in a repository
+fn file_attributes(path: &Path) -> Result<FileAttributes, OxenError> {
+    let path = dunce::canonicalize(path).unwrap_or(path.to_path_buf());
+    let Some(repo_path) = path.parent().and_then(util::fs::get_repo_root) else {
+        return Ok(FileAttributes::default());
+    };
+    let Ok(repo) = LocalRepository::from_dir(&repo_path) else {
+        return Ok(FileAttributes::default());
+    };
+    let relative_path = util::fs::path_relative_to_dir(&path, &repo_path)?;
+    Ok(OxenAttributes::from_repo(&repo)?.attributes_for(relative_path))
+}
+
 /// Interact with DataFrames
-pub fn df<P: AsRef<Path>>(input: P, opts: DFOpts) -> Result<(), OxenError> {
+pub fn df<P: AsRef<Path>>(input: P, mut opts: DFOpts) -> Result<(), OxenError> {
+    file_attributes(input.as_ref())?.apply_to_df_opts(input.as_ref(), &mut opts);
     let mut df = tabular::show_path(input, opts.clone())?;
 
     if let Some(write) = opts.write {
@@ -28,13 +44,18 @@ pub fn df<P: AsRef<Path>>(input: P, opts: DFOpts) -> Result<(), OxenError> {
 }
 
 /// Get a human readable schema for a DataFrame
-pub fn schema<P: AsRef<Path>>(input: P, flatten: bool, opts: DFOpts) -> Result<String, OxenError> {
+pub fn schema<P: AsRef<Path>>(
+    input: P,
+    flatten: bool,
+    mut opts: DFOpts,
+) -> Result<String, OxenError> {
+    file_attributes(input.as_ref())?.apply_to_df_opts(input.as_ref(), &mut opts);
     tabular::schema_to_string(input, flatten, &opts)
 }
 
 /// Add a row to a dataframe
 pub fn add_row(path: &Path, data: &str) -> Result<(), OxenError> {
-    if util::fs::is_tabular(path) {
+    if file_attributes(path)?.is_tabular(path) {
         let mut opts = DFOpts::empty();
         opts.add_row = Some(data.to_string());
         opts.output = Some(path.to_path_buf());
@@ -47,7 +68,7 @@ pub fn add_row(path: &Path, data: &str) -> Result<(), OxenError> {
 
 /// Add a column to a dataframe
 pub fn add_column(path: &Path, data: &str) -> Result<(), OxenError> {
-    if util::fs::is_tabular(path) {
+    if file_attributes(path)?.is_tabular(path) {
         let mut opts = DFOpts::empty();
         opts.add_col = Some(data.to_string());
         opts.output = Some(path.to_path_buf());
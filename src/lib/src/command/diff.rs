@@ -10,6 +10,7 @@
 
 use std::path::{Path, PathBuf};
 
+use crate::core::index::oxenattributes::OxenAttributes;
 use crate::core::index::MergeConflictReader;
 use crate::error::OxenError;
 use crate::model::diff::DiffResult;
@@ -33,9 +34,25 @@ pub fn diff(
     );
 
     // If the user specifies two files without revisions, we will compare the files on disk
-    if revision_1.is_none() && revision_2.is_none() && path_2.is_some() {
+    if let (None, None, Some(path_2)) = (&revision_1, &revision_2, &path_2) {
         // If we do not have revisions set, just compare the files on disk
-        let result = api::local::diff::diff_files(path_1, path_2.unwrap(), keys, targets, vec![])?;
+        let result = match &repo_dir {
+            Some(repo_dir) => {
+                let repo = LocalRepository::new(repo_dir)?;
+                let relative_path = util::fs::path_relative_to_dir(path_1.as_ref(), repo_dir)
+                    .unwrap_or(path_1.as_ref().to_path_buf());
+                let attributes = OxenAttributes::from_repo(&repo)?.attributes_for(relative_path);
+                api::local::diff::diff_files_with_attributes(
+                    path_1,
+                    path_2,
+                    &attributes,
+                    keys,
+                    targets,
+                    vec![],
+                )?
+            }
+            None => api::local::diff::diff_files(path_1, path_2, keys, targets, vec![])?,
+        };
 
         return Ok(result);
     }
@@ -117,6 +134,8 @@ pub fn diff_commits(
     // TODO - anything we can clean up with this mut initialization?
     let mut path_1 = cpath_1.path.clone();
     let mut path_2 = cpath_2.path.clone();
+    // Decrypted versions only stay on disk while they are held
+    let mut version_files = vec![];
 
     if let Some(commit_1) = cpath_1.commit {
         let entry_1 = api::local::entries::get_commit_entry(repo, &commit_1, &cpath_1.path)?
@@ -126,7 +145,10 @@ pub fn diff_commits(
                 )
             })?;
 
-        path_1 = util::fs::version_path(repo, &entry_1);
+        let version_file =
+            util::fs::readable_version_path(repo, &util::fs::version_path(repo, &entry_1))?;
+        path_1 = version_file.to_path_buf();
+        version_files.push(version_file);
     };
 
     if let Some(mut commit_2) = cpath_2.commit {
@@ -144,10 +166,23 @@ pub fn diff_commits(
                 )
             })?;
 
-        path_2 = util::fs::version_path(repo, &entry_2);
+        let version_file =
+            util::fs::readable_version_path(repo, &util::fs::version_path(repo, &entry_2))?;
+        path_2 = version_file.to_path_buf();
+        version_files.push(version_file);
     };
 
-    let compare_result = api::local::diff::diff_files(path_1, path_2, keys, targets, display)?;
+    let relative_path =
+        util::fs::path_relative_to_dir(&cpath_1.path, &repo.path).unwrap_or(cpath_1.path);
+    let attributes = OxenAttributes::from_repo(repo)?.attributes_for(relative_path);
+    let compare_result = api::local::diff::diff_files_with_attributes(
+        path_1,
+        path_2,
+        &attributes,
+        keys,
+        targets,
+        display,
+    )?;
 
     log::debug!("compare result: {:?}", compare_result);
 
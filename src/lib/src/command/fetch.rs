@@ -82,7 +82,7 @@ mod tests {
             for branch in branches.iter() {
                 command::create_checkout(&repo, branch)?;
                 let filepath = repo.path.join(format!("file_{}.txt", branch));
-                test::write_txt_file_to_path(&filepath, &format!("a file on {}", branch))?;
+                test::write_txt_file_to_path(&filepath, format!("a file on {}", branch))?;
                 command::add(&repo, &filepath)?;
                 command::commit(&repo, &format!("Adding file on {}", branch))?;
                 command::push(&repo).await?;
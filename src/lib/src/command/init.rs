@@ -7,6 +7,7 @@ use std::path::Path;
 
 use crate::error::OxenError;
 use crate::model::LocalRepository;
+use crate::util::hasher::HashAlgorithm;
 use crate::{api, constants, util};
 
 /// # Initialize an Empty Oxen Repository
@@ -25,6 +26,14 @@ use crate::{api, constants, util};
 /// # }
 /// ```
 pub fn init(path: &Path) -> Result<LocalRepository, OxenError> {
+    init_with_hash_algorithm(path, HashAlgorithm::default())
+}
+
+/// # Initialize an Empty Oxen Repository that hashes file contents with `algorithm`
+pub fn init_with_hash_algorithm(
+    path: &Path,
+    algorithm: HashAlgorithm,
+) -> Result<LocalRepository, OxenError> {
     let hidden_dir = util::fs::oxen_hidden_dir(path);
     if hidden_dir.exists() {
         let err = format!("Oxen repository already exists: {path:?}");
@@ -32,7 +41,7 @@ pub fn init(path: &Path) -> Result<LocalRepository, OxenError> {
     }
 
     // Cleanup the .oxen dir if init fails
-    match p_init(path) {
+    match p_init(path, algorithm) {
         Ok(result) => Ok(result),
         Err(error) => {
             util::fs::remove_dir_all(hidden_dir)?;
@@ -41,12 +50,15 @@ pub fn init(path: &Path) -> Result<LocalRepository, OxenError> {
     }
 }
 
-fn p_init(path: &Path) -> Result<LocalRepository, OxenError> {
+fn p_init(path: &Path, algorithm: HashAlgorithm) -> Result<LocalRepository, OxenError> {
     let hidden_dir = util::fs::oxen_hidden_dir(path);
 
     std::fs::create_dir_all(hidden_dir)?;
     let config_path = util::fs::config_filepath(path);
-    let repo = LocalRepository::new(path)?;
+    let mut repo = LocalRepository::new(path)?;
+    if algorithm != HashAlgorithm::default() {
+        repo.set_hash_algorithm(algorithm);
+    }
     repo.save(&config_path)?;
 
     api::local::commits::commit_with_no_files(&repo, constants::INITIAL_COMMIT_MSG)?;
@@ -61,8 +73,10 @@ mod tests {
     use crate::constants;
     use crate::core::index::CommitEntryReader;
     use crate::error::OxenError;
+    use crate::model::LocalRepository;
     use crate::test;
     use crate::util;
+    use crate::util::hasher::HashAlgorithm;
 
     #[test]
     fn test_command_init() -> Result<(), OxenError> {
@@ -101,4 +115,27 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_init_with_blake3_hashes_entries_with_blake3() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            command::init::init_with_hash_algorithm(dir, HashAlgorithm::Blake3)?;
+            let repo = LocalRepository::from_dir(dir)?;
+            assert_eq!(repo.hash_algorithm(), HashAlgorithm::Blake3);
+
+            let file = dir.join("hello.txt");
+            util::fs::write_to_path(&file, "hello")?;
+            command::add(&repo, &file)?;
+            let commit = command::commit(&repo, "Adding hello")?;
+
+            let reader = CommitEntryReader::new(&repo, &commit)?;
+            let entry = reader
+                .get_entry(std::path::Path::new("hello.txt"))?
+                .unwrap();
+            assert_eq!(HashAlgorithm::from_hash(&entry.hash), HashAlgorithm::Blake3);
+            assert!(command::status(&repo)?.is_clean());
+
+            Ok(())
+        })
+    }
 }
@@ -4,6 +4,7 @@
 //!
 
 use crate::api;
+use crate::core::hooks::{self, Hook, HookInput};
 use crate::core::index::Merger;
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
@@ -33,6 +34,12 @@ pub fn merge<S: AsRef<str>>(
             merge_branch_name, base_branch.name
         );
         println!("HEAD -> {}", commit.id);
+        if hooks::is_installed(repo, Hook::PostMerge) {
+            let mut input = HookInput::new(repo, Hook::PostMerge)?;
+            input.merge_branch = Some(merge_branch.name.to_owned());
+            input.commit = Some(commit.to_owned());
+            hooks::run(repo, &input)?;
+        }
         Ok(Some(commit))
     } else {
         eprintln!("Automatic merge failed; fix conflicts and then commit the result.");
@@ -17,6 +17,9 @@ pub use cache_dataframe_size::CacheDataFrameSizeMigration;
 pub mod add_directories_to_cache;
 pub use add_directories_to_cache::AddDirectoriesToCacheMigration;
 
+pub mod compress_version_files;
+pub use compress_version_files::CompressVersionFilesMigration;
+
 pub trait Migrate {
     fn up(&self, path: &Path, all: bool) -> Result<(), OxenError>;
     fn down(&self, path: &Path, all: bool) -> Result<(), OxenError>;
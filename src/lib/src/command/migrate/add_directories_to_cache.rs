@@ -52,7 +52,7 @@ pub fn add_directories_to_cache_up(repo: &LocalRepository) -> Result<(), OxenErr
 
     let mut all_commits = reader.list_all()?;
 
-    all_commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    all_commits.sort_by_key(|commit| commit.timestamp);
 
     for commit in all_commits {
         cache::cachers::content_stats::compute(repo, &commit)?;
@@ -83,7 +83,7 @@ pub fn cache_data_frame_size_up(repo: &LocalRepository) -> Result<(), OxenError>
     let reader = CommitReader::new(repo)?;
     let mut all_commits = reader.list_all()?;
     // Sort by timestamp from oldest to newest
-    all_commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    all_commits.sort_by_key(|commit| commit.timestamp);
 
     for current_commit in &all_commits {
         cachers::df_size::compute(repo, current_commit)?;
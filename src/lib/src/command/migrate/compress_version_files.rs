@@ -0,0 +1,230 @@
+use std::path::{Path, PathBuf};
+
+use jwalk::WalkDir;
+
+use super::Migrate;
+
+use crate::constants::{
+    COMPRESSED_VERSION_EXTENSION, DATA_ARROW_FILE, ENCRYPTED_VERSION_EXTENSION, FILES_DIR, TMP_DIR,
+    VERSIONS_DIR, VERSION_FILE_NAME,
+};
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util::progress_bar::{oxen_progress_bar, ProgressBarType};
+use crate::{api, util};
+
+pub struct CompressVersionFilesMigration;
+impl CompressVersionFilesMigration {}
+
+impl Migrate for CompressVersionFilesMigration {
+    fn name(&self) -> &'static str {
+        "compress_version_files"
+    }
+    fn up(&self, path: &Path, all: bool) -> Result<(), OxenError> {
+        if all {
+            compress_version_files_for_all_repos(path, true)?;
+        } else {
+            let repo = LocalRepository::from_dir(path)?;
+            compress_version_files_up(&repo)?;
+        }
+        Ok(())
+    }
+
+    fn down(&self, path: &Path, all: bool) -> Result<(), OxenError> {
+        if all {
+            compress_version_files_for_all_repos(path, false)?;
+        } else {
+            println!("Running down migration");
+            let repo = LocalRepository::from_dir(path)?;
+            compress_version_files_down(&repo)?;
+        }
+        Ok(())
+    }
+
+    fn is_needed(&self, _repo: &LocalRepository) -> Result<bool, OxenError> {
+        // Compression is opt in, never needed
+        Ok(false)
+    }
+}
+
+fn compress_version_files_for_all_repos(path: &Path, up: bool) -> Result<(), OxenError> {
+    println!("🐂 Collecting namespaces to migrate...");
+    let namespaces = api::local::repositories::list_namespaces(path)?;
+    let bar = oxen_progress_bar(namespaces.len() as u64, ProgressBarType::Counter);
+    println!("🐂 Migrating {} namespaces", namespaces.len());
+    for namespace in namespaces {
+        let namespace_path = path.join(namespace);
+        let repos = api::local::repositories::list_repos_in_namespace(&namespace_path);
+        for repo in repos {
+            let result = LocalRepository::from_dir(&repo.path).and_then(|repo| {
+                if up {
+                    compress_version_files_up(&repo)
+                } else {
+                    compress_version_files_down(&repo)
+                }
+            });
+            if let Err(err) = result {
+                log::error!(
+                    "Could not migrate version files for repo {:?}\nErr: {}",
+                    repo.path.canonicalize(),
+                    err
+                )
+            }
+        }
+        bar.inc(1);
+    }
+
+    Ok(())
+}
+
+// The version files themselves, not the HASH files or caches the server keeps next to them
+fn list_version_files(repo: &LocalRepository, compressed: bool) -> Vec<PathBuf> {
+    let files_dir = util::fs::oxen_hidden_dir(&repo.path)
+        .join(VERSIONS_DIR)
+        .join(FILES_DIR);
+    let mut paths = vec![];
+    for entry in WalkDir::new(&files_dir).into_iter().flatten() {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy();
+        if !file_name.starts_with(VERSION_FILE_NAME) || file_name == DATA_ARROW_FILE {
+            continue;
+        }
+        // Encrypted version files are compressed before they are encrypted, if at all
+        if util::fs::has_ext(&path, ENCRYPTED_VERSION_EXTENSION) {
+            continue;
+        }
+        let is_compressed = util::fs::has_ext(&path, COMPRESSED_VERSION_EXTENSION);
+        if is_compressed == compressed {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Compresses every version file in the repository and turns compression on for new ones
+pub fn compress_version_files_up(repo: &LocalRepository) -> Result<(), OxenError> {
+    let mut lock_file = api::local::repositories::get_lock_file(repo)?;
+    let _mutex = api::local::repositories::get_exclusive_lock(&mut lock_file)?;
+
+    let paths = list_version_files(repo, false);
+    let bar = oxen_progress_bar(paths.len() as u64, ProgressBarType::Counter);
+    for path in paths {
+        let compressed_path = util::fs::compressed_version_path(&path);
+        log::debug!("Compressing {:?} to {:?}", path, compressed_path);
+        util::fs::compress_file(&path, &compressed_path)?;
+        util::fs::remove_file(&path)?;
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    let mut repo = repo.clone();
+    repo.set_compress_versions(true);
+    repo.save(&util::fs::config_filepath(&repo.path))?;
+    Ok(())
+}
+
+/// Decompresses every version file in the repository and turns compression off
+pub fn compress_version_files_down(repo: &LocalRepository) -> Result<(), OxenError> {
+    let mut lock_file = api::local::repositories::get_lock_file(repo)?;
+    let _mutex = api::local::repositories::get_exclusive_lock(&mut lock_file)?;
+
+    let paths = list_version_files(repo, true);
+    let bar = oxen_progress_bar(paths.len() as u64, ProgressBarType::Counter);
+    for path in paths {
+        let version_path = path.with_extension("");
+        log::debug!("Decompressing {:?} to {:?}", path, version_path);
+        util::fs::decompress_file(&path, &version_path)?;
+        util::fs::remove_file(&path)?;
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    // Everything is readable in place again
+    let cache_dir = util::fs::oxen_hidden_dir(&repo.path)
+        .join(TMP_DIR)
+        .join(VERSIONS_DIR);
+    if cache_dir.exists() {
+        util::fs::remove_dir_all(&cache_dir)?;
+    }
+
+    let mut repo = repo.clone();
+    repo.set_compress_versions(false);
+    repo.save(&util::fs::config_filepath(&repo.path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::path::Path;
+
+    use crate::api;
+    use crate::command;
+    use crate::command::migrate::{CompressVersionFilesMigration, Migrate};
+    use crate::error::OxenError;
+    use crate::model::LocalRepository;
+    use crate::opts::RestoreOpts;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_compress_version_files_up_and_down() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            let original = util::fs::read_from_path(repo.path.join(&path))?;
+            let head = api::local::commits::head_commit(&repo)?;
+            let entry = api::local::entries::get_commit_entry(&repo, &head, &path)?.unwrap();
+            let version_path = util::fs::version_path(&repo, &entry);
+
+            CompressVersionFilesMigration.up(&repo.path, false)?;
+            let repo = LocalRepository::from_dir(&repo.path)?;
+            assert!(repo.compresses_versions());
+            assert!(!version_path.exists());
+            assert!(util::fs::compressed_version_path(&version_path).exists());
+
+            // Restore still sees the original contents
+            util::fs::remove_file(repo.path.join(&path))?;
+            command::restore(&repo, RestoreOpts::from_path_ref(&path, head.id.clone()))?;
+            assert_eq!(util::fs::read_from_path(repo.path.join(&path))?, original);
+            assert!(command::status(&repo)?.is_clean());
+
+            // Reads from an offset skip what comes before it
+            let mut rest = String::new();
+            util::fs::open_version_file_at(&version_path, 5)?.read_to_string(&mut rest)?;
+            assert_eq!(rest, original[5..]);
+            assert_eq!(
+                util::fs::version_file_mime_type(&version_path)?,
+                "text/plain"
+            );
+
+            // New commits are stored compressed too
+            let new_file = repo.path.join("new.txt");
+            util::fs::write_to_path(&new_file, "compress me")?;
+            command::add(&repo, &new_file)?;
+            let commit = command::commit(&repo, "Adding new file")?;
+            let entry =
+                api::local::entries::get_commit_entry(&repo, &commit, Path::new("new.txt"))?
+                    .unwrap();
+            let new_version_path = util::fs::version_path(&repo, &entry);
+            assert!(util::fs::compressed_version_path(&new_version_path).exists());
+            assert_eq!(
+                util::hasher::hash_version_file_contents(&new_version_path, &entry.hash)?,
+                entry.hash
+            );
+
+            CompressVersionFilesMigration.down(&repo.path, false)?;
+            let repo = LocalRepository::from_dir(&repo.path)?;
+            assert!(!repo.compresses_versions());
+            assert!(version_path.exists());
+            assert_eq!(util::fs::read_from_path(&version_path)?, original);
+            assert!(new_version_path.exists());
+
+            Ok(())
+        })
+    }
+}
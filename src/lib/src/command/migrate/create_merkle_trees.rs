@@ -92,7 +92,7 @@ pub fn create_merkle_trees_up(repo: &LocalRepository) -> Result<(), OxenError> {
     let all_commits = reader.list_all()?;
     // sort these by timestamp from oldest to newest
     let mut all_commits = all_commits.clone();
-    all_commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    all_commits.sort_by_key(|commit| commit.timestamp);
 
     let bar = oxen_progress_bar(all_commits.len() as u64, ProgressBarType::Counter);
     let commit_writer = CommitWriter::new(repo)?;
@@ -85,7 +85,7 @@ pub fn propagate_schemas_up(repo: &LocalRepository) -> Result<(), OxenError> {
     let reader = CommitReader::new(repo)?;
     let mut all_commits = reader.list_all()?;
     // Sort by timestamp from oldest to newest
-    all_commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    all_commits.sort_by_key(|commit| commit.timestamp);
 
     for current_commit in &all_commits {
         for parent_commit_id in &current_commit.parent_ids {
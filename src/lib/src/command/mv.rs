@@ -0,0 +1,216 @@
+//! # oxen mv
+//!
+//! Move or rename files and directories, staging the move
+//!
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::command::add;
+use crate::core::index::{CommitEntryReader, Stager};
+use crate::error::OxenError;
+use crate::model::{LocalRepository, StagedEntryStatus};
+use crate::util;
+
+/// # Move a file or directory and stage the rename
+///
+/// Paths are relative to the root of the repository. If `dst` is an existing
+/// directory the source is moved into it, otherwise it is renamed to `dst`.
+/// The old path is staged as removed and the new path as added, `oxen status`
+/// pairs them up by hash and shows them as moved.
+pub fn mv(
+    repo: &LocalRepository,
+    src: impl AsRef<Path>,
+    dst: impl AsRef<Path>,
+) -> Result<PathBuf, OxenError> {
+    let src = src.as_ref();
+    let full_src = repo.path.join(src);
+    if !full_src.exists() {
+        return Err(OxenError::path_does_not_exist(src));
+    }
+
+    let mut dst = dst.as_ref().to_path_buf();
+    if repo.path.join(&dst).is_dir() {
+        if let Some(file_name) = src.file_name() {
+            dst = dst.join(file_name);
+        }
+    }
+    let full_dst = repo.path.join(&dst);
+    if full_dst.exists() {
+        let error = format!("Destination {dst:?} already exists");
+        return Err(OxenError::basic_str(error));
+    }
+    if full_dst.starts_with(&full_src) {
+        let error = format!("Cannot move {src:?} into itself");
+        return Err(OxenError::basic_str(error));
+    }
+
+    let stager = Stager::new(repo)?;
+    let commit_reader = CommitEntryReader::new_from_head(repo)?;
+    let committed = committed_paths(&commit_reader, src)?;
+    let staged = staged_paths(&stager, &commit_reader, src)?;
+    if committed.is_empty() && staged.is_empty() {
+        let error = format!("Path {src:?} is not tracked, use `oxen add` to track it");
+        return Err(OxenError::basic_str(error));
+    }
+
+    if let Some(parent) = full_dst.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    util::fs::rename(&full_src, &full_dst)?;
+
+    // Staged changes move with the files, the committed files are staged as removed below
+    if !staged.is_empty() {
+        if stager.has_staged_dir(src) {
+            stager.remove_staged_dir(src)?;
+        } else {
+            for path in &staged {
+                stager.remove_staged_file(path)?;
+            }
+        }
+    }
+    // Release the staging dbs before `add` opens them again
+    drop(stager);
+
+    // Only what was tracked is staged at the destination, untracked files moved along with a
+    // directory stay untracked
+    let mut paths: HashSet<PathBuf> = committed
+        .union(&staged)
+        .filter_map(|path| path.strip_prefix(src).ok())
+        .map(|relative| {
+            if relative == Path::new("") {
+                full_dst.to_owned()
+            } else {
+                full_dst.join(relative)
+            }
+        })
+        .filter(|path| path.exists())
+        .collect();
+    if !committed.is_empty() {
+        paths.insert(full_src);
+    }
+    add::add_paths(repo, paths)?;
+
+    Ok(dst)
+}
+
+// Files under `src` in the head commit
+fn committed_paths(
+    commit_reader: &CommitEntryReader,
+    src: &Path,
+) -> Result<HashSet<PathBuf>, OxenError> {
+    if commit_reader.has_file(src) {
+        return Ok(HashSet::from([src.to_path_buf()]));
+    }
+    if !commit_reader.has_dir(src) {
+        return Ok(HashSet::new());
+    }
+    Ok(commit_reader
+        .list_directory(src)?
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect())
+}
+
+// Files under `src` that are staged as added or modified
+fn staged_paths(
+    stager: &Stager,
+    commit_reader: &CommitEntryReader,
+    src: &Path,
+) -> Result<HashSet<PathBuf>, OxenError> {
+    let staged_data = stager.status_without_untracked(commit_reader)?;
+    Ok(staged_data
+        .staged_files
+        .into_iter()
+        .filter(|(path, entry)| path.starts_with(src) && entry.status != StagedEntryStatus::Removed)
+        .map(|(path, _)| path)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::model::StagedEntryStatus;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_mv_file_stages_rename() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let src = Path::new("data.txt");
+            util::fs::write_to_path(repo.path.join(src), "some data")?;
+            command::add(&repo, repo.path.join(src))?;
+            command::commit(&repo, "adding data")?;
+            util::fs::create_dir_all(repo.path.join("train"))?;
+
+            let dst = command::mv(&repo, src, Path::new("train"))?;
+            assert_eq!(dst, Path::new("train").join("data.txt"));
+            assert!(!repo.path.join(src).exists());
+            assert!(repo.path.join(&dst).exists());
+
+            let status = command::status(&repo)?;
+            assert_eq!(status.moved_files.len(), 1);
+            assert_eq!(status.moved_files[0].0, dst);
+            assert_eq!(status.moved_files[0].1, src);
+            assert_eq!(
+                status.staged_files.get(&dst).unwrap().status,
+                StagedEntryStatus::Added
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_mv_dir_stages_only_tracked_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let src = Path::new("images");
+            util::fs::create_dir_all(repo.path.join(src))?;
+            util::fs::write_to_path(repo.path.join(src).join("cat.txt"), "cat")?;
+            command::add(&repo, repo.path.join(src))?;
+            command::commit(&repo, "adding images")?;
+            util::fs::write_to_path(repo.path.join(src).join("dog.txt"), "dog")?;
+            util::fs::write_to_path(repo.path.join(src).join("notes.txt"), "notes")?;
+            command::add(&repo, repo.path.join(src).join("dog.txt"))?;
+
+            let dst = command::mv(&repo, src, Path::new("pictures"))?;
+            assert!(repo.path.join(&dst).join("notes.txt").exists());
+
+            let status = command::status(&repo)?;
+            assert!(status.staged_files.contains_key(&dst.join("cat.txt")));
+            assert!(status.staged_files.contains_key(&dst.join("dog.txt")));
+            assert!(!status.staged_files.contains_key(&dst.join("notes.txt")));
+            assert!(!status.staged_files.contains_key(&src.join("dog.txt")));
+            assert_eq!(
+                status
+                    .staged_files
+                    .get(&src.join("cat.txt"))
+                    .unwrap()
+                    .status,
+                StagedEntryStatus::Removed
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_mv_errors_if_destination_exists() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let src = Path::new("a.txt");
+            let dst = Path::new("b.txt");
+            util::fs::write_to_path(repo.path.join(src), "a")?;
+            util::fs::write_to_path(repo.path.join(dst), "b")?;
+            command::add(&repo, &repo.path)?;
+            command::commit(&repo, "adding files")?;
+
+            assert!(command::mv(&repo, src, dst).is_err());
+            assert!(repo.path.join(src).exists());
+
+            Ok(())
+        })
+    }
+}
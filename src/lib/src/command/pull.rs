@@ -590,6 +590,7 @@ mod tests {
                     branch: branch_name.to_owned(),
                     shallow: false,
                     all: false,
+                    link_checkout: false,
                 };
                 let cloned_repo = command::clone(&opts).await?;
 
@@ -668,6 +669,7 @@ mod tests {
                     branch: DEFAULT_BRANCH_NAME.to_string(),
                     shallow: false,
                     all: false,
+                    link_checkout: false,
                 };
                 let cloned_repo = command::clone(&opts).await?;
 
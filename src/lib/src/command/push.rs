@@ -5,6 +5,7 @@
 
 use crate::api;
 use crate::constants::DEFAULT_BRANCH_NAME;
+use crate::core::hooks::{self, Hook, HookInput};
 use crate::core::index::{pusher, EntryIndexer};
 use crate::error::OxenError;
 use crate::model::{Branch, LocalRepository, RemoteBranch, RemoteRepository};
@@ -59,6 +60,7 @@ pub async fn push(repo: &LocalRepository) -> Result<Branch, OxenError> {
 
     let local_branch_cpy = local_branch.clone();
     remote_branch.branch = local_branch_cpy.clone().name;
+    run_pre_push_hook(repo, &remote_branch, &local_branch)?;
     indexer.push(local_branch_cpy, remote_branch).await?;
     Ok(local_branch)
 }
@@ -78,16 +80,38 @@ pub async fn push_remote_branch(
         remote: String::from(remote),
         branch: String::from(branch_name),
     };
+    run_pre_push_hook(repo, &remote_branch, &local_branch)?;
     indexer.push(local_branch.clone(), remote_branch).await?;
     Ok(local_branch)
 }
 
+// The pre-push hook can veto pushing the local branch to the remote branch
+fn run_pre_push_hook(
+    repo: &LocalRepository,
+    remote_branch: &RemoteBranch,
+    local_branch: &Branch,
+) -> Result<(), OxenError> {
+    if !hooks::is_installed(repo, Hook::PrePush) {
+        return Ok(());
+    }
+    let mut input = HookInput::new(repo, Hook::PrePush)?;
+    input.branch = Some(remote_branch.branch.to_owned());
+    input.remote = Some(remote_branch.remote.to_owned());
+    input.commit = api::local::commits::get_by_id(repo, &local_branch.commit_id)?;
+    hooks::run(repo, &input)
+}
+
 /// Push to a specific remote repository
 pub async fn push_remote_repo_branch(
     local_repo: LocalRepository,
     remote_repo: RemoteRepository,
     branch: Branch,
 ) -> Result<RemoteRepository, OxenError> {
+    let remote_branch = RemoteBranch {
+        remote: remote_repo.remote.name.to_owned(),
+        branch: branch.name.to_owned(),
+    };
+    run_pre_push_hook(&local_repo, &remote_branch, &branch)?;
     pusher::push_remote_repo(&local_repo, remote_repo, branch).await
 }
 
@@ -389,7 +413,7 @@ mod tests {
                 let dir_path = data_dir.join(format!("{}", i));
                 util::fs::create_dir_all(&dir_path)?;
                 let file_path = dir_path.join("file.txt");
-                let file_path = test::write_txt_file_to_path(file_path, &format!("file -> {}", i))?;
+                let file_path = test::write_txt_file_to_path(file_path, format!("file -> {}", i))?;
                 command::add(&repo, &file_path)?;
                 command::commit(&repo, &format!("Adding file -> data/{}/file.txt", i))?;
             }
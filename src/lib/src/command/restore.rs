@@ -182,6 +182,53 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_command_restore_with_link_checkout() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            repo.set_link_checkout(true);
+            repo.save_default()?;
+
+            let hello_filename = "hello.txt";
+            let hello_file = repo.path.join(hello_filename);
+            util::fs::write_to_path(&hello_file, "Hello World")?;
+            command::add(&repo, &hello_file)?;
+            let first_commit = command::commit(&repo, "My message")?;
+
+            // Editors replace the file rather than writing through the link
+            util::fs::remove_file(&hello_file)?;
+            util::fs::write_to_path(&hello_file, "Hola Mundo")?;
+            command::add(&repo, &hello_file)?;
+            let second_commit = command::commit(&repo, "Changing to spanish")?;
+
+            // Restoring over a linked file replaces the link instead of writing into it
+            command::restore(
+                &repo,
+                RestoreOpts::from_path_ref(hello_filename, &first_commit.id),
+            )?;
+            assert_eq!(util::fs::read_from_path(&hello_file)?, "Hello World");
+            command::restore(
+                &repo,
+                RestoreOpts::from_path_ref(hello_filename, &second_commit.id),
+            )?;
+            assert_eq!(util::fs::read_from_path(&hello_file)?, "Hola Mundo");
+
+            // Once the link is broken the file can be edited without touching the version
+            util::fs::break_link(&hello_file)?;
+            assert!(!util::fs::is_linked_file(&hello_file));
+            util::fs::write_to_path(&hello_file, "Bonjour le monde")?;
+            let entry = api::local::entries::get_commit_entry(
+                &repo,
+                &second_commit,
+                Path::new(hello_filename),
+            )?
+            .unwrap();
+            let version_path = util::fs::version_path(&repo, &entry);
+            assert_eq!(util::fs::read_from_path(version_path)?, "Hola Mundo");
+
+            Ok(())
+        })
+    }
+
     #[tokio::test]
     async fn test_command_restore_removed_file_from_branch_with_commits_between(
     ) -> Result<(), OxenError> {
@@ -0,0 +1,37 @@
+//! # oxen search
+//!
+//! Search the text and tabular files of a revision
+//!
+
+use crate::api;
+use crate::error::OxenError;
+use crate::model::{LocalRepository, SearchResult};
+use crate::opts::SearchOpts;
+use crate::view::Pagination;
+
+/// Search a revision of the repository, defaults to HEAD.
+/// If `opts.remote` is set, the search runs on the remote repository.
+pub async fn search(
+    repo: &LocalRepository,
+    opts: &SearchOpts,
+) -> Result<(Vec<SearchResult>, Pagination), OxenError> {
+    if opts.remote {
+        let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
+        let revision = match &opts.revision {
+            Some(revision) => revision.clone(),
+            None => match api::local::branches::current_branch(repo)? {
+                Some(branch) => branch.name,
+                None => api::local::commits::head_commit(repo)?.id,
+            },
+        };
+        let response = api::remote::search::search(&remote_repo, revision, opts).await?;
+        return Ok((response.results, response.pagination));
+    }
+
+    let commit = match &opts.revision {
+        Some(revision) => api::local::revisions::get(repo, revision)?
+            .ok_or(OxenError::revision_not_found(revision.to_owned().into()))?,
+        None => api::local::commits::head_commit(repo)?,
+    };
+    api::local::search::search(repo, &commit, opts)
+}
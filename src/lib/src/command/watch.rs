@@ -0,0 +1,148 @@
+//! # oxen watch
+//!
+//! Watch the working directory for changes so `oxen status` and `oxen add` only have to look at
+//! the files that changed
+//!
+
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::Instant;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::core::index::fs_journal::{self, JournalWriter, WatcherState, HEARTBEAT_INTERVAL};
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util;
+
+/// # oxen watch
+///
+/// Records every path that changes in the repository into the fs journal until the watcher is
+/// stopped with `stop` or replaced by another watcher. Blocks, run it in its own process.
+pub fn watch(repo: &LocalRepository) -> Result<(), OxenError> {
+    let root = repo.path.canonicalize()?;
+    let (sender, receiver) = channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    // Watch before starting the journal so nothing slips in between
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+    let mut writer = JournalWriter::create(&repo.path)?;
+    log::debug!("oxen watch started journal {}", writer.generation());
+
+    let mut last_heartbeat = Instant::now();
+    loop {
+        match receiver.recv_timeout(HEARTBEAT_INTERVAL) {
+            Ok(Ok(event)) => {
+                if event.need_rescan() {
+                    // The OS dropped events, nobody can trust the journal anymore
+                    writer.restart()?;
+                } else if !matches!(event.kind, EventKind::Access(_)) {
+                    let paths: Vec<PathBuf> = event
+                        .paths
+                        .iter()
+                        .filter_map(|path| path.strip_prefix(&root).ok())
+                        .filter(|path| {
+                            !path.as_os_str().is_empty()
+                                && (!util::fs::is_in_oxen_hidden_dir(path)
+                                    || fs_journal::is_sync_path(path))
+                        })
+                        .map(|path| path.to_path_buf())
+                        .collect();
+                    writer.record(&paths)?;
+                }
+            }
+            Ok(Err(err)) => {
+                log::warn!("oxen watch error: {}", err);
+                writer.restart()?;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        if last_heartbeat.elapsed() >= HEARTBEAT_INTERVAL {
+            if !writer.heartbeat()? {
+                log::debug!("oxen watch stopping journal {}", writer.generation());
+                break;
+            }
+            last_heartbeat = Instant::now();
+        }
+    }
+    writer.finish()
+}
+
+/// Stops the watcher running on the repository, returns false if there was none
+pub fn stop(repo: &LocalRepository) -> Result<bool, OxenError> {
+    fs_journal::stop_watcher(&repo.path)
+}
+
+/// The watcher running on the repository, if any
+pub fn running(repo: &LocalRepository) -> Option<WatcherState> {
+    fs_journal::read_watcher_state(&repo.path).filter(|state| state.is_live())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_watch_status_only_sees_dirty_paths() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let watch_repo = repo.clone();
+            let handle = thread::spawn(move || command::watch::watch(&watch_repo));
+            let deadline = Instant::now() + Duration::from_secs(30);
+            while command::watch::running(&repo).is_none() {
+                if handle.is_finished() {
+                    // Surfaces why the watcher could not start, e.g. out of inotify watches
+                    handle.join().unwrap()?;
+                    panic!("watcher exited before it started");
+                }
+                assert!(Instant::now() < deadline, "watcher did not start in time");
+                thread::sleep(Duration::from_millis(50));
+            }
+
+            // First status scans everything and checkpoints the journal
+            let status = command::status(&repo)?;
+            assert!(status.is_clean());
+
+            let readme = repo.path.join("README.md");
+            util::fs::write_to_path(&readme, "Changed the readme")?;
+            let new_file = repo.path.join("annotations").join("new.txt");
+            util::fs::write_to_path(&new_file, "new file")?;
+            let new_dir = repo.path.join("new_dir");
+            util::fs::create_dir_all(&new_dir)?;
+            util::fs::write_to_path(new_dir.join("a.txt"), "a")?;
+            util::fs::remove_file(repo.path.join("labels.txt"))?;
+
+            let status = command::status(&repo)?;
+            assert_eq!(status.modified_files, vec![Path::new("README.md")]);
+            assert_eq!(status.removed_files, vec![Path::new("labels.txt")]);
+            assert_eq!(
+                status.untracked_files,
+                vec![Path::new("annotations").join("new.txt")]
+            );
+            assert_eq!(status.untracked_dirs.len(), 1);
+            assert_eq!(status.untracked_dirs[0].0, Path::new("new_dir"));
+
+            // Still dirty, so still reported without being touched again
+            let status = command::status(&repo)?;
+            assert_eq!(status.modified_files.len(), 1);
+            assert_eq!(status.untracked_dirs.len(), 1);
+
+            // Add only picks up the dirty files
+            command::add(&repo, &repo.path)?;
+            let status = command::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 3);
+
+            assert!(command::watch::stop(&repo)?);
+            handle.join().unwrap()?;
+            assert!(command::watch::running(&repo).is_none());
+            Ok(())
+        })
+    }
+}
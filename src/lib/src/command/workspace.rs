@@ -9,6 +9,8 @@ pub mod create;
 pub mod delete;
 pub mod df;
 pub mod diff;
+pub mod refresh;
+pub mod rename;
 pub mod restore;
 pub mod rm;
 pub mod status;
@@ -20,6 +22,8 @@ pub use create::create;
 pub use delete::delete;
 pub use df::df;
 pub use diff::diff;
+pub use refresh::refresh;
+pub use rename::rename;
 pub use restore::restore;
 pub use rm::rm;
 pub use status::status;
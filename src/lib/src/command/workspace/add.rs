@@ -99,7 +99,7 @@ fn resolve_remote_add_file_path(
                 // We have to get the remote directory from the opts
                 Ok((opts.directory.clone().unwrap(), path))
             } else {
-                return Err(OxenError::workspace_add_file_not_in_repo(path));
+                Err(OxenError::workspace_add_file_not_in_repo(path))
             }
         }
         Err(err) => {
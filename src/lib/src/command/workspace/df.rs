@@ -13,6 +13,7 @@ use crate::core::df::tabular;
 use crate::error::OxenError;
 use crate::model::LocalRepository;
 use crate::opts::DFOpts;
+use crate::view::data_frames::DataFrameBulkRowsSummary;
 use crate::view::StatusMessage;
 
 /// Interact with Remote DataFrames
@@ -177,3 +178,52 @@ pub async fn index(
     let remote_repo = api::remote::repositories::get_default_remote(repository).await?;
     api::remote::workspaces::data_frames::index(&remote_repo, workspace_id, path.as_ref()).await
 }
+
+/// Insert or update the rows of `file` in the workspace data frame, matched on the `keys` columns
+pub async fn upsert_rows(
+    repository: &LocalRepository,
+    workspace_id: &str,
+    path: impl AsRef<Path>,
+    file: impl AsRef<Path>,
+    keys: &[String],
+) -> Result<DataFrameBulkRowsSummary, OxenError> {
+    let remote_repo = api::remote::repositories::get_default_remote(repository).await?;
+    api::remote::workspaces::data_frames::rows::upsert(
+        &remote_repo,
+        workspace_id,
+        path.as_ref(),
+        file.as_ref(),
+        keys,
+    )
+    .await
+}
+
+/// Delete the rows of the workspace data frame that match `file` on the `keys` columns
+pub async fn delete_rows(
+    repository: &LocalRepository,
+    workspace_id: &str,
+    path: impl AsRef<Path>,
+    file: impl AsRef<Path>,
+    keys: &[String],
+) -> Result<DataFrameBulkRowsSummary, OxenError> {
+    let remote_repo = api::remote::repositories::get_default_remote(repository).await?;
+    api::remote::workspaces::data_frames::rows::delete_many(
+        &remote_repo,
+        workspace_id,
+        path.as_ref(),
+        file.as_ref(),
+        keys,
+    )
+    .await
+}
+
+/// Download the current state of the workspace data frame to `output`
+pub async fn export(
+    repository: &LocalRepository,
+    workspace_id: &str,
+    path: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<(), OxenError> {
+    let remote_repo = api::remote::repositories::get_default_remote(repository).await?;
+    api::remote::workspaces::data_frames::export(&remote_repo, workspace_id, path, output).await
+}
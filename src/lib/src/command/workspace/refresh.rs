@@ -0,0 +1,22 @@
+//! # oxen workspace refresh
+//!
+//! Move a workspace on a remote repository onto the latest commit of a branch
+//!
+
+use crate::api;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::view::workspaces::WorkspaceRefreshSummary;
+
+pub async fn refresh(
+    repo: &LocalRepository,
+    workspace_id: impl AsRef<str>,
+    branch_name: impl AsRef<str>,
+) -> Result<WorkspaceRefreshSummary, OxenError> {
+    let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
+
+    let response =
+        api::remote::workspaces::refresh(&remote_repo, workspace_id, branch_name).await?;
+
+    Ok(response.summary)
+}
@@ -0,0 +1,20 @@
+//! # oxen workspace rename
+//!
+//! Change the display name of a workspace on a remote repository
+//!
+
+use crate::api;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+
+pub async fn rename(
+    repo: &LocalRepository,
+    workspace_id: impl AsRef<str>,
+    name: impl AsRef<str>,
+) -> Result<(), OxenError> {
+    let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
+
+    api::remote::workspaces::rename(&remote_repo, workspace_id, name).await?;
+
+    Ok(())
+}
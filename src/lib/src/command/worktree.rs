@@ -0,0 +1,33 @@
+//! # oxen worktree
+//!
+//! Check out branches side by side in extra working directories
+//!
+
+use std::path::Path;
+
+use crate::api;
+use crate::error::OxenError;
+use crate::model::{LocalRepository, Worktree};
+
+/// Create a worktree at `path` with `branch` checked out
+pub fn add(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+    branch: impl AsRef<str>,
+) -> Result<Worktree, OxenError> {
+    api::local::worktrees::add(repo, path, branch.as_ref())
+}
+
+/// List the main working directory and every worktree of the repository
+pub fn list(repo: &LocalRepository) -> Result<Vec<Worktree>, OxenError> {
+    api::local::worktrees::list(repo)
+}
+
+/// Delete the worktree at `path`, `force` throws away any uncommitted changes in it
+pub fn remove(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+    force: bool,
+) -> Result<(), OxenError> {
+    api::local::worktrees::remove(repo, path, force)
+}
@@ -4,11 +4,18 @@ use std::path::Path;
 use crate::error::OxenError;
 use crate::model::Remote;
 use crate::util;
+use crate::util::hasher::HashAlgorithm;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RemoteConfig {
     pub remote_name: Option<String>, // this is the current remote name
     pub remotes: Vec<Remote>,
+    // how file contents are hashed, xxh3 when not set
+    pub hash_algorithm: Option<HashAlgorithm>,
+    // whether version files are stored zstd compressed, off when not set
+    pub compress_versions: Option<bool>,
+    // whether checkouts reflink or link to version files instead of copying them, off when not set
+    pub link_checkout: Option<bool>,
 }
 
 impl Default for RemoteConfig {
@@ -22,6 +29,9 @@ impl RemoteConfig {
         RemoteConfig {
             remote_name: None,
             remotes: Vec::new(),
+            hash_algorithm: None,
+            compress_versions: None,
+            link_checkout: None,
         }
     }
 
@@ -15,8 +15,16 @@ pub const TMP_DIR: &str = ".cache";
 pub const CONFIG_DIR: &str = ".config";
 /// .oxenignore is the name of the file that contains the ignore patterns
 pub const OXEN_IGNORE_FILE: &str = ".oxenignore";
+/// .oxenattributes is the name of the file that assigns attributes to paths
+pub const OXEN_ATTRIBUTES_FILE: &str = ".oxenattributes";
 /// Config file for the repository
 pub const REPO_CONFIG_FILENAME: &str = "config.toml";
+/// Storage quotas for the namespaces and repos on a server, at the root of the sync dir
+pub const QUOTAS_FILENAME: &str = "quotas.toml";
+/// Lock taken around checking and counting uploads against the quotas, next to quotas.toml
+pub const QUOTAS_LOCK_FILENAME: &str = "quotas.lock";
+/// Cached storage usage of a repository, in .oxen/.cache
+pub const USAGE_FILENAME: &str = "usage.json";
 /// HEAD file holds onto where the head commit is (commit_id or branch name)
 pub const HEAD_FILE: &str = "HEAD";
 /// refs/ is a key,val store of branch names to commit ids
@@ -55,19 +63,43 @@ pub const COMPARES_DIR: &str = "compares";
 pub const LEFT_COMPARE_COMMIT: &str = "LEFT";
 /// prefix for the right commit pointer in cached compares
 pub const RIGHT_COMPARE_COMMIT: &str = "RIGHT";
+/// prefix for the cached search index
+pub const SEARCH_DIR: &str = "search";
+/// Name of the duckdb database holding the search index of a commit
+pub const SEARCH_INDEX_FILE: &str = "index.duckdb";
 /// prefix for the stats dir
 pub const STATS_DIR: &str = "stats";
 /// prefix for the staged dirs
 pub const STAGED_DIR: &str = "staged";
+/// Paths changed in the working dir, appended to by `oxen watch`
+pub const FS_JOURNAL_FILE: &str = "fs_journal";
+/// Heartbeat and journal generation of the running `oxen watch`
+pub const FS_WATCHER_FILE: &str = "fs_watcher.json";
+/// Paths still dirty at the last status, and how far into the journal that status read
+pub const FS_JOURNAL_CHECKPOINT_FILE: &str = "fs_journal_checkpoint.json";
+/// Sentinel files written before reading the journal, to know the watcher caught up
+pub const FS_JOURNAL_SYNC_DIR: &str = "fs_journal_sync";
 /// Name of the table in the duckdb db used for remote staging
 pub const TABLE_NAME: &str = "df";
 /// Oxen's internal row id column in duckdb remote staging tables
-pub const OXEN_COLS: [&str; 4] = [OXEN_ID_COL, DIFF_STATUS_COL, OXEN_ROW_ID_COL, DIFF_HASH_COL];
+pub const OXEN_COLS: [&str; 5] = [
+    OXEN_ID_COL,
+    DIFF_STATUS_COL,
+    OXEN_ROW_ID_COL,
+    DIFF_HASH_COL,
+    OXEN_ROW_REVISION_COL,
+];
 pub const OXEN_ROW_ID_COL: &str = "_oxen_row_id";
 /// Oxen's internal id column in duckdb remote staging tables
 pub const OXEN_ID_COL: &str = "_oxen_id";
+/// Counter bumped on every edit of a row in a remote staging table, used to detect conflicting edits
+pub const OXEN_ROW_REVISION_COL: &str = "_oxen_row_revision";
+/// Name of the table in the duckdb db that keeps the feed of row edits
+pub const CHANGES_TABLE_NAME: &str = "df_changes";
 /// Name of the folder of the cache dir in which dfs are indexed as duckdbs
 pub const DUCKDB_CACHE_DIR: &str = "duckdb";
+/// Plain copies of encrypted files while they are read, in .oxen/.cache
+pub const DECRYPTED_DIR: &str = "decrypted";
 /// Default name for duckdb table used for indexing dataframes
 pub const DUCKDB_DF_TABLE_NAME: &str = "df";
 /// Max number of rows to query from a dataframe
@@ -76,6 +108,8 @@ pub const MAX_QUERYABLE_ROWS: usize = 5_000_000;
 pub const SYNC_STATUS_DIR: &str = "sync_status";
 /// Flag for if the repository was cloned in a shallow fashion
 pub const SHALLOW_FLAG: &str = "SHALLOW";
+/// Flag for if the repository is a read only mirror of another server
+pub const READ_ONLY_FLAG: &str = "READ_ONLY";
 /// prefix for the commit indices
 pub const INDICES_DIR: &str = "indices";
 /// prefix for the schema fields that are indexed
@@ -94,6 +128,24 @@ pub const OBJECT_VNODES_DIR: &str = "vnodes";
 pub const OBJECT_SCHEMAS_DIR: &str = "schemas";
 /// File name for files stored in versions directory (>0.8.4). (Was commit id <= 0.8.4)
 pub const VERSION_FILE_NAME: &str = "data";
+/// Extension added to version files stored zstd compressed
+pub const COMPRESSED_VERSION_EXTENSION: &str = "zst";
+/// Extension added to version files stored encrypted
+pub const ENCRYPTED_VERSION_EXTENSION: &str = "enc";
+/// encryption.json holds the wrapped data key of an encrypted repository
+pub const ENCRYPTION_FILE: &str = "encryption.json";
+/// encryption_keys.toml in the config dir maps repositories and namespaces to key files
+pub const ENCRYPTION_KEYS_FILE: &str = "encryption_keys.toml";
+/// worktrees.toml lists the extra working directories attached to a repository
+pub const WORKTREES_FILE: &str = "worktrees.toml";
+/// WORKTREE marks a .oxen dir as a worktree and holds the path of the main repository
+pub const WORKTREE_FILE: &str = "WORKTREE";
+/// hooks/ holds executables that run before and after local commands, named after the hook
+pub const HOOKS_DIR: &str = "hooks";
+/// COMMIT_EDITMSG holds the message the commit-msg hook may edit
+pub const COMMIT_MSG_FILE: &str = "COMMIT_EDITMSG";
+/// trash/ keeps the untracked files removed by `oxen clean` so they can be put back
+pub const TRASH_DIR: &str = "trash";
 /// merge/ is where any merge conflicts are stored so that we can get rid of them
 pub const MERGE_DIR: &str = "merge";
 /// mods/ is where we can stage appends, modifications, deletions to files to be merged later
@@ -155,6 +207,8 @@ pub const AVG_CHUNK_SIZE: u64 = 1024 * 1024 * 4;
 pub const NUM_HTTP_RETRIES: u64 = 10;
 /// Number of workers
 pub const DEFAULT_NUM_WORKERS: usize = 8;
+/// Size the cache of decompressed version files in `.oxen/.cache/versions` is trimmed to, 1gb
+pub const MAX_VERSIONS_CACHE_SIZE: u64 = 1024 * 1024 * 1024;
 
 /// Pagination page size of 10
 pub const DEFAULT_PAGE_SIZE: usize = 100;
@@ -4,4 +4,6 @@
 pub mod cache;
 pub mod db;
 pub mod df;
+pub mod encryption;
+pub mod hooks;
 pub mod index;
@@ -3,3 +3,4 @@ pub mod content_validator;
 pub mod convert_to_arrow;
 pub mod df_size;
 pub mod repo_size;
+pub mod search_index;
@@ -2,6 +2,7 @@
 
 use crate::constants::{CACHE_DIR, DIRS_DIR, HISTORY_DIR};
 use crate::core::df::tabular;
+use crate::core::index::oxenattributes::OxenAttributes;
 use crate::core::index::{CommitEntryReader, CommitReader};
 use crate::error::OxenError;
 use crate::model::{Commit, DirMetadataItem, LocalRepository};
@@ -61,7 +62,12 @@ fn compute_metadata_items(
     commit: &Commit,
 ) -> Result<Vec<DirMetadataItem>, OxenError> {
     let entry_reader = CommitEntryReader::new(repo, commit)?;
-    let entries = entry_reader.list_entries()?;
+    let attributes = OxenAttributes::from_commit(repo, commit)?;
+    let entries = entry_reader
+        .list_entries()?
+        .into_iter()
+        .filter(|entry| attributes.attributes_for(&entry.path).computes_metadata())
+        .collect::<Vec<_>>();
     let dirs = entry_reader.list_dirs()?;
 
     let commit_reader = CommitReader::new(repo)?;
@@ -1,5 +1,6 @@
 use crate::constants::{CACHE_DIR, DATA_FRAMES_DIR, HISTORY_DIR};
 use crate::core::df::tabular;
+use crate::core::index::oxenattributes::OxenAttributes;
 use crate::core::index::CommitEntryReader;
 use crate::error::OxenError;
 use crate::model::{Commit, DataFrameSize, LocalRepository};
@@ -23,11 +24,17 @@ pub fn compute(repo: &LocalRepository, commit: &Commit) -> Result<(), OxenError>
     let cache_path = df_size_cache_path(repo, commit);
     let reader = CommitEntryReader::new(repo, commit)?;
     let entries = reader.list_entries()?;
+    let attributes = OxenAttributes::from_commit(repo, commit)?;
 
     let mut df = get_cache_df(&cache_path)?;
 
     for entry in entries {
+        let file_attributes = attributes.attributes_for(&entry.path);
+        if !file_attributes.computes_metadata() || file_attributes.is_binary() {
+            continue;
+        }
         let path = util::fs::version_path(repo, &entry);
+        let path = util::fs::readable_version_path(repo, &path)?;
 
         // The path may not exist if a file was not fully pushed
         if path.exists() && util::fs::is_tabular(&path) {
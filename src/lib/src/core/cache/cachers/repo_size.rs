@@ -1,8 +1,10 @@
 //! Caches the size of the repo to disk at the time of the commit, so that we can quickly query it
 
 use fs_extra::dir::get_size;
+use lazy_static::lazy_static;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use crate::api;
 use crate::constants::{CACHE_DIR, DIRS_DIR, HISTORY_DIR};
@@ -12,6 +14,20 @@ use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
 use crate::util;
 
+lazy_static! {
+    // Last size computed for each repository in this process, by repository path
+    static ref REPO_SIZES: Mutex<BTreeMap<PathBuf, u64>> = Mutex::new(BTreeMap::new());
+}
+
+/// Sizes of the repositories this process cached a commit for, as of the latest one
+pub fn repo_sizes() -> Vec<(PathBuf, u64)> {
+    let sizes = REPO_SIZES.lock().unwrap();
+    sizes
+        .iter()
+        .map(|(path, size)| (path.to_owned(), *size))
+        .collect()
+}
+
 pub fn repo_size_path(repo: &LocalRepository, commit: &Commit) -> PathBuf {
     util::fs::oxen_hidden_dir(&repo.path)
         .join(HISTORY_DIR)
@@ -62,7 +78,7 @@ pub fn compute(repo: &LocalRepository, commit: &Commit) -> Result<(), OxenError>
     let commit_reader = CommitReader::new(repo)?;
 
     let mut commits = commit_reader.list_all()?;
-    commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    commits.sort_by_key(|commit| commit.timestamp);
 
     let dirs = reader.list_dirs()?;
     log::debug!("REPO_SIZE {commit} Computing size of {} dirs", dirs.len());
@@ -238,6 +254,10 @@ pub fn compute(repo: &LocalRepository, commit: &Commit) -> Result<(), OxenError>
                 size
             );
             write_repo_size(repo, commit, &size.to_string())?;
+            REPO_SIZES
+                .lock()
+                .unwrap()
+                .insert(repo.path.to_owned(), size);
         }
         Err(e) => {
             // If we can't get the size, we'll just write an error message to the file
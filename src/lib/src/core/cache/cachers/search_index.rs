@@ -0,0 +1,303 @@
+//! Builds a full-text and embedding search index for all the files in a commit.
+//!
+//! The index is a duckdb database stored next to the other commit caches, so it never
+//! has to be recomputed once a commit has been indexed. Embeddings are only stored for the
+//! columns listed with `embeddings=` in `.oxenattributes`.
+//!
+
+use crate::constants::{CACHE_DIR, HISTORY_DIR, SEARCH_DIR, SEARCH_INDEX_FILE};
+use crate::core::db::data_frames::df_db;
+use crate::core::df::tabular;
+use crate::core::index::oxenattributes::{DataTypeAttribute, FileAttributes, OxenAttributes};
+use crate::core::index::CommitEntryReader;
+use crate::error::OxenError;
+use crate::model::{Commit, CommitEntry, EntryDataType, LocalRepository};
+use crate::opts::DFOpts;
+use crate::util;
+
+use duckdb::params;
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const DOCUMENTS_TABLE: &str = "documents";
+pub const POSTINGS_TABLE: &str = "postings";
+pub const EMBEDDINGS_TABLE: &str = "embeddings";
+// Vectors are appended as text and cast to lists once all the files are indexed
+const EMBEDDINGS_STAGING_TABLE: &str = "embeddings_staging";
+
+pub fn compute(repo: &LocalRepository, commit: &Commit) -> Result<(), OxenError> {
+    log::debug!(
+        "Running search_index on {:?} for commit {}",
+        repo.path,
+        commit.id
+    );
+
+    let index_path = search_index_path(repo, commit);
+    if index_path.exists() {
+        return Ok(());
+    }
+
+    // Build into a temporary file so a half written index is never picked up by a search. The
+    // name is unique because the commit cacher and a search can both be building the same index.
+    let tmp_path = index_path.with_extension(format!("tmp.{}", uuid::Uuid::new_v4()));
+    if let Err(err) = build_index(repo, commit, &tmp_path) {
+        remove_tmp_index(&tmp_path);
+        return Err(err);
+    }
+
+    if let Err(err) = util::fs::rename(&tmp_path, &index_path) {
+        remove_tmp_index(&tmp_path);
+        // Another builder got there first, the indices are the same
+        if !index_path.exists() {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+fn build_index(repo: &LocalRepository, commit: &Commit, path: &Path) -> Result<(), OxenError> {
+    let conn = df_db::get_connection(path)?;
+    create_tables(&conn)?;
+
+    let reader = CommitEntryReader::new(repo, commit)?;
+    let entries = reader.list_entries()?;
+    let attributes = OxenAttributes::from_commit(repo, commit)?;
+
+    conn.execute_batch("BEGIN TRANSACTION")?;
+    let mut writer = IndexWriter::new(&conn)?;
+    for entry in entries {
+        let file_attributes = attributes.attributes_for(&entry.path);
+        if file_attributes.computes_metadata() && !file_attributes.is_binary() {
+            index_entry(repo, &entry, &file_attributes, &mut writer)?;
+        }
+    }
+    writer.finish()?;
+    conn.execute_batch("COMMIT")?;
+    Ok(())
+}
+
+// Removes a temporary index along with the write ahead log duckdb keeps next to it
+fn remove_tmp_index(path: &Path) {
+    let mut wal_path = path.as_os_str().to_owned();
+    wal_path.push(".wal");
+    for path in [path, Path::new(&wal_path)] {
+        if let Err(err) = std::fs::remove_file(path) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                log::debug!("search_index could not remove {path:?}: {err}");
+            }
+        }
+    }
+}
+
+/// Path to the duckdb search index of a commit
+pub fn search_index_path(repo: &LocalRepository, commit: &Commit) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join(HISTORY_DIR)
+        .join(&commit.id)
+        .join(CACHE_DIR)
+        .join(SEARCH_DIR)
+        .join(SEARCH_INDEX_FILE)
+}
+
+pub fn is_indexed(repo: &LocalRepository, commit: &Commit) -> bool {
+    search_index_path(repo, commit).exists()
+}
+
+/// Lowercase the text and split it on anything that is not alphanumeric.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+fn create_tables(conn: &duckdb::Connection) -> Result<(), OxenError> {
+    let sql = format!(
+        "CREATE TABLE {DOCUMENTS_TABLE} (doc_id BIGINT, path VARCHAR, row_idx BIGINT, column_name VARCHAR, text VARCHAR);
+         CREATE TABLE {POSTINGS_TABLE} (term VARCHAR, doc_id BIGINT, tf INTEGER);
+         CREATE TABLE {EMBEDDINGS_TABLE} (path VARCHAR, row_idx BIGINT, column_name VARCHAR, embedding DOUBLE[]);
+         CREATE TABLE {EMBEDDINGS_STAGING_TABLE} (path VARCHAR, row_idx BIGINT, column_name VARCHAR, embedding VARCHAR);"
+    );
+    conn.execute_batch(&sql)?;
+    Ok(())
+}
+
+struct IndexWriter<'a> {
+    conn: &'a duckdb::Connection,
+    documents: duckdb::Appender<'a>,
+    postings: duckdb::Appender<'a>,
+    embeddings: duckdb::Appender<'a>,
+    next_doc_id: i64,
+}
+
+impl<'a> IndexWriter<'a> {
+    fn new(conn: &'a duckdb::Connection) -> Result<IndexWriter<'a>, OxenError> {
+        Ok(IndexWriter {
+            conn,
+            documents: conn.appender(DOCUMENTS_TABLE)?,
+            postings: conn.appender(POSTINGS_TABLE)?,
+            embeddings: conn.appender(EMBEDDINGS_STAGING_TABLE)?,
+            next_doc_id: 0,
+        })
+    }
+
+    fn finish(&mut self) -> Result<(), OxenError> {
+        self.documents.flush()?;
+        self.postings.flush()?;
+        self.embeddings.flush()?;
+        let sql = format!(
+            "INSERT INTO {EMBEDDINGS_TABLE}
+             SELECT path, row_idx, column_name, CAST(embedding AS DOUBLE[]) FROM {EMBEDDINGS_STAGING_TABLE};
+             DROP TABLE {EMBEDDINGS_STAGING_TABLE};"
+        );
+        self.conn.execute_batch(&sql)?;
+        Ok(())
+    }
+
+    fn add_document(
+        &mut self,
+        path: &str,
+        row: usize,
+        column: Option<&str>,
+        text: &str,
+    ) -> Result<(), OxenError> {
+        let mut term_freqs: HashMap<String, i32> = HashMap::new();
+        for term in tokenize(text) {
+            *term_freqs.entry(term).or_insert(0) += 1;
+        }
+        if term_freqs.is_empty() {
+            return Ok(());
+        }
+
+        let doc_id = self.next_doc_id;
+        self.next_doc_id += 1;
+
+        self.documents
+            .append_row(params![doc_id, path, row as i64, column, text])?;
+        for (term, tf) in term_freqs {
+            self.postings.append_row(params![term, doc_id, tf])?;
+        }
+        Ok(())
+    }
+
+    fn add_embedding(
+        &mut self,
+        path: &str,
+        row: usize,
+        column: &str,
+        embedding: &[f64],
+    ) -> Result<(), OxenError> {
+        let vector = format!(
+            "[{}]",
+            embedding
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        );
+        self.embeddings
+            .append_row(params![path, row as i64, column, vector])?;
+        Ok(())
+    }
+}
+
+fn index_entry(
+    repo: &LocalRepository,
+    entry: &CommitEntry,
+    attributes: &FileAttributes,
+    writer: &mut IndexWriter,
+) -> Result<(), OxenError> {
+    let version_path = util::fs::version_path(repo, entry);
+    let version_path = util::fs::readable_version_path(repo, &version_path)?;
+    // The path may not exist if a file was not fully pushed
+    if !version_path.exists() {
+        return Ok(());
+    }
+
+    let path = entry.path.to_string_lossy().to_string();
+    if attributes.is_tabular(&version_path) {
+        let opts = attributes.df_opts(&version_path);
+        index_tabular(&version_path, &path, opts, &attributes.embeddings, writer)
+    } else if attributes.data_type == Some(DataTypeAttribute::Text)
+        || EntryDataType::Text == util::fs::file_data_type(&version_path)
+    {
+        index_text(&version_path, &path, writer)
+    } else {
+        Ok(())
+    }
+}
+
+fn index_text(version_path: &Path, path: &str, writer: &mut IndexWriter) -> Result<(), OxenError> {
+    let Ok(contents) = std::fs::read_to_string(version_path) else {
+        log::debug!("search_index skipping non utf8 file {:?}", path);
+        return Ok(());
+    };
+
+    for (row, line) in contents.lines().enumerate() {
+        writer.add_document(path, row, None, line)?;
+    }
+    Ok(())
+}
+
+fn index_tabular(
+    version_path: &Path,
+    path: &str,
+    opts: DFOpts,
+    embedding_columns: &[String],
+    writer: &mut IndexWriter,
+) -> Result<(), OxenError> {
+    let df = match tabular::read_df(version_path, opts) {
+        Ok(df) => df,
+        Err(err) => {
+            log::warn!("search_index could not read data frame {:?}: {}", path, err);
+            return Ok(());
+        }
+    };
+
+    for series in df.get_columns() {
+        let column = series.name();
+        match series.dtype() {
+            DataType::String => {
+                for (row, value) in series.str()?.into_iter().enumerate() {
+                    if let Some(value) = value {
+                        writer.add_document(path, row, Some(column), value)?;
+                    }
+                }
+            }
+            DataType::List(inner)
+                if inner.is_float() && embedding_columns.iter().any(|c| c == column) =>
+            {
+                for (row, value) in series.list()?.into_iter().enumerate() {
+                    let Some(value) = value else {
+                        continue;
+                    };
+                    let value = value.cast(&DataType::Float64)?;
+                    // A vector with a missing value can't be compared, leave the row out
+                    let Some(embedding) = value.f64()?.into_iter().collect::<Option<Vec<f64>>>()
+                    else {
+                        log::debug!("search_index skipping {path} row {row}, {column} has nulls");
+                        continue;
+                    };
+                    writer.add_embedding(path, row, column, &embedding)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_index_tokenize() {
+        assert_eq!(
+            tokenize("The quick, brown-fox! 42"),
+            vec!["the", "quick", "brown", "fox", "42"]
+        );
+        assert!(tokenize("  ...  ").is_empty());
+    }
+}
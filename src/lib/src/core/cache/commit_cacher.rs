@@ -9,10 +9,12 @@ use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
 use crate::util;
 
-use super::cachers::{content_stats, content_validator, df_size, repo_size};
+use super::cachers::{content_stats, content_validator, df_size, repo_size, search_index};
 use lazy_static::lazy_static;
 use rocksdb::{DBWithThreadMode, MultiThreaded};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 type CommitCacher = fn(&LocalRepository, &Commit) -> Result<(), OxenError>;
 
@@ -24,8 +26,36 @@ lazy_static! {
         (String::from("REPO_SIZE"), repo_size::compute as CommitCacher),
         (String::from("COMMIT_STATS"), content_stats::compute as CommitCacher),
         (String::from("DF_SIZE"), df_size::compute as CommitCacher),
+        (String::from("SEARCH_INDEX"), search_index::compute as CommitCacher),
         // (String::from("ARROW_CONVERSION"), convert_to_arrow::convert_to_arrow as CommitCacher),
     ];
+
+    /// How many times each cacher succeeded or failed since the process started
+    static ref CACHER_RUNS: Mutex<BTreeMap<String, CacherRuns>> = Mutex::new(BTreeMap::new());
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CacherRuns {
+    pub succeeded: u64,
+    pub failed: u64,
+}
+
+/// Counts of the cacher runs in this process, by cacher name
+pub fn cacher_runs() -> Vec<(String, CacherRuns)> {
+    let runs = CACHER_RUNS.lock().unwrap();
+    runs.iter()
+        .map(|(name, runs)| (name.to_owned(), runs.clone()))
+        .collect()
+}
+
+fn record_cacher_run(name: &str, succeeded: bool) {
+    let mut runs = CACHER_RUNS.lock().unwrap();
+    let runs = runs.entry(name.to_string()).or_default();
+    if succeeded {
+        runs.succeeded += 1;
+    } else {
+        runs.failed += 1;
+    }
 }
 
 fn cached_status_db_path(repo: &LocalRepository, commit: &Commit) -> PathBuf {
@@ -211,11 +241,13 @@ pub fn run_all(repo: &LocalRepository, commit: &Commit, force: bool) -> Result<(
         // run and set appropriate status
         match cacher(repo, commit) {
             Ok(_) => {
+                record_cacher_run(name, true);
                 let status_success = CacherStatus::success();
                 str_json_db::put(&db, name, &status_success)?;
                 log::debug!("run_all done running {:?}", name);
             }
             Err(err) => {
+                record_cacher_run(name, false);
                 let err = format!("{err}");
                 log::error!("{}", err);
                 let status_failed = CacherStatus::failed(&err);
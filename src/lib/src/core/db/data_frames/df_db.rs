@@ -2,7 +2,8 @@
 //!
 
 use crate::constants::{
-    DEFAULT_PAGE_SIZE, DUCKDB_DF_TABLE_NAME, OXEN_ID_COL, OXEN_ROW_ID_COL, TABLE_NAME,
+    DEFAULT_PAGE_SIZE, DUCKDB_DF_TABLE_NAME, OXEN_ID_COL, OXEN_ROW_ID_COL, OXEN_ROW_REVISION_COL,
+    TABLE_NAME,
 };
 
 use crate::core::df::tabular;
@@ -266,6 +267,7 @@ pub fn modify_row_with_polars_df(
     id: &str,
     df: &DataFrame,
     out_schema: &Schema,
+    revision: Option<i64>,
 ) -> Result<DataFrame, OxenError> {
     if df.height() != 1 {
         return Err(OxenError::basic_str(
@@ -287,7 +289,14 @@ pub fn modify_row_with_polars_df(
         .collect::<Vec<String>>()
         .join(", ");
 
-    let where_clause = format!("\"{}\" = '{}'", OXEN_ID_COL, id);
+    // Only update the row if it is still at the revision the caller read
+    let where_clause = match revision {
+        Some(revision) => format!(
+            "\"{}\" = '{}' AND \"{}\" = {}",
+            OXEN_ID_COL, id, OXEN_ROW_REVISION_COL, revision
+        ),
+        None => format!("\"{}\" = '{}'", OXEN_ID_COL, id),
+    };
 
     let sql = format!(
         "UPDATE {} SET {} WHERE {} RETURNING *",
@@ -9,11 +9,13 @@ use sql::Select;
 // use sql::Select;
 use sql_query_builder as sql;
 
-use crate::constants::{DIFF_HASH_COL, DIFF_STATUS_COL, OXEN_COLS, OXEN_ID_COL};
+use crate::constants::{
+    DIFF_HASH_COL, DIFF_STATUS_COL, OXEN_COLS, OXEN_ID_COL, OXEN_ROW_ID_COL, OXEN_ROW_REVISION_COL,
+};
 
 use crate::core::db;
 use crate::core::db::data_frames::workspace_df_db::{
-    full_staged_table_schema, schema_without_oxen_cols,
+    add_revision_col_if_missing, full_staged_table_schema, schema_without_oxen_cols,
 };
 use crate::core::df::tabular;
 use crate::core::index::workspaces::data_frames::data_frame_row_changes_db;
@@ -25,7 +27,14 @@ use polars::prelude::*; // or use polars::lazy::*; if you're working in a lazy c
 
 use super::df_db;
 
+/// Temp tables the rows of a bulk upsert or delete are staged in
+const BULK_ROWS_TABLE: &str = "_oxen_bulk_rows";
+const BULK_UPDATES_TABLE: &str = "_oxen_bulk_updates";
+/// Rows per multi row insert when staging bulk rows
+const BULK_INSERT_BATCH_SIZE: usize = 256;
+
 pub fn append_row(conn: &duckdb::Connection, df: &DataFrame) -> Result<DataFrame, OxenError> {
+    add_revision_col_if_missing(conn)?;
     let table_schema = schema_without_oxen_cols(conn, TABLE_NAME)?;
     let df_schema = df.schema();
 
@@ -65,10 +74,14 @@ pub fn modify_row(
     if df.height() != 1 {
         return Err(OxenError::basic_str("Modify row requires exactly one row"));
     }
+    add_revision_col_if_missing(conn)?;
 
     let table_schema = schema_without_oxen_cols(conn, TABLE_NAME)?;
     let out_schema = full_staged_table_schema(conn)?;
 
+    // If the caller sent the revision it last read, the update only applies if nobody else modified the row since
+    let expected_revision = get_row_revision(df)?;
+
     // Filter it down to exclude any of the OXEN_COLS, we don't want to modify these but hub sends them over
     let schema = df.schema();
     let df_cols = schema.get_names();
@@ -93,6 +106,17 @@ pub fn modify_row(
 
     let maybe_db_data = df_db::select(conn, &select_hash, true, None, None)?;
 
+    if maybe_db_data.height() == 0 {
+        return Err(OxenError::resource_not_found(uuid));
+    }
+
+    let current_revision = get_row_revision(&maybe_db_data)?;
+    if let (Some(expected), Some(current)) = (expected_revision, current_revision) {
+        if expected != current {
+            return Err(OxenError::data_frame_row_conflict(uuid, current));
+        }
+    }
+
     let mut new_row = maybe_db_data.clone().to_owned();
     for col in df.get_columns() {
         // Replace that column in the existing df if it exists
@@ -109,16 +133,52 @@ pub fn modify_row(
     // TODO: Find a better way to do this than overwriting the entire column here.
     new_row.with_column(Series::new(DIFF_STATUS_COL, vec![updated_status]))?;
     new_row.with_column(Series::new(DIFF_HASH_COL, vec![insert_hash]))?;
+    if let Some(current) = current_revision {
+        new_row.with_column(Series::new(OXEN_ROW_REVISION_COL, vec![current + 1]))?;
+    }
 
-    let result = df_db::modify_row_with_polars_df(conn, TABLE_NAME, uuid, &new_row, &out_schema)?;
+    let result = df_db::modify_row_with_polars_df(
+        conn,
+        TABLE_NAME,
+        uuid,
+        &new_row,
+        &out_schema,
+        current_revision,
+    )?;
 
+    // The row was there when we read it, so someone bumped the revision between our read and the update
     if result.height() == 0 {
-        return Err(OxenError::resource_not_found(uuid));
+        return match current_revision {
+            Some(current) => Err(OxenError::data_frame_row_conflict(uuid, current + 1)),
+            None => Err(OxenError::resource_not_found(uuid)),
+        };
     }
     Ok(result)
 }
 
+/// Read the revision counter of a single row data frame, if it has one
+pub fn get_row_revision(row_df: &DataFrame) -> Result<Option<i64>, OxenError> {
+    if row_df.height() != 1 || !row_df.get_column_names().contains(&OXEN_ROW_REVISION_COL) {
+        return Ok(None);
+    }
+
+    let revision = row_df
+        .column(OXEN_ROW_REVISION_COL)?
+        .cast(&DataType::Int64)?;
+    Ok(revision.i64()?.get(0))
+}
+
 pub fn delete_row(conn: &duckdb::Connection, uuid: &str) -> Result<DataFrame, OxenError> {
+    delete_row_at_revision(conn, uuid, None)
+}
+
+/// Delete a row, failing with a conflict if `expected_revision` is set and the row has moved past it
+pub fn delete_row_at_revision(
+    conn: &duckdb::Connection,
+    uuid: &str,
+    expected_revision: Option<i64>,
+) -> Result<DataFrame, OxenError> {
+    add_revision_col_if_missing(conn)?;
     let select_stmt = sql::Select::new()
         .select("*")
         .from(TABLE_NAME)
@@ -130,6 +190,19 @@ pub fn delete_row(conn: &duckdb::Connection, uuid: &str) -> Result<DataFrame, Ox
         return Err(OxenError::resource_not_found(uuid));
     }
 
+    let current_revision = get_row_revision(&row_to_delete)?;
+    let revision_guard = match current_revision {
+        Some(current) => {
+            if let Some(expected) = expected_revision {
+                if expected != current {
+                    return Err(OxenError::data_frame_row_conflict(uuid, current));
+                }
+            }
+            format!(" AND \"{}\" = {}", OXEN_ROW_REVISION_COL, current)
+        }
+        None => String::new(),
+    };
+
     // If it's newly added, delete it. Otherwise, set it to removed
     let status = row_to_delete.column(DIFF_STATUS_COL)?.get(0)?;
     let status_str = status.get_str();
@@ -145,24 +218,403 @@ pub fn delete_row(conn: &duckdb::Connection, uuid: &str) -> Result<DataFrame, Ox
         log::debug!("staged_df_db::delete_row() deleting row");
         let stmt = sql::Delete::new()
             .delete_from(TABLE_NAME)
-            .where_clause(&format!("{} = '{}'", OXEN_ID_COL, uuid));
-        conn.execute(&stmt.to_string(), [])?;
+            .where_clause(&format!("{} = '{}'{}", OXEN_ID_COL, uuid, revision_guard));
+        if conn.execute(&stmt.to_string(), [])? == 0 {
+            return Err(OxenError::data_frame_row_conflict(
+                uuid,
+                current_revision.unwrap_or_default() + 1,
+            ));
+        }
     } else {
         log::debug!("staged_df_db::delete_row() updating row to indicate deletion");
-        let stmt = sql::Update::new()
-            .update(TABLE_NAME)
-            .set(&format!(
-                "\"{}\" = '{}'",
-                DIFF_STATUS_COL,
-                StagedRowStatus::Removed
-            ))
-            .where_clause(&format!("{} = '{}'", OXEN_ID_COL, uuid));
-        conn.execute(&stmt.to_string(), [])?;
+        let mut stmt = sql::Update::new().update(TABLE_NAME).set(&format!(
+            "\"{}\" = '{}'",
+            DIFF_STATUS_COL,
+            StagedRowStatus::Removed
+        ));
+        if current_revision.is_some() {
+            stmt = stmt.set(&format!("\"{0}\" = \"{0}\" + 1", OXEN_ROW_REVISION_COL));
+        }
+        let stmt = stmt.where_clause(&format!("{} = '{}'{}", OXEN_ID_COL, uuid, revision_guard));
+        if conn.execute(&stmt.to_string(), [])? == 0 {
+            return Err(OxenError::data_frame_row_conflict(
+                uuid,
+                current_revision.unwrap_or_default() + 1,
+            ));
+        }
     };
 
     Ok(row_to_delete)
 }
 
+/// The rows touched by [`upsert_rows`]
+pub struct UpsertedRows {
+    /// The existing rows before they were updated, in the same order as `updated`
+    pub before: DataFrame,
+    pub updated: DataFrame,
+    pub added: DataFrame,
+}
+
+/// Update every row that matches a row of `df` on the `keys` columns and append the rest.
+/// The keys of `df` must be unique and not null. If `df` has a revision column, rows that moved past
+/// the revision they were sent with fail the upsert with a conflict. Run it in a transaction, a failure
+/// part way through leaves the table half updated otherwise.
+pub fn upsert_rows(
+    conn: &duckdb::Connection,
+    df: &DataFrame,
+    keys: &[String],
+) -> Result<UpsertedRows, OxenError> {
+    add_revision_col_if_missing(conn)?;
+    // Rows sent with the revision they were read at are only updated if nobody modified them since
+    let has_expected_revision = df.get_column_names().contains(&OXEN_ROW_REVISION_COL);
+    let values = if has_expected_revision {
+        df.drop(OXEN_ROW_REVISION_COL)?
+    } else {
+        df.clone()
+    };
+
+    let table_schema = schema_without_oxen_cols(conn, TABLE_NAME)?;
+    if !table_schema.has_field_names(&values.schema().get_names()) {
+        return Err(OxenError::incompatible_schemas(table_schema));
+    }
+    let out_schema = full_staged_table_schema(conn)?;
+
+    load_bulk_rows(conn, BULK_ROWS_TABLE, df, keys)?;
+    check_bulk_keys(conn, keys)?;
+
+    let join = bulk_keys_join(keys);
+    let live = format!(
+        "d.\"{}\" != '{}'",
+        DIFF_STATUS_COL,
+        StagedRowStatus::Removed
+    );
+    if has_expected_revision {
+        check_bulk_revisions(conn, &join, &live)?;
+    }
+    let before = df_db::select_raw(
+        conn,
+        &format!(
+            "SELECT d.* FROM {TABLE_NAME} d JOIN {BULK_ROWS_TABLE} t ON {join} WHERE {live} ORDER BY d.\"{OXEN_ID_COL}\""
+        ),
+        true,
+        Some(&out_schema),
+    )?;
+
+    let updated = if before.height() > 0 {
+        let replaced = values
+            .get_column_names()
+            .iter()
+            .map(|col| format!("t.\"{col}\" AS \"{col}\""))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let after = df_db::select_raw(
+            conn,
+            &format!(
+                "SELECT d.* REPLACE ({replaced}) FROM {TABLE_NAME} d JOIN {BULK_ROWS_TABLE} t ON {join} WHERE {live} ORDER BY d.\"{OXEN_ID_COL}\""
+            ),
+            true,
+            Some(&out_schema),
+        )?;
+        let updated = with_modified_status(&table_schema, &before, after)?;
+        update_from_bulk_rows(conn, &updated)?;
+        updated
+    } else {
+        DataFrame::default()
+    };
+
+    // Rows whose key did not match anything are new, the update above does not touch the keys
+    let cols = values
+        .get_column_names()
+        .iter()
+        .map(|col| format!("\"{col}\""))
+        .collect::<Vec<String>>();
+    let sql = format!(
+        "INSERT INTO {TABLE_NAME} ({}, \"{DIFF_STATUS_COL}\") SELECT {}, '{}' FROM {BULK_ROWS_TABLE} t WHERE NOT EXISTS (SELECT 1 FROM {TABLE_NAME} d WHERE {join} AND {live}) RETURNING *",
+        cols.join(", "),
+        cols.iter()
+            .map(|col| format!("t.{col}"))
+            .collect::<Vec<String>>()
+            .join(", "),
+        StagedRowStatus::Added
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let result_set: Vec<RecordBatch> = stmt.query_arrow([])?.collect();
+    let added = df_db::record_batches_to_polars_df_explicit_nulls(result_set, &out_schema)?;
+
+    df_db::drop_table(conn, BULK_ROWS_TABLE)?;
+    Ok(UpsertedRows {
+        before,
+        updated,
+        added,
+    })
+}
+
+/// Delete every row that matches a row of `df` on the `keys` columns, returning the rows as they were.
+/// Added rows are dropped and committed rows are marked as removed. If `df` has a revision column, rows
+/// that moved past the revision they were sent with fail the delete with a conflict.
+pub fn delete_rows(
+    conn: &duckdb::Connection,
+    df: &DataFrame,
+    keys: &[String],
+) -> Result<DataFrame, OxenError> {
+    add_revision_col_if_missing(conn)?;
+    let out_schema = full_staged_table_schema(conn)?;
+
+    // Rows sent with the revision they were read at are only deleted if nobody modified them since
+    let has_expected_revision = df.get_column_names().contains(&OXEN_ROW_REVISION_COL);
+    let mut bulk_cols: Vec<&str> = keys.iter().map(|key| key.as_str()).collect();
+    if has_expected_revision {
+        bulk_cols.push(OXEN_ROW_REVISION_COL);
+    }
+    load_bulk_rows(conn, BULK_ROWS_TABLE, &df.select(bulk_cols)?, keys)?;
+
+    let join = bulk_keys_join(keys);
+    let matches = |status: &str| {
+        format!(
+            "SELECT d.\"{OXEN_ID_COL}\" FROM {TABLE_NAME} d JOIN {BULK_ROWS_TABLE} t ON {join} WHERE {status}"
+        )
+    };
+    let live = format!(
+        "d.\"{}\" != '{}'",
+        DIFF_STATUS_COL,
+        StagedRowStatus::Removed
+    );
+    let added = format!("d.\"{}\" = '{}'", DIFF_STATUS_COL, StagedRowStatus::Added);
+    if has_expected_revision {
+        check_bulk_revisions(conn, &join, &live)?;
+    }
+
+    let deleted = df_db::select_raw(
+        conn,
+        &format!(
+            "SELECT d.* FROM {TABLE_NAME} d WHERE \"{OXEN_ID_COL}\" IN ({}) ORDER BY d.\"{OXEN_ID_COL}\"",
+            matches(&live)
+        ),
+        true,
+        Some(&out_schema),
+    )?;
+
+    // Rows that weren't in previous commits are just removed from the staging df, rows in previous commits are tombstoned as "Removed"
+    conn.execute(
+        &format!(
+            "DELETE FROM {TABLE_NAME} WHERE \"{OXEN_ID_COL}\" IN ({})",
+            matches(&added)
+        ),
+        [],
+    )?;
+    conn.execute(
+        &format!(
+            "UPDATE {TABLE_NAME} SET \"{}\" = '{}', \"{OXEN_ROW_REVISION_COL}\" = \"{OXEN_ROW_REVISION_COL}\" + 1 WHERE \"{OXEN_ID_COL}\" IN ({})",
+            DIFF_STATUS_COL,
+            StagedRowStatus::Removed,
+            matches(&live)
+        ),
+        [],
+    )?;
+
+    df_db::drop_table(conn, BULK_ROWS_TABLE)?;
+    Ok(deleted)
+}
+
+/// Copy `df` into a temp table with the column types of the workspace table, so it can be joined against it
+fn load_bulk_rows(
+    conn: &duckdb::Connection,
+    temp_table: &str,
+    df: &DataFrame,
+    keys: &[String],
+) -> Result<(), OxenError> {
+    if keys.is_empty() {
+        return Err(OxenError::basic_str(
+            "Must supply at least one key column to match rows on",
+        ));
+    }
+    let schema = df_db::get_schema(conn, TABLE_NAME)?;
+    for key in keys {
+        if !schema.has_field_name(key) || !df.get_column_names().contains(&key.as_str()) {
+            return Err(OxenError::basic_str(format!(
+                "Key column '{key}' does not exist in the data frame"
+            )));
+        }
+    }
+
+    let cols = df
+        .get_column_names()
+        .iter()
+        .map(|col| format!("\"{col}\""))
+        .collect::<Vec<String>>()
+        .join(", ");
+    conn.execute(
+        &format!(
+            "CREATE OR REPLACE TEMP TABLE {temp_table} AS SELECT {cols} FROM {TABLE_NAME} LIMIT 0"
+        ),
+        [],
+    )?;
+
+    // Values that have no direct duckdb binding go over as strings and are cast by the insert
+    let mut df = df.clone();
+    for series in df.clone().get_columns() {
+        if !matches!(
+            series.dtype(),
+            DataType::Boolean
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float32
+                | DataType::Float64
+                | DataType::String
+                | DataType::Null
+        ) {
+            df.with_column(series.cast(&DataType::String)?)?;
+        }
+    }
+
+    let row_placeholders = format!("({})", vec!["?"; df.width()].join(", "));
+    let mut offset = 0;
+    while offset < df.height() {
+        let batch = df.slice(offset as i64, BULK_INSERT_BATCH_SIZE);
+        let sql = format!(
+            "INSERT INTO {temp_table} ({cols}) VALUES {}",
+            vec![row_placeholders.as_str(); batch.height()].join(", ")
+        );
+        let mut boxed_values: Vec<Box<dyn ToSql>> = vec![];
+        for idx in 0..batch.height() {
+            let row = batch.get(idx).unwrap();
+            boxed_values.extend(row.into_iter().map(tabular::value_to_tosql));
+        }
+        let params: Vec<&dyn ToSql> = boxed_values
+            .iter()
+            .map(|boxed_value| &**boxed_value as &dyn ToSql)
+            .collect();
+        conn.execute(&sql, params.as_slice())?;
+        offset += batch.height();
+    }
+    Ok(())
+}
+
+/// Every row of the bulk upload must name exactly one row, so its keys can't be null or repeated
+fn check_bulk_keys(conn: &duckdb::Connection, keys: &[String]) -> Result<(), OxenError> {
+    let null_keys = keys
+        .iter()
+        .map(|key| format!("\"{key}\" IS NULL"))
+        .collect::<Vec<String>>()
+        .join(" OR ");
+    let has_null_keys = df_db::count_where(conn, BULK_ROWS_TABLE, &null_keys)? > 0;
+    if has_null_keys {
+        return Err(OxenError::basic_str(format!(
+            "Key columns {keys:?} must not be null"
+        )));
+    }
+
+    let key_cols = keys
+        .iter()
+        .map(|key| format!("\"{key}\""))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let sql = format!(
+        "SELECT COUNT(*) FROM (SELECT {key_cols} FROM {BULK_ROWS_TABLE} GROUP BY {key_cols} HAVING COUNT(*) > 1)"
+    );
+    let duplicates: usize = conn.query_row(&sql, [], |row| row.get(0))?;
+    if duplicates > 0 {
+        return Err(OxenError::basic_str(format!(
+            "Found {duplicates} duplicate keys for key columns {keys:?}, each row must have a unique key"
+        )));
+    }
+    Ok(())
+}
+
+/// Fail on the first bulk row whose expected revision no longer matches the row it updates
+fn check_bulk_revisions(
+    conn: &duckdb::Connection,
+    join: &str,
+    live: &str,
+) -> Result<(), OxenError> {
+    let sql = format!(
+        "SELECT d.\"{OXEN_ID_COL}\", d.\"{OXEN_ROW_REVISION_COL}\" FROM {TABLE_NAME} d JOIN {BULK_ROWS_TABLE} t ON {join} WHERE {live} AND t.\"{OXEN_ROW_REVISION_COL}\" IS NOT NULL AND t.\"{OXEN_ROW_REVISION_COL}\" != d.\"{OXEN_ROW_REVISION_COL}\" LIMIT 1"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut conflicts = stmt.query([])?;
+    if let Some(row) = conflicts.next()? {
+        let row_id: String = row.get(0)?;
+        let current: i64 = row.get(1)?;
+        return Err(OxenError::data_frame_row_conflict(row_id, current));
+    }
+    Ok(())
+}
+
+fn bulk_keys_join(keys: &[String]) -> String {
+    keys.iter()
+        .map(|key| format!("d.\"{key}\" = t.\"{key}\""))
+        .collect::<Vec<String>>()
+        .join(" AND ")
+}
+
+/// Fill in the diff status, original hash and revision of the updated rows from the rows before the update
+fn with_modified_status(
+    table_schema: &Schema,
+    before: &DataFrame,
+    after: DataFrame,
+) -> Result<DataFrame, OxenError> {
+    let col_names = table_schema.fields_names();
+    let original_hashes = tabular::df_hash_rows_on_cols(before.clone(), &col_names, "_temp_hash")?;
+    let original_hashes = original_hashes.column("_temp_hash")?.str()?.clone();
+    let new_hashes = tabular::df_hash_rows_on_cols(after.clone(), &col_names, "_temp_hash")?;
+    let new_hashes = new_hashes.column("_temp_hash")?.str()?.clone();
+    let old_statuses = before.column(DIFF_STATUS_COL)?.str()?;
+    let old_hashes = before.column(DIFF_HASH_COL)?.str()?;
+
+    let mut statuses: Vec<String> = Vec::with_capacity(after.height());
+    let mut hashes: Vec<String> = Vec::with_capacity(after.height());
+    for idx in 0..after.height() {
+        let old_status = old_statuses
+            .get(idx)
+            .ok_or_else(|| OxenError::basic_str("Diff status column is not a string"))?;
+        let old_hash = old_hashes.get(idx);
+        // The original hash is computed the first time the row is modified
+        let insert_hash = old_hash
+            .or_else(|| original_hashes.get(idx))
+            .ok_or_else(|| OxenError::basic_str("Diff hash column is not a string"))?;
+        let new_hash = new_hashes
+            .get(idx)
+            .ok_or_else(|| OxenError::basic_str("Diff hash column is not a string"))?;
+        statuses.push(modified_row_status(
+            old_status,
+            old_hash,
+            insert_hash,
+            new_hash,
+        ));
+        hashes.push(insert_hash.to_string());
+    }
+
+    let mut after = after;
+    after.with_column(Series::new(DIFF_STATUS_COL, statuses))?;
+    after.with_column(Series::new(DIFF_HASH_COL, hashes))?;
+    let revision = before
+        .column(OXEN_ROW_REVISION_COL)?
+        .cast(&DataType::Int64)?
+        + 1;
+    after.with_column(revision)?;
+    Ok(after)
+}
+
+/// Write the updated rows back to the workspace table in a single statement
+fn update_from_bulk_rows(conn: &duckdb::Connection, updated: &DataFrame) -> Result<(), OxenError> {
+    let keys = vec![OXEN_ID_COL.to_string()];
+    load_bulk_rows(conn, BULK_UPDATES_TABLE, updated, &keys)?;
+
+    let set_clauses = updated
+        .get_column_names()
+        .iter()
+        .filter(|col| **col != OXEN_ID_COL && **col != OXEN_ROW_ID_COL)
+        .map(|col| format!("\"{col}\" = u.\"{col}\""))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let sql = format!(
+        "UPDATE {TABLE_NAME} SET {set_clauses} FROM {BULK_UPDATES_TABLE} u WHERE {TABLE_NAME}.\"{OXEN_ID_COL}\" = u.\"{OXEN_ID_COL}\""
+    );
+    conn.execute(&sql, [])?;
+    df_db::drop_table(conn, BULK_UPDATES_TABLE)?;
+    Ok(())
+}
+
 fn get_hash_and_status_for_modification(
     conn: &duckdb::Connection,
     old_row: &DataFrame,
@@ -201,11 +653,22 @@ fn get_hash_and_status_for_modification(
             .to_owned()
     };
 
+    let new_status = modified_row_status(old_status, old_hash.get_str(), &insert_hash, new_hash);
+
+    Ok((insert_hash.to_string(), new_status))
+}
+
+/// The status of a row after its values change, from its status and original hash before the change
+fn modified_row_status(
+    old_status: &str,
+    old_hash: Option<&str>,
+    insert_hash: &str,
+    new_hash: &str,
+) -> String {
     // Anything previously added must stay added regardless of any further modifications.
     // Modifying back to original state changes it to unchanged
     // If we have no prior hash info on the original hash state, it is now modified (this is the first modification)
-
-    let new_status = if old_status == StagedRowStatus::Added.to_string() {
+    if old_status == StagedRowStatus::Added.to_string() {
         StagedRowStatus::Added.to_string()
     } else if old_status == StagedRowStatus::Removed.to_string() {
         if insert_hash == new_hash {
@@ -213,15 +676,13 @@ fn get_hash_and_status_for_modification(
         } else {
             StagedRowStatus::Modified.to_string()
         }
-    } else if old_hash.is_null() {
+    } else if old_hash.is_none() {
         StagedRowStatus::Modified.to_string()
     } else if new_hash == insert_hash {
         StagedRowStatus::Unchanged.to_string()
     } else {
         StagedRowStatus::Modified.to_string()
-    };
-
-    Ok((insert_hash.to_string(), new_status))
+    }
 }
 
 /// Insert a row from a polars dataframe into a duckdb table.
@@ -295,13 +756,22 @@ pub fn record_row_change(
         value,
         new_value,
     };
+    record_row_changes(row_changes_path, vec![change])
+}
 
+/// Record the changes of a bulk edit, opening the row changes db once
+pub fn record_row_changes(
+    row_changes_path: &Path,
+    changes: Vec<DataFrameRowChange>,
+) -> Result<(), OxenError> {
     let opts = db::key_val::opts::default();
     let db = DB::open(&opts, dunce::simplified(row_changes_path))?;
 
-    maybe_revert_row_changes(&db, row_id.to_owned())?;
-
-    data_frame_row_changes_db::write_data_frame_row_change(&change, &db)
+    for change in changes {
+        maybe_revert_row_changes(&db, change.row_id.to_owned())?;
+        data_frame_row_changes_db::write_data_frame_row_change(&change, &db)?;
+    }
+    Ok(())
 }
 
 pub fn maybe_revert_row_changes(db: &DB, row_id: String) -> Result<(), OxenError> {
@@ -1,7 +1,7 @@
 use polars::frame::DataFrame;
 use sql_query_builder as sql;
 
-use crate::constants::{DIFF_STATUS_COL, OXEN_COLS, OXEN_ROW_ID_COL};
+use crate::constants::{DIFF_STATUS_COL, OXEN_COLS, OXEN_ROW_ID_COL, OXEN_ROW_REVISION_COL};
 
 use crate::model::schema::Field;
 use crate::model::staged_row_status::StagedRowStatus;
@@ -11,6 +11,7 @@ use polars::prelude::*; // or use polars::lazy::*; if you're working in a lazy c
 
 use super::df_db;
 
+#[allow(clippy::empty_line_after_doc_comments)]
 /// Builds on df_db, but for specific use cases involving remote staging -
 /// i.e., handling additional virtual columns beyond the formal schema, table names, etc.
 
@@ -47,7 +48,7 @@ pub fn enhance_schema_with_oxen_cols(schema: &Schema) -> Result<Schema, OxenErro
         .iter()
         .map(|col| Field {
             name: col.to_string(),
-            dtype: if col == &OXEN_ROW_ID_COL {
+            dtype: if col == &OXEN_ROW_ID_COL || col == &OXEN_ROW_REVISION_COL {
                 DataType::Int32.to_string()
             } else {
                 DataType::String.to_string()
@@ -65,6 +66,20 @@ pub fn enhance_schema_with_oxen_cols(schema: &Schema) -> Result<Schema, OxenErro
     Ok(schema)
 }
 
+/// Workspaces indexed before rows had revisions are missing the column, add it so their rows get conflict checks too
+pub fn add_revision_col_if_missing(conn: &duckdb::Connection) -> Result<(), OxenError> {
+    if df_db::get_schema(conn, TABLE_NAME)?.has_field_name(OXEN_ROW_REVISION_COL) {
+        return Ok(());
+    }
+
+    let query_revision = format!(
+        "ALTER TABLE \"{}\" ADD COLUMN \"{}\" INTEGER DEFAULT 0",
+        TABLE_NAME, OXEN_ROW_REVISION_COL
+    );
+    conn.execute(&query_revision, [])?;
+    Ok(())
+}
+
 pub fn df_diff(conn: &duckdb::Connection) -> Result<DataFrame, OxenError> {
     let select = sql::Select::new()
         .select("*")
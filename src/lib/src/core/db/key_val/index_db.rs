@@ -1,6 +1,7 @@
+use crate::core::db::key_val::kv_db;
 use crate::error::OxenError;
 
-use rocksdb::{DBWithThreadMode, IteratorMode, MultiThreaded};
+use rocksdb::{DBWithThreadMode, MultiThreaded};
 use std::collections::HashMap;
 use std::mem;
 use std::str;
@@ -15,7 +16,7 @@ pub fn insert_indices<S: AsRef<str>>(
     // Could not use the bytevec library here when inserting a larger set of indices
     let byte_indices = u32_to_u8(indices);
 
-    db.put(key, byte_indices)?;
+    kv_db::put_bytes(db, key, &byte_indices)?;
     Ok(())
 }
 
@@ -25,7 +26,7 @@ pub fn get_indices<S: AsRef<str>>(
 ) -> Result<Option<Vec<u32>>, OxenError> {
     let key = key.as_ref();
     let bytes = key.as_bytes();
-    match db.get(bytes) {
+    match kv_db::get_bytes(db, bytes) {
         Ok(Some(raw_indices)) => {
             // found it
             let decoded = u32_from_u8(raw_indices);
@@ -47,7 +48,7 @@ pub fn get_indices<S: AsRef<str>>(
 pub fn list_indices(
     db: &DBWithThreadMode<MultiThreaded>,
 ) -> Result<Vec<(String, Vec<u32>)>, OxenError> {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut results: Vec<(String, Vec<u32>)> = vec![];
     for item in iter {
         match item {
@@ -78,7 +79,7 @@ pub fn list_indices(
 pub fn hash_map_indices(
     db: &DBWithThreadMode<MultiThreaded>,
 ) -> Result<HashMap<String, Vec<u32>>, OxenError> {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut results: HashMap<String, Vec<u32>> = HashMap::new();
     for item in iter {
         match item {
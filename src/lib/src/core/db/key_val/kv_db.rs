@@ -1,15 +1,135 @@
+use crate::core::encryption;
 use crate::error::OxenError;
 
 use rocksdb::{DBWithThreadMode, IteratorMode, ThreadMode};
+use std::borrow::Cow;
 use std::str;
 
+/// Encrypts the key if the repository the db belongs to is encrypted
+pub fn encode_key<'a, T: ThreadMode>(
+    db: &DBWithThreadMode<T>,
+    key: &'a [u8],
+) -> Result<Cow<'a, [u8]>, OxenError> {
+    encryption::encrypt_key(db.path(), key)
+}
+
+/// Decrypts a key read from the db, plain keys are returned as is
+pub fn decode_key<'a, T: ThreadMode>(
+    db: &DBWithThreadMode<T>,
+    key: &'a [u8],
+) -> Result<Cow<'a, [u8]>, OxenError> {
+    encryption::decrypt_key(db.path(), key)
+}
+
+/// Encrypts the value of the plain `key` if the repository the db belongs to is encrypted
+pub fn encode_value<'a, T: ThreadMode>(
+    db: &DBWithThreadMode<T>,
+    key: &[u8],
+    value: &'a [u8],
+) -> Result<Cow<'a, [u8]>, OxenError> {
+    encryption::encrypt_value(db.path(), key, value)
+}
+
+/// Decrypts the value of the plain `key` read from the db, plain values are returned as is
+pub fn decode_value<'a, T: ThreadMode>(
+    db: &DBWithThreadMode<T>,
+    key: &[u8],
+    value: &'a [u8],
+) -> Result<Cow<'a, [u8]>, OxenError> {
+    encryption::decrypt_value(db.path(), key, value)
+}
+
+/// Get the raw value of a key, decrypted
+pub fn get_bytes<T: ThreadMode, K: AsRef<[u8]>>(
+    db: &DBWithThreadMode<T>,
+    key: K,
+) -> Result<Option<Vec<u8>>, OxenError> {
+    let key = key.as_ref();
+    let encoded_key = encode_key(db, key)?;
+    let mut value = db.get(&encoded_key)?;
+    // Written before the repository was encrypted
+    if value.is_none() && *encoded_key != *key {
+        value = db.get(key)?;
+    }
+    match value {
+        Some(value) => Ok(Some(decode_value(db, key, &value)?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// Put a raw value, encrypted if the repository is
+pub fn put_bytes<T: ThreadMode, K: AsRef<[u8]>>(
+    db: &DBWithThreadMode<T>,
+    key: K,
+    value: &[u8],
+) -> Result<(), OxenError> {
+    let key = key.as_ref();
+    let encoded_key = encode_key(db, key)?;
+    db.put(&encoded_key, encode_value(db, key, value)?)?;
+    // Replaces the value written before the repository was encrypted
+    if *encoded_key != *key {
+        db.delete(key)?;
+    }
+    Ok(())
+}
+
+/// A decrypted key and its decrypted value
+pub type DecodedItem = Result<(Box<[u8]>, Box<[u8]>), OxenError>;
+
+fn decode_item<T: ThreadMode>(
+    db: &DBWithThreadMode<T>,
+    key: Box<[u8]>,
+    value: Box<[u8]>,
+) -> DecodedItem {
+    let key = if encryption::is_encrypted_key(&key) {
+        decode_key(db, &key)?.into_owned().into_boxed_slice()
+    } else {
+        key
+    };
+    let value = if encryption::is_encrypted_value(&value) {
+        decode_value(db, &key, &value)?
+            .into_owned()
+            .into_boxed_slice()
+    } else {
+        value
+    };
+    Ok((key, value))
+}
+
+/// Iterate over all keys and values from the start, decrypted. Encrypted keys are not in order
+/// on disk, so for encrypted repositories the whole db is read and sorted by the plain keys.
+pub fn iter_decoded<T: ThreadMode>(
+    db: &DBWithThreadMode<T>,
+) -> Box<dyn Iterator<Item = DecodedItem> + '_> {
+    let items = db.iterator(IteratorMode::Start).map(move |item| {
+        let (key, value) = item?;
+        decode_item(db, key, value)
+    });
+    match encryption::data_key_for_path(db.path()) {
+        Ok(None) => Box::new(items),
+        Ok(Some(_)) => match items.collect::<Result<Vec<_>, OxenError>>() {
+            Ok(mut items) => {
+                items.sort_by(|(a, _), (b, _)| a.cmp(b));
+                Box::new(items.into_iter().map(Ok))
+            }
+            Err(err) => Box::new(std::iter::once(Err(err))),
+        },
+        Err(err) => Box::new(std::iter::once(Err(err))),
+    }
+}
+
 /// More efficient than get since it does not actual deserialize the value
 pub fn has_key<T: ThreadMode, S: AsRef<str>>(db: &DBWithThreadMode<T>, key: S) -> bool {
-    let key = key.as_ref();
-    let bytes = key.as_bytes();
-    match db.get_pinned(bytes) {
-        Ok(Some(_value)) => true,
-        Ok(None) => false,
+    let bytes = key.as_ref().as_bytes();
+    let has = |key: &[u8]| match db.get_pinned(key) {
+        Ok(value) => value.is_some(),
+        Err(err) => {
+            log::error!("Error checking for entry: {}", err);
+            false
+        }
+    };
+    match encode_key(db, bytes) {
+        Ok(encoded_key) => has(&encoded_key) || (*encoded_key != *bytes && has(bytes)),
         Err(err) => {
             log::error!("Error checking for entry: {}", err);
             false
@@ -31,7 +151,11 @@ pub fn delete<T: ThreadMode, S: AsRef<str>>(
     let key = key.as_ref();
     log::debug!("kv_db::delete {:?} from db: {:?}", key, db.path());
 
-    db.delete(key)?;
+    let encoded_key = encode_key(db, key.as_bytes())?;
+    db.delete(&encoded_key)?;
+    if *encoded_key != *key.as_bytes() {
+        db.delete(key)?;
+    }
     Ok(())
 }
 
@@ -42,6 +166,7 @@ pub fn list_keys<T: ThreadMode>(db: &DBWithThreadMode<T>) -> Result<Vec<String>,
     for item in iter {
         match item {
             Ok((key, _value)) => {
+                let key = decode_key(db, &key)?;
                 match str::from_utf8(&key) {
                     Ok(key) => {
                         // return full path
@@ -59,6 +184,10 @@ pub fn list_keys<T: ThreadMode>(db: &DBWithThreadMode<T>) -> Result<Vec<String>,
             }
         }
     }
+    // Encrypted keys are not in order on disk
+    if encryption::data_key_for_path(db.path())?.is_some() {
+        keys.sort();
+    }
     Ok(keys)
 }
 
@@ -9,7 +9,7 @@ use std::hash::Hash;
 use std::path::{Path, PathBuf};
 use std::str;
 
-use crate::core::db::key_val::str_json_db;
+use crate::core::db::key_val::{kv_db, str_json_db};
 
 /// # Checks if the file exists in this directory
 /// More efficient than get_entry since it does not actual deserialize the entry
@@ -109,35 +109,13 @@ pub fn list_paths<T: ThreadMode>(
     db: &DBWithThreadMode<T>,
     base_dir: &Path,
 ) -> Result<Vec<PathBuf>, OxenError> {
-    // log::debug!("path_db::list_paths({:?})", base_dir);
-    let iter = db.iterator(IteratorMode::Start);
-    let mut paths: Vec<PathBuf> = vec![];
-    for item in iter {
-        match item {
-            Ok((key, _value)) => {
-                match str::from_utf8(&key) {
-                    Ok(key) => {
-                        // return path with native slashes
-                        let os_path = OsPath::from(key);
-                        let new_path = os_path.to_pathbuf();
-                        paths.push(base_dir.join(new_path));
-                    }
-                    _ => {
-                        log::error!("list_added_paths() Could not decode key {:?}", key)
-                    }
-                }
-            }
-            _ => {
-                return Err(OxenError::basic_str(
-                    "Could not read iterate over db values",
-                ));
-            }
-        }
-    }
+    let paths = kv_db::list_keys(db)?
+        .into_iter()
+        .map(|key| base_dir.join(OsPath::from(key).to_pathbuf()))
+        .collect();
     Ok(paths)
 }
 
-/// # List file names and attached entries
 pub fn list_path_entries<T: ThreadMode, D>(
     db: &DBWithThreadMode<T>,
     base_dir: &Path,
@@ -146,7 +124,7 @@ where
     D: de::DeserializeOwned,
 {
     // log::debug!("path_db::list_path_entries({:?})", db.path());
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut paths: Vec<(PathBuf, D)> = vec![];
     for item in iter {
         match item {
@@ -201,7 +179,7 @@ where
     D: Hash,
     D: Eq,
 {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut paths: HashSet<D> = HashSet::new();
     for item in iter {
         match item {
@@ -245,7 +223,7 @@ where
     // The iterator doesn't technically have a skip method as far as I can tell
     // so we are just going to manually do it
     let mut paths: Vec<D> = vec![];
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     // Do not go negative, and start from 0
     let start_page = if page == 0 { 0 } else { page - 1 };
     let start_idx = start_page * page_size;
@@ -296,7 +274,7 @@ where
     // The iterator doesn't technically have a skip method as far as I can tell
     // so we are just going to manually do it
     let mut paths: Vec<D> = vec![];
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     // Do not go negative, and start from 0
     for (entry_i, item) in iter.enumerate() {
         match item {
@@ -2,7 +2,7 @@ use crate::core::db::key_val::kv_db;
 use crate::error::OxenError;
 use serde::{de, Serialize};
 
-use rocksdb::{DBWithThreadMode, IteratorMode, ThreadMode};
+use rocksdb::{DBWithThreadMode, ThreadMode};
 use std::{collections::HashMap, str};
 
 /// More efficient than get since it does not actual deserialize the entry
@@ -40,7 +40,7 @@ where
     // log::debug!("str_json_db::get({:?}) from db {:?}", key, db.path());
 
     let bytes = key.as_bytes();
-    match db.get(bytes) {
+    match kv_db::get_bytes(db, bytes) {
         Ok(Some(value)) => {
             // found it
             let str_val = str::from_utf8(&value)?;
@@ -84,7 +84,7 @@ where
     //     db.path()
     // );
 
-    db.put(key, json_val.as_bytes())?;
+    kv_db::put_bytes(db, key, json_val.as_bytes())?;
     Ok(())
 }
 
@@ -93,7 +93,7 @@ pub fn list_vals<T: ThreadMode, D>(db: &DBWithThreadMode<T>) -> Result<Vec<D>, O
 where
     D: de::DeserializeOwned,
 {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut values: Vec<D> = vec![];
     for item in iter {
         match item {
@@ -126,7 +126,7 @@ pub fn list<T: ThreadMode, D>(db: &DBWithThreadMode<T>) -> Result<Vec<(String, D
 where
     D: de::DeserializeOwned,
 {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut results: Vec<(String, D)> = vec![];
     for item in iter {
         match item {
@@ -163,7 +163,7 @@ pub fn hash_map<T: ThreadMode, D>(db: &DBWithThreadMode<T>) -> Result<HashMap<St
 where
     D: de::DeserializeOwned,
 {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut results: HashMap<String, D> = HashMap::new();
     for item in iter {
         // log::debug!("str_json_db::hash_map() got item {:?}", item);
@@ -1,7 +1,7 @@
 use crate::core::db::key_val::kv_db;
 use crate::error::OxenError;
 
-use rocksdb::{DBWithThreadMode, IteratorMode, MultiThreaded, ThreadMode};
+use rocksdb::{DBWithThreadMode, MultiThreaded, ThreadMode};
 use std::collections::HashMap;
 use std::str;
 
@@ -40,7 +40,7 @@ where
     log::debug!("str_val_db::get({:?}) from db {:?}", key, db.path());
 
     let key_bytes = key.as_bytes();
-    match db.get(key_bytes) {
+    match kv_db::get_bytes(db, key_bytes) {
         Ok(Some(value)) => {
             // found it
             if let Ok(entry) = D::decode::<u8>(&value) {
@@ -81,7 +81,7 @@ where
 
     match entry.encode::<u8>() {
         Ok(val) => {
-            db.put(key, val)?;
+            kv_db::put_bytes(db, key, &val)?;
             Ok(())
         }
         Err(err) => {
@@ -96,7 +96,7 @@ pub fn list_vals<T: ThreadMode, D>(db: &DBWithThreadMode<T>) -> Result<Vec<D>, O
 where
     D: bytevec::ByteDecodable,
 {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut values: Vec<D> = vec![];
     for item in iter {
         match item {
@@ -124,7 +124,7 @@ pub fn list<T>(db: &DBWithThreadMode<MultiThreaded>) -> Result<Vec<(String, T)>,
 where
     T: bytevec::ByteDecodable + std::fmt::Debug,
 {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut results: Vec<(String, T)> = vec![];
     for item in iter {
         match item {
@@ -158,7 +158,7 @@ pub fn hash_map<T>(db: &DBWithThreadMode<MultiThreaded>) -> Result<HashMap<Strin
 where
     T: bytevec::ByteDecodable + std::fmt::Debug,
 {
-    let iter = db.iterator(IteratorMode::Start);
+    let iter = kv_db::iter_decoded(db);
     let mut results: HashMap<String, T> = HashMap::new();
     for item in iter {
         match item {
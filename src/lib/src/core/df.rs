@@ -1,6 +1,7 @@
 //! DataFrame helpers
 //!
 
+pub mod encoding;
 pub mod filter;
 pub mod pretty_print;
 pub mod sql;
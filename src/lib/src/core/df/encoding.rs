@@ -0,0 +1,176 @@
+//! Binary encodings of data frames sent over the wire, negotiated with the Accept header so that
+//! typed columns (dates, lists, nulls) survive the trip that JSON would flatten them on
+
+use std::io::{Cursor, Write};
+
+use polars::prelude::*;
+
+use crate::error::OxenError;
+
+pub const ARROW_STREAM_MIME_TYPE: &str = "application/vnd.apache.arrow.stream";
+pub const PARQUET_MIME_TYPE: &str = "application/vnd.apache.parquet";
+pub const JSON_MIME_TYPE: &str = "application/json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFrameEncoding {
+    ArrowStream,
+    Parquet,
+}
+
+impl DataFrameEncoding {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            DataFrameEncoding::ArrowStream => ARROW_STREAM_MIME_TYPE,
+            DataFrameEncoding::Parquet => PARQUET_MIME_TYPE,
+        }
+    }
+
+    pub fn from_mime_type(mime_type: &str) -> Option<DataFrameEncoding> {
+        match mime_type.trim().to_lowercase().as_str() {
+            ARROW_STREAM_MIME_TYPE => Some(DataFrameEncoding::ArrowStream),
+            PARQUET_MIME_TYPE => Some(DataFrameEncoding::Parquet),
+            _ => None,
+        }
+    }
+
+    /// The binary encoding preferred by an Accept header, None when the client would rather have
+    /// JSON or did not ask for anything we can send
+    pub fn from_accept(accept: &str) -> Option<DataFrameEncoding> {
+        let mut best: Option<(f32, Option<DataFrameEncoding>)> = None;
+        for media_range in accept.split(',') {
+            let mut parts = media_range.split(';');
+            let mime_type = parts.next().unwrap_or_default().trim().to_lowercase();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality <= 0.0 {
+                continue;
+            }
+
+            let candidate = match mime_type.as_str() {
+                JSON_MIME_TYPE | "application/*" | "*/*" => None,
+                _ => match DataFrameEncoding::from_mime_type(&mime_type) {
+                    Some(encoding) => Some(encoding),
+                    None => continue,
+                },
+            };
+            // Ties go to whichever came first
+            if best.is_none_or(|(best_quality, _)| quality > best_quality) {
+                best = Some((quality, candidate));
+            }
+        }
+        best.and_then(|(_, encoding)| encoding)
+    }
+}
+
+pub fn encode(df: &mut DataFrame, encoding: DataFrameEncoding) -> Result<Vec<u8>, OxenError> {
+    let mut buffer: Vec<u8> = vec![];
+    match encoding {
+        DataFrameEncoding::ArrowStream => {
+            IpcStreamWriter::new(&mut buffer).finish(df)?;
+        }
+        DataFrameEncoding::Parquet => {
+            ParquetWriter::new(&mut buffer).finish(df)?;
+        }
+    }
+    Ok(buffer)
+}
+
+/// Writes the data frame to `writer` `batch_size` rows at a time, as one record batch or row
+/// group each, so the bytes can be sent on before the whole frame is encoded
+pub fn encode_to<W: Write>(
+    df: &DataFrame,
+    encoding: DataFrameEncoding,
+    writer: W,
+    batch_size: usize,
+) -> Result<(), OxenError> {
+    let batch_size = batch_size.max(1);
+    // At least one batch, so an empty frame still writes its schema
+    let mut batches = (0..df.height().max(1)).step_by(batch_size).map(|offset| {
+        let mut batch = df.slice(offset as i64, batch_size);
+        batch.align_chunks();
+        batch
+    });
+    match encoding {
+        DataFrameEncoding::ArrowStream => {
+            // The stream writer sends every chunk as its own record batch
+            let mut chunked = batches.next().unwrap_or_default();
+            for batch in batches {
+                chunked.vstack_mut(&batch)?;
+            }
+            IpcStreamWriter::new(writer).finish(&mut chunked)?;
+        }
+        DataFrameEncoding::Parquet => {
+            let mut batched = ParquetWriter::new(writer).batched(&df.schema())?;
+            for batch in batches {
+                batched.write_batch(&batch)?;
+            }
+            batched.finish()?;
+        }
+    }
+    Ok(())
+}
+
+pub fn decode(bytes: Vec<u8>, encoding: DataFrameEncoding) -> Result<DataFrame, OxenError> {
+    let cursor = Cursor::new(bytes);
+    let df = match encoding {
+        DataFrameEncoding::ArrowStream => IpcStreamReader::new(cursor).finish()?,
+        DataFrameEncoding::Parquet => ParquetReader::new(cursor).finish()?,
+    };
+    Ok(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+
+    use crate::core::df::encoding::{self, DataFrameEncoding};
+    use crate::error::OxenError;
+
+    #[test]
+    fn test_encoding_from_accept() {
+        assert_eq!(DataFrameEncoding::from_accept("application/json"), None);
+        assert_eq!(DataFrameEncoding::from_accept("*/*"), None);
+        assert_eq!(DataFrameEncoding::from_accept("text/html"), None);
+        assert_eq!(
+            DataFrameEncoding::from_accept("application/vnd.apache.arrow.stream"),
+            Some(DataFrameEncoding::ArrowStream)
+        );
+        assert_eq!(
+            DataFrameEncoding::from_accept(
+                "application/json;q=0.5, application/vnd.apache.parquet"
+            ),
+            Some(DataFrameEncoding::Parquet)
+        );
+        assert_eq!(
+            DataFrameEncoding::from_accept(
+                "application/vnd.apache.parquet;q=0.2, application/json;q=0.9"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_keeps_types() -> Result<(), OxenError> {
+        let mut df = df!(
+            "id" => &[1i64, 2, 3],
+            "label" => &[Some("cat"), None, Some("dog")],
+        )?;
+        df.with_column(Series::new("day", &[0i32, 19000, 19723]).cast(&DataType::Date)?)?;
+
+        for encoding in [DataFrameEncoding::ArrowStream, DataFrameEncoding::Parquet] {
+            let bytes = encoding::encode(&mut df, encoding)?;
+            let decoded = encoding::decode(bytes, encoding)?;
+            assert!(decoded.equals_missing(&df));
+            assert_eq!(decoded.schema(), df.schema());
+
+            // In batches smaller than the frame
+            let mut bytes = vec![];
+            encoding::encode_to(&df, encoding, &mut bytes, 2)?;
+            let decoded = encoding::decode(bytes, encoding)?;
+            assert!(decoded.equals_missing(&df));
+        }
+        Ok(())
+    }
+}
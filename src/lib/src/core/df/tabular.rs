@@ -228,11 +228,7 @@ pub fn row_from_str_and_schema(
 
     let mut vec: Vec<Series> = Vec::new();
 
-    for ((name, value), dtype) in schema
-        .iter_names()
-        .zip(values.into_iter())
-        .zip(schema.iter_dtypes())
-    {
+    for ((name, value), dtype) in schema.iter_names().zip(values).zip(schema.iter_dtypes()) {
         let typed_val = val_from_str_and_dtype(value, dtype);
         match Series::from_any_values_and_dtype(name, &[typed_val], dtype, false) {
             Ok(series) => {
@@ -736,8 +732,17 @@ pub fn read_df(path: impl AsRef<Path>, opts: DFOpts) -> Result<DataFrame, OxenEr
                 }
                 read_df_arrow(path)
             }
+            // Files marked tabular in .oxenattributes come with a delimiter
+            _ if opts.delimiter.is_some() => {
+                let delimiter = sniff_db_csv_delimiter(path, &opts)?;
+                read_df_csv(path, delimiter)
+            }
             _ => Err(OxenError::basic_str(err)),
         },
+        None if opts.delimiter.is_some() => {
+            let delimiter = sniff_db_csv_delimiter(path, &opts)?;
+            read_df_csv(path, delimiter)
+        }
         None => Err(OxenError::basic_str(err)),
     }?;
 
@@ -772,8 +777,16 @@ pub fn scan_df(
             "tsv" => scan_df_csv(path, b'\t', total_rows),
             "parquet" => scan_df_parquet(path, total_rows),
             "arrow" => scan_df_arrow(path, total_rows),
+            _ if opts.delimiter.is_some() => {
+                let delimiter = sniff_db_csv_delimiter(&path, opts)?;
+                scan_df_csv(path, delimiter, total_rows)
+            }
             _ => Err(OxenError::basic_str(err)),
         },
+        None if opts.delimiter.is_some() => {
+            let delimiter = sniff_db_csv_delimiter(&path, opts)?;
+            scan_df_csv(path, delimiter, total_rows)
+        }
         None => Err(OxenError::basic_str(err)),
     }
 }
@@ -0,0 +1,848 @@
+//! Envelope encryption of repositories at rest.
+//!
+//! Every encrypted repository has its own random data key that encrypts its version files and the
+//! values in its rocksdb databases. The data key is kept in `.oxen/encryption.json`, wrapped by a
+//! key encryption key from a `KeyManagementService`. `LocalKeyFile` is the stand in that keeps the
+//! key encryption key in a local file, configured per repository or per namespace in
+//! `encryption_keys.toml` in the oxen config dir:
+//!
+//! ```toml
+//! [repositories]
+//! "ox/patients" = "/etc/oxen/keys/patients.key"
+//!
+//! [namespaces]
+//! ox = "/etc/oxen/keys/ox.key"
+//! ```
+//!
+//! Database keys, mostly file paths and hashes, are encrypted deterministically so they can still
+//! be looked up, at the cost of iteration order: iterating a database of an encrypted repository
+//! reads all of it and sorts it by the plain keys. Values are bound to their key, so they cannot
+//! be swapped between keys. Encrypted keys, values and files start with a header, so plain and
+//! encrypted data can be read side by side while a repository is being encrypted. Not covered yet
+//! are the DuckDB indexes and staged files of workspaces and the resized images the server caches
+//! next to version files.
+//!
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use rocksdb::{IteratorMode, DB};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::constants::{ENCRYPTION_FILE, ENCRYPTION_KEYS_FILE, OXEN_HIDDEN_DIR};
+use crate::core::db;
+use crate::core::db::key_val::kv_db;
+use crate::error::OxenError;
+use crate::util;
+
+const KEY_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+// Database keys are paths and hashes and values are serialized records, neither starts with a
+// NUL byte when plain. Version files are never sniffed, they are told apart by the `.enc` name.
+const KEY_MAGIC: &[u8] = b"\0OXENCK1";
+// Sealed without associated data, wrapped keys and database values written before values were
+// bound to their keys
+const VALUE_MAGIC: &[u8] = b"\0OXENCV1";
+const DB_VALUE_MAGIC: &[u8] = b"\0OXENCV2";
+const FILE_MAGIC: &[u8] = b"\0OXENCF1";
+// Files are sealed in segments so they can be streamed, each gets the prefix plus its index as
+// nonce and whether it is the last one as associated data so segments cannot be dropped or moved
+const SEGMENT_SIZE: usize = 1024 * 1024;
+const NONCE_PREFIX_LEN: usize = NONCE_LEN - 4;
+
+/// Wraps and unwraps repository data keys with a key encryption key that never leaves it
+pub trait KeyManagementService: Send + Sync {
+    /// Identifies the key encryption key, stored next to the data keys it wrapped
+    fn key_id(&self) -> String;
+    fn wrap_key(&self, data_key: &[u8]) -> Result<Vec<u8>, OxenError>;
+    fn unwrap_key(&self, wrapped_key: &[u8]) -> Result<Vec<u8>, OxenError>;
+}
+
+/// Local stand in for a key management service, the key encryption key is a 256 bit key in hex
+/// in a file that only the server should be able to read
+pub struct LocalKeyFile {
+    key_id: String,
+    key: DataKey,
+}
+
+impl LocalKeyFile {
+    pub fn open(path: &Path) -> Result<LocalKeyFile, OxenError> {
+        let contents = util::fs::read_from_path(path)?;
+        let bytes = from_hex(contents.trim())
+            .filter(|bytes| bytes.len() == KEY_LEN)
+            .ok_or_else(|| {
+                OxenError::basic_str(format!("Key file {path:?} must hold a 256 bit key in hex"))
+            })?;
+        let key_id = to_hex(&Sha256::digest(&bytes)[..8]);
+        Ok(LocalKeyFile {
+            key_id,
+            key: DataKey::new(&bytes)?,
+        })
+    }
+
+    /// Writes a new random key to `path`, readable only by its owner
+    pub fn generate(path: &Path) -> Result<LocalKeyFile, OxenError> {
+        if path.exists() {
+            return Err(OxenError::basic_str(format!(
+                "Key file {path:?} already exists"
+            )));
+        }
+        util::fs::write_to_path(path, to_hex(&random_bytes(KEY_LEN)?))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        LocalKeyFile::open(path)
+    }
+}
+
+impl KeyManagementService for LocalKeyFile {
+    fn key_id(&self) -> String {
+        self.key_id.to_owned()
+    }
+
+    fn wrap_key(&self, data_key: &[u8]) -> Result<Vec<u8>, OxenError> {
+        self.key.encrypt_value(data_key)
+    }
+
+    fn unwrap_key(&self, wrapped_key: &[u8]) -> Result<Vec<u8>, OxenError> {
+        self.key.decrypt_value(wrapped_key)
+    }
+}
+
+/// An AES-256-GCM key
+pub struct DataKey {
+    key: LessSafeKey,
+    // Derives the nonces of database keys from the keys themselves, so they encrypt the same
+    // every time
+    nonce_key: hmac::Key,
+}
+
+impl DataKey {
+    fn new(bytes: &[u8]) -> Result<DataKey, OxenError> {
+        let key = UnboundKey::new(&AES_256_GCM, bytes)
+            .map_err(|_| OxenError::basic_str("Invalid encryption key"))?;
+        let nonce_key = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, bytes),
+            b"oxen database key nonces",
+        );
+        Ok(DataKey {
+            key: LessSafeKey::new(key),
+            nonce_key: hmac::Key::new(hmac::HMAC_SHA256, nonce_key.as_ref()),
+        })
+    }
+
+    fn seal(
+        &self,
+        magic: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        value: &[u8],
+    ) -> Result<Vec<u8>, OxenError> {
+        let mut sealed = value.to_vec();
+        self.key
+            .seal_in_place_append_tag(nonce_from(nonce)?, Aad::from(aad), &mut sealed)
+            .map_err(|_| OxenError::basic_str("Could not encrypt value"))?;
+
+        let mut encrypted = Vec::with_capacity(magic.len() + NONCE_LEN + sealed.len());
+        encrypted.extend_from_slice(magic);
+        encrypted.extend_from_slice(nonce);
+        encrypted.extend_from_slice(&sealed);
+        Ok(encrypted)
+    }
+
+    fn open(&self, magic: &[u8], aad: &[u8], value: &[u8]) -> Result<Vec<u8>, OxenError> {
+        let sealed = value
+            .strip_prefix(magic)
+            .filter(|sealed| sealed.len() >= NONCE_LEN + TAG_LEN)
+            .ok_or_else(|| OxenError::basic_str("Value is not encrypted"))?;
+        let (nonce, sealed) = sealed.split_at(NONCE_LEN);
+        let mut sealed = sealed.to_vec();
+        let plain = self
+            .key
+            .open_in_place(nonce_from(nonce)?, Aad::from(aad), &mut sealed)
+            .map_err(|_| OxenError::basic_str("Could not decrypt value, wrong key?"))?;
+        Ok(plain.to_vec())
+    }
+
+    fn encrypt_value(&self, value: &[u8]) -> Result<Vec<u8>, OxenError> {
+        self.seal(VALUE_MAGIC, &random_bytes(NONCE_LEN)?, &[], value)
+    }
+
+    fn decrypt_value(&self, value: &[u8]) -> Result<Vec<u8>, OxenError> {
+        self.open(VALUE_MAGIC, &[], value)
+    }
+
+    // The plain database key is the associated data, a value moved to another key won't open
+    fn encrypt_db_value(&self, key: &[u8], value: &[u8]) -> Result<Vec<u8>, OxenError> {
+        self.seal(DB_VALUE_MAGIC, &random_bytes(NONCE_LEN)?, key, value)
+    }
+
+    fn decrypt_db_value(&self, key: &[u8], value: &[u8]) -> Result<Vec<u8>, OxenError> {
+        if value.starts_with(VALUE_MAGIC) {
+            return self.decrypt_value(value);
+        }
+        self.open(DB_VALUE_MAGIC, key, value)
+    }
+
+    // A synthetic nonce from the key makes it encrypt the same every time, which is safe since
+    // the same nonce is only ever used for the same key
+    fn encrypt_db_key(&self, key: &[u8]) -> Result<Vec<u8>, OxenError> {
+        let nonce = hmac::sign(&self.nonce_key, key);
+        self.seal(KEY_MAGIC, &nonce.as_ref()[..NONCE_LEN], KEY_MAGIC, key)
+    }
+
+    fn decrypt_db_key(&self, key: &[u8]) -> Result<Vec<u8>, OxenError> {
+        self.open(KEY_MAGIC, KEY_MAGIC, key)
+    }
+
+    fn segment_nonce(prefix: &[u8], counter: u32) -> Result<Nonce, OxenError> {
+        let mut nonce = prefix.to_vec();
+        nonce.extend_from_slice(&counter.to_be_bytes());
+        nonce_from(&nonce)
+    }
+}
+
+fn nonce_from(bytes: &[u8]) -> Result<Nonce, OxenError> {
+    Nonce::try_assume_unique_for_key(bytes).map_err(|_| OxenError::basic_str("Invalid nonce"))
+}
+
+fn random_bytes(len: usize) -> Result<Vec<u8>, OxenError> {
+    let mut bytes = vec![0; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| OxenError::basic_str("Could not generate random bytes"))?;
+    Ok(bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// What `.oxen/encryption.json` holds
+#[derive(Serialize, Deserialize)]
+struct EncryptionInfo {
+    key_id: String,
+    wrapped_key: String,
+}
+
+#[derive(Deserialize, Default)]
+struct KeysConfig {
+    #[serde(default)]
+    repositories: HashMap<String, PathBuf>,
+    #[serde(default)]
+    namespaces: HashMap<String, PathBuf>,
+}
+
+type KmsRegistry = RwLock<HashMap<PathBuf, Arc<dyn KeyManagementService>>>;
+type DataKeyCache = RwLock<HashMap<PathBuf, Option<Arc<DataKey>>>>;
+
+fn kms_registry() -> &'static KmsRegistry {
+    static REGISTRY: OnceLock<KmsRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+// Unwrapping keys can be a round trip to a KMS, so remember them for the life of the process.
+// Repositories without a key are remembered too, every database read and write looks the key up.
+// `enable` and `forget` clear what is remembered.
+fn data_key_cache() -> &'static DataKeyCache {
+    static CACHE: OnceLock<DataKeyCache> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+fn info_path(repo_path: &Path) -> PathBuf {
+    util::fs::oxen_hidden_dir(repo_path).join(ENCRYPTION_FILE)
+}
+
+pub fn is_encrypted(repo_path: &Path) -> bool {
+    info_path(repo_path).exists()
+}
+
+/// Uses `kms` for the repository at `repo_path` instead of the key files in
+/// `encryption_keys.toml`, to plug in a real key management service
+pub fn register_kms(repo_path: &Path, kms: Arc<dyn KeyManagementService>) {
+    kms_registry()
+        .write()
+        .unwrap()
+        .insert(repo_path.to_path_buf(), kms);
+}
+
+/// The key management service for the repository, by its path, its `namespace/name` or its
+/// namespace. None when no key is configured for it.
+pub fn kms_for_repo(repo_path: &Path) -> Result<Option<Arc<dyn KeyManagementService>>, OxenError> {
+    if let Some(kms) = kms_registry().read().unwrap().get(repo_path) {
+        return Ok(Some(kms.clone()));
+    }
+
+    let Ok(config_dir) = util::fs::oxen_config_dir() else {
+        return Ok(None);
+    };
+    let config_path = config_dir.join(ENCRYPTION_KEYS_FILE);
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let config: KeysConfig = toml::from_str(&util::fs::read_from_path(&config_path)?)?;
+
+    let name = repo_path.file_name().map(|name| name.to_string_lossy());
+    let namespace = repo_path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|namespace| namespace.to_string_lossy());
+    let key_file = config
+        .repositories
+        .get(&repo_path.to_string_lossy().to_string())
+        .or_else(|| match (&namespace, &name) {
+            (Some(namespace), Some(name)) => {
+                config.repositories.get(&format!("{namespace}/{name}"))
+            }
+            _ => None,
+        })
+        .or_else(|| {
+            namespace
+                .as_ref()
+                .and_then(|namespace| config.namespaces.get(namespace.as_ref()))
+        });
+
+    match key_file {
+        Some(key_file) => Ok(Some(Arc::new(LocalKeyFile::open(key_file)?))),
+        None => Ok(None),
+    }
+}
+
+/// Gives the repository its own data key, wrapped by the key management service configured for
+/// it. Data written from now on is encrypted, `oxen-server repo encrypt` encrypts what is already
+/// there.
+pub fn enable(repo_path: &Path) -> Result<(), OxenError> {
+    if is_encrypted(repo_path) {
+        return Err(OxenError::basic_str(format!(
+            "Repository {repo_path:?} is already encrypted"
+        )));
+    }
+    let kms = kms_for_repo(repo_path)?.ok_or_else(|| {
+        OxenError::basic_str(format!(
+            "No encryption key is configured for repository {repo_path:?}"
+        ))
+    })?;
+
+    let data_key = random_bytes(KEY_LEN)?;
+    let info = EncryptionInfo {
+        key_id: kms.key_id(),
+        wrapped_key: to_hex(&kms.wrap_key(&data_key)?),
+    };
+    util::fs::write_to_path(info_path(repo_path), serde_json::to_string(&info)?)?;
+
+    // Paths may have been cached as unencrypted under other spellings
+    let mut cache = data_key_cache().write().unwrap();
+    cache.clear();
+    cache.insert(
+        repo_path.to_path_buf(),
+        Some(Arc::new(DataKey::new(&data_key)?)),
+    );
+    Ok(())
+}
+
+/// Forgets what is known about the repository at `repo_path`, for when it is moved or deleted
+pub fn forget(repo_path: &Path) {
+    data_key_cache().write().unwrap().remove(repo_path);
+    kms_registry().write().unwrap().remove(repo_path);
+}
+
+fn load_data_key(repo_path: &Path) -> Result<Option<Arc<DataKey>>, OxenError> {
+    let path = info_path(repo_path);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let info: EncryptionInfo = serde_json::from_str(&util::fs::read_from_path(&path)?)?;
+    let kms = kms_for_repo(repo_path)?.ok_or_else(|| {
+        OxenError::basic_str(format!(
+            "Repository {repo_path:?} is encrypted but no key is configured for it"
+        ))
+    })?;
+    if kms.key_id() != info.key_id {
+        return Err(OxenError::basic_str(format!(
+            "Repository {repo_path:?} is encrypted with key {} but key {} is configured",
+            info.key_id,
+            kms.key_id()
+        )));
+    }
+    let wrapped_key = from_hex(&info.wrapped_key)
+        .ok_or_else(|| OxenError::basic_str(format!("Invalid wrapped key in {path:?}")))?;
+    let data_key = kms.unwrap_key(&wrapped_key)?;
+    Ok(Some(Arc::new(DataKey::new(&data_key)?)))
+}
+
+// The repository a path inside `.oxen` belongs to. Workspaces nest `.oxen` dirs inside the
+// repository's, the outermost one is the repository.
+fn repo_root(path: &Path) -> Option<&Path> {
+    path.ancestors()
+        .filter(|dir| dir.file_name().is_some_and(|name| name == OXEN_HIDDEN_DIR))
+        .last()
+        .and_then(|dir| dir.parent())
+}
+
+/// The data key of the repository that `path` is in, None when it is not encrypted
+pub fn data_key_for_path(path: &Path) -> Result<Option<Arc<DataKey>>, OxenError> {
+    let Some(repo_path) = repo_root(path) else {
+        return Ok(None);
+    };
+    if let Some(key) = data_key_cache().read().unwrap().get(repo_path) {
+        return Ok(key.clone());
+    }
+    let key = load_data_key(repo_path)?;
+    data_key_cache()
+        .write()
+        .unwrap()
+        .insert(repo_path.to_path_buf(), key.clone());
+    Ok(key)
+}
+
+fn required_data_key(path: &Path) -> Result<Arc<DataKey>, OxenError> {
+    data_key_for_path(path)?.ok_or_else(|| {
+        OxenError::basic_str(format!(
+            "{path:?} is encrypted but its repository has no data key"
+        ))
+    })
+}
+
+/// Encrypts a key for the database at `db_path` if its repository is encrypted, the same key
+/// always encrypts the same so it can be looked up
+pub fn encrypt_key<'a>(db_path: &Path, key: &'a [u8]) -> Result<Cow<'a, [u8]>, OxenError> {
+    match data_key_for_path(db_path)? {
+        Some(data_key) => Ok(Cow::Owned(data_key.encrypt_db_key(key)?)),
+        None => Ok(Cow::Borrowed(key)),
+    }
+}
+
+/// Decrypts a key read from the database at `db_path`, plain keys are passed through
+pub fn decrypt_key<'a>(db_path: &Path, key: &'a [u8]) -> Result<Cow<'a, [u8]>, OxenError> {
+    if !is_encrypted_key(key) {
+        return Ok(Cow::Borrowed(key));
+    }
+    Ok(Cow::Owned(required_data_key(db_path)?.decrypt_db_key(key)?))
+}
+
+pub fn is_encrypted_key(key: &[u8]) -> bool {
+    key.starts_with(KEY_MAGIC)
+}
+
+/// Encrypts the value of the plain `key` for the database at `db_path` if its repository is
+/// encrypted
+pub fn encrypt_value<'a>(
+    db_path: &Path,
+    key: &[u8],
+    value: &'a [u8],
+) -> Result<Cow<'a, [u8]>, OxenError> {
+    match data_key_for_path(db_path)? {
+        Some(data_key) => Ok(Cow::Owned(data_key.encrypt_db_value(key, value)?)),
+        None => Ok(Cow::Borrowed(value)),
+    }
+}
+
+/// Decrypts the value of the plain `key` read from the database at `db_path`, plain values are
+/// passed through
+pub fn decrypt_value<'a>(
+    db_path: &Path,
+    key: &[u8],
+    value: &'a [u8],
+) -> Result<Cow<'a, [u8]>, OxenError> {
+    if !is_encrypted_value(value) {
+        return Ok(Cow::Borrowed(value));
+    }
+    Ok(Cow::Owned(
+        required_data_key(db_path)?.decrypt_db_value(key, value)?,
+    ))
+}
+
+pub fn is_encrypted_value(value: &[u8]) -> bool {
+    value.starts_with(DB_VALUE_MAGIC) || value.starts_with(VALUE_MAGIC)
+}
+
+/// Whether a key and value read from a database still have to be encrypted, or encrypted again
+/// because the value was sealed without its key
+pub fn needs_encryption(key: &[u8], value: &[u8]) -> bool {
+    !is_encrypted_key(key) || !value.starts_with(DB_VALUE_MAGIC)
+}
+
+/// Copies the directory `src` to `dst` with the values of every database in it decrypted, for
+/// clients that download the databases of an encrypted repository
+pub fn copy_decrypted(src: &Path, dst: &Path) -> Result<(), OxenError> {
+    util::fs::create_dir_all(dst)?;
+    // Every rocksdb database has a CURRENT file
+    if src.join("CURRENT").is_file() {
+        let opts = db::key_val::opts::default();
+        let src_db = DB::open_for_read_only(&opts, dunce::simplified(src), false)?;
+        let dst_db = DB::open(&opts, dunce::simplified(dst))?;
+        for item in kv_db::iter_decoded(&src_db) {
+            let (key, value) = item?;
+            dst_db.put(key, value)?;
+        }
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(src)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        if path.is_dir() {
+            copy_decrypted(&path, &dst.join(name))?;
+        } else {
+            util::fs::copy(&path, dst.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies the directory `src` to `dst` with the keys and values of every database in it
+/// encrypted for the repository `dst` is in, for the plain databases clients push. Databases that
+/// already exist in `dst` are added to.
+pub fn copy_encrypted(src: &Path, dst: &Path) -> Result<(), OxenError> {
+    util::fs::create_dir_all(dst)?;
+    if src.join("CURRENT").is_file() {
+        let opts = db::key_val::opts::default();
+        let src_db = DB::open_for_read_only(&opts, dunce::simplified(src), false)?;
+        let dst_db = DB::open(&opts, dunce::simplified(dst))?;
+        for item in src_db.iterator(IteratorMode::Start) {
+            let (key, value) = item?;
+            kv_db::put_bytes(&dst_db, &key, &value)?;
+        }
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(src)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        if path.is_dir() {
+            copy_encrypted(&path, &dst.join(name))?;
+        } else {
+            util::fs::copy(&path, dst.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Encrypts the keys and values of `db` that are still plain, or were sealed before values were
+/// bound to their keys, then compacts it so the plain copies are dropped from disk. Returns how
+/// many values it encrypted.
+pub fn encrypt_db(db: &DB) -> Result<u64, OxenError> {
+    let mut count = 0;
+    for item in db.iterator(IteratorMode::Start) {
+        let (key, value) = item?;
+        if !needs_encryption(&key, &value) {
+            continue;
+        }
+        let plain_key = kv_db::decode_key(db, &key)?.into_owned();
+        let plain_value = kv_db::decode_value(db, &plain_key, &value)?.into_owned();
+        kv_db::put_bytes(db, &plain_key, &plain_value)?;
+        count += 1;
+    }
+    if count > 0 {
+        db.compact_range::<&[u8], &[u8]>(None, None);
+    }
+    Ok(count)
+}
+
+/// Encrypts everything written to it in segments, `finish` seals the last one
+pub struct EncryptWriter<W: Write> {
+    inner: W,
+    key: Arc<DataKey>,
+    nonce_prefix: Vec<u8>,
+    counter: u32,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> EncryptWriter<W> {
+    pub fn new(key: Arc<DataKey>, mut inner: W) -> Result<EncryptWriter<W>, OxenError> {
+        let nonce_prefix = random_bytes(NONCE_PREFIX_LEN)?;
+        inner.write_all(FILE_MAGIC)?;
+        inner.write_all(&nonce_prefix)?;
+        Ok(EncryptWriter {
+            inner,
+            key,
+            nonce_prefix,
+            counter: 0,
+            buffer: Vec::with_capacity(SEGMENT_SIZE),
+        })
+    }
+
+    fn seal_segment(&mut self, len: usize, last: bool) -> std::io::Result<()> {
+        let mut segment: Vec<u8> = self.buffer.drain(..len).collect();
+        let nonce = DataKey::segment_nonce(&self.nonce_prefix, self.counter)
+            .map_err(std::io::Error::other)?;
+        self.key
+            .key
+            .seal_in_place_append_tag(nonce, Aad::from([last as u8]), &mut segment)
+            .map_err(|_| std::io::Error::other("Could not encrypt segment"))?;
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or_else(|| std::io::Error::other("File too large to encrypt"))?;
+        self.inner.write_all(&segment)
+    }
+
+    pub fn finish(mut self) -> Result<W, OxenError> {
+        let len = self.buffer.len();
+        self.seal_segment(len, true)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        // Always keep some bytes back, the last segment is sealed by finish
+        while self.buffer.len() > SEGMENT_SIZE {
+            self.seal_segment(SEGMENT_SIZE, false)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reads what an `EncryptWriter` wrote
+pub struct DecryptReader<R: Read> {
+    inner: R,
+    key: Arc<DataKey>,
+    nonce_prefix: Vec<u8>,
+    counter: u32,
+    sealed: Vec<u8>,
+    plain: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl<R: Read> DecryptReader<R> {
+    pub fn new(key: Arc<DataKey>, mut inner: R) -> Result<DecryptReader<R>, OxenError> {
+        let mut header = vec![0; FILE_MAGIC.len() + NONCE_PREFIX_LEN];
+        inner.read_exact(&mut header)?;
+        if !header.starts_with(FILE_MAGIC) {
+            return Err(OxenError::basic_str("File is not encrypted"));
+        }
+        Ok(DecryptReader {
+            inner,
+            key,
+            nonce_prefix: header[FILE_MAGIC.len()..].to_vec(),
+            counter: 0,
+            sealed: Vec::with_capacity(SEGMENT_SIZE + TAG_LEN + 1),
+            plain: vec![],
+            pos: 0,
+            done: false,
+        })
+    }
+
+    fn open_segment(&mut self) -> std::io::Result<()> {
+        // Read one byte past a full segment to know whether this is the last one
+        let want = SEGMENT_SIZE + TAG_LEN + 1;
+        while self.sealed.len() < want {
+            let start = self.sealed.len();
+            self.sealed.resize(want, 0);
+            let count = self.inner.read(&mut self.sealed[start..])?;
+            self.sealed.truncate(start + count);
+            if count == 0 {
+                break;
+            }
+        }
+        let last = self.sealed.len() < want;
+        let len = if last {
+            self.sealed.len()
+        } else {
+            SEGMENT_SIZE + TAG_LEN
+        };
+
+        let mut segment: Vec<u8> = self.sealed.drain(..len).collect();
+        let nonce = DataKey::segment_nonce(&self.nonce_prefix, self.counter)
+            .map_err(std::io::Error::other)?;
+        let plain_len = self
+            .key
+            .key
+            .open_in_place(nonce, Aad::from([last as u8]), &mut segment)
+            .map_err(|_| std::io::Error::other("Could not decrypt file, wrong key or corrupt"))?
+            .len();
+        segment.truncate(plain_len);
+        self.plain = segment;
+        self.pos = 0;
+        self.counter += 1;
+        self.done = last;
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> DecryptReader<R> {
+    /// Skips to `offset` in the plain contents, only the segment it falls in is decrypted
+    pub fn seek_to(&mut self, offset: u64) -> std::io::Result<()> {
+        // An offset on a segment boundary is read from the end of the segment before it, as
+        // there may be no segment after a full last one
+        let segment = offset.saturating_sub(1) / SEGMENT_SIZE as u64;
+        let counter = u32::try_from(segment)
+            .map_err(|_| std::io::Error::other("Offset past the end of the file"))?;
+        let header_len = (FILE_MAGIC.len() + NONCE_PREFIX_LEN) as u64;
+        self.inner.seek(SeekFrom::Start(
+            header_len + segment * (SEGMENT_SIZE + TAG_LEN) as u64,
+        ))?;
+        self.counter = counter;
+        self.sealed.clear();
+        self.plain.clear();
+        self.pos = 0;
+        self.done = false;
+
+        if offset > 0 {
+            self.open_segment()?;
+            let within = (offset - segment * SEGMENT_SIZE as u64) as usize;
+            self.pos = within.min(self.plain.len());
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos == self.plain.len() {
+            if self.done {
+                return Ok(0);
+            }
+            self.open_segment()?;
+        }
+        let count = buf.len().min(self.plain.len() - self.pos);
+        buf[..count].copy_from_slice(&self.plain[self.pos..self.pos + count]);
+        self.pos += count;
+        Ok(count)
+    }
+}
+
+/// Encrypts a file for the repository it is in
+pub fn encrypt_writer<W: Write>(path: &Path, inner: W) -> Result<EncryptWriter<W>, OxenError> {
+    EncryptWriter::new(required_data_key(path)?, inner)
+}
+
+/// Decrypts a file of the repository it is in
+pub fn decrypt_reader<R: Read>(path: &Path, inner: R) -> Result<DecryptReader<R>, OxenError> {
+    DecryptReader::new(required_data_key(path)?, inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Write};
+    use std::sync::Arc;
+
+    use crate::core::encryption::{
+        self, DataKey, DecryptReader, EncryptWriter, KeyManagementService, LocalKeyFile,
+        SEGMENT_SIZE,
+    };
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_encrypt_file_round_trips_across_segments() -> Result<(), OxenError> {
+        let key = Arc::new(DataKey::new(&[7; 32])?);
+        for len in [0, 10, SEGMENT_SIZE, SEGMENT_SIZE + 1, 2 * SEGMENT_SIZE + 3] {
+            let plain: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let mut writer = EncryptWriter::new(key.clone(), vec![])?;
+            writer.write_all(&plain)?;
+            let sealed = writer.finish()?;
+            assert_ne!(sealed[sealed.len().saturating_sub(10)..], plain[..]);
+
+            let mut decrypted = vec![];
+            DecryptReader::new(key.clone(), &sealed[..])?.read_to_end(&mut decrypted)?;
+            assert_eq!(decrypted, plain);
+
+            // Seeking only decrypts from the segment the offset is in
+            for offset in [0, len / 2, len.saturating_sub(1), len] {
+                let mut reader = DecryptReader::new(key.clone(), Cursor::new(&sealed[..]))?;
+                reader.seek_to(offset as u64)?;
+                let mut rest = vec![];
+                reader.read_to_end(&mut rest)?;
+                assert_eq!(rest, plain[offset..]);
+            }
+
+            // Dropping the last segment is caught
+            if len > SEGMENT_SIZE {
+                let truncated = &sealed[..8 + 8 + SEGMENT_SIZE + 16];
+                let mut out = vec![];
+                assert!(DecryptReader::new(key.clone(), truncated)?
+                    .read_to_end(&mut out)
+                    .is_err());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypt_values_with_repository_key() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let key_file = repo.path.join("test.key");
+            let kms = Arc::new(LocalKeyFile::generate(&key_file)?);
+            let wrapped = kms.wrap_key(b"data key")?;
+            assert_eq!(kms.unwrap_key(&wrapped)?, b"data key");
+
+            let db_path = repo.path.join(".oxen").join("refs");
+            // Plain until the repository is encrypted
+            assert_eq!(&*encryption::encrypt_key(&db_path, b"main")?, b"main");
+            assert_eq!(
+                &*encryption::encrypt_value(&db_path, b"main", b"abc")?,
+                b"abc"
+            );
+
+            // Not being encrypted is remembered until the repository is encrypted
+            let info_path = encryption::info_path(&repo.path);
+            util::fs::write_to_path(&info_path, "{}")?;
+            assert_eq!(&*encryption::encrypt_key(&db_path, b"main")?, b"main");
+            util::fs::remove_file(&info_path)?;
+            encryption::register_kms(&repo.path, kms);
+            encryption::enable(&repo.path)?;
+            assert!(encryption::is_encrypted(&repo.path));
+
+            // Keys encrypt the same every time so they can be looked up
+            let key = encryption::encrypt_key(&db_path, b"main")?.to_vec();
+            assert!(encryption::is_encrypted_key(&key));
+            assert_eq!(*encryption::encrypt_key(&db_path, b"main")?, *key);
+            assert_ne!(*encryption::encrypt_key(&db_path, b"dev")?, *key);
+            assert_eq!(&*encryption::decrypt_key(&db_path, &key)?, b"main");
+
+            let sealed = encryption::encrypt_value(&db_path, b"main", b"abc")?.to_vec();
+            assert!(encryption::is_encrypted_value(&sealed));
+            assert_eq!(
+                &*encryption::decrypt_value(&db_path, b"main", &sealed)?,
+                b"abc"
+            );
+            // Values are bound to their key
+            assert!(encryption::decrypt_value(&db_path, b"dev", &sealed).is_err());
+            // Plain keys and values written before are still readable
+            assert_eq!(&*encryption::decrypt_key(&db_path, b"dev")?, b"dev");
+            assert_eq!(
+                &*encryption::decrypt_value(&db_path, b"dev", b"def")?,
+                b"def"
+            );
+
+            // Another process has to unwrap the data key again
+            encryption::data_key_cache().write().unwrap().clear();
+            assert_eq!(
+                &*encryption::decrypt_value(&db_path, b"main", &sealed)?,
+                b"abc"
+            );
+            Ok(())
+        })
+    }
+}
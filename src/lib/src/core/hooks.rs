@@ -0,0 +1,369 @@
+//! Hooks that run around local commands.
+//!
+//! A hook is an executable in `.oxen/hooks/` named after the point it runs at, the same way git
+//! hooks work. Each hook gets a JSON description of what is about to happen (or just happened) on
+//! stdin and runs from the root of the repository:
+//!
+//! ```json
+//! {
+//!   "hook": "pre-commit",
+//!   "repo_path": "/data/cats-vs-dogs",
+//!   "branch": "main",
+//!   "message": "Adding more cats",
+//!   "staged_entries": [{ "path": "train/cat_1.jpg", "status": "Added", "hash": "..." }]
+//! }
+//! ```
+//!
+//! `pre-add`, `pre-commit`, `commit-msg` and `pre-push` can veto the command by exiting with a
+//! non zero status, whatever they wrote to stderr (or stdout) is shown as the reason.
+//! `post-commit` and `post-merge` run once the command is done, so they are only for
+//! notifications and a failure (or a hook that cannot be started) is logged and otherwise ignored.
+//!
+//! Like in git, `commit-msg` also gets the path of `.oxen/COMMIT_EDITMSG` as its first argument.
+//! The file holds the message and the commit uses whatever the hook left in it.
+//!
+
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::api;
+use crate::constants::{COMMIT_MSG_FILE, HOOKS_DIR};
+use crate::error::OxenError;
+use crate::model::{Commit, LocalRepository, StagedData, StagedEntryStatus};
+use crate::util;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hook {
+    PreAdd,
+    PreCommit,
+    CommitMsg,
+    PostCommit,
+    PrePush,
+    PostMerge,
+}
+
+impl Hook {
+    /// Name of the hook executable in `.oxen/hooks/`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hook::PreAdd => "pre-add",
+            Hook::PreCommit => "pre-commit",
+            Hook::CommitMsg => "commit-msg",
+            Hook::PostCommit => "post-commit",
+            Hook::PrePush => "pre-push",
+            Hook::PostMerge => "post-merge",
+        }
+    }
+
+    /// Whether a failing hook stops the command, post hooks run when it is too late for that
+    pub fn can_veto(&self) -> bool {
+        !matches!(self, Hook::PostCommit | Hook::PostMerge)
+    }
+}
+
+impl std::fmt::Display for Hook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HookEntry {
+    pub path: PathBuf,
+    pub status: StagedEntryStatus,
+    pub hash: String,
+}
+
+/// What a hook is told about the command on stdin
+#[derive(Debug, Clone, Serialize)]
+pub struct HookInput {
+    pub hook: Hook,
+    pub repo_path: PathBuf,
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PathBuf>,
+    pub staged_entries: Vec<HookEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<Commit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_branch: Option<String>,
+}
+
+impl HookInput {
+    pub fn new(repo: &LocalRepository, hook: Hook) -> Result<HookInput, OxenError> {
+        let branch = api::local::branches::current_branch(repo)?.map(|branch| branch.name);
+        Ok(HookInput {
+            hook,
+            repo_path: repo.path.to_owned(),
+            branch,
+            message: None,
+            paths: vec![],
+            staged_entries: vec![],
+            commit: None,
+            remote: None,
+            merge_branch: None,
+        })
+    }
+
+    /// Fills in `staged_entries` from the status of the stager, sorted by path
+    pub fn with_staged_data(mut self, status: &StagedData) -> HookInput {
+        let mut entries: Vec<HookEntry> = status
+            .staged_files
+            .iter()
+            .map(|(path, entry)| HookEntry {
+                path: path.to_owned(),
+                status: entry.status.to_owned(),
+                hash: entry.hash.to_owned(),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        self.staged_entries = entries;
+        self
+    }
+}
+
+/// Path to the executable for `hook` if one is installed in the repository
+pub fn hook_path(repo: &LocalRepository, hook: Hook) -> Option<PathBuf> {
+    let path = util::fs::oxen_hidden_dir(&repo.path)
+        .join(HOOKS_DIR)
+        .join(hook.name());
+    if !path.is_file() {
+        return None;
+    }
+    if !is_executable(&path) {
+        log::warn!("Ignoring the {hook} hook {path:?}, it is not executable");
+        return None;
+    }
+    Some(path)
+}
+
+/// Whether `hook` is installed, so callers can skip gathering its input when it is not
+pub fn is_installed(repo: &LocalRepository, hook: Hook) -> bool {
+    hook_path(repo, hook).is_some()
+}
+
+/// Runs `hook` with `input` on stdin if it is installed. Returns `OxenError::HookRejected` when a
+/// hook that can veto the command exits with a non zero status.
+pub fn run(repo: &LocalRepository, input: &HookInput) -> Result<(), OxenError> {
+    let hook = input.hook;
+    match run_with_args(repo, input, &[]) {
+        Err(err) if !hook.can_veto() => {
+            log::warn!("The {hook} hook failed: {err}");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Runs the `commit-msg` hook if it is installed and returns the message it left in
+/// `.oxen/COMMIT_EDITMSG`, or `message` when there is no hook
+pub fn run_commit_msg(
+    repo: &LocalRepository,
+    input: &HookInput,
+    message: &str,
+) -> Result<String, OxenError> {
+    if !is_installed(repo, Hook::CommitMsg) {
+        return Ok(message.to_string());
+    }
+    let msg_file = util::fs::oxen_hidden_dir(&repo.path).join(COMMIT_MSG_FILE);
+    util::fs::write_to_path(&msg_file, message)?;
+    // Absolute, the hook runs from the root of the repository
+    let msg_file =
+        dunce::canonicalize(&msg_file).map_err(|err| OxenError::file_error(&msg_file, err))?;
+    run_with_args(repo, input, &[&msg_file])?;
+
+    let edited = util::fs::read_from_path(&msg_file)?;
+    let edited = edited.trim_end();
+    if edited.trim().is_empty() {
+        return Err(OxenError::hook_rejected(
+            Hook::CommitMsg.name(),
+            "Aborting commit due to empty commit message",
+        ));
+    }
+    Ok(edited.to_string())
+}
+
+fn run_with_args(
+    repo: &LocalRepository,
+    input: &HookInput,
+    args: &[&Path],
+) -> Result<(), OxenError> {
+    let hook = input.hook;
+    let Some(path) = hook_path(repo, hook) else {
+        return Ok(());
+    };
+    log::debug!("Running {hook} hook {path:?}");
+
+    // The hook runs from the root of the repository, so a relative path would no longer resolve
+    let path = dunce::canonicalize(&path).map_err(|err| OxenError::file_error(&path, err))?;
+    let mut child = Command::new(&path)
+        .args(args)
+        .current_dir(&repo.path)
+        .env("OXEN_HOOK", hook.name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| OxenError::file_error(&path, err))?;
+
+    // Write stdin from another thread while the output is drained here, a hook that prints more
+    // than a pipe holds before it reads its input would otherwise block us both
+    let json = serde_json::to_vec(input)?;
+    let writer = child.stdin.take().map(|mut stdin| {
+        std::thread::spawn(move || match stdin.write_all(&json) {
+            // Hooks that don't care about their input may exit before reading it
+            Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            result => result,
+        })
+    });
+    let output = child.wait_with_output()?;
+    if let Some(writer) = writer {
+        writer
+            .join()
+            .map_err(|_| OxenError::basic_str(format!("Could not write to the {hook} hook")))??;
+    }
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let message = match (stderr.trim(), stdout.trim()) {
+        ("", "") => format!("{path:?} exited with {}", output.status),
+        ("", stdout) => stdout.to_string(),
+        (stderr, _) => stderr.to_string(),
+    };
+    Err(OxenError::hook_rejected(hook.name(), message))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use crate::command;
+    use crate::constants::HOOKS_DIR;
+    use crate::core::hooks::Hook;
+    use crate::error::OxenError;
+    use crate::model::LocalRepository;
+    use crate::test;
+    use crate::util;
+
+    fn install_hook(repo: &LocalRepository, hook: Hook, script: &str) -> Result<(), OxenError> {
+        let hooks_dir = util::fs::oxen_hidden_dir(&repo.path).join(HOOKS_DIR);
+        util::fs::create_dir_all(&hooks_dir)?;
+        let path = hooks_dir.join(hook.name());
+        util::fs::write_to_path(&path, script)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_pre_commit_hook_vetoes_commit() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            // Hooks run from the root of the repository
+            install_hook(
+                &repo,
+                Hook::PreCommit,
+                "#!/bin/sh\ncat > hook_input.json\necho 'labels.csv has an unknown label' >&2\nexit 1\n",
+            )?;
+            let input_file = repo.path.join("hook_input.json");
+
+            let labels = repo.path.join("labels.csv");
+            util::fs::write_to_path(&labels, "file,label\ncat.jpg,cat\n")?;
+            command::add(&repo, &labels)?;
+
+            let result = command::commit(&repo, "Adding labels");
+            assert!(matches!(result, Err(OxenError::HookRejected(_))));
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("labels.csv has an unknown label"));
+
+            // Nothing was committed and the hook saw the staged file
+            let status = command::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+            let input: serde_json::Value =
+                serde_json::from_str(&util::fs::read_from_path(&input_file)?)?;
+            assert_eq!(input["hook"], "pre-commit");
+            assert_eq!(input["message"], "Adding labels");
+            assert_eq!(input["staged_entries"][0]["path"], "labels.csv");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_commit_msg_hook_edits_message() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            install_hook(
+                &repo,
+                Hook::CommitMsg,
+                "#!/bin/sh\nmsg=$(cat \"$1\")\necho \"[data] $msg\" > \"$1\"\n",
+            )?;
+
+            let hello = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello, "Hello World")?;
+            command::add(&repo, &hello)?;
+            let commit = command::commit(&repo, "Adding hello")?;
+            assert_eq!(commit.message, "[data] Adding hello");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_hook_with_large_output_does_not_block() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            // Fills the stdout pipe before reading an input too big for the stdin pipe
+            install_hook(
+                &repo,
+                Hook::PreCommit,
+                "#!/bin/sh\nhead -c 1000000 /dev/zero\ncat > /dev/null\n",
+            )?;
+
+            let hello = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello, "Hello World")?;
+            command::add(&repo, &hello)?;
+            command::commit(&repo, &"Adding hello ".repeat(100_000))?;
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_post_commit_hook_failure_does_not_fail_commit() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            install_hook(&repo, Hook::PostCommit, "#!/bin/sh\nexit 1\n")?;
+
+            let hello = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello, "Hello World")?;
+            command::add(&repo, &hello)?;
+            command::commit(&repo, "Adding hello")?;
+
+            let status = command::status(&repo)?;
+            assert!(status.staged_files.is_empty());
+
+            Ok(())
+        })
+    }
+}
@@ -8,6 +8,7 @@ pub mod commit_sync_status;
 pub mod commit_validator;
 pub mod commit_writer;
 pub mod entry_indexer;
+pub mod fs_journal;
 pub mod legacy_commit_dir_entry_reader;
 pub mod legacy_commit_entry_reader;
 pub mod legacy_schema_reader;
@@ -16,6 +17,7 @@ pub mod merge_conflict_reader;
 pub mod merge_conflict_writer;
 pub mod merger;
 pub mod object_db_reader;
+pub mod oxenattributes;
 pub mod oxenignore;
 pub mod puller;
 pub mod pusher;
@@ -1,4 +1,4 @@
-use crate::core::db::key_val::str_json_db;
+use crate::core::db::key_val::{kv_db, str_json_db};
 use crate::core::index::RefReader;
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
@@ -102,7 +102,7 @@ impl CommitDBReader {
     ) -> Result<Option<Commit>, OxenError> {
         // Check if the id is in the DB
         let key = commit_id.as_bytes();
-        match db.get(key) {
+        match kv_db::get_bytes(db, key) {
             Ok(Some(value)) => {
                 let commit: Commit = serde_json::from_str(str::from_utf8(&value)?)?;
                 Ok(Some(commit))
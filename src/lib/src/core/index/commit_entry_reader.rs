@@ -270,6 +270,17 @@ impl CommitEntryReader {
         Ok(entries)
     }
 
+    /// Lists the files directly in `dir`, not the ones in its sub directories
+    pub fn list_dir_entries(&self, dir: &Path) -> Result<Vec<CommitEntry>, OxenError> {
+        let entry_reader = CommitDirEntryReader::new_from_path(
+            &self.base_path,
+            &self.commit_id,
+            dir,
+            self.object_reader.clone(),
+        )?;
+        entry_reader.list_entries()
+    }
+
     pub fn list_directory_set(&self, dir: &Path) -> Result<HashSet<CommitEntry>, OxenError> {
         log::debug!("CommitEntryReader::list_directory_set() dir: {:?}", dir);
         let entries = self.list_directory(dir)?;
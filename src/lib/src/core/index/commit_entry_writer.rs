@@ -2,9 +2,10 @@ use crate::constants::{
     self, DEFAULT_BRANCH_NAME, HISTORY_DIR, SCHEMAS_TREE_PREFIX, TMP_DIR, VERSIONS_DIR,
 };
 use crate::core::db;
-use crate::core::db::key_val::path_db;
 use crate::core::db::key_val::tree_db;
 use crate::core::db::key_val::tree_db::{TreeObject, TreeObjectChild, TreeObjectChildWithStatus};
+use crate::core::db::key_val::{kv_db, path_db};
+use crate::core::index::oxenattributes::OxenAttributes;
 use crate::core::index::{LegacyCommitDirEntryReader, ObjectDBReader, RefWriter, SchemaWriter};
 use crate::error::OxenError;
 use crate::model::{
@@ -33,6 +34,7 @@ pub struct CommitEntryWriter {
     pub schemas_db: DBWithThreadMode<MultiThreaded>,
     pub dirs_db: DBWithThreadMode<MultiThreaded>,
     pub vnodes_db: DBWithThreadMode<MultiThreaded>,
+    attributes: OxenAttributes,
     commit: Commit,
 }
 
@@ -159,6 +161,7 @@ impl CommitEntryWriter {
             dirs_db: DBWithThreadMode::open(&opts, dunce::simplified(&dirs_db_path))?,
             vnodes_db: DBWithThreadMode::open(&opts, dunce::simplified(&vnodes_db_path))?,
             dir_hashes_db: DBWithThreadMode::open(&opts, dunce::simplified(&dir_hashes_db_path))?,
+            attributes: OxenAttributes::from_repo(repository)?,
             commit: commit.to_owned(),
         })
     }
@@ -222,7 +225,10 @@ impl CommitEntryWriter {
         let metadata = fs::metadata(&full_path)?;
 
         // // Re-hash for issues w/ adding
-        let hash = util::hasher::hash_file_contents(&full_path)?;
+        let hash = util::hasher::hash_file_contents_with_algorithm(
+            &full_path,
+            self.repository.hash_algorithm(),
+        )?;
 
         // // Create entry object to as json
         let entry = CommitEntry {
@@ -279,7 +285,16 @@ impl CommitEntryWriter {
             std::fs::create_dir_all(versions_entry_dir)?;
         }
 
-        util::fs::copy(full_path, versions_entry_path)?;
+        let compress = self
+            .attributes
+            .attributes_for(&commit_entry.path)
+            .compresses(&self.repository);
+        util::fs::write_version_file_with_compression(
+            &self.repository,
+            &full_path,
+            &versions_entry_path,
+            compress,
+        )?;
 
         Ok(commit_entry)
     }
@@ -1128,7 +1143,7 @@ impl CommitEntryWriter {
             &self.dirs_db,
             &self.vnodes_db,
         ] {
-            let iter = db.iterator(rocksdb::IteratorMode::Start);
+            let iter = kv_db::iter_decoded(db);
             for item in iter {
                 match item {
                     Ok((key_bytes, value_bytes)) => {
@@ -1292,7 +1307,11 @@ impl CommitEntryWriter {
                         let mtime = FileTime::from_last_modification_time(&metadata);
 
                         // Re-hash in case modified after adding
-                        let hash = util::hasher::hash_file_contents(&full_path).unwrap();
+                        let hash = util::hasher::hash_file_contents_with_algorithm(
+                            &full_path,
+                            self.repository.hash_algorithm(),
+                        )
+                        .unwrap();
 
                         let file_res = TreeObject::File {
                             num_bytes: metadata.len(),
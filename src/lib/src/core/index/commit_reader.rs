@@ -68,7 +68,7 @@ impl CommitReader {
         let mut commits: HashSet<Commit> = HashSet::new();
         CommitDBReader::history_from_commit_id(&self.db, commit_id, &mut commits)?;
         let mut commits: Vec<Commit> = commits.into_iter().collect();
-        commits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        commits.sort_by_key(|commit| std::cmp::Reverse(commit.timestamp));
         Ok(commits)
     }
 
@@ -86,7 +86,7 @@ impl CommitReader {
         )?;
 
         let mut commits: Vec<Commit> = commits.into_iter().collect();
-        commits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        commits.sort_by_key(|commit| std::cmp::Reverse(commit.timestamp));
         Ok(commits)
     }
 
@@ -100,7 +100,7 @@ impl CommitReader {
         let mut commits: Vec<Commit> = CommitDBReader::history_from_commit(&self.db, &head_commit)?
             .into_iter()
             .collect();
-        commits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        commits.sort_by_key(|commit| std::cmp::Reverse(commit.timestamp));
         Ok(commits)
     }
 
@@ -80,7 +80,7 @@ fn compute_versions_hash(
             continue;
         }
 
-        let hash = util::hasher::hash_file_contents_with_retry(&version_path)?;
+        let hash = util::hasher::hash_file_contents_with_retry(&version_path, &entry.hash)?;
         // log::debug!("Got hash: {:?} -> {}", entry.path, hash);
 
         hashes.push(SimpleHash { hash })
@@ -129,7 +129,7 @@ fn new_r_validate_complete_merkle_node(
                 }
                 Ok(true)
             } else {
-                let disk_hash = util::hasher::hash_file_contents_with_retry(&version_path)?;
+                let disk_hash = util::hasher::hash_file_contents_with_retry(&version_path, hash)?;
                 if hash != &disk_hash {
                     log::debug!("found file issue for file {:?}", path);
                     Ok(false)
@@ -377,7 +377,7 @@ fn r_validate_changed_parts_of_merkle_node(
                 }
                 Ok(true)
             } else {
-                let disk_hash = util::hasher::hash_file_contents_with_retry(&version_path)?;
+                let disk_hash = util::hasher::hash_file_contents_with_retry(&version_path, hash)?;
                 if hash != &disk_hash {
                     log::debug!("found file issue for file {:?}", path);
                     Ok(false)
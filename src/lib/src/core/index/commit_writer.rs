@@ -1,6 +1,6 @@
 use crate::config::UserConfig;
 use crate::constants::{COMMITS_DIR, MERGE_HEAD_FILE, ORIG_HEAD_FILE};
-use crate::core::db::key_val::path_db;
+use crate::core::db::key_val::{kv_db, path_db};
 
 use crate::core::db;
 use crate::core::index::object_db_reader::get_object_reader;
@@ -259,7 +259,7 @@ impl CommitWriter {
             } else {
                 log::debug!("apply_mods copying non-tabular file");
                 // Non-tabular files are copied from their version path into the working dir
-                util::fs::copy(&version_path, &entry_path)?;
+                util::fs::copy_version_file(&version_path, &entry_path)?;
             }
 
             workspaces::files::add(workspace, &entry_path)?;
@@ -491,7 +491,7 @@ impl CommitWriter {
         // Write commit json to db
         let commit_json = serde_json::to_string(&commit)?;
         log::debug!("add_commit_to_db [{}] -> {}", commit.id, commit_json);
-        self.commits_db.put(&commit.id, commit_json.as_bytes())?;
+        kv_db::put_bytes(&self.commits_db, &commit.id, commit_json.as_bytes())?;
         Ok(())
     }
 
@@ -638,8 +638,8 @@ impl CommitWriter {
                     }
                 } else {
                     // we do have it, check if we need to update it
-                    let dst_hash =
-                        util::hasher::hash_file_contents(&dst_path).expect("Could not hash file");
+                    let dst_hash = util::hasher::hash_file_contents_like(&dst_path, &entry.hash)
+                        .expect("Could not hash file");
 
                     // let old_contents = util::fs::read_from_path(&version_path)?;
                     // let current_contents = util::fs::read_from_path(&dst_path)?;
@@ -782,7 +782,7 @@ impl CommitWriter {
     pub fn get_commit_by_id(&self, commit_id: &str) -> Result<Option<Commit>, OxenError> {
         // Check if the id is in the DB
         let key = commit_id.as_bytes();
-        match self.commits_db.get(key) {
+        match kv_db::get_bytes(&self.commits_db, key) {
             Ok(Some(value)) => {
                 let commit: Commit = serde_json::from_str(str::from_utf8(&value)?)?;
                 Ok(Some(commit))
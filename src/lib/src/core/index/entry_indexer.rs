@@ -79,11 +79,7 @@ impl EntryIndexer {
         // original head commit, only applies to pulling commits after initial clone
         let maybe_head_commit = api::local::commits::head_commit(&self.repository);
 
-        let head_commit = if let Ok(commit) = maybe_head_commit {
-            Some(commit)
-        } else {
-            None
-        };
+        let head_commit = maybe_head_commit.ok();
 
         // For pulling after the initial clone, save status to avoid overwriting local untracked changes
         let mut status = StagedData::empty();
@@ -401,6 +397,50 @@ impl EntryIndexer {
         Ok(None)
     }
 
+    /// Sync a remote branch into the versions dir without touching the working directory, so a
+    /// server can mirror another server's repository. Returns the commits that were newly synced.
+    pub async fn mirror_branch(
+        &self,
+        remote_repo: &RemoteRepository,
+        branch: &Branch,
+    ) -> Result<Vec<Commit>, OxenError> {
+        let rb = RemoteBranch::from_branch(&branch.name);
+        // Brings down the commits db and the history of any commits we do not have yet
+        self.pull_all_commit_objects(remote_repo, &rb).await?;
+
+        let commits = api::local::commits::list_from(&self.repository, &branch.commit_id)?;
+        let unsynced_commits: Vec<Commit> = commits
+            .into_iter()
+            .rev()
+            .filter(|c| !index::commit_sync_status::commit_is_synced(&self.repository, c))
+            .collect();
+        self.pull_tree_objects_for_commits(remote_repo, &unsynced_commits)
+            .await?;
+
+        for commit in &unsynced_commits {
+            let mut entries: Vec<Entry> = self
+                .read_pulled_commit_entries(commit, 0)?
+                .into_iter()
+                .map(Entry::from)
+                .collect();
+            entries.extend(
+                self.read_pulled_schema_entries(commit, 0)?
+                    .into_iter()
+                    .map(Entry::from),
+            );
+            // Versions we already have from other commits are skipped
+            puller::pull_entries_to_versions_dir(remote_repo, &entries, &self.repository.path)
+                .await?;
+            index::commit_sync_status::mark_commit_as_synced(&self.repository, commit)?;
+        }
+
+        // Only move the ref once all the data it points to is here
+        let ref_writer = RefWriter::new(&self.repository)?;
+        ref_writer.set_branch_commit_id(&branch.name, &branch.commit_id)?;
+
+        Ok(unsynced_commits)
+    }
+
     fn set_branch_name_for_commit(
         &self,
         name: &str,
@@ -498,7 +538,7 @@ impl EntryIndexer {
             });
         }
 
-        while finished_queue.len() > 0 {
+        while !finished_queue.is_empty() {
             // log::debug!("Before waiting for {} workers to finish...", queue.len());
             sleep(Duration::from_secs(1)).await;
         }
@@ -760,7 +800,9 @@ impl EntryIndexer {
                     //     entry.path()
                     // );
                     let version_path = util::fs::version_path_for_entry(&self.repository, entry);
-                    match util::fs::copy_mkdir(version_path, &filepath) {
+                    match util::fs::create_dir_all(filepath.parent().unwrap()).and_then(|_| {
+                        util::fs::checkout_version_file(&self.repository, &version_path, &filepath)
+                    }) {
                         Ok(_) => {}
                         Err(err) => {
                             log::error!("pull_entries_for_commit unpack error: {}", err);
@@ -0,0 +1,395 @@
+//! Journal of paths changed in the working directory, written by `oxen watch` so that status and
+//! add only have to look at what changed instead of walking the whole repository.
+//!
+//! The watcher appends one JSON encoded path, relative to the repository, per line to
+//! `.oxen/fs_journal` and keeps a heartbeat in `.oxen/fs_watcher.json`. Every full status saves a
+//! checkpoint of the paths that were still dirty and how far into the journal it had read, so the
+//! next status only looks at those plus the paths appended since.
+//!
+//! Events reach the watcher some time after the writes, so before reading the journal a status
+//! writes a sentinel file into `.oxen/fs_journal_sync` and waits for the watcher to journal it.
+//! Events arrive in order, everything written before the sentinel is in the journal by then.
+//! When the journal cannot be trusted (no watcher, a stale heartbeat, a journal restarted after
+//! dropped events, a watcher that did not catch up in time) callers fall back to a full scan,
+//! which checks the modified time and size of every file.
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::constants::{
+    FS_JOURNAL_CHECKPOINT_FILE, FS_JOURNAL_FILE, FS_JOURNAL_SYNC_DIR, FS_WATCHER_FILE,
+    OXEN_HIDDEN_DIR,
+};
+use crate::error::OxenError;
+use crate::util;
+
+/// How often the watcher proves it is still running
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+// A watcher that has not checked in for this long may have missed events
+const STALE_AFTER_SECS: i64 = 5;
+
+// How long to wait for the watcher to journal a sentinel before giving up on the journal
+const SYNC_TIMEOUT: Duration = Duration::from_secs(2);
+const SYNC_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+// Start a new generation instead of letting the journal grow forever
+const MAX_JOURNAL_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WatcherState {
+    pub pid: u32,
+    pub generation: String,
+    pub heartbeat: i64,
+}
+
+impl WatcherState {
+    pub fn is_live(&self) -> bool {
+        OffsetDateTime::now_utc().unix_timestamp() - self.heartbeat <= STALE_AFTER_SECS
+    }
+}
+
+/// How far into which journal a status has read
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JournalPosition {
+    pub generation: String,
+    pub offset: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Checkpoint {
+    position: JournalPosition,
+    dirty: Vec<PathBuf>,
+}
+
+/// Paths that may differ from what the last status saw, everything else is known to be unchanged
+#[derive(Debug)]
+pub struct DirtyPaths {
+    pub position: JournalPosition,
+    pub paths: HashSet<PathBuf>,
+}
+
+fn journal_path(repo_path: &Path) -> PathBuf {
+    util::fs::oxen_hidden_dir(repo_path).join(FS_JOURNAL_FILE)
+}
+
+fn watcher_path(repo_path: &Path) -> PathBuf {
+    util::fs::oxen_hidden_dir(repo_path).join(FS_WATCHER_FILE)
+}
+
+fn checkpoint_path(repo_path: &Path) -> PathBuf {
+    util::fs::oxen_hidden_dir(repo_path).join(FS_JOURNAL_CHECKPOINT_FILE)
+}
+
+/// Whether a path relative to the repository is a sentinel, the only paths in `.oxen` the watcher
+/// journals
+pub fn is_sync_path(path: &Path) -> bool {
+    path.starts_with(Path::new(OXEN_HIDDEN_DIR).join(FS_JOURNAL_SYNC_DIR))
+}
+
+// Writes a sentinel and reads the journal until the watcher recorded it, None if it does not
+// within SYNC_TIMEOUT
+fn read_until_synced(repo_path: &Path, reader: &mut BufReader<File>) -> Option<String> {
+    let sync_dir = util::fs::oxen_hidden_dir(repo_path).join(FS_JOURNAL_SYNC_DIR);
+    let name = uuid::Uuid::new_v4().to_string();
+    let sentinel = Path::new(OXEN_HIDDEN_DIR)
+        .join(FS_JOURNAL_SYNC_DIR)
+        .join(&name);
+    let sentinel_line = format!("{}\n", serde_json::to_string(&sentinel).ok()?);
+    util::fs::create_dir_all(&sync_dir).ok()?;
+    let sentinel_path = sync_dir.join(&name);
+    util::fs::write_to_path(&sentinel_path, "").ok()?;
+
+    let started = std::time::Instant::now();
+    let mut appended = String::new();
+    let synced = loop {
+        reader.read_to_string(&mut appended).ok()?;
+        if appended.contains(&sentinel_line) {
+            break true;
+        }
+        if started.elapsed() > SYNC_TIMEOUT {
+            break false;
+        }
+        std::thread::sleep(SYNC_POLL_INTERVAL);
+    };
+    if let Err(err) = std::fs::remove_file(&sentinel_path) {
+        log::debug!("Could not remove fs journal sentinel {sentinel_path:?}: {err}");
+    }
+    if !synced {
+        log::debug!("oxen watch did not catch up within {SYNC_TIMEOUT:?}, not using the journal");
+        return None;
+    }
+    Some(appended)
+}
+
+// Readers never see a half written file
+fn write_atomically(path: &Path, contents: &str) -> Result<(), OxenError> {
+    let tmp_path = path.with_extension(format!("tmp.{}", std::process::id()));
+    util::fs::write_to_path(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+pub fn read_watcher_state(repo_path: &Path) -> Option<WatcherState> {
+    let contents = std::fs::read_to_string(watcher_path(repo_path)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+// Opens the journal of the live watcher, positioned after its header line
+fn open_live_journal(repo_path: &Path) -> Option<(WatcherState, BufReader<File>, u64)> {
+    let state = read_watcher_state(repo_path).filter(|s| s.is_live())?;
+    let mut reader = BufReader::new(File::open(journal_path(repo_path)).ok()?);
+    let mut header = String::new();
+    let header_len = reader.read_line(&mut header).ok()? as u64;
+    if header.trim_end() != state.generation {
+        return None;
+    }
+    Some((state, reader, header_len))
+}
+
+/// The end of the live watcher's journal, None when no watcher is running
+pub fn position(repo_path: &Path) -> Option<JournalPosition> {
+    let (state, reader, _) = open_live_journal(repo_path)?;
+    let len = reader.get_ref().metadata().ok()?.len();
+    Some(JournalPosition {
+        generation: state.generation,
+        offset: len,
+    })
+}
+
+/// Paths changed since the last checkpoint, None when the journal cannot be trusted and the caller
+/// has to look at everything
+pub fn dirty_paths(repo_path: &Path) -> Option<DirtyPaths> {
+    let (state, mut reader, header_len) = open_live_journal(repo_path)?;
+    let contents = std::fs::read_to_string(checkpoint_path(repo_path)).ok()?;
+    let checkpoint: Checkpoint = serde_json::from_str(&contents).ok()?;
+    if checkpoint.position.generation != state.generation || checkpoint.position.offset < header_len
+    {
+        return None;
+    }
+
+    reader
+        .seek(SeekFrom::Start(checkpoint.position.offset))
+        .ok()?;
+    let appended = read_until_synced(repo_path, &mut reader)?;
+    // The watcher may be half way through a line, leave it for next time
+    let complete = appended.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    let mut paths: HashSet<PathBuf> = checkpoint.dirty.into_iter().collect();
+    for line in appended[..complete].lines() {
+        let path: PathBuf = serde_json::from_str(line).ok()?;
+        if !is_sync_path(&path) {
+            paths.insert(path);
+        }
+    }
+    log::debug!("fs journal has {} dirty paths", paths.len());
+    Some(DirtyPaths {
+        position: JournalPosition {
+            generation: state.generation,
+            offset: checkpoint.position.offset + complete as u64,
+        },
+        paths,
+    })
+}
+
+/// Remembers which paths were still dirty when the journal was read up to `position`
+pub fn save_checkpoint(
+    repo_path: &Path,
+    position: &JournalPosition,
+    dirty: impl IntoIterator<Item = PathBuf>,
+) -> Result<(), OxenError> {
+    let checkpoint = Checkpoint {
+        position: position.to_owned(),
+        dirty: dirty.into_iter().collect(),
+    };
+    write_atomically(
+        &checkpoint_path(repo_path),
+        &serde_json::to_string(&checkpoint)?,
+    )
+}
+
+/// Asks the running watcher to exit, returns false if there was none
+pub fn stop_watcher(repo_path: &Path) -> Result<bool, OxenError> {
+    let path = watcher_path(repo_path);
+    if !path.exists() {
+        return Ok(false);
+    }
+    util::fs::remove_file(path)?;
+    Ok(true)
+}
+
+/// The watcher's end of the journal
+pub struct JournalWriter {
+    repo_path: PathBuf,
+    generation: String,
+    file: File,
+}
+
+impl JournalWriter {
+    /// Starts a new journal generation, taking over from any other watcher on the repository
+    pub fn create(repo_path: &Path) -> Result<JournalWriter, OxenError> {
+        let (generation, file) = JournalWriter::new_journal(repo_path)?;
+        let writer = JournalWriter {
+            repo_path: repo_path.to_path_buf(),
+            generation,
+            file,
+        };
+        writer.write_state()?;
+        Ok(writer)
+    }
+
+    fn new_journal(repo_path: &Path) -> Result<(String, File), OxenError> {
+        let generation = uuid::Uuid::new_v4().to_string();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(journal_path(repo_path))?;
+        writeln!(file, "{generation}")?;
+        Ok((generation, file))
+    }
+
+    fn write_state(&self) -> Result<(), OxenError> {
+        let state = WatcherState {
+            pid: std::process::id(),
+            generation: self.generation.to_owned(),
+            heartbeat: OffsetDateTime::now_utc().unix_timestamp(),
+        };
+        write_atomically(
+            &watcher_path(&self.repo_path),
+            &serde_json::to_string(&state)?,
+        )
+    }
+
+    pub fn generation(&self) -> &str {
+        &self.generation
+    }
+
+    /// Appends changed paths, relative to the repository
+    pub fn record(&mut self, paths: &[PathBuf]) -> Result<(), OxenError> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let mut lines = String::new();
+        for path in paths {
+            lines.push_str(&serde_json::to_string(path)?);
+            lines.push('\n');
+        }
+        // One write per batch so readers rarely see a partial line
+        self.file.write_all(lines.as_bytes())?;
+        if self.file.metadata()?.len() > MAX_JOURNAL_BYTES {
+            self.restart()?;
+        }
+        Ok(())
+    }
+
+    /// Throws the journal away when events may have been dropped, the next status does a full
+    /// scan and checkpoints the new generation
+    pub fn restart(&mut self) -> Result<(), OxenError> {
+        log::debug!("restarting fs journal {}", self.generation);
+        let (generation, file) = JournalWriter::new_journal(&self.repo_path)?;
+        self.generation = generation;
+        self.file = file;
+        self.write_state()
+    }
+
+    /// Checks in, returns false once another watcher took over or `oxen watch --stop` was run
+    pub fn heartbeat(&self) -> Result<bool, OxenError> {
+        match read_watcher_state(&self.repo_path) {
+            Some(state) if state.generation == self.generation => {
+                self.write_state()?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Stops vouching for the journal
+    pub fn finish(self) -> Result<(), OxenError> {
+        if read_watcher_state(&self.repo_path).is_some_and(|s| s.generation == self.generation) {
+            util::fs::remove_file(watcher_path(&self.repo_path))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, Instant};
+
+    use crate::constants::{FS_JOURNAL_SYNC_DIR, OXEN_HIDDEN_DIR};
+    use crate::core::index::fs_journal::{self, DirtyPaths, JournalWriter};
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    // Reads the dirty paths while standing in for the watcher, journaling the sentinel
+    fn synced_dirty_paths(repo_path: &Path, writer: &mut JournalWriter) -> Option<DirtyPaths> {
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let sync_dir = util::fs::oxen_hidden_dir(repo_path).join(FS_JOURNAL_SYNC_DIR);
+                let started = Instant::now();
+                while started.elapsed() < Duration::from_secs(5) {
+                    if let Some(Ok(entry)) = std::fs::read_dir(&sync_dir)
+                        .ok()
+                        .and_then(|mut dir| dir.next())
+                    {
+                        let sentinel = Path::new(OXEN_HIDDEN_DIR)
+                            .join(FS_JOURNAL_SYNC_DIR)
+                            .join(entry.file_name());
+                        writer.record(&[sentinel]).unwrap();
+                        return;
+                    }
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            });
+            fs_journal::dirty_paths(repo_path)
+        })
+    }
+
+    #[test]
+    fn test_fs_journal_dirty_paths_since_checkpoint() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            // No watcher, no journal
+            assert!(fs_journal::position(&repo.path).is_none());
+
+            let mut writer = JournalWriter::create(&repo.path)?;
+            writer.record(&[PathBuf::from("images/cat.jpg")])?;
+
+            // Nothing to go on until a full status has checkpointed the journal
+            assert!(fs_journal::dirty_paths(&repo.path).is_none());
+            let position = fs_journal::position(&repo.path).unwrap();
+            fs_journal::save_checkpoint(&repo.path, &position, vec![PathBuf::from("a.txt")])?;
+
+            // A watcher that does not journal the sentinel in time can't be trusted
+            writer.record(&[PathBuf::from("b.txt"), PathBuf::from("dir/c d.txt")])?;
+            assert!(fs_journal::dirty_paths(&repo.path).is_none());
+
+            let dirty = synced_dirty_paths(&repo.path, &mut writer).unwrap();
+            assert_eq!(dirty.paths.len(), 3);
+            assert!(dirty.paths.contains(Path::new("a.txt")));
+            assert!(dirty.paths.contains(Path::new("dir/c d.txt")));
+            assert!(!dirty.paths.contains(Path::new("images/cat.jpg")));
+            assert!(dirty
+                .paths
+                .iter()
+                .all(|path| !fs_journal::is_sync_path(path)));
+
+            // A restarted journal cannot be trusted against the old checkpoint
+            writer.restart()?;
+            assert!(fs_journal::dirty_paths(&repo.path).is_none());
+
+            assert!(fs_journal::stop_watcher(&repo.path)?);
+            assert!(!writer.heartbeat()?);
+            assert!(fs_journal::position(&repo.path).is_none());
+            Ok(())
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::db::key_val::kv_db;
 use crate::error::OxenError;
 use crate::model::MergeConflict;
 
@@ -19,7 +20,7 @@ impl MergeConflictDBReader {
     pub fn get_conflict(db: &DB, path: &Path) -> Result<Option<MergeConflict>, OxenError> {
         let key = path.to_str().unwrap();
         let bytes = key.as_bytes();
-        match db.get(bytes) {
+        match kv_db::get_bytes(db, bytes) {
             Ok(Some(value)) => match str::from_utf8(&value) {
                 Ok(value) => {
                     let entry: MergeConflict = serde_json::from_str(value)?;
@@ -44,7 +45,7 @@ impl MergeConflictDBReader {
 
     pub fn list_conflicts(db: &DB) -> Result<Vec<MergeConflict>, OxenError> {
         let mut conflicts: Vec<MergeConflict> = vec![];
-        let iter = db.iterator(IteratorMode::Start);
+        let iter = kv_db::iter_decoded(db);
         for item in iter {
             match item {
                 Ok((_, value)) => {
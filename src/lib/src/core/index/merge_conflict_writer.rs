@@ -3,6 +3,7 @@
 
 use crate::constants::{MERGE_HEAD_FILE, ORIG_HEAD_FILE};
 use crate::core::db;
+use crate::core::db::key_val::kv_db;
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository, MergeConflict};
 use crate::util;
@@ -45,7 +46,7 @@ pub fn write_conflicts_to_disk(
         let key_bytes = key.as_bytes();
         let val_json = serde_json::to_string(&conflict)?;
 
-        db.put(key_bytes, val_json.as_bytes())?;
+        kv_db::put_bytes(db, key_bytes, val_json.as_bytes())?;
     }
 
     Ok(())
@@ -2,6 +2,9 @@ use crate::api;
 use crate::config::UserConfig;
 use crate::constants::MERGE_DIR;
 use crate::core::db;
+use crate::core::db::key_val::kv_db;
+use crate::core::df::tabular;
+use crate::core::index::oxenattributes::{FileAttributes, MergeStrategy, OxenAttributes};
 use crate::core::index::{
     oxenignore, CommitEntryReader, CommitEntryWriter, CommitReader, CommitWriter,
     MergeConflictDBReader, RefReader, RefWriter, SchemaReader, Stager,
@@ -11,7 +14,10 @@ use crate::model::{Branch, Commit, CommitEntry, LocalRepository, MergeConflict};
 
 use crate::util;
 
+use polars::prelude::{BooleanChunked, DataFrame, UniqueKeepStrategy};
 use rocksdb::{DBWithThreadMode, MultiThreaded, DB};
+use std::collections::HashSet;
+use std::hash::Hash;
 use std::path::{Path, PathBuf};
 use std::str;
 
@@ -384,8 +390,7 @@ impl Merger {
 
     pub fn remove_conflict_path(&self, path: &Path) -> Result<(), OxenError> {
         let path_str = path.to_str().unwrap();
-        let key = path_str.as_bytes();
-        self.merge_db.delete(key)?;
+        kv_db::delete(&self.merge_db, path_str)?;
         Ok(())
     }
 
@@ -659,6 +664,17 @@ impl Merger {
         let files_db = CommitEntryWriter::files_db_dir(&self.repository);
         let files_db = DBWithThreadMode::open(&opts, dunce::simplified(&files_db))?;
 
+        // Conflicts in files with a merge strategy in .oxenattributes are resolved on the spot,
+        // the attributes of the branch being merged into apply
+        let attributes = OxenAttributes::from_commit(&self.repository, &merge_commits.base)?;
+        let mut add_conflict = |conflict: MergeConflict| -> Result<(), OxenError> {
+            let file_attributes = attributes.attributes_for(&conflict.merge_entry.path);
+            if !self.resolve_conflict(&conflict, &file_attributes, write_to_disk, &files_db)? {
+                conflicts.push(conflict);
+            }
+            Ok(())
+        };
+
         // Check all the entries in the candidate merge
         for merge_entry in merge_entries.iter() {
             // log::debug!("Considering entry {}", merge_entries.len());
@@ -687,20 +703,20 @@ impl Merger {
                         && lca_entry.hash != merge_entry.hash
                         && base_entry.hash != merge_entry.hash
                     {
-                        conflicts.push(MergeConflict {
+                        add_conflict(MergeConflict {
                             lca_entry: lca_entry.to_owned(),
                             base_entry: base_entry.to_owned(),
                             merge_entry: merge_entry.to_owned(),
-                        });
+                        })?;
                     }
                 } else {
                     // merge entry doesn't exist in LCA, so just check if it's different from base
                     if base_entry.hash != merge_entry.hash {
-                        conflicts.push(MergeConflict {
+                        add_conflict(MergeConflict {
                             lca_entry: base_entry.to_owned(),
                             base_entry: base_entry.to_owned(),
                             merge_entry: merge_entry.to_owned(),
-                        });
+                        })?;
                     }
                 }
             } else if write_to_disk {
@@ -714,6 +730,145 @@ impl Merger {
         Ok(conflicts)
     }
 
+    /// Resolves a conflict with the merge strategy of the file, returns false if it has none
+    fn resolve_conflict(
+        &self,
+        conflict: &MergeConflict,
+        attributes: &FileAttributes,
+        write_to_disk: bool,
+        files_db: &DBWithThreadMode<MultiThreaded>,
+    ) -> Result<bool, OxenError> {
+        let Some(strategy) = attributes.merge else {
+            return Ok(false);
+        };
+        log::debug!(
+            "resolve_conflict {:?} with strategy {:?}",
+            conflict.merge_entry.path,
+            strategy
+        );
+
+        if write_to_disk {
+            match strategy {
+                MergeStrategy::Ours => {}
+                MergeStrategy::Theirs => self.update_entry(&conflict.merge_entry, files_db)?,
+                MergeStrategy::Union | MergeStrategy::RowMerge => {
+                    self.combine_entries(conflict, attributes, strategy)?
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Writes the lines or rows of both sides of the conflict to the working file. Lines or rows
+    /// added on either side are kept and the ones that were in the common ancestor but were
+    /// deleted on either side are dropped.
+    fn combine_entries(
+        &self,
+        conflict: &MergeConflict,
+        attributes: &FileAttributes,
+        strategy: MergeStrategy,
+    ) -> Result<(), OxenError> {
+        let repo = &self.repository;
+        let base_path = util::fs::version_path(repo, &conflict.base_entry);
+        let base_path = util::fs::readable_version_path(repo, &base_path)?;
+        let merge_path = util::fs::version_path(repo, &conflict.merge_entry);
+        let merge_path = util::fs::readable_version_path(repo, &merge_path)?;
+        // Conflicts on files that were added on both sides have no common version
+        let lca_path = if conflict.lca_entry.hash == conflict.base_entry.hash {
+            None
+        } else {
+            let lca_path = util::fs::version_path(repo, &conflict.lca_entry);
+            Some(util::fs::readable_version_path(repo, &lca_path)?)
+        };
+        let working_path = repo.path.join(&conflict.base_entry.path);
+        if util::fs::is_linked_file(&working_path) {
+            util::fs::remove_file(&working_path)?;
+        }
+
+        if attributes.is_tabular(&working_path) {
+            let opts = attributes.df_opts(&working_path);
+            let df_base = tabular::read_df(&*base_path, opts.clone())?;
+            let df_merge = tabular::read_df(&*merge_path, opts.clone())?;
+            let df_lca = match &lca_path {
+                Some(lca_path) => Some(tabular::read_df(&**lca_path, opts)?),
+                None => None,
+            };
+            // A row merge matches rows on their keys and keeps their version of a row
+            let (subset, keep) = match strategy {
+                MergeStrategy::RowMerge if !attributes.keys.is_empty() => {
+                    (Some(attributes.keys.clone()), UniqueKeepStrategy::Last)
+                }
+                MergeStrategy::RowMerge => (None, UniqueKeepStrategy::Last),
+                _ => (None, UniqueKeepStrategy::First),
+            };
+
+            let base_keys = row_keys(&df_base, subset.as_deref())?;
+            let merge_keys = row_keys(&df_merge, subset.as_deref())?;
+            let merge_rows = row_keys(&df_merge, None)?;
+            let (lca_keys, lca_rows) = match &df_lca {
+                Some(df_lca) => (
+                    row_keys(df_lca, subset.as_deref())?,
+                    row_keys(df_lca, None)?,
+                ),
+                None => (vec![], vec![]),
+            };
+            let deleted = deleted_on_either_side(&lca_keys, &base_keys, &merge_keys);
+            let lca_rows: HashSet<&String> = lca_rows.iter().collect();
+
+            let base_mask: BooleanChunked =
+                base_keys.iter().map(|key| !deleted.contains(key)).collect();
+            // Rows they left as they were in the common ancestor must not override ours
+            let merge_mask: BooleanChunked = merge_keys
+                .iter()
+                .zip(merge_rows.iter())
+                .map(|(key, row)| !deleted.contains(key) && !lca_rows.contains(row))
+                .collect();
+            let mut df = df_base
+                .filter(&base_mask)?
+                .vstack(&df_merge.filter(&merge_mask)?)?
+                .unique_stable(subset.as_deref(), keep, None)?;
+            match &attributes.delimiter {
+                Some(delimiter) => {
+                    tabular::write_df_csv(&mut df, &working_path, delimiter.as_bytes()[0])
+                }
+                None => tabular::write_df(&mut df, &working_path),
+            }
+        } else if strategy == MergeStrategy::Union {
+            let base = util::fs::read_from_path(&*base_path)?;
+            let merge = util::fs::read_from_path(&*merge_path)?;
+            let lca = match &lca_path {
+                Some(lca_path) => util::fs::read_from_path(&**lca_path)?,
+                None => String::new(),
+            };
+            let base_lines: Vec<&str> = base.lines().collect();
+            let merge_lines: Vec<&str> = merge.lines().collect();
+            let lca_lines: Vec<&str> = lca.lines().collect();
+            let deleted = deleted_on_either_side(&lca_lines, &base_lines, &merge_lines);
+
+            let base_set: HashSet<&str> = base_lines.iter().copied().collect();
+            let mut lines: Vec<&str> = base_lines
+                .iter()
+                .filter(|line| !deleted.contains(*line))
+                .copied()
+                .collect();
+            lines.extend(
+                merge_lines
+                    .iter()
+                    .filter(|line| !deleted.contains(*line) && !base_set.contains(*line)),
+            );
+            let mut contents = lines.join("\n");
+            if !lines.is_empty() && (base.ends_with('\n') || merge.ends_with('\n')) {
+                contents.push('\n');
+            }
+            util::fs::write_to_path(&working_path, contents)
+        } else {
+            Err(OxenError::basic_str(format!(
+                "Cannot row-merge {:?}, it is not tabular",
+                conflict.base_entry.path
+            )))
+        }
+    }
+
     fn update_entry(
         &self,
         merge_entry: &CommitEntry,
@@ -730,6 +885,30 @@ impl Merger {
     }
 }
 
+/// Identifies each row of the data frame by the values of the subset columns, or the whole row
+fn row_keys(df: &DataFrame, subset: Option<&[String]>) -> Result<Vec<String>, OxenError> {
+    let df = match subset {
+        Some(columns) => df.select(columns)?,
+        None => df.clone(),
+    };
+    (0..df.height())
+        .map(|i| Ok(format!("{:?}", df.get_row(i)?.0)))
+        .collect()
+}
+
+/// The items of the common ancestor that are missing from either side
+fn deleted_on_either_side<'a, T: Eq + Hash>(
+    lca: &'a [T],
+    base: &[T],
+    merge: &[T],
+) -> HashSet<&'a T> {
+    let base: HashSet<&T> = base.iter().collect();
+    let merge: HashSet<&T> = merge.iter().collect();
+    lca.iter()
+        .filter(|item| !base.contains(item) || !merge.contains(item))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::api;
@@ -1087,6 +1266,111 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_merge_conflict_resolved_by_union_attribute() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let main_branch = api::local::branches::current_branch(&repo)?.unwrap();
+            let attributes_path = repo.path.join(".oxenattributes");
+            util::fs::write_to_path(&attributes_path, "labels.txt merge=union\n")?;
+            let labels_path = repo.path.join("labels.txt");
+            util::fs::write_to_path(&labels_path, "cat\n")?;
+            // Adding the root dir skips hidden files
+            command::add(&repo, &attributes_path)?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Adding labels")?;
+
+            // Both branches add a different label
+            let merge_branch_name = "more-labels";
+            api::local::branches::create_checkout(&repo, merge_branch_name)?;
+            util::fs::write_to_path(&labels_path, "cat\ndog\n")?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Adding dog")?;
+
+            command::checkout(&repo, &main_branch.name).await?;
+            util::fs::write_to_path(&labels_path, "cat\nbird\n")?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Adding bird")?;
+
+            let merger = Merger::new(&repo)?;
+            let commit = merger.merge(merge_branch_name)?;
+            assert!(commit.is_some());
+            assert!(!MergeConflictReader::new(&repo)?.has_conflicts()?);
+            assert_eq!(util::fs::read_from_path(&labels_path)?, "cat\nbird\ndog\n");
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_merge_union_attribute_drops_deleted_lines() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let main_branch = api::local::branches::current_branch(&repo)?.unwrap();
+            let attributes_path = repo.path.join(".oxenattributes");
+            util::fs::write_to_path(&attributes_path, "labels.txt merge=union\n")?;
+            let labels_path = repo.path.join("labels.txt");
+            util::fs::write_to_path(&labels_path, "cat\ncow\n")?;
+            command::add(&repo, &attributes_path)?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Adding labels")?;
+
+            // They drop cow and add dog, we drop cat and add bird
+            let merge_branch_name = "more-labels";
+            api::local::branches::create_checkout(&repo, merge_branch_name)?;
+            util::fs::write_to_path(&labels_path, "cat\ndog\n")?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Swapping cow for dog")?;
+
+            command::checkout(&repo, &main_branch.name).await?;
+            util::fs::write_to_path(&labels_path, "cow\nbird\n")?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Swapping cat for bird")?;
+
+            let merger = Merger::new(&repo)?;
+            assert!(merger.merge(merge_branch_name)?.is_some());
+            assert_eq!(util::fs::read_from_path(&labels_path)?, "bird\ndog\n");
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_merge_row_merge_attribute_is_three_way() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let main_branch = api::local::branches::current_branch(&repo)?.unwrap();
+            let attributes_path = repo.path.join(".oxenattributes");
+            util::fs::write_to_path(&attributes_path, "labels.csv key=file merge=row-merge\n")?;
+            let labels_path = repo.path.join("labels.csv");
+            util::fs::write_to_path(&labels_path, "file,label\na,1\nb,2\nc,3\n")?;
+            command::add(&repo, &attributes_path)?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Adding labels")?;
+
+            // They relabel b and add d, we relabel a and delete c
+            let merge_branch_name = "more-labels";
+            api::local::branches::create_checkout(&repo, merge_branch_name)?;
+            util::fs::write_to_path(&labels_path, "file,label\na,1\nb,5\nc,3\nd,4\n")?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Relabel b")?;
+
+            command::checkout(&repo, &main_branch.name).await?;
+            util::fs::write_to_path(&labels_path, "file,label\na,9\nb,2\n")?;
+            command::add(&repo, &labels_path)?;
+            command::commit(&repo, "Relabel a")?;
+
+            let merger = Merger::new(&repo)?;
+            assert!(merger.merge(merge_branch_name)?.is_some());
+            let contents = util::fs::read_from_path(&labels_path)?;
+            let mut rows: Vec<&str> = contents.lines().skip(1).collect();
+            rows.sort();
+            assert_eq!(rows, vec!["a,9", "b,5", "d,4"]);
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_merge_conflict_three_way_merge_post_merge_branch() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|repo| async move {
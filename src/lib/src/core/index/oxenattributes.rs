@@ -0,0 +1,368 @@
+//! `.oxenattributes` assigns attributes to paths in the repository, the way `.gitattributes`
+//! does for git. Each line is a glob followed by the attributes for the files it matches, and
+//! when more than one line matches a file the later line wins:
+//!
+//! ```text
+//! # Comments and blank lines are ignored
+//! *.psv               tabular delimiter=|
+//! logs/**             binary -metadata
+//! annotations/*.csv   key=file,label merge=row-merge
+//! *.parquet           -compress
+//! ```
+//!
+//! A glob without a `/` matches the file name at any depth, otherwise it is matched against the
+//! whole path from the root of the repository. The attributes are:
+//!
+//! * `tabular`, `binary`, `text` - how to treat the file instead of guessing from its extension.
+//!   Tabular files with an extension oxen doesn't know are read as CSV.
+//! * `delimiter=<char>` - the CSV delimiter, `\t` or `tab` for tabs.
+//! * `key=<col>,<col>` - the key columns to match rows on in diffs and row merges.
+//! * `embeddings=<col>,<col>` - columns of float vectors to store in the search index for nearest
+//!   neighbour search, none are stored unless they are listed.
+//! * `merge=<strategy>` - resolve merge conflicts automatically: `ours`, `theirs`, `union` (keep
+//!   the lines or rows added on both sides) or `row-merge` (match rows on the key columns, their
+//!   version of a row wins). Lines or rows deleted on either side since the common ancestor are
+//!   dropped, and the attributes of the branch being merged into decide the strategy.
+//! * `metadata` / `-metadata` - whether to compute metadata, schemas and search indices.
+//! * `compress` / `-compress` - whether to zstd compress the version file, overriding the
+//!   `compress_versions` setting of the repository.
+//!
+//! Locally the file in the working directory is used. The server reads it from the commit it is
+//! processing.
+//!
+
+use glob::{MatchOptions, Pattern};
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::api;
+use crate::constants::OXEN_ATTRIBUTES_FILE;
+use crate::error::OxenError;
+use crate::model::{Commit, LocalRepository};
+use crate::opts::DFOpts;
+use crate::util;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataTypeAttribute {
+    Tabular,
+    Binary,
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    Ours,
+    Theirs,
+    Union,
+    RowMerge,
+}
+
+impl FromStr for MergeStrategy {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ours" => Ok(MergeStrategy::Ours),
+            "theirs" => Ok(MergeStrategy::Theirs),
+            "union" => Ok(MergeStrategy::Union),
+            "row-merge" => Ok(MergeStrategy::RowMerge),
+            _ => Err(OxenError::basic_str(format!(
+                "Unknown merge strategy '{s}', expected ours, theirs, union or row-merge"
+            ))),
+        }
+    }
+}
+
+/// The attributes that apply to a single file, unset attributes fall back to the defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileAttributes {
+    pub data_type: Option<DataTypeAttribute>,
+    pub delimiter: Option<String>,
+    pub keys: Vec<String>,
+    pub embeddings: Vec<String>,
+    pub merge: Option<MergeStrategy>,
+    pub metadata: Option<bool>,
+    pub compress: Option<bool>,
+}
+
+impl FileAttributes {
+    /// Whether to treat the file as tabular, `path` is used to guess when it is not set
+    pub fn is_tabular(&self, path: &Path) -> bool {
+        match self.data_type {
+            Some(data_type) => data_type == DataTypeAttribute::Tabular,
+            None => util::fs::is_tabular(path),
+        }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.data_type == Some(DataTypeAttribute::Binary)
+    }
+
+    /// Whether to treat the file as text, `path` is used to guess when it is not set
+    pub fn is_text(&self, path: &Path) -> bool {
+        match self.data_type {
+            Some(data_type) => data_type == DataTypeAttribute::Text,
+            None => util::fs::is_utf8(path),
+        }
+    }
+
+    pub fn computes_metadata(&self) -> bool {
+        self.metadata.unwrap_or(true)
+    }
+
+    pub fn compresses(&self, repo: &LocalRepository) -> bool {
+        self.compress.unwrap_or(repo.compresses_versions())
+    }
+
+    /// Fills in the options for reading the file as a data frame, keeping anything already set
+    pub fn apply_to_df_opts(&self, path: &Path, opts: &mut DFOpts) {
+        if opts.delimiter.is_none() {
+            opts.delimiter = self.delimiter.clone();
+        }
+        let is_unknown_tabular =
+            self.data_type == Some(DataTypeAttribute::Tabular) && !util::fs::is_tabular(path);
+        if opts.delimiter.is_none() && is_unknown_tabular {
+            opts.delimiter = Some(String::from(","));
+        }
+    }
+
+    /// Options for reading the file as a data frame
+    pub fn df_opts(&self, path: &Path) -> DFOpts {
+        let mut opts = DFOpts::empty();
+        self.apply_to_df_opts(path, &mut opts);
+        opts
+    }
+
+    fn apply(&mut self, other: &FileAttributes) {
+        if other.data_type.is_some() {
+            self.data_type = other.data_type;
+        }
+        if other.delimiter.is_some() {
+            self.delimiter.clone_from(&other.delimiter);
+        }
+        if !other.keys.is_empty() {
+            self.keys.clone_from(&other.keys);
+        }
+        if !other.embeddings.is_empty() {
+            self.embeddings.clone_from(&other.embeddings);
+        }
+        if other.merge.is_some() {
+            self.merge = other.merge;
+        }
+        if other.metadata.is_some() {
+            self.metadata = other.metadata;
+        }
+        if other.compress.is_some() {
+            self.compress = other.compress;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AttributeRule {
+    pattern: Pattern,
+    matches_file_name: bool,
+    attributes: FileAttributes,
+}
+
+/// The parsed rules of an `.oxenattributes` file
+#[derive(Debug, Clone, Default)]
+pub struct OxenAttributes {
+    rules: Vec<AttributeRule>,
+}
+
+impl OxenAttributes {
+    /// Loads the `.oxenattributes` in the working directory, empty if there is none
+    pub fn from_repo(repo: &LocalRepository) -> Result<OxenAttributes, OxenError> {
+        let path = repo.path.join(OXEN_ATTRIBUTES_FILE);
+        if !path.exists() {
+            return Ok(OxenAttributes::default());
+        }
+        OxenAttributes::parse(&util::fs::read_from_path(&path)?)
+    }
+
+    /// Loads the `.oxenattributes` committed in `commit`, empty if there is none
+    pub fn from_commit(
+        repo: &LocalRepository,
+        commit: &Commit,
+    ) -> Result<OxenAttributes, OxenError> {
+        let path = Path::new(OXEN_ATTRIBUTES_FILE);
+        let Some(entry) = api::local::entries::get_commit_entry(repo, commit, path)? else {
+            return Ok(OxenAttributes::default());
+        };
+        let version_path = util::fs::version_path(repo, &entry);
+        if !util::fs::version_file_exists(&version_path) {
+            log::warn!(
+                "{OXEN_ATTRIBUTES_FILE} in commit {} is not synced",
+                commit.id
+            );
+            return Ok(OxenAttributes::default());
+        }
+        let mut contents = String::new();
+        util::fs::open_version_file(&version_path)?.read_to_string(&mut contents)?;
+        OxenAttributes::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> Result<OxenAttributes, OxenError> {
+        let mut rules = vec![];
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let Some(glob) = parts.next() else {
+                continue;
+            };
+            let matches_file_name = !glob.contains('/');
+            let glob = glob.trim_start_matches('/');
+            let pattern = Pattern::new(glob).map_err(|err| {
+                OxenError::basic_str(format!(
+                    "Invalid pattern on line {} of {OXEN_ATTRIBUTES_FILE}: {err}",
+                    i + 1
+                ))
+            })?;
+
+            let mut attributes = FileAttributes::default();
+            for attribute in parts {
+                parse_attribute(attribute, &mut attributes)?;
+            }
+            rules.push(AttributeRule {
+                pattern,
+                matches_file_name,
+                attributes,
+            });
+        }
+        Ok(OxenAttributes { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The attributes of every rule matching `path`, relative to the root of the repository
+    pub fn attributes_for(&self, path: impl AsRef<Path>) -> FileAttributes {
+        let path = path.as_ref();
+        let mut attributes = FileAttributes::default();
+        for rule in &self.rules {
+            let is_match = if rule.matches_file_name {
+                path.file_name()
+                    .map(|name| {
+                        rule.pattern
+                            .matches_with(&name.to_string_lossy(), MATCH_OPTIONS)
+                    })
+                    .unwrap_or(false)
+            } else {
+                rule.pattern.matches_path_with(path, MATCH_OPTIONS)
+            };
+            if is_match {
+                attributes.apply(&rule.attributes);
+            }
+        }
+        attributes
+    }
+}
+
+fn parse_attribute(attribute: &str, attributes: &mut FileAttributes) -> Result<(), OxenError> {
+    match attribute.split_once('=') {
+        Some(("delimiter", value)) => {
+            let delimiter = match value {
+                "\\t" | "tab" => "\t",
+                value => value,
+            };
+            if delimiter.len() != 1 {
+                return Err(OxenError::basic_str(format!(
+                    "Delimiter in {OXEN_ATTRIBUTES_FILE} must be a single character, got '{value}'"
+                )));
+            }
+            attributes.delimiter = Some(delimiter.to_string());
+        }
+        Some(("key", value)) => {
+            attributes.keys = value.split(',').map(String::from).collect();
+        }
+        Some(("embeddings", value)) => {
+            attributes.embeddings = value.split(',').map(String::from).collect();
+        }
+        Some(("merge", value)) => {
+            attributes.merge = Some(value.parse()?);
+        }
+        Some(_) => {
+            log::warn!("Ignoring unknown attribute '{attribute}' in {OXEN_ATTRIBUTES_FILE}");
+        }
+        None => match attribute {
+            "tabular" => attributes.data_type = Some(DataTypeAttribute::Tabular),
+            "binary" => attributes.data_type = Some(DataTypeAttribute::Binary),
+            "text" => attributes.data_type = Some(DataTypeAttribute::Text),
+            "metadata" => attributes.metadata = Some(true),
+            "-metadata" => attributes.metadata = Some(false),
+            "compress" => attributes.compress = Some(true),
+            "-compress" => attributes.compress = Some(false),
+            _ => {
+                log::warn!("Ignoring unknown attribute '{attribute}' in {OXEN_ATTRIBUTES_FILE}");
+            }
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::core::index::oxenattributes::{DataTypeAttribute, MergeStrategy, OxenAttributes};
+    use crate::error::OxenError;
+
+    #[test]
+    fn test_attributes_for_later_rules_win() -> Result<(), OxenError> {
+        let attributes = OxenAttributes::parse(
+            r"
+# tabular data
+*.psv                tabular delimiter=|
+annotations/*.csv    key=file,label merge=row-merge
+annotations/**       -metadata
+*.jsonl              embeddings=embedding
+annotations/raw.csv  merge=theirs delimiter=\t
+",
+        )?;
+
+        let psv = attributes.attributes_for(Path::new("data/train/images.psv"));
+        assert_eq!(psv.data_type, Some(DataTypeAttribute::Tabular));
+        assert_eq!(psv.delimiter, Some(String::from("|")));
+        assert!(psv.is_tabular(Path::new("data/train/images.psv")));
+        assert!(psv.embeddings.is_empty());
+
+        let jsonl = attributes.attributes_for(Path::new("data/train/images.jsonl"));
+        assert_eq!(jsonl.embeddings, vec!["embedding"]);
+
+        let bbox = attributes.attributes_for(Path::new("annotations/bounding_box.csv"));
+        assert_eq!(bbox.keys, vec!["file", "label"]);
+        assert_eq!(bbox.merge, Some(MergeStrategy::RowMerge));
+        assert!(!bbox.computes_metadata());
+
+        let raw = attributes.attributes_for(Path::new("annotations/raw.csv"));
+        assert_eq!(raw.keys, vec!["file", "label"]);
+        assert_eq!(raw.merge, Some(MergeStrategy::Theirs));
+        assert_eq!(raw.delimiter, Some(String::from("\t")));
+
+        // Globs with a slash only match from the root, `*` does not cross directories
+        let nested = attributes.attributes_for(Path::new("train/annotations/bounding_box.csv"));
+        assert!(nested.keys.is_empty());
+        assert!(nested.computes_metadata());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_values() {
+        assert!(OxenAttributes::parse("*.csv merge=rebase").is_err());
+        assert!(OxenAttributes::parse("*.csv delimiter=ab").is_err());
+    }
+}
@@ -125,7 +125,7 @@ fn get_missing_entries(entries: &[Entry], dst: impl AsRef<Path>) -> Vec<Entry> {
 
     for entry in entries {
         let version_path = util::fs::version_path_from_dst_generic(dst, entry);
-        if !version_path.exists() {
+        if !util::fs::version_file_exists(&version_path) {
             missing_entries.push(entry.to_owned())
         }
     }
@@ -215,7 +215,7 @@ async fn pull_large_entries(
         });
     }
 
-    while finished_queue.len() > 0 {
+    while !finished_queue.is_empty() {
         // log::debug!("Before waiting for {} workers to finish...", queue.len());
         sleep(Duration::from_secs(1)).await;
     }
@@ -309,7 +309,7 @@ async fn pull_small_entries(
             }
         });
     }
-    while finished_queue.len() > 0 {
+    while !finished_queue.is_empty() {
         // log::debug!("Waiting for {} workers to finish...", queue.len());
         sleep(Duration::from_millis(1)).await;
     }
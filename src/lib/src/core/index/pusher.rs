@@ -13,7 +13,7 @@ use futures::prelude::*;
 use indicatif::ProgressBar;
 use std::collections::{HashSet, VecDeque};
 
-use std::io::{BufReader, Read};
+use std::io::Read;
 use std::sync::Arc;
 
 use tokio::time::Duration;
@@ -68,8 +68,16 @@ async fn validate_repo_is_pushable(
 ) -> Result<bool, OxenError> {
     if remote_is_ahead_of_local(head_commit, remote_repo, commit_reader, branch).await? {
         log::debug!("remote is ahead of local for commit {:#?}", head_commit);
-        if api::remote::commits::can_push(remote_repo, &branch.name, local_repo, head_commit)
-            .await?
+        let (bytes, files) = push_size(local_repo, remote_repo, head_commit, branch).await?;
+        if api::remote::commits::can_push(
+            remote_repo,
+            &branch.name,
+            local_repo,
+            head_commit,
+            bytes,
+            files,
+        )
+        .await?
         {
             // log::debug!("can_push is true for commit {:#?}", head_commit);
             return Ok(true); // We need a merge commit
@@ -82,6 +90,23 @@ async fn validate_repo_is_pushable(
     Ok(false)
 }
 
+/// Bytes and number of files a push of `head_commit` would send
+async fn push_size(
+    local_repo: &LocalRepository,
+    remote_repo: &RemoteRepository,
+    head_commit: &Commit,
+    branch: &Branch,
+) -> Result<(u64, u64), OxenError> {
+    let commits_to_push =
+        get_commit_objects_to_sync(local_repo, remote_repo, head_commit, branch).await?;
+    let (unsynced_entries, total_size) = find_unsynced_entries(local_repo, &commits_to_push)?;
+    let total_files = unsynced_entries
+        .iter()
+        .map(|c| c.entries.len() as u64)
+        .sum();
+    Ok((total_size, total_files))
+}
+
 pub async fn push_remote_repo(
     local_repo: &LocalRepository,
     remote_repo: RemoteRepository,
@@ -189,8 +214,16 @@ pub async fn try_push_remote_repo(
 
     let maybe_remote_branch = api::remote::branches::get_by_name(remote_repo, &branch.name).await?;
 
-    let (unsynced_entries, _total_size) =
-        push_missing_commit_objects(local_repo, remote_repo, &commits_to_push, &branch).await?;
+    let (unsynced_entries, total_size) = find_unsynced_entries(local_repo, &commits_to_push)?;
+
+    // Fail before sending any data if the push would go over the remote's storage quota
+    let total_files = unsynced_entries
+        .iter()
+        .map(|c| c.entries.len() as u64)
+        .sum();
+    api::remote::repositories::check_quota(remote_repo, total_size, total_files).await?;
+
+    push_missing_commit_objects(local_repo, remote_repo, &unsynced_entries, &branch).await?;
 
     log::debug!("🐂 Identifying unsynced commits dbs...");
     let unsynced_db_commits =
@@ -397,11 +430,9 @@ fn get_unsynced_entries_for_commit(
     Ok((unsynced_commits, total_size))
 }
 
-async fn push_missing_commit_objects(
+fn find_unsynced_entries(
     local_repo: &LocalRepository,
-    remote_repo: &RemoteRepository,
     commits: &Vec<Commit>,
-    branch: &Branch,
 ) -> Result<(Vec<UnsyncedCommitEntries>, u64), OxenError> {
     let mut unsynced_commits: Vec<UnsyncedCommitEntries> = Vec::new();
 
@@ -426,19 +457,28 @@ async fn push_missing_commit_objects(
     }
     spinner.finish_and_clear();
 
+    Ok((unsynced_commits, total_size))
+}
+
+async fn push_missing_commit_objects(
+    local_repo: &LocalRepository,
+    remote_repo: &RemoteRepository,
+    unsynced_commits: &Vec<UnsyncedCommitEntries>,
+    branch: &Branch,
+) -> Result<(), OxenError> {
     // Spin during async bulk create
     let spinner = spinner_with_msg(format!("🐂 Syncing {} commits", unsynced_commits.len()));
 
     api::remote::commits::post_commits_to_server(
         local_repo,
         remote_repo,
-        &unsynced_commits,
+        unsynced_commits,
         branch.name.clone(),
     )
     .await?;
 
     spinner.finish_and_clear();
-    Ok((unsynced_commits, total_size))
+    Ok(())
 }
 
 async fn remote_is_ahead_of_local(
@@ -545,7 +585,8 @@ async fn poll_until_synced(
             Err(err) => {
                 retries += 1;
                 // Back off, but don't want to go all the way to 100s
-                let sleep_time = 2 * retries;
+                let sleep_time = api::remote::client::retry_after(&err)
+                    .unwrap_or(Duration::from_secs(2 * retries));
                 if retries >= NUM_HTTP_RETRIES {
                     bar.finish_and_clear();
                     return Err(err);
@@ -556,7 +597,7 @@ async fn poll_until_synced(
                     NUM_HTTP_RETRIES
                 );
                 // Extra sleep time in error cases
-                std::thread::sleep(std::time::Duration::from_secs(sleep_time));
+                std::thread::sleep(sleep_time);
             }
         }
         std::thread::sleep(std::time::Duration::from_millis(1000));
@@ -649,7 +690,7 @@ async fn push_missing_commit_dbs(
             }
         });
     }
-    while finished_queue.len() > 0 {
+    while !finished_queue.is_empty() {
         // log::debug!("Waiting for {} workers to finish...", queue.len());
         sleep(Duration::from_secs(1)).await;
     }
@@ -880,7 +921,7 @@ async fn chunk_and_send_large_entries(
         });
     }
 
-    while finished_queue.len() > 0 {
+    while !finished_queue.is_empty() {
         // log::debug!("Before waiting for {} workers to finish...", queue.len());
         sleep(Duration::from_secs(1)).await;
     }
@@ -903,8 +944,7 @@ async fn upload_large_file_chunks(
 ) {
     // Open versioned file
     let version_path = util::fs::version_path_for_entry(&repo, &entry);
-    let f = std::fs::File::open(&version_path).unwrap();
-    let mut reader = BufReader::new(f);
+    let mut reader = util::fs::open_version_file(&version_path).unwrap();
 
     // These variables are the same for every chunk
     // let is_compressed = false;
@@ -1169,7 +1209,17 @@ async fn bundle_and_send_small_entries(
                     let version_path = util::fs::version_path_for_entry(&repo, &entry);
                     let name = util::fs::path_relative_to_dir(&version_path, &hidden_dir).unwrap();
 
-                    tar.append_path_with_name(version_path, name).unwrap();
+                    if version_path.exists() {
+                        tar.append_path_with_name(version_path, name).unwrap();
+                    } else {
+                        // Stored compressed, the server gets the uncompressed contents
+                        let mut header = tar::Header::new_gnu();
+                        header.set_size(entry.num_bytes());
+                        header.set_mode(0o644);
+                        header.set_cksum();
+                        let reader = util::fs::open_version_file(&version_path).unwrap();
+                        tar.append_data(&mut header, name, reader).unwrap();
+                    }
                 }
 
                 let buffer = match tar.into_inner() {
@@ -1217,7 +1267,7 @@ async fn bundle_and_send_small_entries(
             }
         });
     }
-    while finished_queue.len() > 0 {
+    while !finished_queue.is_empty() {
         // log::debug!("Waiting for {} workers to finish...", queue.len());
         sleep(Duration::from_secs(1)).await;
     }
@@ -1266,7 +1316,8 @@ mod tests {
             assert_eq!(unsynced_commits.len(), 1);
 
             // Push commit objects only
-            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_commits, &branch)
+            let (unsynced_entries, _) = pusher::find_unsynced_entries(&repo, &unsynced_commits)?;
+            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_entries, &branch)
                 .await?;
 
             // There should be none unsynced
@@ -1300,7 +1351,8 @@ mod tests {
             assert_eq!(unsynced_commits.len(), 1);
 
             // Push commit objects only
-            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_commits, &branch)
+            let (unsynced_entries, _) = pusher::find_unsynced_entries(&repo, &unsynced_commits)?;
+            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_entries, &branch)
                 .await?;
 
             // There should be none unsynced
@@ -1335,7 +1387,8 @@ mod tests {
             let unsynced_commits =
                 pusher::get_commit_objects_to_sync(&repo, &remote_repo, &head_commit, &branch)
                     .await?;
-            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_commits, &branch)
+            let (unsynced_entries, _) = pusher::find_unsynced_entries(&repo, &unsynced_commits)?;
+            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_entries, &branch)
                 .await?;
 
             // Should have one missing commit db - root created on repo creation
@@ -1375,7 +1428,8 @@ mod tests {
             let unsynced_commits =
                 pusher::get_commit_objects_to_sync(&repo, &remote_repo, &head_commit, &branch)
                     .await?;
-            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_commits, &branch)
+            let (unsynced_entries, _) = pusher::find_unsynced_entries(&repo, &unsynced_commits)?;
+            pusher::push_missing_commit_objects(&repo, &remote_repo, &unsynced_entries, &branch)
                 .await?;
 
             // Get missing commit dbs and push
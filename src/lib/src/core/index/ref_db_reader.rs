@@ -1,17 +1,13 @@
+use crate::core::db::key_val::kv_db;
 use crate::error::OxenError;
-use rocksdb::{IteratorMode, DB};
+use rocksdb::DB;
 use std::str;
 
 pub struct RefDBReader {}
 
 impl RefDBReader {
     pub fn has_branch(db: &DB, name: &str) -> bool {
-        let bytes = name.as_bytes();
-        match db.get(bytes) {
-            Ok(Some(_)) => true,
-            Ok(None) => false,
-            Err(_) => false,
-        }
+        kv_db::has_key(db, name)
     }
 
     pub fn get_commit_id_for_branch(
@@ -19,7 +15,7 @@ impl RefDBReader {
         branch_name: &str,
     ) -> Result<Option<String>, OxenError> {
         let bytes = branch_name.as_bytes();
-        match db.get(bytes) {
+        match kv_db::get_bytes(db, bytes) {
             Ok(Some(value)) => Ok(Some(String::from(str::from_utf8(&value)?))),
             Ok(None) => Ok(None),
             Err(err) => {
@@ -31,7 +27,7 @@ impl RefDBReader {
 
     pub fn list_values(db: &DB) -> Result<Vec<(String, String)>, OxenError> {
         let mut values: Vec<(String, String)> = vec![];
-        let iter = db.iterator(IteratorMode::Start);
+        let iter = kv_db::iter_decoded(db);
         for item in iter {
             match item {
                 Ok((key, value)) => match (str::from_utf8(&key), str::from_utf8(&value)) {
@@ -1,11 +1,12 @@
 use crate::constants::{HEAD_FILE, REFS_DIR};
 use crate::core::db;
+use crate::core::db::key_val::kv_db;
 use crate::core::index::CommitReader;
 use crate::error::OxenError;
 use crate::model::{Branch, LocalRepository};
 use crate::util;
 
-use rocksdb::{IteratorMode, DB};
+use rocksdb::DB;
 use std::path::PathBuf;
 use std::str;
 
@@ -41,12 +42,7 @@ impl RefReader {
     }
 
     pub fn has_branch(&self, name: &str) -> bool {
-        let bytes = name.as_bytes();
-        match self.refs_db.get(bytes) {
-            Ok(Some(_)) => true,
-            Ok(None) => false,
-            Err(_) => false,
-        }
+        kv_db::has_key(&self.refs_db, name)
     }
 
     pub fn get_current_branch(&self) -> Result<Option<Branch>, OxenError> {
@@ -69,7 +65,7 @@ impl RefReader {
 
     pub fn get_commit_id_for_branch(&self, name: &str) -> Result<Option<String>, OxenError> {
         let bytes = name.as_bytes();
-        match self.refs_db.get(bytes) {
+        match kv_db::get_bytes(&self.refs_db, bytes) {
             Ok(Some(value)) => Ok(Some(String::from(str::from_utf8(&value)?))),
             Ok(None) => {
                 // log::debug!(
@@ -135,7 +131,7 @@ impl RefReader {
     pub fn list_branches(&self) -> Result<Vec<Branch>, OxenError> {
         let mut branch_names: Vec<Branch> = vec![];
         let maybe_head_ref = self.read_head_ref()?;
-        let iter = self.refs_db.iterator(IteratorMode::Start);
+        let iter = kv_db::iter_decoded(&self.refs_db);
         for item in iter {
             match item {
                 Ok((key, value)) => match (str::from_utf8(&key), str::from_utf8(&value)) {
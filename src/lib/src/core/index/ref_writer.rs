@@ -1,11 +1,12 @@
 use crate::constants::{HEAD_FILE, REFS_DIR};
 use crate::core::db;
+use crate::core::db::key_val::kv_db;
 use crate::core::index::RefDBReader;
 use crate::error::OxenError;
 use crate::model::{Branch, LocalRepository};
 use crate::util;
 
-use rocksdb::{IteratorMode, DB};
+use rocksdb::DB;
 use std::path::{Path, PathBuf};
 use std::str;
 
@@ -83,11 +84,11 @@ impl RefWriter {
             Err(OxenError::local_branch_not_found(new_name))
         } else {
             // Get old id
-            let old_id = self.refs_db.get(old_name)?.unwrap();
+            let old_id = kv_db::get_bytes(&self.refs_db, old_name)?.unwrap();
             // Delete old ref
-            self.refs_db.delete(old_name)?;
+            kv_db::delete(&self.refs_db, old_name)?;
             // Add new ref
-            self.refs_db.put(new_name, old_id)?;
+            kv_db::put_bytes(&self.refs_db, new_name, &old_id)?;
             Ok(())
         }
     }
@@ -101,14 +102,14 @@ impl RefWriter {
             let err = format!("Branch does not exist: {name}");
             return Err(OxenError::basic_str(err));
         };
-        self.refs_db.delete(name)?;
+        kv_db::delete(&self.refs_db, name)?;
         Ok(branch)
     }
 
     pub fn set_branch_commit_id(&self, name: &str, commit_id: &str) -> Result<(), OxenError> {
         log::debug!("self.refs_db.path {:?}", self.refs_db.path());
         log::debug!("self.refs_db.put {} -> {}", name, commit_id);
-        self.refs_db.put(name, commit_id)?;
+        kv_db::put_bytes(&self.refs_db, name, commit_id.as_bytes())?;
         Ok(())
     }
 
@@ -133,7 +134,7 @@ impl RefWriter {
     pub fn list_branches(&self) -> Result<Vec<Branch>, OxenError> {
         let mut branch_names: Vec<Branch> = vec![];
         let head_ref = self.read_head_ref()?;
-        let iter = self.refs_db.iterator(IteratorMode::Start);
+        let iter = kv_db::iter_decoded(&self.refs_db);
         for item in iter {
             match item {
                 Ok((key, value)) => match (str::from_utf8(&key), str::from_utf8(&value)) {
@@ -191,7 +192,7 @@ impl RefWriter {
 
     pub fn get_commit_id_for_branch(&self, name: &str) -> Result<Option<String>, OxenError> {
         let bytes = name.as_bytes();
-        match self.refs_db.get(bytes) {
+        match kv_db::get_bytes(&self.refs_db, bytes) {
             Ok(Some(value)) => Ok(Some(String::from(str::from_utf8(&value)?))),
             Ok(None) => Ok(None),
             Err(err) => {
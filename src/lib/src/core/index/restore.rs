@@ -126,6 +126,6 @@ fn restore_regular(
         util::fs::create_dir_all(parent)?;
     }
 
-    util::fs::copy(version_path, working_path.clone())?;
+    util::fs::checkout_version_file(repo, &version_path, &working_path)?;
     Ok(())
 }
@@ -10,7 +10,9 @@ use crate::core::db;
 use crate::core::db::key_val::path_db;
 use crate::core::db::key_val::str_json_db;
 use crate::core::df::tabular;
+use crate::core::index::fs_journal::{self, JournalPosition};
 use crate::core::index::object_db_reader::get_object_reader;
+use crate::core::index::oxenattributes::OxenAttributes;
 use crate::core::index::oxenignore;
 use crate::core::index::ObjectDBReader;
 use crate::core::index::SchemaReader;
@@ -24,8 +26,6 @@ use crate::model::schema::staged_schema::StagedSchema;
 use indicatif::ProgressStyle;
 use jwalk::WalkDirGeneric;
 
-use crate::opts::DFOpts;
-
 use crate::model::schema;
 use crate::model::{
     CommitEntry, LocalRepository, MergeConflict, StagedData, StagedDirStats, StagedEntry,
@@ -62,6 +62,7 @@ pub struct Stager {
     schemas_db: DBWithThreadMode<MultiThreaded>,
     pub repository: LocalRepository,
     merger: Option<Merger>,
+    attributes: OxenAttributes,
 }
 
 impl Stager {
@@ -99,6 +100,7 @@ impl Stager {
             schemas_db: DBWithThreadMode::open(&opts, dunce::simplified(&schemas_db_path))?,
             repository: repository.clone(),
             merger: None,
+            attributes: OxenAttributes::from_repo(repository)?,
         })
     }
 
@@ -112,6 +114,7 @@ impl Stager {
             schemas_db: DBWithThreadMode::open(&opts, dunce::simplified(&schemas_db_path))?,
             repository: repository.clone(),
             merger: Some(Merger::new(&repository.clone())?),
+            attributes: OxenAttributes::from_repo(repository)?,
         })
     }
 
@@ -278,7 +281,20 @@ impl Stager {
             return Err(OxenError::repo_is_shallow());
         }
 
-        // Get the time before doing this
+        // With `oxen watch` running we only look at the paths it saw change, a full status
+        // checkpoints the journal at the position before it started looking so changes made
+        // during the scan are looked at again next time
+        let is_full_status = dir == self.repository.path;
+        let journal = if is_full_status {
+            fs_journal::dirty_paths(&self.repository.path)
+        } else {
+            None
+        };
+        let journal_position = match &journal {
+            Some(journal) => Some(journal.position.to_owned()),
+            None if is_full_status => fs_journal::position(&self.repository.path),
+            None => None,
+        };
 
         let mut staged_data = StagedData::empty();
         let ignore = oxenignore::create(&self.repository);
@@ -295,6 +311,7 @@ impl Stager {
             "compute_staged_data Got <added> dirs: {}",
             staged_dirs.len()
         );
+        let mut tracked_dirs: HashSet<PathBuf> = HashSet::from([PathBuf::from("")]);
         for (dir, status) in staged_dirs {
             log::debug!("compute_staged_data considering added dir {:?}", dir);
             let full_path = self.repository.path.join(&dir);
@@ -303,7 +320,8 @@ impl Stager {
             log::debug!("compute_staged_data got stats {:?}", stats);
 
             log::debug!("compute_staged_data adding <added> dir {:?}", dir);
-            candidate_dirs.insert(self.repository.path.join(dir));
+            candidate_dirs.insert(self.repository.path.join(&dir));
+            tracked_dirs.insert(dir);
         }
         let mut committed_dirs = entry_reader.list_dirs()?;
         if dir.is_relative() && dir != self.repository.path {
@@ -313,15 +331,47 @@ impl Stager {
             "compute_staged_data Got <committed> dirs: {}",
             committed_dirs.len()
         );
-        for dir in committed_dirs.iter() {
-            log::debug!("compute_staged_data adding <committed> dir {:?}", dir);
-            if !self.should_ignore_path(&ignore, dir) {
-                candidate_dirs.insert(self.repository.path.join(dir));
+        tracked_dirs.extend(committed_dirs.iter().cloned());
+
+        // Dirty paths grouped by the dir they are in
+        let mut candidate_paths: HashMap<PathBuf, HashSet<PathBuf>> = HashMap::new();
+        if let Some(journal) = &journal {
+            log::debug!(
+                "compute_staged_data Got <dirty> paths: {}",
+                journal.paths.len()
+            );
+            for path in journal.paths.iter() {
+                if self.should_ignore_path(&ignore, path) {
+                    continue;
+                }
+                // A dir that appeared, moved or went away, look at everything we track under it
+                if tracked_dirs.contains(path) {
+                    for committed_dir in committed_dirs.iter() {
+                        if committed_dir.starts_with(path)
+                            && !self.should_ignore_path(&ignore, committed_dir)
+                        {
+                            candidate_dirs.insert(self.repository.path.join(committed_dir));
+                        }
+                    }
+                    candidate_dirs.insert(self.repository.path.join(path));
+                }
+                let parent = path.parent().unwrap_or(Path::new("")).to_path_buf();
+                candidate_paths
+                    .entry(parent)
+                    .or_default()
+                    .insert(path.to_owned());
+            }
+        } else {
+            for dir in committed_dirs.iter() {
+                log::debug!("compute_staged_data adding <committed> dir {:?}", dir);
+                if !self.should_ignore_path(&ignore, dir) {
+                    candidate_dirs.insert(self.repository.path.join(dir));
+                }
             }
-        }
 
-        log::debug!("compute_staged_data Considering <current> dir: {:?}", dir);
-        candidate_dirs.insert(dir.to_path_buf());
+            log::debug!("compute_staged_data Considering <current> dir: {:?}", dir);
+            candidate_dirs.insert(dir.to_path_buf());
+        }
 
         let committer = CommitReader::new(&self.repository)?;
         let commit = committer.head_commit()?;
@@ -351,6 +401,26 @@ impl Stager {
             )?;
         }
 
+        for (parent, paths) in candidate_paths {
+            let full_dir = self.repository.path.join(&parent);
+            // Files in untracked dirs are reported as the dir, like a full scan does
+            if candidate_dirs.contains(&full_dir) || !tracked_dirs.contains(&parent) {
+                continue;
+            }
+            self.process_paths(
+                &full_dir,
+                paths,
+                &mut staged_data,
+                &entry_reader,
+                object_reader.clone(),
+                bar.clone(),
+            )?;
+        }
+
+        if let Some(position) = journal_position {
+            self.checkpoint_journal(&position, &staged_data);
+        }
+
         // Make pairs from Added + Removed stage entries with same hash, store in staged_data.moved_entries
         self.find_moved_files(&mut staged_data)?;
 
@@ -531,6 +601,71 @@ impl Stager {
             relative_dir
         );
 
+        self.process_candidates(
+            candidate_files,
+            staged_data,
+            commit_reader,
+            &staged_dir_db,
+            &root_commit_dir_reader,
+            bar,
+        );
+        Ok(())
+    }
+
+    // Only look at some of the paths in a dir, the ones the fs journal says may have changed
+    fn process_paths(
+        &self,
+        full_dir: &Path,
+        candidate_files: HashSet<PathBuf>,
+        staged_data: &mut StagedData,
+        commit_reader: &CommitEntryReader,
+        object_reader: Arc<ObjectDBReader>,
+        bar: Arc<ProgressBar>,
+    ) -> Result<(), OxenError> {
+        log::debug!(
+            "calling process_paths on {} paths in {:?}",
+            candidate_files.len(),
+            full_dir
+        );
+        let committer = CommitReader::new(&self.repository)?;
+        let commit = committer.head_commit()?;
+        let relative_dir = util::fs::path_relative_to_dir(full_dir, &self.repository.path)?;
+        let staged_dir_db: StagedDirEntryDB<SingleThreaded> =
+            StagedDirEntryDB::new(&self.repository, &relative_dir)?;
+        let commit_dir_reader =
+            CommitDirEntryReader::new(&self.repository, &commit.id, &relative_dir, object_reader)?;
+
+        // Files that came and went again since the last status are not worth reporting
+        let candidate_files: HashSet<PathBuf> = candidate_files
+            .into_iter()
+            .filter(|path| {
+                self.repository.path.join(path).exists()
+                    || path.file_name().is_some_and(|name| {
+                        staged_dir_db.has_entry(name) || commit_dir_reader.has_file(name)
+                    })
+            })
+            .collect();
+
+        self.process_candidates(
+            candidate_files,
+            staged_data,
+            commit_reader,
+            &staged_dir_db,
+            &commit_dir_reader,
+            bar,
+        );
+        Ok(())
+    }
+
+    fn process_candidates(
+        &self,
+        candidate_files: HashSet<PathBuf>,
+        staged_data: &mut StagedData,
+        commit_reader: &CommitEntryReader,
+        staged_dir_db: &StagedDirEntryDB<SingleThreaded>,
+        root_commit_dir_reader: &CommitDirEntryReader,
+        bar: Arc<ProgressBar>,
+    ) {
         if let Some(combined_changes) = candidate_files
             .par_iter()
             .map(|relative| {
@@ -565,8 +700,8 @@ impl Stager {
                     let file_status = Stager::get_file_status(
                         &self.repository.path,
                         relative,
-                        &staged_dir_db,
-                        &root_commit_dir_reader,
+                        staged_dir_db,
+                        root_commit_dir_reader,
                     );
                     log::debug!("process_dir got status {:?} {:?}", relative, file_status);
                     if let Some(file_type) = file_status {
@@ -625,7 +760,21 @@ impl Stager {
                 .staged_files
                 .extend(combined_changes.staged_files);
         }
-        Ok(())
+    }
+
+    // Everything that is still dirty has to be looked at again next status
+    fn checkpoint_journal(&self, position: &JournalPosition, staged_data: &StagedData) {
+        let dirty = staged_data
+            .modified_files
+            .iter()
+            .chain(staged_data.removed_files.iter())
+            .chain(staged_data.untracked_files.iter())
+            .chain(staged_data.untracked_dirs.iter().map(|(path, _)| path))
+            .chain(staged_data.staged_files.keys())
+            .cloned();
+        if let Err(err) = fs_journal::save_checkpoint(&self.repository.path, position, dirty) {
+            log::warn!("Could not checkpoint fs journal: {}", err);
+        }
     }
 
     fn get_file_status<T: ThreadMode>(
@@ -702,7 +851,8 @@ impl Stager {
             // );
 
             // Then check the hashes, because the data might not be different, timestamp is just an optimization
-            let hash = util::hasher::hash_file_contents(&full_path).unwrap();
+            let hash =
+                util::hasher::hash_file_contents_like(&full_path, &commit_entry.hash).unwrap();
             if hash != commit_entry.hash {
                 return true;
             }
@@ -731,7 +881,7 @@ impl Stager {
 
             let staged_dir: StagedDirEntryDB<SingleThreaded> =
                 StagedDirEntryDB::new(&self.repository, parent)?;
-            if util::fs::is_tabular(path) {
+            if self.attributes.attributes_for(path).is_tabular(path) {
                 log::debug!("remove_staged_file {:?} is tabular, removing schema", path);
                 self.remove_staged_schema(path)?;
             }
@@ -783,7 +933,7 @@ impl Stager {
             let short_path = util::fs::path_relative_to_dir(parent, &self.repository.path)?;
             path_db::put(&self.dir_db, short_path, &StagedEntryStatus::Removed)?;
             // Also add as removed to staged schema db
-            if util::fs::is_tabular(path) {
+            if self.attributes.attributes_for(&relative).is_tabular(path) {
                 // Get schema for this file
                 let schema_reader = SchemaReader::new(&self.repository, &entry.commit_id)?;
                 let schema = schema_reader.get_schema_for_file(path)?;
@@ -1000,6 +1150,49 @@ impl Stager {
         (files, total)
     }
 
+    // Like list_unstaged_files_in_dir but from the paths in the fs journal, only walking dirs
+    // that are new since the last status
+    fn list_dirty_files_in_dir(
+        &self,
+        dir: &Path,
+        dirty_paths: &HashSet<PathBuf>,
+    ) -> (HashMap<PathBuf, Vec<PathBuf>>, usize) {
+        let mut files: HashMap<PathBuf, HashSet<PathBuf>> = HashMap::new();
+        for path in dirty_paths.iter() {
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let is_hidden = relative
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+            if is_hidden {
+                continue;
+            }
+
+            let full_path = self.repository.path.join(path);
+            if full_path.is_file() {
+                if let Some(parent) = path.parent() {
+                    files
+                        .entry(parent.to_path_buf())
+                        .or_default()
+                        .insert(path.to_owned());
+                }
+            } else if full_path.is_dir() {
+                let (dir_files, _) = self.list_unstaged_files_in_dir(&full_path);
+                for (parent, paths) in dir_files {
+                    files.entry(parent).or_default().extend(paths);
+                }
+            }
+        }
+
+        let total = files.values().map(|paths| paths.len()).sum();
+        let files = files
+            .into_iter()
+            .map(|(parent, paths)| (parent, paths.into_iter().collect()))
+            .collect();
+        (files, total)
+    }
+
     pub fn add_dir(&self, dir: &Path, entry_reader: &CommitEntryReader) -> Result<(), OxenError> {
         if !dir.exists() || !dir.is_dir() {
             let err = format!("Cannot stage non-existant dir: {dir:?}");
@@ -1012,8 +1205,12 @@ impl Stager {
         path_db::put(&self.dir_db, &short_path, &StagedEntryStatus::Added)?;
         // log::debug!("Stager.add_dir added path {short_path:?}");
 
-        // Add all untracked files and modified files
-        let (dir_paths, total) = self.list_unstaged_files_in_dir(dir);
+        // Add all untracked files and modified files, only the ones `oxen watch` saw change if
+        // it is running
+        let (dir_paths, total) = match fs_journal::dirty_paths(&self.repository.path) {
+            Some(journal) => self.list_dirty_files_in_dir(&short_path, &journal.paths),
+            None => self.list_unstaged_files_in_dir(dir),
+        };
         // log::debug!("Stager.add_dir {:?} -> {}", dir, total);
         let schema_reader = SchemaReader::new(&self.repository, &entry_reader.commit_id)?;
         // println!("Adding files in directory: {short_path:?}");
@@ -1218,12 +1415,16 @@ impl Stager {
         }
 
         // compute the hash to know if it has changed
-        let hash = util::hasher::hash_file_contents(path)?;
+        let hash = util::hasher::hash_file_contents_with_algorithm(
+            path,
+            self.repository.hash_algorithm(),
+        )?;
 
         // Key is the filename relative to the repository
         // if repository: /Users/username/Datasets/MyRepo
         //   /Users/username/Datasets/MyRepo/train -> train
         //   /Users/username/Datasets/MyRepo/annotations/train.txt -> annotations/train.txt
+        let working_path = path;
         let path = util::fs::path_relative_to_dir(path, &self.repository.path)?;
         log::debug!("Add file: got relative path {:?}", path);
 
@@ -1236,6 +1437,7 @@ impl Stager {
         if let Some(merger) = &self.merger {
             if merger.has_file(&path)? {
                 log::debug!("add_staged_entry_in_dir_db merger has file! {:?}", path);
+                util::fs::break_link(working_path)?;
                 self.add_staged_entry_to_db(&path, &staged_entry, staged_db, schema_reader)?;
                 merger.remove_conflict_path(&path)?;
                 return Ok(path);
@@ -1269,6 +1471,9 @@ impl Stager {
         }
 
         log::debug!("add_staged_entry_in_dir_db {:?} {:?}", path, staged_entry);
+        // Files checked out as links share their bytes with a version file, give a staged
+        // file its own copy so the next edit to it can't change what was committed
+        util::fs::break_link(working_path)?;
         self.add_staged_entry_to_db(&path, &staged_entry, staged_db, schema_reader)?;
 
         Ok(path)
@@ -1296,14 +1501,16 @@ impl Stager {
             }
 
             // If tabular, add schema
-            if util::fs::is_tabular(path) {
+            let full_path = self.repository.path.join(path);
+            let attributes = self.attributes.attributes_for(path);
+            if attributes.is_tabular(&full_path) && attributes.computes_metadata() {
                 log::debug!(
                     "add_staged_entry_to_db is tabular! compute schema {:?}",
                     path
                 );
-                let full_path = self.repository.path.join(path);
+                let opts = attributes.df_opts(&full_path);
 
-                match tabular::read_df(full_path, DFOpts::empty()) {
+                match tabular::read_df(full_path, opts) {
                     Ok(df) => {
                         self.add_schema_for_tabular(&df, path, schema_reader)?;
                     }
@@ -10,6 +10,7 @@ use super::TreeObjectReader;
 
 // This is an interface to enable reading tree objects for merge, and must be capable of reading
 // from either a TreeObjectReader (for local commits) or a single rocksdb (for pre-push validation of a single reduced tree db)
+#[allow(clippy::large_enum_variant)]
 pub enum CommitTreeReader {
     TreeObjectReader(TreeObjectReader),
     DB(DBWithThreadMode<MultiThreaded>),
@@ -49,7 +49,7 @@ pub fn should_copy_schema(_schema: &Schema, path: &Path) -> bool {
 }
 
 fn path_hash_is_different(entry: &CommitEntry, path: &Path) -> bool {
-    if let Ok(hash) = util::hasher::hash_file_contents(path) {
+    if let Ok(hash) = util::hasher::hash_file_contents_like(path, &entry.hash) {
         return hash != entry.hash;
     }
     false
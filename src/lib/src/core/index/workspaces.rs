@@ -16,8 +16,11 @@ use super::CommitWriter;
 
 pub mod data_frames;
 pub mod files;
+pub mod refresh;
 pub mod stager;
 
+pub use refresh::refresh;
+
 pub fn get(repo: &LocalRepository, workspace_id: impl AsRef<str>) -> Result<Workspace, OxenError> {
     Workspace::new(repo, workspace_id)
 }
@@ -26,6 +29,57 @@ pub fn list(repo: &LocalRepository) -> Result<Vec<Workspace>, OxenError> {
     Workspace::list(repo)
 }
 
+/// List the workspaces created from `branch_name` and by `creator`, a None filter matches everything
+pub fn list_by(
+    repo: &LocalRepository,
+    branch_name: Option<&str>,
+    creator: Option<&str>,
+) -> Result<Vec<Workspace>, OxenError> {
+    let workspaces = Workspace::list(repo)?
+        .into_iter()
+        .filter(|w| branch_name.is_none() || w.branch_name.as_deref() == branch_name)
+        .filter(|w| creator.is_none() || w.creator.as_deref() == creator)
+        .collect();
+    Ok(workspaces)
+}
+
+/// Change the display name of the workspace, the id stays the same
+pub fn rename(workspace: &Workspace, name: impl AsRef<str>) -> Result<Workspace, OxenError> {
+    let name = name.as_ref();
+    if name.trim().is_empty() {
+        return Err(OxenError::basic_str("Workspace name cannot be empty"));
+    }
+
+    let mut workspace = Workspace::new(&workspace.base_repo, &workspace.id)?;
+    workspace.name = Some(name.to_string());
+    workspace.updated_at = Some(OffsetDateTime::now_utc());
+    workspace.save()?;
+    Ok(workspace)
+}
+
+/// Mark the workspace as updated so it does not expire while it is in use
+pub fn touch(workspace: &Workspace) -> Result<(), OxenError> {
+    workspace.touch()
+}
+
+/// Delete every workspace whose ttl has passed since it was last updated
+pub fn cleanup_expired(repo: &LocalRepository) -> Result<Vec<Workspace>, OxenError> {
+    let now = OffsetDateTime::now_utc();
+    let mut removed = vec![];
+    for workspace in Workspace::list(repo)? {
+        if workspace.is_expired(now) {
+            log::debug!(
+                "workspace::cleanup_expired removing workspace {} in {:?}",
+                workspace.id,
+                repo.path
+            );
+            delete(&workspace)?;
+            removed.push(workspace);
+        }
+    }
+    Ok(removed)
+}
+
 pub fn create(
     base_repo: &LocalRepository,
     commit: &Commit,
@@ -54,6 +108,10 @@ pub fn delete(workspace: &Workspace) -> Result<(), OxenError> {
         ),
         Err(e) => log::error!("workspace::delete error removing workspace dir: {:?}", e),
     }
+    let lock_path = workspace_dir.with_extension("lock");
+    if lock_path.exists() {
+        util::fs::remove_file(&lock_path)?;
+    }
 
     Ok(())
 }
@@ -144,11 +202,18 @@ fn status_for_workspace(workspace: &Workspace) -> Result<StagedData, OxenError>
 mod tests {
     use std::path::Path;
 
+    use time::{Duration, OffsetDateTime};
+
+    use serde_json::json;
+
     use crate::api;
+    use crate::command;
     use crate::config::UserConfig;
-    use crate::constants::DEFAULT_BRANCH_NAME;
+    use crate::constants::{DEFAULT_BRANCH_NAME, OXEN_ID_COL, TABLE_NAME};
+    use crate::core::db::data_frames::df_db;
     use crate::core::index;
     use crate::error::OxenError;
+    use crate::model::diff::DiffResult;
     use crate::model::NewCommitBody;
     use crate::test;
     use crate::util;
@@ -215,4 +280,128 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_workspace_rename_list_by_and_cleanup_expired() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let commit = api::local::commits::head_commit(&repo)?;
+
+            let mut workspace = index::workspaces::create(&repo, &commit, "ws_1", true)?;
+            assert!(workspace.created_at.is_some());
+            workspace.branch_name = Some(DEFAULT_BRANCH_NAME.to_string());
+            workspace.creator = Some(String::from("ox@oxen.ai"));
+            workspace.save()?;
+
+            let mut expired = index::workspaces::create(&repo, &commit, "ws_2", true)?;
+            expired.branch_name = Some(String::from("feature"));
+            expired.ttl_secs = Some(60);
+            expired.updated_at = Some(OffsetDateTime::now_utc() - Duration::hours(1));
+            expired.save()?;
+
+            // Used since it was saved, so it has not expired
+            let mut touched = index::workspaces::create(&repo, &commit, "ws_3", true)?;
+            touched.ttl_secs = Some(60);
+            touched.updated_at = Some(OffsetDateTime::now_utc() - Duration::hours(1));
+            touched.save()?;
+            index::workspaces::touch(&touched)?;
+
+            let renamed = index::workspaces::rename(&workspace, "My Workspace")?;
+            assert_eq!(renamed.id, "ws_1");
+            assert_eq!(renamed.display_name(), "My Workspace");
+            let workspace = index::workspaces::get(&repo, "ws_1")?;
+            assert_eq!(workspace.name, Some(String::from("My Workspace")));
+
+            let on_main = index::workspaces::list_by(&repo, Some(DEFAULT_BRANCH_NAME), None)?;
+            assert_eq!(on_main.len(), 1);
+            assert_eq!(on_main[0].id, "ws_1");
+            let by_user = index::workspaces::list_by(&repo, None, Some("ox@oxen.ai"))?;
+            assert_eq!(by_user.len(), 1);
+            assert_eq!(index::workspaces::list_by(&repo, None, None)?.len(), 3);
+
+            let removed = index::workspaces::cleanup_expired(&repo)?;
+            assert_eq!(removed.len(), 1);
+            assert_eq!(removed[0].id, "ws_2");
+            let mut remaining: Vec<String> = index::workspaces::list(&repo)?
+                .into_iter()
+                .map(|workspace| workspace.id)
+                .collect();
+            remaining.sort();
+            assert_eq!(remaining, vec!["ws_1", "ws_3"]);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_workspace_refresh_replays_edits_onto_branch_head() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = api::local::commits::head_commit(&repo)?;
+            let workspace = index::workspaces::create(&repo, &commit, "ws_refresh", true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            index::workspaces::data_frames::index(&workspace, &file_path)?;
+
+            let row_id = |file: &str| -> Result<String, OxenError> {
+                let db_path = index::workspaces::data_frames::duckdb_path(&workspace, &file_path);
+                let conn = df_db::get_connection(db_path)?;
+                let sql = format!("SELECT \"{OXEN_ID_COL}\" FROM {TABLE_NAME} WHERE file = ?");
+                Ok(conn.query_row(&sql, [file], |row| row.get(0))?)
+            };
+
+            // Edit the workspace: one update, one delete and one new row
+            let dog_id = row_id("train/dog_2.jpg")?;
+            let cat_id = row_id("train/cat_1.jpg")?;
+            index::workspaces::data_frames::rows::update(
+                &workspace,
+                &file_path,
+                &dog_id,
+                &json!({"label": "doggo"}),
+            )?;
+            index::workspaces::data_frames::rows::delete(&workspace, &file_path, &cat_id)?;
+            let json_data = json!({"file": "train/fish_1.jpg", "label": "fish", "min_x": 1.0, "min_y": 2.0, "width": 3, "height": 4});
+            index::workspaces::data_frames::rows::add(&workspace, &file_path, &json_data)?;
+
+            // Meanwhile the branch moves on, touching a different row and the one we deleted
+            let full_path = repo.path.join(&file_path);
+            let contents = util::fs::read_from_path(&full_path)?
+                .replace("train/dog_3.jpg,dog", "train/dog_3.jpg,puppy")
+                .replace(
+                    "train/cat_1.jpg,cat,57.0,35.5",
+                    "train/cat_1.jpg,cat,57.0,36.5",
+                );
+            util::fs::write_to_path(&full_path, contents)?;
+            command::add(&repo, &full_path)?;
+            let head = command::commit(&repo, "Relabel a puppy")?;
+
+            let (refreshed, summary) = index::workspaces::refresh(&workspace, DEFAULT_BRANCH_NAME)?;
+            assert_eq!(refreshed.commit.id, head.id);
+            assert_eq!(summary.replayed_rows, 2);
+            assert_eq!(summary.conflicts.len(), 1);
+            assert_eq!(summary.conflicts[0].operation, "removed");
+            assert_eq!(summary.conflicts[0].row_id, Some(cat_id));
+
+            let refreshed = index::workspaces::get(&repo, "ws_refresh")?;
+            assert_eq!(refreshed.commit.id, head.id);
+            // The copy it was rebuilt in is gone
+            assert_eq!(index::workspaces::list(&repo)?.len(), 1);
+            let workspaces_dir = refreshed.dir().parent().unwrap().to_path_buf();
+            assert_eq!(util::fs::list_dirs_in_dir(&workspaces_dir)?.len(), 1);
+            let staged = index::workspaces::stager::list_files(&refreshed)?;
+            assert_eq!(staged, vec![file_path.clone()]);
+            let DiffResult::Tabular(diff) =
+                index::workspaces::data_frames::diff(&refreshed, &file_path)?
+            else {
+                panic!("Expected a tabular diff");
+            };
+            assert_eq!(diff.summary.modifications.row_counts.added, 1);
+            assert_eq!(diff.summary.modifications.row_counts.modified, 1);
+            assert_eq!(diff.summary.modifications.row_counts.removed, 0);
+
+            Ok(())
+        })
+    }
 }
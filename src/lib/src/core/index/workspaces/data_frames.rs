@@ -5,7 +5,9 @@ use sql_query_builder::{Delete, Select};
 
 use crate::api;
 use crate::constants::MODS_DIR;
-use crate::constants::{DIFF_HASH_COL, DIFF_STATUS_COL, OXEN_COLS, TABLE_NAME};
+use crate::constants::{
+    DIFF_HASH_COL, DIFF_STATUS_COL, OXEN_COLS, OXEN_ROW_ID_COL, OXEN_ROW_REVISION_COL, TABLE_NAME,
+};
 use crate::core::db::data_frames::workspace_df_db::select_cols_from_schema;
 use crate::core::db::data_frames::{df_db, workspace_df_db};
 use crate::core::df::tabular;
@@ -18,11 +20,14 @@ use crate::model::diff::tabular_diff::{
 use crate::model::diff::{AddRemoveModifyCounts, DiffResult, TabularDiff};
 
 use crate::model::staged_row_status::StagedRowStatus;
-use crate::model::{Commit, CommitEntry, EntryDataType, LocalRepository, Workspace};
+use crate::model::{
+    BulkFileFormat, Commit, CommitEntry, EntryDataType, LocalRepository, Workspace,
+};
 use crate::opts::DFOpts;
 use crate::{error::OxenError, util};
 use std::path::{Path, PathBuf};
 
+pub mod change_feed;
 pub mod columns;
 pub mod data_frame_column_changes_db;
 pub mod data_frame_row_changes_db;
@@ -94,6 +99,7 @@ pub fn get_queryable_data_frame_workspace(
         .ok_or_else(|| OxenError::basic_str("Entry not found"))?;
 
     let version_path = util::fs::version_path(repo, &entry);
+    let version_path = util::fs::readable_version_path(repo, &version_path)?;
 
     let data_type = util::fs::file_data_type(&version_path);
 
@@ -138,6 +144,8 @@ pub fn is_queryable_data_frame_indexed(
 }
 
 pub fn index(workspace: &Workspace, path: &Path) -> Result<(), OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     // Is tabular just looks at the file extensions
     if !util::fs::is_tabular(path) {
         return Err(OxenError::basic_str(
@@ -172,6 +180,7 @@ pub fn index(workspace: &Workspace, path: &Path) -> Result<(), OxenError> {
         df_db::drop_table(&conn, TABLE_NAME)?;
     }
     let version_path = util::fs::version_path(repo, &entry);
+    let version_path = util::fs::readable_version_path(repo, &version_path)?;
 
     log::debug!(
         "core::index::workspaces::data_frames::index({:?}) got version path: {:?}",
@@ -234,10 +243,18 @@ fn add_row_status_cols(conn: &Connection) -> Result<(), OxenError> {
         TABLE_NAME, DIFF_HASH_COL
     );
     conn.execute(&query_hash, [])?;
+
+    let query_revision = format!(
+        "ALTER TABLE \"{}\" ADD COLUMN \"{}\" INTEGER DEFAULT 0",
+        TABLE_NAME, OXEN_ROW_REVISION_COL
+    );
+    conn.execute(&query_revision, [])?;
     Ok(())
 }
 
 pub fn unindex(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let path = path.as_ref();
     let db_path = duckdb_path(workspace, path);
     let conn = df_db::get_connection(db_path)?;
@@ -254,6 +271,9 @@ pub fn is_indexed(workspace: &Workspace, path: &Path) -> Result<bool, OxenError>
 
     let table_exists = df_db::table_exists(&conn, TABLE_NAME)?;
     log::debug!("dataset_is_indexed() got table_exists: {:?}", table_exists);
+    if table_exists {
+        workspace_df_db::add_revision_col_if_missing(&conn)?;
+    }
     Ok(table_exists)
 }
 
@@ -433,6 +453,61 @@ pub fn restore(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), Oxen
     Ok(())
 }
 
+/// Path to write a file uploaded or exported in bulk for a data frame in the workspace
+pub fn bulk_file_path(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    format: BulkFileFormat,
+) -> PathBuf {
+    let path_hash = util::hasher::hash_str(path.as_ref().to_string_lossy());
+    workspace
+        .dir()
+        .join(MODS_DIR)
+        .join("duckdb")
+        .join(path_hash)
+        .join("bulk")
+        .join(format!("{}.{}", uuid::Uuid::new_v4(), format.extension()))
+}
+
+/// Write the current state of the workspace data frame to `output`, the format is picked from
+/// the extension. Keeps the `_oxen_id` and `_oxen_diff_status` columns so the rows can be
+/// reviewed or matched back up on import.
+pub fn export(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<(), OxenError> {
+    let path = path.as_ref();
+    let output = output.as_ref();
+    if !is_indexed(workspace, path)? {
+        return Err(OxenError::basic_str("Dataset is not indexed"));
+    }
+    // Fail before reading the rows if we can't write them
+    BulkFileFormat::from_path(output)?;
+
+    if let Some(parent) = output.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            util::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let db_path = duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+    let schema =
+        df_db::get_schema_excluding_cols(&conn, TABLE_NAME, &[OXEN_ROW_ID_COL, DIFF_HASH_COL])?;
+    let cols = schema
+        .fields
+        .iter()
+        .map(|field| format!("\"{}\"", field.name))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let select = Select::new().select(&cols).from(TABLE_NAME);
+    let mut df = df_db::select(&conn, &select, true, Some(&schema), None)?;
+    tabular::write_df(&mut df, output)?;
+
+    Ok(())
+}
+
 fn export_rest(path: &Path, conn: &Connection) -> Result<(), OxenError> {
     log::debug!("export_rest()");
     let excluded_cols = OXEN_COLS
@@ -520,12 +595,16 @@ fn export_parquet(path: &Path, conn: &Connection) -> Result<(), OxenError> {
 mod tests {
     use std::path::Path;
 
+    use polars::df;
+    use polars::prelude::AnyValue;
     use serde_json::json;
 
     use crate::api;
     use crate::command;
     use crate::config::UserConfig;
-    use crate::constants::OXEN_ID_COL;
+    use crate::constants::{DIFF_STATUS_COL, OXEN_ID_COL, OXEN_ROW_REVISION_COL, TABLE_NAME};
+    use crate::core::db::data_frames::df_db;
+    use crate::core::df::tabular;
     use crate::core::index::workspaces;
     use crate::error::OxenError;
     use crate::model::diff::DiffResult;
@@ -1192,4 +1271,249 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_bulk_upsert_delete_and_export_rows() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = api::local::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            workspaces::data_frames::index(&workspace, &file_path)?;
+
+            let keys = vec![String::from("file"), String::from("min_x")];
+            let upload = df!(
+                "file" => ["train/dog_2.jpg", "train/new.jpg"],
+                "label" => ["cat", "dog"],
+                "min_x" => [7.0, 1.0],
+                "min_y" => [29.5, 2.0],
+                "width" => [246, 10],
+                "height" => [247, 10]
+            )?;
+            let summary =
+                workspaces::data_frames::rows::upsert(&workspace, &file_path, &upload, &keys)?;
+            assert_eq!(summary.added, 1);
+            assert_eq!(summary.updated, 1);
+
+            let to_delete = df!(
+                "file" => ["train/cat_1.jpg", "train/missing.jpg"],
+                "min_x" => [57.0, 0.0]
+            )?;
+            let summary = workspaces::data_frames::rows::delete_many(
+                &workspace, &file_path, &to_delete, &keys,
+            )?;
+            assert_eq!(summary.deleted, 1);
+
+            // Every row of an upload must name a single row
+            let duplicate_keys = df!(
+                "file" => ["train/dog_1.jpg", "train/dog_1.jpg"],
+                "label" => ["cat", "fish"],
+                "min_x" => [101.5, 101.5]
+            )?;
+            let result = workspaces::data_frames::rows::upsert(
+                &workspace,
+                &file_path,
+                &duplicate_keys,
+                &keys,
+            );
+            assert!(result.is_err());
+            let null_keys = df!(
+                "file" => [Some("train/dog_1.jpg"), None],
+                "label" => ["cat", "fish"],
+                "min_x" => [101.5, 101.5]
+            )?;
+            let result =
+                workspaces::data_frames::rows::upsert(&workspace, &file_path, &null_keys, &keys);
+            assert!(result.is_err());
+
+            let diff = workspaces::data_frames::diff(&workspace, &file_path)?;
+            match diff {
+                DiffResult::Tabular(tabular_diff) => {
+                    let counts = tabular_diff.summary.modifications.row_counts;
+                    assert_eq!(counts.added, 1);
+                    assert_eq!(counts.modified, 1);
+                    assert_eq!(counts.removed, 1);
+                }
+                _ => panic!("Expected tabular diff result"),
+            }
+
+            let output = repo.path.join("export.csv");
+            workspaces::data_frames::export(&workspace, &file_path, &output)?;
+            let output = repo.path.join("export.sh");
+            assert!(workspaces::data_frames::export(&workspace, &file_path, &output).is_err());
+            let output = repo.path.join("export.csv");
+            let exported = tabular::read_df(&output, DFOpts::empty())?;
+            assert_eq!(exported.height(), 7);
+            assert!(exported.get_column_names().contains(&DIFF_STATUS_COL));
+            assert!(exported.get_column_names().contains(&OXEN_ID_COL));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_row_revision_conflict_and_change_feed() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = api::local::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            workspaces::data_frames::index(&workspace, &file_path)?;
+
+            let json_data = json!({"file": "dawg1.jpg", "label": "dog", "min_x": 13, "min_y": 14, "width": 100, "height": 100});
+            let row = workspaces::data_frames::rows::add(&workspace, &file_path, &json_data)?;
+            let row_id = workspaces::data_frames::rows::get_row_id(&row)?.unwrap();
+            assert_eq!(
+                workspaces::data_frames::rows::get_row_revision(&row)?,
+                Some(0)
+            );
+
+            // Updating at the revision we read bumps it
+            let json_data = json!({"label": "cat", "_oxen_row_revision": 0});
+            let row =
+                workspaces::data_frames::rows::update(&workspace, &file_path, &row_id, &json_data)?;
+            assert_eq!(
+                workspaces::data_frames::rows::get_row_revision(&row)?,
+                Some(1)
+            );
+
+            // A stale revision is rejected for updates and deletes
+            let json_data = json!({"label": "fish", "_oxen_row_revision": 0});
+            let result =
+                workspaces::data_frames::rows::update(&workspace, &file_path, &row_id, &json_data);
+            assert!(matches!(result, Err(OxenError::DataFrameRowConflict(_))));
+            let result = workspaces::data_frames::rows::delete_at_revision(
+                &workspace,
+                &file_path,
+                &row_id,
+                Some(0),
+            );
+            assert!(matches!(result, Err(OxenError::DataFrameRowConflict(_))));
+
+            let row = workspaces::data_frames::rows::get_by_id(&workspace, &file_path, &row_id)?;
+            assert_eq!(row.column("label")?.get(0)?, AnyValue::String("cat"));
+
+            let changes =
+                workspaces::data_frames::change_feed::list_since(&workspace, &file_path, 0, 100)?;
+            assert_eq!(changes.len(), 2);
+            assert_eq!(changes[0].operation, "added");
+            assert_eq!(changes[1].operation, "updated");
+            assert_eq!(changes[1].revision, Some(1));
+
+            let latest = workspaces::data_frames::change_feed::latest_seq(&workspace, &file_path)?;
+            assert_eq!(latest, changes[1].seq);
+            let changes = workspaces::data_frames::change_feed::list_since(
+                &workspace, &file_path, latest, 100,
+            )?;
+            assert!(changes.is_empty());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_row_revisions_on_old_workspaces_and_bulk_upserts() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = api::local::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            let commit_entry =
+                api::local::entries::get_commit_entry(&repo, &commit, &file_path)?.unwrap();
+            workspaces::data_frames::index(&workspace, &file_path)?;
+
+            // Workspaces indexed before revisions existed get the column when they are loaded
+            {
+                let conn = df_db::get_connection(workspaces::data_frames::duckdb_path(
+                    &workspace, &file_path,
+                ))?;
+                conn.execute(
+                    &format!("ALTER TABLE {TABLE_NAME} DROP COLUMN \"{OXEN_ROW_REVISION_COL}\""),
+                    [],
+                )?;
+            }
+            assert!(workspaces::data_frames::is_indexed(&workspace, &file_path)?);
+            let staged_df =
+                workspaces::data_frames::query(&workspace, &file_path, &DFOpts::empty())?;
+            assert!(staged_df
+                .get_column_names()
+                .contains(&OXEN_ROW_REVISION_COL));
+
+            // Restoring a row that was never changed is not a change
+            let row_id = staged_df.column(OXEN_ID_COL)?.get(0)?.to_string();
+            let row_id = row_id.replace('"', "");
+            workspaces::data_frames::rows::restore(&workspace, &commit_entry, &row_id)?;
+            let changes =
+                workspaces::data_frames::change_feed::list_since(&workspace, &file_path, 0, 100)?;
+            assert!(changes.is_empty());
+
+            // Bulk rows sent with a stale revision are rejected, the current one goes through
+            let keys = vec![String::from("file"), String::from("min_x")];
+            let stale = df!(
+                "file" => ["train/dog_2.jpg"],
+                "label" => ["cat"],
+                "min_x" => [7.0],
+                OXEN_ROW_REVISION_COL => [3i64]
+            )?;
+            let result =
+                workspaces::data_frames::rows::upsert(&workspace, &file_path, &stale, &keys);
+            assert!(matches!(result, Err(OxenError::DataFrameRowConflict(_))));
+
+            let current = df!(
+                "file" => ["train/dog_2.jpg"],
+                "label" => ["cat"],
+                "min_x" => [7.0],
+                OXEN_ROW_REVISION_COL => [0i64]
+            )?;
+            let summary =
+                workspaces::data_frames::rows::upsert(&workspace, &file_path, &current, &keys)?;
+            assert_eq!(summary.updated, 1);
+            let changes =
+                workspaces::data_frames::change_feed::list_since(&workspace, &file_path, 0, 100)?;
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].revision, Some(1));
+
+            // Same for bulk deletes, the row moved on to revision 1 with the upsert
+            let stale = df!(
+                "file" => ["train/dog_2.jpg"],
+                "min_x" => [7.0],
+                OXEN_ROW_REVISION_COL => [0i64]
+            )?;
+            let result =
+                workspaces::data_frames::rows::delete_many(&workspace, &file_path, &stale, &keys);
+            assert!(matches!(result, Err(OxenError::DataFrameRowConflict(_))));
+
+            let current = df!(
+                "file" => ["train/dog_2.jpg"],
+                "min_x" => [7.0],
+                OXEN_ROW_REVISION_COL => [1i64]
+            )?;
+            let summary = workspaces::data_frames::rows::delete_many(
+                &workspace, &file_path, &current, &keys,
+            )?;
+            assert_eq!(summary.deleted, 1);
+            let changes =
+                workspaces::data_frames::change_feed::list_since(&workspace, &file_path, 0, 100)?;
+            assert_eq!(changes.len(), 2);
+            assert_eq!(changes[1].operation, "deleted");
+            assert_eq!(changes[1].revision, Some(2));
+
+            Ok(())
+        })
+    }
 }
@@ -0,0 +1,190 @@
+//! Ordered feed of the row edits made to a workspace data frame.
+//!
+//! Every add, update, delete and restore appends an entry with an increasing sequence number,
+//! so clients can poll for everything that happened after the last sequence number they saw.
+//!
+
+use std::path::Path;
+
+use duckdb::{params, ToSql};
+use polars::prelude::*;
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::constants::{CHANGES_TABLE_NAME, OXEN_ID_COL, OXEN_ROW_REVISION_COL};
+use crate::core::db::data_frames::df_db;
+use crate::core::index::workspaces;
+use crate::error::OxenError;
+use crate::model::Workspace;
+use crate::view::data_frames::DataFrameChange;
+use crate::view::JsonDataFrameView;
+
+/// Edits appended per insert statement when a whole data frame of rows is recorded at once
+const RECORD_BATCH_SIZE: usize = 256;
+
+fn create_table(conn: &duckdb::Connection) -> Result<(), OxenError> {
+    let sql = format!(
+        "CREATE SEQUENCE IF NOT EXISTS {CHANGES_TABLE_NAME}_seq START 1;
+         CREATE TABLE IF NOT EXISTS {CHANGES_TABLE_NAME} (
+            seq BIGINT DEFAULT nextval('{CHANGES_TABLE_NAME}_seq'),
+            row_id VARCHAR,
+            operation VARCHAR,
+            revision BIGINT,
+            timestamp VARCHAR,
+            value VARCHAR
+         );"
+    );
+    conn.execute_batch(&sql)?;
+    Ok(())
+}
+
+/// Append an edit of `row_id` to the feed
+pub fn record(
+    conn: &duckdb::Connection,
+    row_id: impl AsRef<str>,
+    operation: impl AsRef<str>,
+    revision: Option<i64>,
+    value: &Value,
+) -> Result<(), OxenError> {
+    create_table(conn)?;
+    let timestamp = now_timestamp()?;
+    let sql = format!(
+        "INSERT INTO {CHANGES_TABLE_NAME} (row_id, operation, revision, timestamp, value) VALUES (?, ?, ?, ?, ?)"
+    );
+    conn.execute(
+        &sql,
+        params![
+            row_id.as_ref(),
+            operation.as_ref(),
+            revision,
+            timestamp,
+            value.to_string()
+        ],
+    )?;
+    Ok(())
+}
+
+/// Append an edit for every row of `rows` to the feed, in order, with the revision of each row
+/// plus `revision_offset`. Returns the value recorded for each row, as `record` would have.
+pub fn record_rows(
+    conn: &duckdb::Connection,
+    operation: impl AsRef<str>,
+    rows: &DataFrame,
+    revision_offset: i64,
+) -> Result<Vec<Value>, OxenError> {
+    if rows.height() == 0 {
+        return Ok(vec![]);
+    }
+    create_table(conn)?;
+    let timestamp = now_timestamp()?;
+    let operation = operation.as_ref();
+
+    let row_ids = rows.column(OXEN_ID_COL)?.cast(&DataType::String)?;
+    let row_ids = row_ids.str()?;
+    let revisions = if rows.get_column_names().contains(&OXEN_ROW_REVISION_COL) {
+        Some(rows.column(OXEN_ROW_REVISION_COL)?.cast(&DataType::Int64)?)
+    } else {
+        None
+    };
+    let revisions = revisions.as_ref().map(|col| col.i64()).transpose()?;
+    let values = row_values(rows)?;
+    let value_strings: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+
+    let mut offset = 0;
+    while offset < rows.height() {
+        let end = (offset + RECORD_BATCH_SIZE).min(rows.height());
+        let sql = format!(
+            "INSERT INTO {CHANGES_TABLE_NAME} (row_id, operation, revision, timestamp, value) VALUES {}",
+            vec!["(?, ?, ?, ?, ?)"; end - offset].join(", ")
+        );
+        let mut batch: Vec<Box<dyn ToSql>> = vec![];
+        for (idx, value) in value_strings.iter().enumerate().take(end).skip(offset) {
+            let row_id = row_ids
+                .get(idx)
+                .ok_or_else(|| OxenError::basic_str("Row id not found on bulk row"))?;
+            let revision = revisions
+                .and_then(|revisions| revisions.get(idx))
+                .map(|revision| revision + revision_offset);
+            batch.push(Box::new(row_id.to_string()));
+            batch.push(Box::new(operation.to_string()));
+            batch.push(Box::new(revision));
+            batch.push(Box::new(timestamp.clone()));
+            batch.push(Box::new(value.clone()));
+        }
+        let params: Vec<&dyn ToSql> = batch.iter().map(|value| &**value).collect();
+        conn.execute(&sql, params.as_slice())?;
+        offset = end;
+    }
+    Ok(values)
+}
+
+/// The value of every row of `rows` as a data frame of one row, the way single row edits are
+/// recorded, serialized in one go
+pub fn row_values(rows: &DataFrame) -> Result<Vec<Value>, OxenError> {
+    if rows.height() == 0 {
+        return Ok(vec![]);
+    }
+    match JsonDataFrameView::json_from_df(&mut rows.clone()) {
+        Value::Array(values) => Ok(values
+            .into_iter()
+            .map(|value| Value::Array(vec![value]))
+            .collect()),
+        value => Err(OxenError::basic_str(format!(
+            "Expected the rows to serialize to an array, got {value}"
+        ))),
+    }
+}
+
+fn now_timestamp() -> Result<String, OxenError> {
+    OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .map_err(|e| OxenError::basic_str(format!("Could not format timestamp: {e}")))
+}
+
+/// List up to `limit` edits with a sequence number greater than `since`, oldest first
+pub fn list_since(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    since: i64,
+    limit: usize,
+) -> Result<Vec<DataFrameChange>, OxenError> {
+    let db_path = workspaces::data_frames::duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+    create_table(&conn)?;
+
+    let sql = format!(
+        "SELECT seq, row_id, operation, revision, timestamp, value FROM {CHANGES_TABLE_NAME}
+         WHERE seq > ? ORDER BY seq LIMIT {limit}"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut rows = stmt.query(params![since])?;
+
+    let mut changes = vec![];
+    while let Some(row) = rows.next()? {
+        let timestamp: String = row.get(4)?;
+        let value: String = row.get(5)?;
+        changes.push(DataFrameChange {
+            seq: row.get(0)?,
+            row_id: row.get(1)?,
+            operation: row.get(2)?,
+            revision: row.get(3)?,
+            timestamp: OffsetDateTime::parse(&timestamp, &Rfc3339).map_err(|e| {
+                OxenError::basic_str(format!("Could not parse timestamp {timestamp}: {e}"))
+            })?,
+            value: serde_json::from_str(&value)?,
+        });
+    }
+    Ok(changes)
+}
+
+/// Sequence number of the most recent edit, 0 if nothing has been edited yet
+pub fn latest_seq(workspace: &Workspace, path: impl AsRef<Path>) -> Result<i64, OxenError> {
+    let db_path = workspaces::data_frames::duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+    create_table(&conn)?;
+
+    let sql = format!("SELECT COALESCE(MAX(seq), 0) FROM {CHANGES_TABLE_NAME}");
+    let seq: i64 = conn.query_row(&sql, [], |row| row.get(0))?;
+    Ok(seq)
+}
@@ -24,6 +24,8 @@ pub fn add(
     file_path: impl AsRef<Path>,
     new_column: &NewColumn,
 ) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let file_path = file_path.as_ref();
     let db_path = workspaces::data_frames::duckdb_path(workspace, file_path);
     let column_changes_path = workspaces::data_frames::column_changes_path(workspace, file_path);
@@ -53,6 +55,8 @@ pub fn delete(
     file_path: impl AsRef<Path>,
     column_to_delete: &ColumnToDelete,
 ) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let file_path = file_path.as_ref();
     let db_path = workspaces::data_frames::duckdb_path(workspace, file_path);
     let column_changes_path = workspaces::data_frames::column_changes_path(workspace, file_path);
@@ -86,6 +90,8 @@ pub fn update(
     file_path: impl AsRef<Path>,
     column_to_update: &ColumnToUpdate,
 ) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let file_path = file_path.as_ref();
     let db_path = workspaces::data_frames::duckdb_path(workspace, file_path);
     let column_changes_path = workspaces::data_frames::column_changes_path(workspace, file_path);
@@ -135,6 +141,8 @@ pub fn restore(
     file_path: impl AsRef<Path>,
     column_to_restore: &ColumnToRestore,
 ) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let file_path = file_path.as_ref();
     let db_path = workspaces::data_frames::duckdb_path(workspace, file_path);
     let column_changes_path = workspaces::data_frames::column_changes_path(workspace, file_path);
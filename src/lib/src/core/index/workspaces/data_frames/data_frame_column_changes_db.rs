@@ -1,5 +1,6 @@
 use rocksdb::DB;
 
+use crate::core::db::key_val::kv_db;
 use crate::{error::OxenError, view::data_frames::DataFrameColumnChange};
 
 pub fn write_data_frame_column_change(
@@ -26,7 +27,7 @@ pub fn save_data_frame_column_changes(
     let key = column_name;
     let val_json = serde_json::to_string(data_frame_column_change)?;
 
-    db.put(key, val_json.as_bytes())?;
+    kv_db::put_bytes(db, key, val_json.as_bytes())?;
 
     log::debug!(
         "save_data_frame_column_changes() saved change: {:?}",
@@ -37,7 +38,7 @@ pub fn save_data_frame_column_changes(
 }
 
 pub fn delete_data_frame_column_changes(db: &DB, column_name: &str) -> Result<(), OxenError> {
-    db.delete(column_name)?;
+    kv_db::delete(db, column_name)?;
 
     log::debug!(
         "delete_data_frame_column_changes() deleted change in: {:?}",
@@ -51,7 +52,7 @@ pub fn get_all_data_frame_column_changes(db: &DB) -> Result<Vec<DataFrameColumnC
     let mut changes = Vec::new();
 
     // Iterate from the start
-    let iterator = db.iterator(rocksdb::IteratorMode::Start);
+    let iterator = kv_db::iter_decoded(db);
 
     for item in iterator {
         match item {
@@ -76,7 +77,7 @@ pub fn get_data_frame_column_change(
     db: &DB,
     name: &str,
 ) -> Result<Option<DataFrameColumnChange>, OxenError> {
-    let val = db.get(name)?;
+    let val = kv_db::get_bytes(db, name)?;
 
     match val {
         Some(val) => {
@@ -1,5 +1,6 @@
 use rocksdb::DB;
 
+use crate::core::db::key_val::kv_db;
 use crate::{error::OxenError, view::data_frames::DataFrameRowChange};
 
 pub fn write_data_frame_row_change(
@@ -16,7 +17,7 @@ pub fn save_data_frame_row_changes(
     let key = &data_frame_row_change.row_id;
     let val_json = serde_json::to_string(data_frame_row_change)?;
 
-    db.put(key, val_json.as_bytes())?;
+    kv_db::put_bytes(db, key, val_json.as_bytes())?;
 
     log::debug!(
         "save_data_frame_row_changes() saved change: {:?}",
@@ -27,7 +28,7 @@ pub fn save_data_frame_row_changes(
 }
 
 pub fn delete_data_frame_row_changes(db: &DB, row_id: &str) -> Result<(), OxenError> {
-    db.delete(row_id)?;
+    kv_db::delete(db, row_id)?;
 
     log::debug!(
         "delete_data_frame_row_changes() deleted change in: {:?}",
@@ -41,7 +42,7 @@ pub fn get_all_data_frame_row_changes(db: &DB) -> Result<Vec<DataFrameRowChange>
     let mut changes = Vec::new();
 
     // Iterate from the start
-    let iterator = db.iterator(rocksdb::IteratorMode::Start);
+    let iterator = kv_db::iter_decoded(db);
 
     for item in iterator {
         match item {
@@ -66,7 +67,7 @@ pub fn get_data_frame_row_change(
     db: &DB,
     name: &str,
 ) -> Result<Option<DataFrameRowChange>, OxenError> {
-    let val = db.get(name)?;
+    let val = kv_db::get_bytes(db, name)?;
 
     match val {
         Some(val) => {
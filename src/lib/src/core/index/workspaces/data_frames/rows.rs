@@ -1,4 +1,4 @@
-use polars::datatypes::AnyValue;
+use polars::datatypes::{AnyValue, DataType};
 use polars::frame::DataFrame;
 
 use polars::prelude::NamedFrom;
@@ -6,7 +6,9 @@ use polars::series::Series;
 use rocksdb::DB;
 use sql_query_builder::Select;
 
-use crate::constants::{DIFF_STATUS_COL, OXEN_ID_COL, OXEN_ROW_ID_COL, TABLE_NAME};
+use crate::constants::{
+    DIFF_STATUS_COL, OXEN_COLS, OXEN_ID_COL, OXEN_ROW_ID_COL, OXEN_ROW_REVISION_COL, TABLE_NAME,
+};
 use crate::core::db;
 use crate::opts::DFOpts;
 
@@ -18,11 +20,12 @@ use crate::model::diff::DiffResult;
 use crate::model::staged_row_status::StagedRowStatus;
 use crate::model::{CommitEntry, LocalRepository, Workspace};
 use crate::util;
-use crate::view::data_frames::DataFrameRowChange;
+use crate::view::data_frames::{DataFrameBulkRowsSummary, DataFrameRowChange};
 use crate::view::JsonDataFrameView;
 
 use std::path::Path;
 
+use super::change_feed;
 use super::data_frame_row_changes_db::get_all_data_frame_row_changes;
 
 /// Get a single row by the _oxen_id val
@@ -79,6 +82,8 @@ pub fn add(
     file_path: impl AsRef<Path>,
     data: &serde_json::Value,
 ) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let file_path = file_path.as_ref();
     let db_path = workspaces::data_frames::duckdb_path(workspace, file_path);
     let row_changes_path = workspaces::data_frames::row_changes_path(workspace, file_path);
@@ -101,6 +106,8 @@ pub fn add(
 
     let row = JsonDataFrameView::json_from_df(&mut result);
 
+    let revision = rows::get_row_revision(&result)?;
+    change_feed::record(&conn, &row_id, "added", revision, &row)?;
     rows::record_row_change(&row_changes_path, row_id, "added".to_owned(), row, None)?;
 
     workspaces::stager::add(workspace, file_path)?;
@@ -113,6 +120,8 @@ pub fn restore(
     entry: &CommitEntry,
     row_id: impl AsRef<str>,
 ) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let row_id = row_id.as_ref();
     let restored_row = restore_row_in_db(workspace, entry, row_id)?;
     let diff = workspaces::data_frames::diff(workspace, &entry.path)?;
@@ -151,6 +160,10 @@ pub fn restore_row_in_db(
         get_row_status(&row)?.ok_or_else(|| OxenError::basic_str("Row status not found"))?;
 
     let result_row = match row_status {
+        StagedRowStatus::Unchanged => {
+            // Row is unchanged, nothing to restore
+            return Ok(row);
+        }
         StagedRowStatus::Added => {
             // Row is added, just delete it
             log::debug!("restore_row() row is added, deleting");
@@ -165,12 +178,13 @@ pub fn restore_row_in_db(
             rows::revert_row_changes(&db, row_id.to_owned())?;
             rows::modify_row(&conn, &mut insert_row, row_id)?
         }
-        StagedRowStatus::Unchanged => {
-            // Row is unchanged, just return it
-            row
-        }
     };
 
+    let mut restored = result_row.clone();
+    let revision = rows::get_row_revision(&restored)?;
+    let value = JsonDataFrameView::json_from_df(&mut restored);
+    change_feed::record(&conn, row_id, "restored", revision, &value)?;
+
     log::debug!("we're returning this row: {:?}", result_row);
 
     Ok(result_row)
@@ -196,6 +210,10 @@ pub fn get_row_idx(row_df: &DataFrame) -> Result<Option<usize>, OxenError> {
     }
 }
 
+pub fn get_row_revision(row_df: &DataFrame) -> Result<Option<i64>, OxenError> {
+    rows::get_row_revision(row_df)
+}
+
 /// TODO: we should really be storing the original row contents
 ///       so that we can both do row level diffs and restore
 ///       this is very inefficient to load the entire original data frame
@@ -210,6 +228,7 @@ fn prepare_modified_or_removed_row(
 
     // let scan_rows = 10000 as usize;
     let committed_df_path = util::fs::version_path(repo, entry);
+    let committed_df_path = util::fs::readable_version_path(repo, &committed_df_path)?;
 
     // TODONOW should not be using all rows - just need to parse delim
     let lazy_df = tabular::read_df(committed_df_path, DFOpts::empty())?;
@@ -233,13 +252,29 @@ pub fn delete(
     path: impl AsRef<Path>,
     row_id: &str,
 ) -> Result<DataFrame, OxenError> {
+    delete_at_revision(workspace, path, row_id, None)
+}
+
+/// Delete a row, only if it is still at `revision` when one is given
+pub fn delete_at_revision(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    row_id: &str,
+    revision: Option<i64>,
+) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let path = path.as_ref();
     let db_path = workspaces::data_frames::duckdb_path(workspace, path);
     let row_changes_path = workspaces::data_frames::row_changes_path(workspace, path);
 
     let mut deleted_row = {
         let conn = df_db::get_connection(db_path)?;
-        rows::delete_row(&conn, row_id)?
+        let mut deleted_row = rows::delete_row_at_revision(&conn, row_id, revision)?;
+        let revision = rows::get_row_revision(&deleted_row)?.map(|r| r + 1);
+        let value = JsonDataFrameView::json_from_df(&mut deleted_row);
+        change_feed::record(&conn, row_id, "deleted", revision, &value)?;
+        deleted_row
     };
 
     let row = JsonDataFrameView::json_from_df(&mut deleted_row);
@@ -275,6 +310,8 @@ pub fn update(
     row_id: &str,
     data: &serde_json::Value,
 ) -> Result<DataFrame, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let path = path.as_ref();
     let db_path = workspaces::data_frames::duckdb_path(workspace, path);
     let conn = df_db::get_connection(db_path)?;
@@ -290,6 +327,9 @@ pub fn update(
 
     let row_after = JsonDataFrameView::json_from_df(&mut result);
 
+    let revision = rows::get_row_revision(&result)?;
+    change_feed::record(&conn, row_id, "updated", revision, &row_after)?;
+
     rows::record_row_change(
         &row_changes_path,
         row_id.to_owned(),
@@ -313,6 +353,139 @@ pub fn update(
     Ok(result)
 }
 
+/// Insert or update every row of `df`, matching existing rows on the `keys` columns.
+/// Rows whose key is not in the data frame yet are appended. The keys of `df` must be unique and not null.
+pub fn upsert(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    df: &DataFrame,
+    keys: &[String],
+) -> Result<DataFrameBulkRowsSummary, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
+    let path = path.as_ref();
+    let db_path = workspaces::data_frames::duckdb_path(workspace, path);
+    let row_changes_path = workspaces::data_frames::row_changes_path(workspace, path);
+    let mut conn = df_db::get_connection(db_path)?;
+
+    // The hub and `export` send the oxen columns along, they are not part of the user schema.
+    // The revision is kept so rows that changed since they were read are not overwritten
+    let df = drop_oxen_cols(df)?;
+
+    let tx = conn.transaction()?;
+    let rows = rows::upsert_rows(&tx, &df, keys)?;
+    let mut changes = vec![];
+    let updated = change_feed::record_rows(&tx, "updated", &rows.updated, 0)?;
+    let before = change_feed::row_values(&rows.before)?;
+    for ((row_id, value), row_before) in bulk_row_ids(&rows.updated)?
+        .into_iter()
+        .zip(updated)
+        .zip(before)
+    {
+        changes.push(DataFrameRowChange {
+            row_id,
+            operation: "updated".to_owned(),
+            value: row_before,
+            new_value: Some(value),
+        });
+    }
+    let added = change_feed::record_rows(&tx, "added", &rows.added, 0)?;
+    for (row_id, value) in bulk_row_ids(&rows.added)?.into_iter().zip(added) {
+        changes.push(DataFrameRowChange {
+            row_id,
+            operation: "added".to_owned(),
+            value,
+            new_value: None,
+        });
+    }
+    tx.commit()?;
+
+    rows::record_row_changes(&row_changes_path, changes)?;
+
+    track_bulk_changes(workspace, path)?;
+    Ok(DataFrameBulkRowsSummary {
+        added: rows.added.height(),
+        updated: rows.updated.height(),
+        ..Default::default()
+    })
+}
+
+/// Delete every row in the workspace data frame whose `keys` columns match a row in `df`.
+pub fn delete_many(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    df: &DataFrame,
+    keys: &[String],
+) -> Result<DataFrameBulkRowsSummary, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
+    let path = path.as_ref();
+    let db_path = workspaces::data_frames::duckdb_path(workspace, path);
+    let row_changes_path = workspaces::data_frames::row_changes_path(workspace, path);
+    let mut conn = df_db::get_connection(db_path)?;
+
+    let tx = conn.transaction()?;
+    let deleted = rows::delete_rows(&tx, df, keys)?;
+    let mut changes = vec![];
+    // The rows are returned as they were, the delete bumped their revision
+    let values = change_feed::record_rows(&tx, "deleted", &deleted, 1)?;
+    for (row_id, value) in bulk_row_ids(&deleted)?.into_iter().zip(values) {
+        changes.push(DataFrameRowChange {
+            row_id,
+            operation: "deleted".to_owned(),
+            value,
+            new_value: None,
+        });
+    }
+    tx.commit()?;
+
+    rows::record_row_changes(&row_changes_path, changes)?;
+
+    track_bulk_changes(workspace, path)?;
+    Ok(DataFrameBulkRowsSummary {
+        deleted: deleted.height(),
+        ..Default::default()
+    })
+}
+
+fn drop_oxen_cols(df: &DataFrame) -> Result<DataFrame, OxenError> {
+    let cols: Vec<&str> = df
+        .get_column_names()
+        .into_iter()
+        .filter(|col| *col == OXEN_ROW_REVISION_COL || !OXEN_COLS.contains(col))
+        .collect();
+    Ok(df.select(cols)?)
+}
+
+fn bulk_row_ids(rows: &DataFrame) -> Result<Vec<String>, OxenError> {
+    if rows.height() == 0 {
+        return Ok(vec![]);
+    }
+    let row_ids = rows.column(OXEN_ID_COL)?.cast(&DataType::String)?;
+    row_ids
+        .str()?
+        .into_iter()
+        .map(|row_id| {
+            row_id
+                .map(|row_id| row_id.to_string())
+                .ok_or_else(|| OxenError::basic_str("Row id not found on bulk row"))
+        })
+        .collect()
+}
+
+fn track_bulk_changes(workspace: &Workspace, path: &Path) -> Result<(), OxenError> {
+    workspaces::stager::add(workspace, path)?;
+
+    let diff = workspaces::data_frames::diff(workspace, path)?;
+    if let DiffResult::Tabular(diff) = diff {
+        if !diff.has_changes() {
+            // Restored to original state == delete file from staged db
+            workspaces::stager::rm(workspace, path)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn get_row_diff(
     workspace: &Workspace,
     file_path: impl AsRef<Path>,
@@ -10,6 +10,8 @@ use crate::util;
 
 // Stages a file in a specified directory
 pub fn add(workspace: &Workspace, filepath: &Path) -> Result<PathBuf, OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let repo = &workspace.base_repo;
     let workspace_repo = &workspace.workspace_repo;
     let commit = &workspace.commit;
@@ -37,6 +39,8 @@ pub fn has_file(workspace: &Workspace, filepath: &Path) -> Result<bool, OxenErro
 }
 
 pub fn delete_file(workspace: &Workspace, filepath: &Path) -> Result<(), OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     // Stager will be in the repo workspace
     let workspace_repo = &workspace.workspace_repo;
     let stager = Stager::new(workspace_repo)?;
@@ -0,0 +1,550 @@
+//! Moves a workspace onto the latest commit of a branch.
+//!
+//! The edits made to indexed data frames are read out of the workspace before the data frames
+//! are re-indexed at the new commit, then replayed on top. Updates and deletes are matched to
+//! the new version of the file by the original contents of the row, so rows that were changed
+//! or removed on the branch in the meantime are reported as conflicts instead of clobbered.
+//!
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use polars::prelude::*;
+use rocksdb::DB;
+use sql_query_builder::Select;
+
+use crate::api;
+use crate::constants::{
+    DIFF_STATUS_COL, MODS_DIR, OXEN_COLS, OXEN_HIDDEN_DIR, OXEN_ID_COL, TABLE_NAME,
+    WORKSPACE_CONFIG,
+};
+use crate::core::db;
+use crate::core::db::data_frames::{columns, df_db, rows, workspace_df_db};
+use crate::core::df::tabular;
+use crate::core::index::workspaces;
+use crate::core::index::workspaces::data_frames::{change_feed, data_frame_column_changes_db};
+use crate::error::OxenError;
+use crate::model::diff::DiffResult;
+use crate::model::staged_row_status::StagedRowStatus;
+use crate::model::{Commit, CommitEntry, LocalRepository, Workspace};
+use crate::opts::DFOpts;
+use crate::util;
+use crate::view::data_frames::columns::{ColumnToDelete, ColumnToUpdate, NewColumn};
+use crate::view::data_frames::DataFrameColumnChange;
+use crate::view::workspaces::{WorkspaceRefreshConflict, WorkspaceRefreshSummary};
+use crate::view::JsonDataFrameView;
+
+/// Appended to the id of the workspace being refreshed to get the dir it is rebuilt in
+const REFRESH_SUFFIX: &str = ".refresh";
+
+/// A row edit read out of the workspace before it is re-indexed
+enum RowEdit {
+    Added {
+        row: DataFrame,
+    },
+    Modified {
+        row_id: String,
+        original: DataFrame,
+        row: DataFrame,
+    },
+    Removed {
+        row_id: String,
+        original: DataFrame,
+    },
+}
+
+struct DataFrameEdits {
+    path: PathBuf,
+    columns: Vec<DataFrameColumnChange>,
+    rows: Vec<RowEdit>,
+}
+
+/// Rebase the workspace onto the head of `branch_name`, replaying its staged edits.
+/// Returns the refreshed workspace and the edits that could not be replayed.
+pub fn refresh(
+    workspace: &Workspace,
+    branch_name: impl AsRef<str>,
+) -> Result<(Workspace, WorkspaceRefreshSummary), OxenError> {
+    let branch_name = branch_name.as_ref();
+    let repo = &workspace.base_repo;
+    let branch = api::local::branches::get_by_name(repo, branch_name)?
+        .ok_or_else(|| OxenError::local_branch_not_found(branch_name))?;
+    let head = api::local::commits::get_by_id(repo, &branch.commit_id)?
+        .ok_or_else(|| OxenError::commit_id_does_not_exist(&branch.commit_id))?;
+
+    let mut summary = WorkspaceRefreshSummary::default();
+    if head.id == workspace.commit.id {
+        log::debug!("workspace::refresh {} is already up to date", workspace.id);
+        return Ok((workspace.clone(), summary));
+    }
+
+    // Edits wait until the refreshed workspace is swapped in, so none are lost with the old dir
+    let mut lock = workspace.lock_file()?;
+    let _lock = lock.write()?;
+
+    // Read all the edits out before the indices are thrown away
+    let mut edits = vec![];
+    let mut files = vec![];
+    for path in workspaces::stager::list_files(workspace)? {
+        if workspaces::data_frames::duckdb_path(workspace, &path).exists() {
+            edits.push(read_edits(workspace, &path)?);
+        } else {
+            files.push(path);
+        }
+    }
+
+    // The refreshed workspace is built next to the old one and only swapped in once every edit
+    // was replayed, so a failure part way through leaves the workspace as it was
+    let mut staging = workspace.clone();
+    staging.id = format!("{}{}", workspace.id, REFRESH_SUFFIX);
+    let staging_dir = staging.dir();
+    if staging_dir.exists() {
+        util::fs::remove_dir_all(&staging_dir)?;
+    }
+    let result = copy_without_indices(&workspace.dir(), &staging_dir).and_then(|_| {
+        rebuild(
+            workspace,
+            &mut staging,
+            &head,
+            branch_name,
+            files,
+            edits,
+            &mut summary,
+        )
+    });
+    // The staging copy takes its own lock while its edits are replayed
+    let staging_lock_path = staging_dir.with_extension("lock");
+    if let Err(err) = result {
+        if let Err(cleanup_err) = remove_staging(&staging_dir, &staging_lock_path) {
+            log::error!("workspace::refresh could not clean up {staging_dir:?}: {cleanup_err}");
+        }
+        return Err(err);
+    }
+
+    let workspace_dir = workspace.dir();
+    let old_dir = staging_dir.with_extension("old");
+    util::fs::rename(&workspace_dir, &old_dir)?;
+    util::fs::rename(&staging_dir, &workspace_dir)?;
+
+    let mut refreshed = staging;
+    refreshed.id = workspace.id.to_owned();
+    refreshed.workspace_repo = LocalRepository::new(&workspace_dir)?;
+    refreshed.save()?;
+    util::fs::remove_dir_all(&old_dir)?;
+    remove_staging(&staging_dir, &staging_lock_path)?;
+    Ok((refreshed, summary))
+}
+
+fn remove_staging(staging_dir: &Path, staging_lock_path: &Path) -> Result<(), OxenError> {
+    if staging_dir.exists() {
+        util::fs::remove_dir_all(staging_dir)?;
+    }
+    if staging_lock_path.exists() {
+        util::fs::remove_file(staging_lock_path)?;
+    }
+    Ok(())
+}
+
+/// Copy the workspace except for its data frame indices, which point at the old commit.
+/// The copy has no config until it is swapped in, so it is not listed as a workspace.
+fn copy_without_indices(workspace_dir: &Path, staging_dir: &Path) -> Result<(), OxenError> {
+    for entry in std::fs::read_dir(workspace_dir)? {
+        let path = entry?.path();
+        let dst = staging_dir.join(path.file_name().unwrap_or_default());
+        if path.file_name() == Some(OsStr::new(MODS_DIR)) {
+            util::fs::create_dir_all(&dst)?;
+            for entry in std::fs::read_dir(&path)? {
+                let path = entry?.path();
+                if path.file_name() == Some(OsStr::new("duckdb")) {
+                    continue;
+                }
+                let dst = dst.join(path.file_name().unwrap_or_default());
+                if path.is_dir() {
+                    util::fs::copy_dir_all(&path, &dst)?;
+                } else {
+                    util::fs::copy(&path, &dst)?;
+                }
+            }
+        } else if path.is_dir() {
+            util::fs::copy_dir_all(&path, &dst)?;
+        } else {
+            util::fs::copy(&path, &dst)?;
+        }
+    }
+    util::fs::remove_file(staging_dir.join(OXEN_HIDDEN_DIR).join(WORKSPACE_CONFIG))?;
+    Ok(())
+}
+
+/// Move the staging copy of the workspace onto `head` and replay the edits read out of the workspace
+fn rebuild(
+    workspace: &Workspace,
+    staging: &mut Workspace,
+    head: &Commit,
+    branch_name: &str,
+    files: Vec<PathBuf>,
+    edits: Vec<DataFrameEdits>,
+    summary: &mut WorkspaceRefreshSummary,
+) -> Result<(), OxenError> {
+    staging.rebase(head)?;
+
+    for path in files {
+        if file_changed_upstream(workspace, head, &path)? {
+            summary.conflicts.push(WorkspaceRefreshConflict {
+                path: path.clone(),
+                operation: String::from("modified"),
+                row_id: None,
+                column: None,
+                value: None,
+                reason: format!("{:?} was also changed on branch {}", path, branch_name),
+            });
+        }
+    }
+
+    for edit in edits {
+        replay_edits(staging, head, branch_name, edit, summary)?;
+    }
+    Ok(())
+}
+
+fn read_edits(workspace: &Workspace, path: &Path) -> Result<DataFrameEdits, OxenError> {
+    let repo = &workspace.base_repo;
+    let column_changes_path = workspaces::data_frames::column_changes_path(workspace, path);
+    let columns = if column_changes_path.exists() {
+        let opts = db::key_val::opts::default();
+        let db = DB::open_for_read_only(&opts, dunce::simplified(&column_changes_path), false)?;
+        data_frame_column_changes_db::get_all_data_frame_column_changes(&db)?
+    } else {
+        vec![]
+    };
+
+    let db_path = workspaces::data_frames::duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+    let schema = workspace_df_db::full_staged_table_schema(&conn)?;
+    let select = Select::new()
+        .select("*")
+        .from(TABLE_NAME)
+        .where_clause(&format!(
+            "\"{}\" != '{}'",
+            DIFF_STATUS_COL,
+            StagedRowStatus::Unchanged
+        ));
+    let changed = df_db::select(&conn, &select, true, Some(&schema), None)?;
+
+    // Original contents of the modified and removed rows, to find them again in the new version
+    let entry = api::local::entries::get_commit_entry(repo, &workspace.commit, path)?
+        .ok_or_else(|| OxenError::entry_does_not_exist(path))?;
+    let committed = if changed.height() > 0 {
+        let version_path = util::fs::version_path(repo, &entry);
+        tabular::read_df(
+            util::fs::readable_version_path(repo, &version_path)?,
+            DFOpts::empty(),
+        )?
+    } else {
+        DataFrame::empty()
+    };
+
+    let mut edits = vec![];
+    for idx in 0..changed.height() {
+        let row = changed.slice(idx as i64, 1);
+        let status = workspaces::data_frames::rows::get_row_status(&row)?
+            .ok_or_else(|| OxenError::basic_str("Row status not found"))?;
+        let row_id = workspaces::data_frames::rows::get_row_id(&row)?
+            .ok_or_else(|| OxenError::basic_str("Row id not found"))?;
+        match status {
+            StagedRowStatus::Added => edits.push(RowEdit::Added {
+                row: drop_oxen_cols(&row)?,
+            }),
+            StagedRowStatus::Modified => edits.push(RowEdit::Modified {
+                original: original_row(&committed, &row)?,
+                row: drop_oxen_cols(&row)?,
+                row_id,
+            }),
+            StagedRowStatus::Removed => edits.push(RowEdit::Removed {
+                original: original_row(&committed, &row)?,
+                row_id,
+            }),
+            StagedRowStatus::Unchanged => {}
+        }
+    }
+
+    Ok(DataFrameEdits {
+        path: path.to_path_buf(),
+        columns,
+        rows: edits,
+    })
+}
+
+fn replay_edits(
+    workspace: &Workspace,
+    head: &Commit,
+    branch_name: &str,
+    edits: DataFrameEdits,
+    summary: &mut WorkspaceRefreshSummary,
+) -> Result<(), OxenError> {
+    let path = &edits.path;
+    let repo = &workspace.base_repo;
+    if api::local::entries::get_commit_entry(repo, head, path)?.is_none() {
+        for edit in edits.rows {
+            summary.conflicts.push(row_conflict(
+                path,
+                edit,
+                format!("{:?} was removed on branch {}", path, branch_name),
+            ));
+        }
+        workspaces::stager::rm(workspace, path)?;
+        return Ok(());
+    }
+
+    workspaces::data_frames::index(workspace, path)?;
+    let db_path = workspaces::data_frames::duckdb_path(workspace, path);
+    let row_changes_path = workspaces::data_frames::row_changes_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+
+    // Match the rows up before the columns change, while the schemas still line up
+    let mut used: HashSet<String> = HashSet::new();
+    let mut matched = vec![];
+    for edit in edits.rows {
+        let original = match &edit {
+            RowEdit::Added { .. } => None,
+            RowEdit::Modified { original, .. } | RowEdit::Removed { original, .. } => {
+                Some(original)
+            }
+        };
+        let target = match original {
+            Some(original) => match find_row(&conn, original, &used)? {
+                Some(id) => {
+                    used.insert(id.clone());
+                    Some(id)
+                }
+                None => {
+                    summary.conflicts.push(row_conflict(
+                        path,
+                        edit,
+                        format!("Row was changed or removed on branch {}", branch_name),
+                    ));
+                    continue;
+                }
+            },
+            None => None,
+        };
+        matched.push((edit, target));
+    }
+
+    for change in &edits.columns {
+        match replay_column_change(&conn, change) {
+            Ok(_) => {
+                let column_changes_path =
+                    workspaces::data_frames::column_changes_path(workspace, path);
+                columns::record_column_change(
+                    &column_changes_path,
+                    change.operation.to_owned(),
+                    change.column_before.clone(),
+                    change.column_after.clone(),
+                )?;
+                summary.replayed_columns += 1;
+            }
+            Err(err) => {
+                let column = change
+                    .column_after
+                    .as_ref()
+                    .or(change.column_before.as_ref())
+                    .map(|c| c.column_name.to_owned());
+                summary.conflicts.push(WorkspaceRefreshConflict {
+                    path: path.to_owned(),
+                    operation: change.operation.to_owned(),
+                    row_id: None,
+                    column,
+                    value: None,
+                    reason: format!("Could not replay column change: {err}"),
+                });
+            }
+        }
+    }
+
+    for (edit, target) in matched {
+        match (edit, target) {
+            (RowEdit::Added { row }, _) => {
+                let mut added = rows::append_row(&conn, &row)?;
+                let row_id = workspaces::data_frames::rows::get_row_id(&added)?
+                    .ok_or_else(|| OxenError::basic_str("Row id not found on added row"))?;
+                let revision = rows::get_row_revision(&added)?;
+                let value = JsonDataFrameView::json_from_df(&mut added);
+                change_feed::record(&conn, &row_id, "added", revision, &value)?;
+                rows::record_row_change(
+                    &row_changes_path,
+                    row_id,
+                    "added".to_owned(),
+                    value,
+                    None,
+                )?;
+            }
+            (RowEdit::Modified { mut row, .. }, Some(row_id)) => {
+                let mut before =
+                    workspaces::data_frames::rows::get_by_id(workspace, path, &row_id)?;
+                let mut after = rows::modify_row(&conn, &mut row, &row_id)?;
+                let revision = rows::get_row_revision(&after)?;
+                let value = JsonDataFrameView::json_from_df(&mut after);
+                change_feed::record(&conn, &row_id, "updated", revision, &value)?;
+                rows::record_row_change(
+                    &row_changes_path,
+                    row_id,
+                    "updated".to_owned(),
+                    JsonDataFrameView::json_from_df(&mut before),
+                    Some(value),
+                )?;
+            }
+            (RowEdit::Removed { .. }, Some(row_id)) => {
+                let mut deleted = rows::delete_row(&conn, &row_id)?;
+                let revision = rows::get_row_revision(&deleted)?.map(|r| r + 1);
+                let value = JsonDataFrameView::json_from_df(&mut deleted);
+                change_feed::record(&conn, &row_id, "deleted", revision, &value)?;
+                rows::record_row_change(
+                    &row_changes_path,
+                    row_id,
+                    "deleted".to_owned(),
+                    value,
+                    None,
+                )?;
+            }
+            _ => continue,
+        }
+        summary.replayed_rows += 1;
+    }
+
+    workspaces::stager::add(workspace, path)?;
+    if let DiffResult::Tabular(diff) = workspaces::data_frames::diff(workspace, path)? {
+        if !diff.has_changes() {
+            workspaces::stager::rm(workspace, path)?;
+        }
+    }
+    Ok(())
+}
+
+fn replay_column_change(
+    conn: &duckdb::Connection,
+    change: &DataFrameColumnChange,
+) -> Result<(), OxenError> {
+    match (
+        change.operation.as_str(),
+        &change.column_before,
+        &change.column_after,
+    ) {
+        ("added", _, Some(after)) => {
+            let new_column = NewColumn {
+                name: after.column_name.to_owned(),
+                data_type: after.column_data_type.clone().unwrap_or_default(),
+            };
+            columns::add_column(conn, &new_column)?;
+        }
+        ("deleted", Some(before), _) => {
+            let column = ColumnToDelete {
+                name: before.column_name.to_owned(),
+            };
+            columns::delete_column(conn, &column)?;
+        }
+        ("modified", Some(before), Some(after)) => {
+            let schema = workspace_df_db::schema_without_oxen_cols(conn, TABLE_NAME)?;
+            let column = ColumnToUpdate {
+                name: before.column_name.to_owned(),
+                new_name: Some(after.column_name.to_owned()),
+                new_data_type: after.column_data_type.clone(),
+            };
+            columns::update_column(conn, &column, &schema)?;
+        }
+        (operation, _, _) => {
+            return Err(OxenError::basic_str(format!(
+                "Unknown column operation {operation}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Find a row in the new index with the same contents as `original`, skipping rows already matched
+fn find_row(
+    conn: &duckdb::Connection,
+    original: &DataFrame,
+    used: &HashSet<String>,
+) -> Result<Option<String>, OxenError> {
+    let schema = workspace_df_db::schema_without_oxen_cols(conn, TABLE_NAME)?;
+    let mut clauses = vec![];
+    let mut values: Vec<Option<String>> = vec![];
+    for series in original.get_columns() {
+        let name = series.name();
+        if !schema.has_field_name(name) {
+            // The column was dropped on the branch, the row can't be the same
+            return Ok(None);
+        }
+        clauses.push(format!(
+            "CAST(\"{name}\" AS VARCHAR) IS NOT DISTINCT FROM ?"
+        ));
+        values.push(match series.get(0)? {
+            AnyValue::Null => None,
+            AnyValue::String(s) => Some(s.to_string()),
+            AnyValue::StringOwned(s) => Some(s.to_string()),
+            val => Some(val.to_string()),
+        });
+    }
+
+    let sql = format!(
+        "SELECT \"{OXEN_ID_COL}\" FROM {TABLE_NAME} WHERE {}",
+        clauses.join(" AND ")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut rows = stmt.query(duckdb::params_from_iter(values.iter()))?;
+    while let Some(row) = rows.next()? {
+        let id: String = row.get(0)?;
+        if !used.contains(&id) {
+            return Ok(Some(id));
+        }
+    }
+    Ok(None)
+}
+
+fn file_changed_upstream(
+    workspace: &Workspace,
+    head: &Commit,
+    path: &Path,
+) -> Result<bool, OxenError> {
+    let repo = &workspace.base_repo;
+    let before: Option<CommitEntry> =
+        api::local::entries::get_commit_entry(repo, &workspace.commit, path)?;
+    let after: Option<CommitEntry> = api::local::entries::get_commit_entry(repo, head, path)?;
+    Ok(match (before, after) {
+        (Some(before), Some(after)) => before.hash != after.hash,
+        (None, None) => false,
+        _ => true,
+    })
+}
+
+fn original_row(committed: &DataFrame, row: &DataFrame) -> Result<DataFrame, OxenError> {
+    let row_idx = workspaces::data_frames::rows::get_row_idx(row)?
+        .ok_or_else(|| OxenError::basic_str("Row index not found"))?;
+    Ok(committed.slice((row_idx - 1) as i64, 1))
+}
+
+fn drop_oxen_cols(df: &DataFrame) -> Result<DataFrame, OxenError> {
+    let cols: Vec<&str> = df
+        .get_column_names()
+        .into_iter()
+        .filter(|col| !OXEN_COLS.contains(col))
+        .collect();
+    Ok(df.select(cols)?)
+}
+
+fn row_conflict(path: &Path, edit: RowEdit, reason: String) -> WorkspaceRefreshConflict {
+    let (operation, row_id, mut row) = match edit {
+        RowEdit::Added { row } => ("added", None, row),
+        RowEdit::Modified { row_id, row, .. } => ("modified", Some(row_id), row),
+        RowEdit::Removed { row_id, original } => ("removed", Some(row_id), original),
+    };
+    WorkspaceRefreshConflict {
+        path: path.to_owned(),
+        operation: operation.to_owned(),
+        row_id,
+        column: None,
+        value: Some(JsonDataFrameView::json_from_df(&mut row)),
+        reason,
+    }
+}
@@ -5,7 +5,7 @@ use std::path::{Path, PathBuf};
 use crate::constants::{FILES_DIR, MODS_DIR};
 use crate::core::db;
 use crate::core::db::key_val::str_json_db;
-use crate::core::index::{CommitEntryReader, Stager};
+use crate::core::index::{workspaces, CommitEntryReader, Stager};
 use crate::error::OxenError;
 use crate::model::workspace::Workspace;
 use crate::model::StagedData;
@@ -62,16 +62,21 @@ pub fn list_files(workspace: &Workspace) -> Result<Vec<PathBuf>, OxenError> {
 }
 
 pub fn add(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let path = path.as_ref();
     let db_path = files_db_path(workspace);
     log::debug!("workspaces::stager::add to db_path {db_path:?}");
     let opts = db::key_val::opts::default();
     let db: DBWithThreadMode<MultiThreaded> = rocksdb::DBWithThreadMode::open(&opts, db_path)?;
     let key = path.to_string_lossy();
-    str_json_db::put(&db, &key, &key)
+    str_json_db::put(&db, &key, &key)?;
+    workspaces::touch(workspace)
 }
 
 pub fn rm(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), OxenError> {
+    let lock = workspace.lock_file()?;
+    let _lock = lock.read()?;
     let opts = db::key_val::opts::default();
     let files_db_path = files_db_path(workspace);
     let files_db: DBWithThreadMode<MultiThreaded> =
@@ -43,6 +43,7 @@ pub enum OxenError {
     // Repo
     RepoNotFound(Box<RepoNew>),
     RepoAlreadyExists(Box<RepoNew>),
+    QuotaExceeded(StringError),
 
     // Remotes
     RemoteRepoNotFound(Box<Remote>),
@@ -50,6 +51,9 @@ pub enum OxenError {
     IncompleteLocalHistory(StringError),
     RemoteBranchLocked(StringError),
     UpstreamMergeConflict(StringError),
+    /// Seconds the server asked us to wait before trying again
+    #[display(fmt = "Rate limited by the server, retry after {} seconds", _0)]
+    RateLimited(#[error(not(source))] u64),
 
     // Branches/Commits
     BranchNotFound(Box<StringError>),
@@ -63,6 +67,7 @@ pub enum OxenError {
     WorkspaceNotFound(Box<StringError>),
     QueryableWorkspaceNotFound(),
     WorkspaceBehind(Branch),
+    DataFrameRowConflict(StringError),
 
     // Resources (paths, uris, etc.)
     ResourceNotFound(StringError),
@@ -93,6 +98,9 @@ pub enum OxenError {
     // CLI Interaction
     OperationCancelled(StringError),
 
+    // Hooks
+    HookRejected(StringError),
+
     // fs / io
     StripPrefixError(StringError),
 
@@ -116,6 +124,7 @@ pub enum OxenError {
     JwalkError(jwalk::Error),
     PatternError(glob::PatternError),
     GlobError(glob::GlobError),
+    WatchError(notify::Error),
     PolarsError(polars::prelude::PolarsError),
     ParseIntError(ParseIntError),
 
@@ -165,6 +174,10 @@ impl OxenError {
         ))
     }
 
+    pub fn quota_exceeded(value: impl AsRef<str>) -> Self {
+        OxenError::QuotaExceeded(StringError::from(value.as_ref()))
+    }
+
     pub fn upstream_merge_conflict() -> Self {
         OxenError::UpstreamMergeConflict(StringError::from(
             "\nRemote has conflicts with local branch. To fix run:\n\n  oxen pull\n\nThen resolve conflicts and commit changes.\n",
@@ -187,6 +200,15 @@ impl OxenError {
         OxenError::OperationCancelled(StringError::from("\nOperation cancelled.\n"))
     }
 
+    pub fn hook_rejected(hook: impl AsRef<str>, message: impl AsRef<str>) -> Self {
+        let err = format!(
+            "\n{} hook rejected the operation:\n\n{}\n",
+            hook.as_ref(),
+            message.as_ref()
+        );
+        OxenError::HookRejected(StringError::from(err))
+    }
+
     pub fn resource_not_found(value: impl AsRef<str>) -> Self {
         OxenError::ResourceNotFound(StringError::from(value.as_ref()))
     }
@@ -223,6 +245,14 @@ impl OxenError {
         OxenError::WorkspaceBehind(branch)
     }
 
+    pub fn data_frame_row_conflict(row_id: impl AsRef<str>, revision: i64) -> Self {
+        OxenError::DataFrameRowConflict(StringError::from(format!(
+            "Row '{}' was modified by another request, current revision is {}",
+            row_id.as_ref(),
+            revision
+        )))
+    }
+
     pub fn root_commit_does_not_match(commit: Commit) -> Self {
         OxenError::RootCommitDoesNotMatch(Box::new(commit))
     }
@@ -569,6 +599,12 @@ impl From<glob::PatternError> for OxenError {
     }
 }
 
+impl From<notify::Error> for OxenError {
+    fn from(error: notify::Error) -> Self {
+        OxenError::WatchError(error)
+    }
+}
+
 impl From<PolarsError> for OxenError {
     fn from(err: PolarsError) -> Self {
         OxenError::PolarsError(err)
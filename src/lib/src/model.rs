@@ -3,6 +3,7 @@
 
 pub mod base_head;
 pub mod branch;
+pub mod bulk_file_format;
 pub mod commit;
 pub mod content_type;
 pub mod data_frame_size;
@@ -19,12 +20,14 @@ pub mod remote_branch;
 pub mod remote_dataset;
 pub mod repository;
 pub mod schema;
+pub mod search_result;
 pub mod staged_data;
 pub mod staged_dir_stats;
 pub mod staged_row_status;
 pub mod summarized_staged_dir_stats;
 pub mod user;
 pub mod workspace;
+pub mod worktree;
 
 // Repository
 pub use crate::model::repository::local_repository::LocalRepository;
@@ -44,6 +47,7 @@ pub use crate::model::branch::Branch;
 pub use crate::model::remote_branch::RemoteBranch;
 
 // Entry
+pub use crate::model::bulk_file_format::BulkFileFormat;
 pub use crate::model::content_type::ContentType;
 pub use crate::model::diff::diff_entry::DiffEntry;
 pub use crate::model::entry::commit_entry::CommitEntry;
@@ -75,9 +79,12 @@ pub use crate::model::diff::data_frame_diff::DataFrameDiff;
 pub use crate::model::schema::staged_schema::StagedSchema;
 pub use crate::model::schema::Schema;
 
+pub use crate::model::search_result::SearchResult;
+
 pub use crate::model::namespace::Namespace;
 
 // Dataset
 pub use crate::model::remote_dataset::RemoteDataset;
 
 pub use crate::model::workspace::Workspace;
+pub use crate::model::worktree::Worktree;
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::error::OxenError;
+use crate::util;
+
+/// The file formats rows of a data frame can be uploaded or exported as in bulk
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkFileFormat {
+    Csv,
+    Tsv,
+    Parquet,
+    #[serde(alias = "ndjson")]
+    Jsonl,
+    Arrow,
+}
+
+impl std::str::FromStr for BulkFileFormat {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<BulkFileFormat, OxenError> {
+        match s {
+            "csv" => Ok(BulkFileFormat::Csv),
+            "tsv" => Ok(BulkFileFormat::Tsv),
+            "parquet" => Ok(BulkFileFormat::Parquet),
+            "jsonl" | "ndjson" => Ok(BulkFileFormat::Jsonl),
+            "arrow" => Ok(BulkFileFormat::Arrow),
+            _ => Err(OxenError::basic_str(format!(
+                "Unsupported format '{s}', expected csv, tsv, parquet, jsonl or arrow"
+            ))),
+        }
+    }
+}
+
+impl BulkFileFormat {
+    /// The format of a file from its extension
+    pub fn from_path(path: impl AsRef<Path>) -> Result<BulkFileFormat, OxenError> {
+        util::fs::file_extension(path.as_ref()).parse()
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BulkFileFormat::Csv => "csv",
+            BulkFileFormat::Tsv => "tsv",
+            BulkFileFormat::Parquet => "parquet",
+            BulkFileFormat::Jsonl => "jsonl",
+            BulkFileFormat::Arrow => "arrow",
+        }
+    }
+}
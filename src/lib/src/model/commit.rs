@@ -8,9 +8,10 @@ use crate::core::index::CommitReader;
 use crate::error::OxenError;
 use crate::view::workspaces::WorkspaceCommit;
 use core::convert::Into;
+use utoipa::ToSchema;
 
 /// NewCommitBody is used to parse the json into a Commit from the API
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct NewCommitBody {
     pub message: String,
     pub author: String,
@@ -40,7 +41,7 @@ impl NewCommit {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct Commit {
     pub id: String,
     pub parent_ids: Vec<String>,
@@ -85,7 +86,7 @@ pub struct CommitWithSize {
 }
 
 // TODO: is there a way to derive all these values...and just add one new?
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct CommitWithBranchName {
     pub id: String,
     pub parent_ids: Vec<String>,
@@ -232,7 +233,7 @@ impl CommitWithBranchName {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct CommitStats {
     pub commit: Commit,
     pub num_entries: usize, // this is how many entries are in our commit db
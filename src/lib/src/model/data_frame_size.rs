@@ -1,7 +1,9 @@
 use polars::frame::DataFrame;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct DataFrameSize {
     pub height: usize,
     pub width: usize,
@@ -2,12 +2,15 @@ use polars::frame::DataFrame;
 use serde::{Deserialize, Serialize};
 
 use crate::{constants::DIFF_STATUS_COL, error::OxenError};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct AddRemoveModifyCounts {
     pub added: usize,
     pub removed: usize,
     pub modified: usize,
+    #[serde(default)]
+    pub renamed: usize,
 }
 
 impl AddRemoveModifyCounts {
@@ -37,6 +40,7 @@ impl AddRemoveModifyCounts {
             added: added_rows,
             removed: removed_rows,
             modified: modified_rows,
+            renamed: 0,
         })
     }
 }
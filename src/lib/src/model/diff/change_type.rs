@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash, Copy)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash, Copy, ToSchema)]
 pub enum ChangeType {
     Added,
     Removed,
@@ -17,6 +17,7 @@ use crate::{
     model::{CommitEntry, LocalRepository},
     util,
 };
+use utoipa::ToSchema;
 
 use super::diff_entry_status::DiffEntryStatus;
 use super::dir_diff_summary::DirDiffSummary;
@@ -24,7 +25,7 @@ use super::generic_diff::GenericDiff;
 use super::generic_diff_summary::GenericDiffSummary;
 use super::tabular_diff_summary::TabularDiffWrapper;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DiffEntry {
     pub status: String,
     pub data_type: EntryDataType,
@@ -187,6 +188,7 @@ impl DiffEntry {
             )
         };
 
+        let version_path = util::fs::readable_version_path(repo, &version_path)?;
         let data_type = util::fs::file_data_type(&version_path);
 
         let base_resource = DiffEntry::resource_from_entry(base_entry.clone());
@@ -291,10 +293,7 @@ impl DiffEntry {
         commit: &Commit,
     ) -> Option<MetadataEntry> {
         if let Some(dir) = dir {
-            match api::local::entries::get_meta_entry(repo, commit, dir) {
-                Ok(entry) => Some(entry),
-                Err(_) => None,
-            }
+            api::local::entries::get_meta_entry(repo, commit, dir).ok()
         } else {
             None
         }
@@ -431,6 +430,7 @@ impl DiffEntry {
                     added: num_added,
                     removed: num_removed,
                     modified: num_modified,
+                    renamed: 0,
                 },
             },
         })))
@@ -466,6 +466,7 @@ impl DiffEntry {
                     added: 0,
                     removed: num_removed,
                     modified: 0,
+                    renamed: 0,
                 },
             },
         })))
@@ -504,6 +505,7 @@ impl DiffEntry {
                     added: num_added,
                     removed: 0,
                     modified: 0,
+                    renamed: 0,
                 },
             },
         })))
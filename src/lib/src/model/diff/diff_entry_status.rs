@@ -1,11 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DiffEntryStatus {
     Added,
     Modified,
     Removed,
+    Renamed,
 }
 
 // Downcase the status
@@ -15,6 +18,7 @@ impl std::fmt::Display for DiffEntryStatus {
             DiffEntryStatus::Added => "added",
             DiffEntryStatus::Modified => "modified",
             DiffEntryStatus::Removed => "removed",
+            DiffEntryStatus::Renamed => "renamed",
         };
         write!(f, "{}", status)
     }
@@ -29,6 +33,7 @@ impl std::str::FromStr for DiffEntryStatus {
             "added" => Ok(DiffEntryStatus::Added),
             "modified" => Ok(DiffEntryStatus::Modified),
             "removed" => Ok(DiffEntryStatus::Removed),
+            "renamed" => Ok(DiffEntryStatus::Renamed),
             _ => Err(format!("Could not parse {} as a DiffEntryStatus", s)),
         }
     }
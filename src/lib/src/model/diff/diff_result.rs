@@ -3,6 +3,7 @@ use crate::model::diff::tabular_diff::TabularDiff;
 use crate::model::diff::text_diff::TextDiff;
 
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum DiffResult {
     Tabular(TabularDiff),
     Text(TextDiff),
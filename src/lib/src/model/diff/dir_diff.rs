@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use super::dir_diff_summary::DirDiffSummary;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DirDiff {
     #[serde(flatten)]
     pub summary: DirDiffSummary,
@@ -1,14 +1,15 @@
 use serde::{Deserialize, Serialize};
 
 use crate::model::diff::AddRemoveModifyCounts;
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DirDiffSummary {
     pub dir: DirDiffSummaryImpl,
 }
 
 // Impl is so that we can wrap the json response in the "dir" field to make summaries easier to distinguish
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DirDiffSummaryImpl {
     pub file_counts: AddRemoveModifyCounts,
 }
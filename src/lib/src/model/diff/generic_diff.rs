@@ -3,9 +3,11 @@ use serde::{Deserialize, Serialize};
 use crate::model::diff::dir_diff::DirDiff;
 use crate::model::diff::text_diff::TextDiff;
 use crate::view::tabular_diff_view::TabularDiffView;
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum GenericDiff {
     DirDiff(DirDiff),
     TabularDiff(TabularDiffView),
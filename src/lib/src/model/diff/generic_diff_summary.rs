@@ -2,8 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::model::diff::dir_diff_summary::DirDiffSummary;
 use crate::model::diff::tabular_diff_summary::TabularDiffWrapper;
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 #[serde(untagged)]
 pub enum GenericDiffSummary {
     DirDiffSummary(DirDiffSummary),
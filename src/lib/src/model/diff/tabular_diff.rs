@@ -66,6 +66,7 @@ impl TabularDiffMods {
                 added: 0,
                 removed: 0,
                 modified: 0,
+                renamed: 0,
             },
             col_changes: TabularSchemaDiff::empty(),
         }
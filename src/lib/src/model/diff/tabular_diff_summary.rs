@@ -8,20 +8,21 @@ use crate::error::OxenError;
 use crate::model::{CommitEntry, DataFrameSize, LocalRepository};
 use crate::opts::DFOpts;
 use crate::util;
+use utoipa::ToSchema;
 
 // THE DIFFERENCE BETWEEN WRAPPER AND SUMMARY IS JUST THE KEY NAME IN THE JSON RESPONSE
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct TabularDiffWrapper {
     pub tabular: TabularDiffSummaryImpl,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct TabularDiffSummary {
     pub summary: TabularDiffSummaryImpl,
 }
 
 // Impl is so that we can wrap the json response in the "tabular" field to make summaries easier to distinguish
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct TabularDiffSummaryImpl {
     pub num_added_rows: usize,
     pub num_added_cols: usize,
@@ -48,8 +49,14 @@ impl TabularDiffWrapper {
             (Some(base_entry), Some(head_entry)) => {
                 let commit_reader = CommitReader::new(repo)?;
 
-                let base_version_file = util::fs::version_path(repo, base_entry);
-                let head_version_file = util::fs::version_path(repo, head_entry);
+                let base_version_file = util::fs::readable_version_path(
+                    repo,
+                    &util::fs::version_path(repo, base_entry),
+                )?;
+                let head_version_file = util::fs::readable_version_path(
+                    repo,
+                    &util::fs::version_path(repo, head_entry),
+                )?;
 
                 let base_commit = commit_reader.get_commit_by_id(&base_entry.commit_id)?;
                 let head_commit = commit_reader.get_commit_by_id(&head_entry.commit_id)?;
@@ -81,29 +88,13 @@ impl TabularDiffWrapper {
                 // TODO - this can be made less naive
                 let schema_has_changed = base_size.width != head_size.width;
 
-                let num_added_rows = if base_size.height < head_size.height {
-                    head_size.height - base_size.height
-                } else {
-                    0
-                };
+                let num_added_rows = head_size.height.saturating_sub(base_size.height);
 
-                let num_removed_rows = if base_size.height > head_size.height {
-                    base_size.height - head_size.height
-                } else {
-                    0
-                };
+                let num_removed_rows = base_size.height.saturating_sub(head_size.height);
 
-                let num_added_cols = if base_size.width < head_size.width {
-                    head_size.width - base_size.width
-                } else {
-                    0
-                };
+                let num_added_cols = head_size.width.saturating_sub(base_size.width);
 
-                let num_removed_cols = if base_size.width > head_size.width {
-                    base_size.width - head_size.width
-                } else {
-                    0
-                };
+                let num_removed_cols = base_size.width.saturating_sub(head_size.width);
 
                 Ok(TabularDiffWrapper {
                     tabular: TabularDiffSummaryImpl {
@@ -116,7 +107,10 @@ impl TabularDiffWrapper {
                 })
             }
             (Some(base_entry), None) => {
-                let base_version_file = util::fs::version_path(repo, base_entry);
+                let base_version_file = util::fs::readable_version_path(
+                    repo,
+                    &util::fs::version_path(repo, base_entry),
+                )?;
                 let base_commit =
                     CommitReader::new(repo)?.get_commit_by_id(&base_entry.commit_id)?;
 
@@ -144,7 +138,10 @@ impl TabularDiffWrapper {
             }
 
             (None, Some(head_entry)) => {
-                let head_version_file = util::fs::version_path(repo, head_entry);
+                let head_version_file = util::fs::readable_version_path(
+                    repo,
+                    &util::fs::version_path(repo, head_entry),
+                )?;
 
                 let head_commit =
                     CommitReader::new(repo)?.get_commit_by_id(&head_entry.commit_id)?;
@@ -187,11 +184,10 @@ impl TabularDiffWrapper {
     pub fn maybe_get_df(repo: &LocalRepository, entry: &Option<CommitEntry>) -> Option<DataFrame> {
         match entry {
             Some(entry) => {
-                let version_path = util::fs::version_path(repo, entry);
-                match tabular::read_df(version_path, DFOpts::empty()) {
-                    Ok(df) => Some(df),
-                    Err(_) => None,
-                }
+                let version_path =
+                    util::fs::readable_version_path(repo, &util::fs::version_path(repo, entry))
+                        .ok()?;
+                tabular::read_df(version_path, DFOpts::empty()).ok()
             }
             None => None,
         }
@@ -1,13 +1,14 @@
 use crate::model::diff::change_type::ChangeType;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct LineDiff {
     pub modification: ChangeType,
     pub text: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct TextDiff {
     pub lines: Vec<LineDiff>,
 }
@@ -1,6 +1,7 @@
 use crate::constants::VERSION_FILE_NAME;
 use crate::model::{Commit, ContentHashable, LocalRepository, RemoteEntry, Schema};
 use crate::util;
+use utoipa::ToSchema;
 
 use filetime::FileTime;
 use serde::{Deserialize, Serialize};
@@ -78,9 +79,10 @@ pub struct CommitPath {
     pub path: PathBuf,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct CommitEntry {
     pub commit_id: String,
+    #[schema(value_type = String)]
     pub path: PathBuf,
     pub hash: String,
     pub num_bytes: u64,
@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Deserialize, Serialize, Debug, Clone, Eq, Hash, PartialEq)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, Hash, PartialEq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum EntryDataType {
     Dir,
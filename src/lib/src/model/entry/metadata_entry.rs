@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::api;
 use crate::model::metadata::generic_metadata::GenericMetadata;
 use crate::model::{Commit, CommitEntry, EntryDataType, LocalRepository, ParsedResource};
+use utoipa::ToSchema;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CLIMetadataEntry {
@@ -20,7 +21,7 @@ pub struct CLIMetadataEntry {
     pub extension: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct MetadataEntry {
     pub filename: String,
     pub is_dir: bool,
@@ -46,10 +47,6 @@ impl MetadataEntry {
         entry: Option<CommitEntry>,
         commit: &Commit,
     ) -> Option<MetadataEntry> {
-        entry.as_ref()?;
-        match api::local::metadata::from_commit_entry(repo, &entry.unwrap(), commit) {
-            Ok(metadata) => Some(metadata),
-            Err(_) => None,
-        }
+        api::local::metadata::from_commit_entry(repo, &entry?, commit).ok()
     }
 }
@@ -5,20 +5,22 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use crate::model::Schema;
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, ToSchema)]
 pub enum ModType {
     Append,
     Delete,
     Modify,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct ModEntry {
     pub uuid: String,
     pub modification_type: ModType, // append, delete, modify
     pub schema: Option<Schema>,
     pub data: String,
+    #[schema(value_type = String)]
     pub path: PathBuf,
     #[serde(with = "time::serde::rfc3339")]
     pub timestamp: OffsetDateTime,
@@ -1,7 +1,8 @@
 use crate::model::CommitEntry;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct RemoteEntry {
     pub filename: String,
     pub hash: String,
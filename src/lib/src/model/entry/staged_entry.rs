@@ -1,7 +1,8 @@
 use crate::model::ContentHashable;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, ToSchema)]
 pub enum StagedEntryStatus {
     Added,
     Modified,
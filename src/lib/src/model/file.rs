@@ -3,8 +3,11 @@ use super::User;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct FileNew {
+    #[schema(value_type = String)]
     pub path: PathBuf,
     pub contents: String,
     pub user: User,
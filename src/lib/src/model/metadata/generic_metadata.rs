@@ -4,7 +4,9 @@ use crate::model::metadata::{
     MetadataAudio, MetadataDir, MetadataImage, MetadataTabular, MetadataText, MetadataVideo,
 };
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 #[serde(untagged)]
 pub enum GenericMetadata {
     MetadataDir(MetadataDir),
@@ -4,13 +4,18 @@ use serde::{Deserialize, Serialize};
 use super::{Branch, Commit};
 use std::path::PathBuf;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct ParsedResource {
     pub commit: Option<Commit>, // Maybe resolves to a commit
     pub branch: Option<Branch>, // Maybe resolves to a branch
-    pub path: PathBuf,          // File path that was past the commit or branch
-    pub version: PathBuf,       // This is the split out branch / commit id
-    pub resource: PathBuf,      // full resource we parsed
+    #[schema(value_type = String)]
+    pub path: PathBuf, // File path that was past the commit or branch
+    #[schema(value_type = String)]
+    pub version: PathBuf, // This is the split out branch / commit id
+    #[schema(value_type = String)]
+    pub resource: PathBuf, // full resource we parsed
 }
 
 impl std::fmt::Display for ParsedResource {
@@ -1,21 +1,27 @@
 use crate::config::RemoteConfig;
 use crate::constants;
-use crate::constants::SHALLOW_FLAG;
+use crate::constants::{READ_ONLY_FLAG, SHALLOW_FLAG};
 use crate::error;
 use crate::error::OxenError;
 use crate::model::{Remote, RemoteRepository};
 use crate::util;
+use crate::util::hasher::HashAlgorithm;
 use crate::view::RepositoryView;
+use utoipa::ToSchema;
 
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct LocalRepository {
+    #[schema(value_type = String)]
     pub path: PathBuf,
     // Optional remotes to sync the data to
     remote_name: Option<String>, // name of the current remote ("origin" by default)
     pub remotes: Vec<Remote>,    // List of possible remotes
+    hash_algorithm: Option<HashAlgorithm>, // How file contents are hashed, xxh3 by default
+    compress_versions: Option<bool>, // Whether version files are stored zstd compressed
+    link_checkout: Option<bool>, // Whether checked out files are links to their version files
 }
 
 impl LocalRepository {
@@ -25,6 +31,9 @@ impl LocalRepository {
             path: path.to_path_buf(),
             remotes: vec![],
             remote_name: None,
+            hash_algorithm: None,
+            compress_versions: None,
+            link_checkout: None,
         })
     }
 
@@ -33,6 +42,9 @@ impl LocalRepository {
             path: std::env::current_dir()?.join(view.name),
             remotes: vec![],
             remote_name: None,
+            hash_algorithm: None,
+            compress_versions: None,
+            link_checkout: None,
         })
     }
 
@@ -41,6 +53,9 @@ impl LocalRepository {
             path: path.to_owned(),
             remotes: vec![repo.remote],
             remote_name: Some(String::from(constants::DEFAULT_REMOTE_NAME)),
+            hash_algorithm: None,
+            compress_versions: None,
+            link_checkout: None,
         })
     }
 
@@ -54,6 +69,9 @@ impl LocalRepository {
             path: dir.to_path_buf(),
             remotes: remote_cfg.remotes,
             remote_name: remote_cfg.remote_name,
+            hash_algorithm: remote_cfg.hash_algorithm,
+            compress_versions: remote_cfg.compress_versions,
+            link_checkout: remote_cfg.link_checkout,
         };
         Ok(repo)
     }
@@ -69,10 +87,37 @@ impl LocalRepository {
         String::from(self.path.file_name().unwrap().to_str().unwrap())
     }
 
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm.unwrap_or_default()
+    }
+
+    pub fn set_hash_algorithm(&mut self, algorithm: HashAlgorithm) {
+        self.hash_algorithm = Some(algorithm);
+    }
+
+    pub fn compresses_versions(&self) -> bool {
+        self.compress_versions.unwrap_or(false)
+    }
+
+    pub fn set_compress_versions(&mut self, compress: bool) {
+        self.compress_versions = Some(compress);
+    }
+
+    pub fn links_checkout(&self) -> bool {
+        self.link_checkout.unwrap_or(false)
+    }
+
+    pub fn set_link_checkout(&mut self, link: bool) {
+        self.link_checkout = Some(link);
+    }
+
     pub fn save(&self, path: &Path) -> Result<(), OxenError> {
         let cfg = RemoteConfig {
             remote_name: self.remote_name.clone(),
             remotes: self.remotes.clone(),
+            hash_algorithm: self.hash_algorithm,
+            compress_versions: self.compress_versions,
+            link_checkout: self.link_checkout,
         };
         let toml = toml::to_string(&cfg)?;
         util::fs::write_to_path(path, toml)?;
@@ -158,6 +203,23 @@ impl LocalRepository {
         let shallow_flag_path = util::fs::oxen_hidden_dir(&self.path).join(SHALLOW_FLAG);
         shallow_flag_path.exists()
     }
+
+    pub fn write_is_read_only(&self, read_only: bool) -> Result<(), OxenError> {
+        let read_only_flag_path = util::fs::oxen_hidden_dir(&self.path).join(READ_ONLY_FLAG);
+        log::debug!("Write is read only [{read_only}] to path: {read_only_flag_path:?}");
+        if read_only {
+            util::fs::write_to_path(&read_only_flag_path, "true")?;
+        } else if read_only_flag_path.exists() {
+            util::fs::remove_file(&read_only_flag_path)?;
+        }
+        Ok(())
+    }
+
+    /// Read only repositories are mirrors of another server and reject pushes
+    pub fn is_read_only(&self) -> bool {
+        let read_only_flag_path = util::fs::oxen_hidden_dir(&self.path).join(READ_ONLY_FLAG);
+        read_only_flag_path.exists()
+    }
 }
 
 #[cfg(test)]
@@ -189,6 +251,19 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_write_is_read_only() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|local_repo| {
+            assert!(!local_repo.is_read_only());
+            local_repo.write_is_read_only(true)?;
+            assert!(local_repo.is_read_only());
+            local_repo.write_is_read_only(false)?;
+            assert!(!local_repo.is_read_only());
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_delete_remote() -> Result<(), OxenError> {
         test::run_empty_local_repo_test(|mut local_repo| {
@@ -5,8 +5,10 @@ use crate::constants::DEFAULT_HOST;
 use crate::error::OxenError;
 use crate::model::commit::Commit;
 use crate::model::file::FileNew;
+use crate::util::hasher::HashAlgorithm;
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct RepoNew {
     pub namespace: String,
     pub name: String,
@@ -23,6 +25,9 @@ pub struct RepoNew {
     pub description: Option<String>,
     // Files that you want to seed the repo with
     pub files: Option<Vec<FileNew>>,
+    // How the local repository hashes file contents, so the server hashes them the same way
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_algorithm: Option<HashAlgorithm>,
 }
 
 impl std::fmt::Display for RepoNew {
@@ -74,6 +79,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            hash_algorithm: None,
         })
     }
 
@@ -96,6 +102,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            hash_algorithm: None,
         }
     }
 
@@ -113,6 +120,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            hash_algorithm: None,
         }
     }
 
@@ -130,6 +138,7 @@ impl RepoNew {
             root_commit: Some(root_commit),
             description: None,
             files: None,
+            hash_algorithm: None,
         }
     }
 
@@ -147,6 +156,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: Some(files),
+            hash_algorithm: None,
         }
     }
 
@@ -170,6 +180,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            hash_algorithm: None,
         })
     }
 }
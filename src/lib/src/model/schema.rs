@@ -12,8 +12,9 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{collections::HashMap, fmt, path::PathBuf};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct Schema {
     pub name: Option<String>,
     pub hash: String,
@@ -298,7 +299,7 @@ impl Schema {
     }
 
     pub fn num_bytes(&self) -> u64 {
-        let bytes = serde_json::to_string(&self).unwrap().as_bytes().len();
+        let bytes = serde_json::to_string(&self).unwrap().len();
         bytes as u64
     }
 
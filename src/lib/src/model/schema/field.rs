@@ -2,10 +2,11 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::model::schema::DataType;
+use utoipa::ToSchema;
 
 use super::CustomDataType;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct Field {
     pub name: String,
     pub dtype: String,
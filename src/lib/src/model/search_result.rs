@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use utoipa::ToSchema;
+
+/// A single hit from the search index of a commit.
+///
+/// Text files are indexed line by line, so `row` is the zero-based line number
+/// and `column` is empty. Tabular files are indexed per cell of their string columns.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct SearchResult {
+    #[schema(value_type = String)]
+    pub path: PathBuf,
+    pub row: Option<usize>,
+    pub column: Option<String>,
+    pub text: String,
+    pub score: f64,
+}
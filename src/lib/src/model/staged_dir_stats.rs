@@ -5,9 +5,12 @@ use serde::{Deserialize, Serialize};
 
 use super::StagedEntryStatus;
 
+use utoipa::ToSchema;
+
 // Used for a quick summary of directory
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct StagedDirStats {
+    #[schema(value_type = String)]
     pub path: PathBuf,
     pub num_files_staged: usize,
     pub total_files: usize,
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::fs::File;
 use std::path::{Path, PathBuf};
+use time::{Duration, OffsetDateTime};
 
 use crate::api;
 use crate::constants;
@@ -9,6 +11,18 @@ use crate::model::{Commit, LocalRepository};
 use crate::util;
 use toml;
 
+// Parts of the base repo's .oxen dir that a workspace keeps a copy of
+const WORKSPACE_REPO_DIRS: [&str; 5] = [
+    constants::COMMITS_DIR,
+    constants::HISTORY_DIR,
+    constants::REFS_DIR,
+    constants::HEAD_FILE,
+    constants::OBJECTS_DIR,
+];
+
+// Touched whenever the workspace is used, so it doesn't expire, without rewriting the config
+const WORKSPACE_TOUCHED: &str = "WORKSPACE_TOUCHED";
+
 fn workspace_dir(repo: &LocalRepository, workspace_id_hash: &str) -> PathBuf {
     repo.path
         .join(OXEN_HIDDEN_DIR)
@@ -16,12 +30,38 @@ fn workspace_dir(repo: &LocalRepository, workspace_id_hash: &str) -> PathBuf {
         .join(workspace_id_hash)
 }
 
+/// The later of the time saved in the config and the last time the workspace was touched
+fn last_updated(
+    updated_at: Option<OffsetDateTime>,
+    workspace_dir: &Path,
+) -> Option<OffsetDateTime> {
+    let touched_path = workspace_dir.join(OXEN_HIDDEN_DIR).join(WORKSPACE_TOUCHED);
+    let touched_at = std::fs::metadata(touched_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(OffsetDateTime::from);
+    updated_at.max(touched_at)
+}
+
 // Define a struct for the workspace config to make it easier to serialize
+// Everything after workspace_name was added later, so is optional to keep reading older configs
 #[derive(Serialize, Deserialize)]
 struct WorkspaceConfig {
     workspace_commit_id: String,
     is_editable: bool,
     workspace_name: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    branch_name: Option<String>,
+    #[serde(default)]
+    creator: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    created_at: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    updated_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    ttl_secs: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -35,6 +75,18 @@ pub struct Workspace {
     // .oxen/workspaces/<workspace_ id>/.oxen/WORKSPACE_CONFIG
     pub is_editable: bool,
     pub commit: Commit,
+    // Human readable name, defaults to the id. Can be changed without moving the workspace dir.
+    pub name: Option<String>,
+    // Branch the workspace was created from
+    pub branch_name: Option<String>,
+    // Who created the workspace
+    pub creator: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+    // Workspaces that have not been updated for ttl_secs are removed by the cleanup task
+    pub ttl_secs: Option<u64>,
 }
 
 impl Workspace {
@@ -74,6 +126,12 @@ impl Workspace {
             workspace_repo: LocalRepository::new(&workspace_dir)?,
             commit,
             is_editable: config.is_editable,
+            name: config.name,
+            branch_name: config.branch_name,
+            creator: config.creator,
+            created_at: config.created_at,
+            updated_at: last_updated(config.updated_at, &workspace_dir),
+            ttl_secs: config.ttl_secs,
         })
     }
 
@@ -85,7 +143,6 @@ impl Workspace {
         is_editable: bool,
     ) -> Result<Self, OxenError> {
         let workspace_id = workspace_id.as_ref();
-        let workspace_name = workspace_id.to_owned();
         let workspace_id_hash = util::hasher::hash_str_sha256(workspace_id);
         let workspace_dir = workspace_dir(base_repo, &workspace_id_hash);
         let oxen_dir = workspace_dir.join(OXEN_HIDDEN_DIR);
@@ -121,11 +178,38 @@ impl Workspace {
 
         let workspace_repo = Self::init_workspace_repo(base_repo, &workspace_dir)?;
 
+        let now = OffsetDateTime::now_utc();
+        let workspace = Workspace {
+            id: workspace_id.to_owned(),
+            base_repo: base_repo.clone(),
+            workspace_repo,
+            commit: commit.clone(),
+            is_editable,
+            name: None,
+            branch_name: None,
+            creator: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+            ttl_secs: None,
+        };
+        workspace.save()?;
+
+        Ok(workspace)
+    }
+
+    /// Writes the workspace metadata to .oxen/workspaces/<workspace_id>/.oxen/WORKSPACE_CONFIG
+    pub fn save(&self) -> Result<(), OxenError> {
         // Serialize the workspace config to TOML
         let workspace_config = WorkspaceConfig {
-            workspace_commit_id: commit.id.clone(),
-            is_editable,
-            workspace_name: workspace_name.clone(),
+            workspace_commit_id: self.commit.id.clone(),
+            is_editable: self.is_editable,
+            workspace_name: self.id.clone(),
+            name: self.name.clone(),
+            branch_name: self.branch_name.clone(),
+            creator: self.creator.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            ttl_secs: self.ttl_secs,
         };
 
         let toml_string = match toml::to_string(&workspace_config) {
@@ -139,23 +223,67 @@ impl Workspace {
         };
 
         // Write the TOML string to WORKSPACE_CONFIG
-        let commit_id_path = workspace_repo
-            .path
-            .join(OXEN_HIDDEN_DIR)
-            .join(WORKSPACE_CONFIG);
+        let config_path = self.dir().join(OXEN_HIDDEN_DIR).join(WORKSPACE_CONFIG);
         log::debug!(
-            "index::workspaces::create writing workspace config to: {:?}",
-            commit_id_path
+            "workspace::save writing workspace config to: {:?}",
+            config_path
         );
-        util::fs::write_to_path(&commit_id_path, toml_string)?;
+        util::fs::write_to_path(&config_path, toml_string)?;
+        Ok(())
+    }
 
-        Ok(Workspace {
-            id: workspace_id.to_owned(),
-            base_repo: base_repo.clone(),
-            workspace_repo,
-            commit: commit.clone(),
-            is_editable,
-        })
+    /// Marks the workspace as used now, by the modified time of a file next to the config so it
+    /// never races with other updates to the config
+    pub fn touch(&self) -> Result<(), OxenError> {
+        let path = self.dir().join(OXEN_HIDDEN_DIR).join(WORKSPACE_TOUCHED);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|err| OxenError::file_error(&path, err))?;
+        file.set_modified(std::time::SystemTime::now())
+            .map_err(|err| OxenError::file_error(&path, err))?;
+        Ok(())
+    }
+
+    /// Moves the workspace onto a new base commit, refreshing its copy of the repository history.
+    /// Staged changes are left alone, it is up to the caller to replay them and save() the workspace.
+    pub fn rebase(&mut self, commit: &Commit) -> Result<(), OxenError> {
+        let workspace_dir = self.dir();
+        let workspace_hidden_dir = workspace_dir.join(OXEN_HIDDEN_DIR);
+        for dir in WORKSPACE_REPO_DIRS {
+            let path = workspace_hidden_dir.join(dir);
+            if path.is_dir() {
+                util::fs::remove_dir_all(&path)?;
+            } else if path.exists() {
+                util::fs::remove_file(&path)?;
+            }
+        }
+
+        self.workspace_repo = Self::init_workspace_repo(&self.base_repo, &workspace_dir)?;
+        self.commit = commit.clone();
+        self.updated_at = Some(OffsetDateTime::now_utc());
+        Ok(())
+    }
+
+    /// Display name of the workspace, falls back to the id if it was never named
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Time after which the workspace can be cleaned up, None if it never expires
+    pub fn expires_at(&self) -> Option<OffsetDateTime> {
+        let ttl_secs = self.ttl_secs?;
+        let last_touched = self.updated_at.or(self.created_at)?;
+        Some(last_touched + Duration::seconds(ttl_secs as i64))
+    }
+
+    pub fn is_expired(&self, now: OffsetDateTime) -> bool {
+        match self.expires_at() {
+            Some(expires_at) => expires_at <= now,
+            None => false,
+        }
     }
 
     pub fn list(repo: &LocalRepository) -> Result<Vec<Self>, OxenError> {
@@ -223,15 +351,7 @@ impl Workspace {
         log::debug!("init_workspace_repo {workspace_hidden_dir:?}");
         util::fs::create_dir_all(&workspace_hidden_dir)?;
 
-        let dirs_to_copy = vec![
-            constants::COMMITS_DIR,
-            constants::HISTORY_DIR,
-            constants::REFS_DIR,
-            constants::HEAD_FILE,
-            constants::OBJECTS_DIR,
-        ];
-
-        for dir in dirs_to_copy {
+        for dir in WORKSPACE_REPO_DIRS {
             let oxen_dir = oxen_hidden_dir.join(dir);
             let target_dir = workspace_hidden_dir.join(dir);
 
@@ -251,4 +371,19 @@ impl Workspace {
         let workspace_id_hash = util::hasher::hash_str_sha256(&self.id);
         workspace_dir(&self.base_repo, &workspace_id_hash)
     }
+
+    /// Lock on the workspace, edits hold it shared and a refresh holds it exclusively while it
+    /// swaps the workspace dir, so no edit lands in the dir that is thrown away. It lives next to
+    /// the workspace dir so the swap doesn't move it.
+    pub fn lock_file(&self) -> Result<fd_lock::RwLock<File>, OxenError> {
+        let path = self.dir().with_extension("lock");
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|err| OxenError::file_error(&path, err))?;
+        Ok(fd_lock::RwLock::new(file))
+    }
 }
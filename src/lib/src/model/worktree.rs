@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A working directory of a repository, the main repository counts as one too
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Worktree {
+    pub path: PathBuf,
+    // Branch name or commit id checked out, None if the worktree has no HEAD
+    pub head: Option<String>,
+    pub is_main: bool,
+}
+
+impl std::fmt::Display for Worktree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}",
+            self.path.display(),
+            self.head.as_deref().unwrap_or("(no HEAD)")
+        )
+    }
+}
@@ -2,6 +2,7 @@
 //!
 
 pub mod add_opts;
+pub mod clean_opts;
 pub mod clone_opts;
 pub mod count_lines_opts;
 pub mod df_opts;
@@ -15,9 +16,11 @@ pub mod paginate_opts;
 pub mod pull_opts;
 pub mod restore_opts;
 pub mod rm_opts;
+pub mod search_opts;
 pub mod upload_opts;
 
 pub use crate::opts::add_opts::AddOpts;
+pub use crate::opts::clean_opts::CleanOpts;
 pub use crate::opts::clone_opts::CloneOpts;
 pub use crate::opts::count_lines_opts::CountLinesOpts;
 pub use crate::opts::df_opts::DFOpts;
@@ -30,4 +33,5 @@ pub use crate::opts::paginate_opts::PaginateOpts;
 pub use crate::opts::pull_opts::PullOpts;
 pub use crate::opts::restore_opts::RestoreOpts;
 pub use crate::opts::rm_opts::RmOpts;
+pub use crate::opts::search_opts::SearchOpts;
 pub use crate::opts::upload_opts::UploadOpts;
@@ -0,0 +1,9 @@
+#[derive(Clone, Debug, Default)]
+pub struct CleanOpts {
+    /// Only report what would be removed
+    pub dry_run: bool,
+    /// Also remove untracked directories
+    pub dirs: bool,
+    /// Purge the regenerable caches under .oxen instead of untracked files
+    pub caches: bool,
+}
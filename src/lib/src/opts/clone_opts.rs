@@ -9,10 +9,12 @@ pub struct CloneOpts {
     pub branch: String,
     pub shallow: bool,
     pub all: bool,
+    // Link files in the working directory to their version files instead of copying them
+    pub link_checkout: bool,
 }
 
 impl CloneOpts {
-    /// Sets `branch` to `DEFAULT_BRANCH_NAME` and defaults `shallow` and `all` to `false`
+    /// Sets `branch` to `DEFAULT_BRANCH_NAME` and defaults `shallow`, `all` and `link_checkout` to `false`
     pub fn new(url: String, dst: impl AsRef<Path>) -> CloneOpts {
         CloneOpts {
             url,
@@ -20,6 +22,7 @@ impl CloneOpts {
             branch: DEFAULT_BRANCH_NAME.to_string(),
             shallow: false,
             all: false,
+            link_checkout: false,
         }
     }
 }
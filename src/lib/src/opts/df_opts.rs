@@ -8,6 +8,7 @@ use crate::core::df::filter::{self, DFFilterExp};
 use crate::error::OxenError;
 use crate::model::schema::Field;
 use crate::model::Schema;
+use utoipa::ToSchema;
 
 #[derive(Debug)]
 pub struct AddColVals {
@@ -52,12 +53,12 @@ pub struct DFOpts {
     pub vstack: Option<Vec<PathBuf>>,
     pub write: Option<PathBuf>,
 }
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct DFOptsView {
     pub opts: Vec<DFOptView>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct DFOptView {
     pub name: String,
     pub value: serde_json::Value,
@@ -276,16 +277,8 @@ impl DFOpts {
         } else {
             Some(String::from("false"))
         };
-        let page = if self.page.is_some() {
-            Some(format!("{}", self.page.unwrap()))
-        } else {
-            None
-        };
-        let page_size = if self.page_size.is_some() {
-            Some(format!("{}", self.page_size.unwrap()))
-        } else {
-            None
-        };
+        let page = self.page.map(|page| format!("{page}"));
+        let page_size = self.page_size.map(|page_size| format!("{page_size}"));
         let params = vec![
             ("item", self.item.clone()),
             ("columns", self.columns.clone()),
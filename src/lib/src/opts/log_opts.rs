@@ -1,5 +1,9 @@
+use std::path::PathBuf;
+
 #[derive(Clone, Debug)]
 pub struct LogOpts {
     pub revision: Option<String>, // commit id or branch name
+    pub path: Option<PathBuf>,    // only list the commits that changed this path
+    pub follow: bool,             // keep following the path across renames
     pub remote: bool,
 }
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use crate::constants::{DEFAULT_PAGE_NUM, DEFAULT_PAGE_SIZE};
+
+#[derive(Clone, Debug)]
+pub struct SearchOpts {
+    pub query: String,
+    pub revision: Option<String>, // commit id or branch name
+    pub path: Option<PathBuf>,    // only return results under this path
+    pub page_num: usize,
+    pub page_size: usize,
+    pub remote: bool,
+}
+
+impl SearchOpts {
+    /// Searches HEAD for the query with the default pagination
+    pub fn from_query(query: impl AsRef<str>) -> SearchOpts {
+        SearchOpts {
+            query: query.as_ref().to_string(),
+            revision: None,
+            path: None,
+            page_num: DEFAULT_PAGE_NUM,
+            page_size: DEFAULT_PAGE_SIZE,
+            remote: false,
+        }
+    }
+}
@@ -17,11 +17,18 @@ use std::path::PathBuf;
 
 use crate::constants;
 use crate::constants::CACHE_DIR;
+use crate::constants::COMPRESSED_VERSION_EXTENSION;
 use crate::constants::CONTENT_IS_VALID;
 use crate::constants::DATA_ARROW_FILE;
+use crate::constants::DECRYPTED_DIR;
+use crate::constants::ENCRYPTED_VERSION_EXTENSION;
 use crate::constants::HISTORY_DIR;
+use crate::constants::MAX_VERSIONS_CACHE_SIZE;
 use crate::constants::OXEN_HIDDEN_DIR;
+use crate::constants::TMP_DIR;
+use crate::constants::VERSIONS_DIR;
 use crate::constants::VERSION_FILE_NAME;
+use crate::core::encryption;
 use crate::error::OxenError;
 use crate::model::entry::commit_entry::Entry;
 use crate::model::metadata::metadata_image::ImgResize;
@@ -78,16 +85,16 @@ pub fn version_path_for_commit_id(
     repo: &LocalRepository,
     commit_id: &str,
     filepath: &Path,
-) -> Result<PathBuf, OxenError> {
+) -> Result<ReadableVersionPath, OxenError> {
     match api::local::commits::get_by_id(repo, commit_id)? {
         Some(commit) => match api::local::entries::get_commit_entry(repo, &commit, filepath)? {
             Some(entry) => {
                 let path = version_path(repo, &entry);
                 let arrow_path = path.parent().unwrap().join(DATA_ARROW_FILE);
                 if arrow_path.exists() {
-                    Ok(arrow_path)
+                    Ok(ReadableVersionPath::new(arrow_path))
                 } else {
-                    Ok(path)
+                    readable_version_path(repo, &path)
                 }
             }
             None => Err(OxenError::path_does_not_exist(filepath)),
@@ -167,6 +174,9 @@ pub fn version_file_size(repo: &LocalRepository, entry: &CommitEntry) -> Result<
     //     Ok(meta.len())
     // } else {
     if !version_path.exists() {
+        if version_file_exists(&version_path) {
+            return Ok(entry.num_bytes);
+        }
         return Err(OxenError::entry_does_not_exist(version_path));
     }
     let meta = util::fs::metadata(&version_path)?;
@@ -178,6 +188,574 @@ pub fn version_path(repo: &LocalRepository, entry: &CommitEntry) -> PathBuf {
     version_path_from_hash_and_file(&repo.path, entry.hash.clone(), entry.filename())
 }
 
+/// Where a version file lives when the repository stores it zstd compressed, `data.csv.zst` next
+/// to where `data.csv` would be
+pub fn compressed_version_path(version_path: &Path) -> PathBuf {
+    path_with_added_extension(version_path, COMPRESSED_VERSION_EXTENSION)
+}
+
+/// Where a version file lives when the repository is encrypted, `data.csv.enc` or
+/// `data.csv.zst.enc` if it is compressed too
+pub fn encrypted_version_path(version_path: &Path) -> PathBuf {
+    path_with_added_extension(version_path, ENCRYPTED_VERSION_EXTENSION)
+}
+
+fn path_with_added_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+// How a version file is stored
+struct StoredVersionFile {
+    path: PathBuf,
+    compressed: bool,
+    encrypted: bool,
+}
+
+fn stored_version_file(version_path: &Path) -> Option<StoredVersionFile> {
+    let compressed_path = compressed_version_path(version_path);
+    [
+        (version_path.to_path_buf(), false, false),
+        (compressed_path.clone(), true, false),
+        (encrypted_version_path(version_path), false, true),
+        (encrypted_version_path(&compressed_path), true, true),
+    ]
+    .into_iter()
+    .find(|(path, _, _)| path.exists())
+    .map(|(path, compressed, encrypted)| StoredVersionFile {
+        path,
+        compressed,
+        encrypted,
+    })
+}
+
+/// Whether the version file is stored, in any form
+pub fn version_file_exists(version_path: &Path) -> bool {
+    stored_version_file(version_path).is_some()
+}
+
+/// Whether the version file is stored encrypted
+pub fn version_file_is_encrypted(version_path: &Path) -> bool {
+    stored_version_file(version_path).is_some_and(|stored| stored.encrypted)
+}
+
+/// Stores `src` as the version file at `version_path`, compressed and encrypted if the
+/// repository is
+pub fn write_version_file(
+    repo: &LocalRepository,
+    src: &Path,
+    version_path: &Path,
+) -> Result<(), OxenError> {
+    write_version_file_with_compression(repo, src, version_path, repo.compresses_versions())
+}
+
+/// Stores `src` as the version file at `version_path` like `write_version_file`, with
+/// compression decided by the caller, as `.oxenattributes` can turn it on or off per path
+pub fn write_version_file_with_compression(
+    repo: &LocalRepository,
+    src: &Path,
+    version_path: &Path,
+    compress: bool,
+) -> Result<(), OxenError> {
+    let encrypt = encryption::is_encrypted(&repo.path);
+    if !compress && !encrypt {
+        return copy(src, version_path);
+    }
+
+    let mut reader =
+        BufReader::new(File::open(src).map_err(|err| OxenError::file_error(src, err))?);
+    write_version_file_from_reader(repo, &mut reader, version_path, compress)
+}
+
+/// Stores what `reader` reads as the version file at `version_path`, compressed and encrypted
+/// as it is written so plain contents never reach the disk of an encrypted repository
+pub fn write_version_file_from_reader(
+    repo: &LocalRepository,
+    reader: &mut impl Read,
+    version_path: &Path,
+    compress: bool,
+) -> Result<(), OxenError> {
+    let encrypt = encryption::is_encrypted(&repo.path);
+    let mut dst = version_path.to_path_buf();
+    if compress {
+        dst = compressed_version_path(&dst);
+    }
+    if encrypt {
+        dst = encrypted_version_path(&dst);
+    }
+    write_through_tmp_file(&dst, |file| {
+        if encrypt {
+            let mut writer = encryption::encrypt_writer(version_path, file)?;
+            if compress {
+                let mut encoder = zstd::Encoder::new(&mut writer, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+                std::io::copy(reader, &mut encoder)?;
+                encoder.finish()?;
+            } else {
+                std::io::copy(reader, &mut writer)?;
+            }
+            writer.finish()?;
+        } else if compress {
+            let mut encoder = zstd::Encoder::new(file, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+            std::io::copy(reader, &mut encoder)?;
+            encoder.finish()?;
+        } else {
+            std::io::copy(reader, file)?;
+        }
+        Ok(())
+    })
+}
+
+/// Encrypts a version file stored in plain text, as it is compressed or not, for a repository
+/// that was just encrypted. Returns false if it was already encrypted.
+pub fn encrypt_version_file(version_path: &Path) -> Result<bool, OxenError> {
+    let Some(stored) = stored_version_file(version_path) else {
+        return Err(OxenError::entry_does_not_exist(version_path));
+    };
+    if stored.encrypted {
+        return Ok(false);
+    }
+    let mut reader = BufReader::new(
+        File::open(&stored.path).map_err(|err| OxenError::file_error(&stored.path, err))?,
+    );
+    write_through_tmp_file(&encrypted_version_path(&stored.path), |file| {
+        let mut writer = encryption::encrypt_writer(version_path, file)?;
+        std::io::copy(&mut reader, &mut writer)?;
+        writer.finish()?;
+        Ok(())
+    })?;
+    remove_file(&stored.path)?;
+    Ok(true)
+}
+
+/// Opens the plain contents of a version file however it is stored
+pub fn open_version_file(version_path: &Path) -> Result<Box<dyn Read + Send>, OxenError> {
+    let Some(stored) = stored_version_file(version_path) else {
+        return Err(OxenError::entry_does_not_exist(version_path));
+    };
+    let file = File::open(&stored.path).map_err(|err| OxenError::file_error(&stored.path, err))?;
+    let mut reader: Box<dyn Read + Send> = Box::new(BufReader::new(file));
+    if stored.encrypted {
+        reader = Box::new(encryption::decrypt_reader(version_path, reader)?);
+    }
+    if stored.compressed {
+        reader = Box::new(zstd::Decoder::new(reader)?);
+    }
+    Ok(reader)
+}
+
+/// Opens the plain contents of a version file from `offset`. Plain and encrypted files seek to
+/// it, an encrypted file only decrypts from the segment the offset falls in. Compressed files
+/// still have to decompress everything before it.
+pub fn open_version_file_at(
+    version_path: &Path,
+    offset: u64,
+) -> Result<Box<dyn Read + Send>, OxenError> {
+    let Some(stored) = stored_version_file(version_path) else {
+        return Err(OxenError::entry_does_not_exist(version_path));
+    };
+    let mut file = BufReader::new(
+        File::open(&stored.path).map_err(|err| OxenError::file_error(&stored.path, err))?,
+    );
+    let mut skip = offset;
+    let mut reader: Box<dyn Read + Send> = if stored.encrypted {
+        let mut reader = encryption::decrypt_reader(version_path, file)?;
+        if !stored.compressed {
+            reader.seek_to(offset)?;
+            skip = 0;
+        }
+        Box::new(reader)
+    } else {
+        if !stored.compressed {
+            file.seek(std::io::SeekFrom::Start(offset))?;
+            skip = 0;
+        }
+        Box::new(file)
+    };
+    if stored.compressed {
+        reader = Box::new(zstd::Decoder::new(reader)?);
+    }
+    std::io::copy(&mut reader.by_ref().take(skip), &mut std::io::sink())?;
+    Ok(reader)
+}
+
+/// Mime type of a version file like `file_mime_type`, sniffed from the start of its plain
+/// contents so compressed and encrypted files don't have to be written out first
+pub fn version_file_mime_type(version_path: &Path) -> Result<String, OxenError> {
+    let mut head = Vec::with_capacity(8192);
+    open_version_file(version_path)?
+        .take(8192)
+        .read_to_end(&mut head)?;
+    if let Some(kind) = infer::get(&head) {
+        return Ok(String::from(kind.mime_type()));
+    }
+    if is_markdown(version_path) {
+        return Ok(String::from("text/markdown"));
+    }
+    // The last character may have been cut off by the 8kb we read
+    match std::str::from_utf8(&head) {
+        Err(err) if err.error_len().is_some() => Ok(String::from("application/octet-stream")),
+        _ => Ok(String::from("text/plain")),
+    }
+}
+
+/// Copies the plain contents of a version file to `dst`
+pub fn copy_version_file(version_path: &Path, dst: &Path) -> Result<(), OxenError> {
+    // Copying over a link would write straight into the file it links to
+    if is_linked_file(dst) {
+        remove_file(dst)?;
+    }
+    if version_path.exists() {
+        return copy(version_path, dst);
+    }
+    let mut reader = open_version_file(version_path)?;
+    write_through_tmp_file(dst, |file| {
+        std::io::copy(&mut reader, file)?;
+        Ok(())
+    })
+}
+
+/// Checks out a version file to `dst` in the working directory. Repositories with
+/// `link_checkout` set share the bytes of plain version files with the working directory
+/// instead of copying them: a reflink where the filesystem supports it, otherwise a hard
+/// link or a symlink to the version file, which is made read-only so it can't be edited
+/// in place. Everything else falls back to `copy_version_file`.
+pub fn checkout_version_file(
+    repo: &LocalRepository,
+    version_path: &Path,
+    dst: &Path,
+) -> Result<(), OxenError> {
+    if !repo.links_checkout() || !version_path.exists() {
+        return copy_version_file(version_path, dst);
+    }
+
+    if dst.exists() || is_linked_file(dst) {
+        remove_file(dst)?;
+    }
+    if reflink(version_path, dst).is_ok() {
+        return Ok(());
+    }
+
+    set_read_only(version_path)?;
+    if std::fs::hard_link(version_path, dst).is_ok() {
+        return Ok(());
+    }
+    let target = dunce::canonicalize(version_path)?;
+    if symlink(&target, dst).is_ok() {
+        return Ok(());
+    }
+
+    log::debug!("checkout_version_file could not link {dst:?}, copying it instead");
+    copy_version_file(version_path, dst)
+}
+
+/// Whether the file at `path` shares its bytes with another file through a hard link or is a
+/// symlink to a file, these are what `checkout_version_file` leaves in the working directory.
+/// Reflinks are not reported, writing to them never changes the file they were cloned from.
+pub fn is_linked_file(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return false;
+    };
+    if metadata.file_type().is_symlink() {
+        return path.is_file();
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.is_file() && metadata.nlink() > 1
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Replaces a linked file with a private, writable copy of its contents, so edits to it
+/// never reach the version file it was linked to. Files that are not links are left alone.
+pub fn break_link(path: &Path) -> Result<(), OxenError> {
+    if !is_linked_file(path) {
+        return Ok(());
+    }
+    log::debug!("break_link replacing {path:?} with a copy");
+    let mut reader =
+        BufReader::new(File::open(path).map_err(|err| OxenError::file_error(path, err))?);
+    write_through_tmp_file(path, |file| {
+        std::io::copy(&mut reader, file)?;
+        Ok(())
+    })
+}
+
+fn set_read_only(path: &Path) -> Result<(), OxenError> {
+    let mut permissions = std::fs::metadata(path)
+        .map_err(|err| OxenError::file_error(path, err))?
+        .permissions();
+    if !permissions.readonly() {
+        permissions.set_readonly(true);
+        std::fs::set_permissions(path, permissions)
+            .map_err(|err| OxenError::file_error(path, err))?;
+    }
+    Ok(())
+}
+
+/// Clones `src` into `dst` sharing the same extents on disk, only btrfs and xfs support it
+#[cfg(target_os = "linux")]
+fn reflink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // _IOW(0x94, 9, int) from linux/fs.h
+    const FICLONE: u32 = 0x40049409;
+
+    let src_file = File::open(src)?;
+    let dst_file = OpenOptions::new().write(true).create_new(true).open(dst)?;
+    // SAFETY: both descriptors are open for the duration of the call
+    let result = unsafe { libc::ioctl(dst_file.as_raw_fd(), FICLONE as _, src_file.as_raw_fd()) };
+    if result == -1 {
+        let err = std::io::Error::last_os_error();
+        drop(dst_file);
+        let _ = std::fs::remove_file(dst);
+        return Err(err);
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reflink(_src: &Path, _dst: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "reflinks are not supported on this platform",
+    ))
+}
+
+/// A path to the plain contents of a version file, see `readable_version_path`. Keep it around
+/// while reading the file, for encrypted repositories it is a private file removed on drop.
+#[derive(Debug)]
+pub struct ReadableVersionPath {
+    path: PathBuf,
+    is_temporary: bool,
+}
+
+impl ReadableVersionPath {
+    fn new(path: PathBuf) -> ReadableVersionPath {
+        ReadableVersionPath {
+            path,
+            is_temporary: false,
+        }
+    }
+}
+
+impl std::ops::Deref for ReadableVersionPath {
+    type Target = PathBuf;
+
+    fn deref(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for ReadableVersionPath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ReadableVersionPath {
+    fn drop(&mut self) {
+        if self.is_temporary && self.path.exists() {
+            if let Err(err) = std::fs::remove_file(&self.path) {
+                log::warn!("Could not remove decrypted file {:?}: {}", self.path, err);
+            }
+        }
+    }
+}
+
+/// A path to the plain contents of a version file, for readers that need a path on disk.
+/// Compressed versions are decompressed into `.oxen/.cache/versions`, which is safe to delete
+/// at any time and is trimmed back to `MAX_VERSIONS_CACHE_SIZE`, least recently read first.
+/// Encrypted versions are decrypted into a file in `.oxen/.cache/decrypted` that only its owner
+/// can read and that only lives as long as the returned path.
+pub fn readable_version_path(
+    repo: &LocalRepository,
+    version_path: &Path,
+) -> Result<ReadableVersionPath, OxenError> {
+    let stored = match stored_version_file(version_path) {
+        Some(stored) if stored.compressed || stored.encrypted => stored,
+        _ => return Ok(ReadableVersionPath::new(version_path.to_path_buf())),
+    };
+
+    if stored.encrypted {
+        let file_name = version_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let decrypted_dir = decrypted_dir(&repo.path)?;
+        let readable_path = ReadableVersionPath {
+            path: decrypted_dir.join(format!("{}-{}", uuid::Uuid::new_v4(), file_name)),
+            is_temporary: true,
+        };
+        let mut file = create_private_file(&readable_path)?;
+        std::io::copy(&mut open_version_file(version_path)?, &mut file)
+            .map_err(|err| OxenError::file_error(&readable_path.path, err))?;
+        return Ok(readable_path);
+    }
+
+    let versions_dir = oxen_hidden_dir(&repo.path).join(VERSIONS_DIR);
+    let relative_path = path_relative_to_dir(version_path, &versions_dir)?;
+    let cache_dir = oxen_hidden_dir(&repo.path).join(TMP_DIR).join(VERSIONS_DIR);
+    let cached_path = cache_dir.join(relative_path);
+    if cached_path.exists() {
+        // The modified time is when it was last read, so it is the last to be evicted
+        if let Err(err) = File::options()
+            .write(true)
+            .open(&cached_path)
+            .and_then(|file| file.set_modified(std::time::SystemTime::now()))
+        {
+            log::debug!("readable_version_path could not touch {cached_path:?}: {err}");
+        }
+    } else {
+        if let Some(parent) = cached_path.parent() {
+            create_dir_all(parent)?;
+        }
+        decompress_file(&stored.path, &cached_path)?;
+        trim_versions_cache(&cache_dir, &cached_path, MAX_VERSIONS_CACHE_SIZE);
+    }
+    Ok(ReadableVersionPath::new(cached_path))
+}
+
+// Decrypted files left behind by a process that did not get to remove them are this old at most
+const MAX_DECRYPTED_FILE_AGE: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// `.oxen/.cache/decrypted` of the repository, where the plain contents of encrypted files are
+/// kept while they are read. Only its owner can list it, and anything left over from a process
+/// that exited before removing its files is removed.
+pub fn decrypted_dir(repo_path: &Path) -> Result<PathBuf, OxenError> {
+    let dir = oxen_hidden_dir(repo_path).join(TMP_DIR).join(DECRYPTED_DIR);
+    if !dir.exists() {
+        create_dir_all(&dir)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))
+                .map_err(|err| OxenError::file_error(&dir, err))?;
+        }
+    }
+
+    let now = std::time::SystemTime::now();
+    for entry in std::fs::read_dir(&dir).map_err(|err| OxenError::file_error(&dir, err))? {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let is_stale = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| {
+                now.duration_since(modified)
+                    .is_ok_and(|age| age > MAX_DECRYPTED_FILE_AGE)
+            });
+        if is_stale {
+            let path = entry.path();
+            let removed = if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            if let Err(err) = removed {
+                log::warn!("Could not remove stale decrypted file {path:?}: {err}");
+            }
+        }
+    }
+    Ok(dir)
+}
+
+/// Creates a new file only its owner can read or write
+pub fn create_private_file(path: &Path) -> Result<File, OxenError> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .map_err(|err| OxenError::file_create_error(path, err))
+}
+
+// Files read this recently may still be about to be opened by the reader they were handed to
+const MIN_CACHED_VERSION_AGE: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Removes the least recently read files from the cache of decompressed versions until it is no
+/// bigger than `max_size`, other than `keep` and anything read in the last minute
+fn trim_versions_cache(cache_dir: &Path, keep: &Path, max_size: u64) {
+    let mut files: Vec<(std::time::SystemTime, u64, PathBuf)> = WalkDir::new(cache_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+        })
+        .collect();
+    let mut total_size: u64 = files.iter().map(|(_, size, _)| size).sum();
+    if total_size <= max_size {
+        return;
+    }
+
+    files.sort();
+    let now = std::time::SystemTime::now();
+    for (modified, size, path) in files {
+        if total_size <= max_size {
+            break;
+        }
+        let recently_read = now
+            .duration_since(modified)
+            .is_ok_and(|age| age < MIN_CACHED_VERSION_AGE);
+        if path == keep || recently_read {
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(_) => total_size -= size,
+            Err(err) => log::warn!("Could not evict cached version {path:?}: {err}"),
+        }
+    }
+}
+
+/// zstd compresses `src` into `dst`, readers never see a partially written `dst`
+pub fn compress_file(src: &Path, dst: &Path) -> Result<(), OxenError> {
+    let mut reader =
+        BufReader::new(File::open(src).map_err(|err| OxenError::file_error(src, err))?);
+    write_through_tmp_file(dst, |file| {
+        let mut encoder = zstd::Encoder::new(file, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+        std::io::copy(&mut reader, &mut encoder)?;
+        encoder.finish()?;
+        Ok(())
+    })
+}
+
+/// Decompresses the zstd compressed `src` into `dst`, readers never see a partially written `dst`
+pub fn decompress_file(src: &Path, dst: &Path) -> Result<(), OxenError> {
+    let file = File::open(src).map_err(|err| OxenError::file_error(src, err))?;
+    let mut decoder = zstd::Decoder::new(file)?;
+    write_through_tmp_file(dst, |file| {
+        std::io::copy(&mut decoder, file)?;
+        Ok(())
+    })
+}
+
+fn write_through_tmp_file(
+    dst: &Path,
+    write: impl FnOnce(&mut File) -> Result<(), OxenError>,
+) -> Result<(), OxenError> {
+    let tmp_path = dst.with_extension(format!("tmp.{}", uuid::Uuid::new_v4()));
+    let mut file =
+        File::create(&tmp_path).map_err(|err| OxenError::file_create_error(&tmp_path, err))?;
+    if let Err(err) = write(&mut file) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    rename(&tmp_path, dst)
+}
+
 pub fn version_path_for_entry(repo: &LocalRepository, entry: &Entry) -> PathBuf {
     match entry {
         Entry::CommitEntry(commit_entry) => version_path(repo, commit_entry),
@@ -678,6 +1256,26 @@ pub fn rename(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<(), OxenEr
     }
 }
 
+/// Create a symbolic link at `dst` pointing to `src`, `src` decides between a file or dir link on windows
+pub fn symlink(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<(), OxenError> {
+    let src = src.as_ref();
+    let dst = dst.as_ref();
+
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(src, dst);
+    #[cfg(windows)]
+    let result = if src.is_dir() {
+        std::os::windows::fs::symlink_dir(src, dst)
+    } else {
+        std::os::windows::fs::symlink_file(src, dst)
+    };
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(err) => Err(OxenError::file_error(dst, err)),
+    }
+}
+
 /// Wrapper around the std::fs::copy which makes the parent directory of the dst if it doesn't exist
 pub fn copy_mkdir(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<(), OxenError> {
     let src = src.as_ref();
@@ -1008,7 +1606,7 @@ pub fn recursive_eligible_files(dir: &Path) -> Vec<PathBuf> {
                 if !path.is_dir() {
                     files.push(path);
 
-                    if files.len() % mod_idx == 0 {
+                    if files.len().is_multiple_of(mod_idx) {
                         log::debug!("Got {} files", files.len());
                         mod_idx *= 2;
                     }
@@ -1238,24 +1836,12 @@ pub fn resize_cache_image(
         }
     };
 
-    let resized_img = if resize.width.is_some() && resize.height.is_some() {
-        img.resize_exact(
-            resize.width.unwrap(),
-            resize.height.unwrap(),
-            image::imageops::FilterType::Lanczos3,
-        )
-    } else if resize.width.is_some() {
-        img.resize(
-            resize.width.unwrap(),
-            resize.width.unwrap(),
-            image::imageops::FilterType::Lanczos3,
-        )
-    } else if resize.height.is_some() {
-        img.resize(
-            resize.height.unwrap(),
-            resize.height.unwrap(),
-            image::imageops::FilterType::Lanczos3,
-        )
+    let resized_img = if let (Some(width), Some(height)) = (resize.width, resize.height) {
+        img.resize_exact(width, height, image::imageops::FilterType::Lanczos3)
+    } else if let Some(width) = resize.width {
+        img.resize(width, width, image::imageops::FilterType::Lanczos3)
+    } else if let Some(height) = resize.height {
+        img.resize(height, height, image::imageops::FilterType::Lanczos3)
     } else {
         img
     };
@@ -1485,4 +2071,38 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn trim_versions_cache_evicts_least_recently_read() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let hour_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+            let old = dir.join("old");
+            let older = dir.join("older");
+            let new = dir.join("new");
+            for (path, modified) in [
+                (&old, hour_ago),
+                (&older, hour_ago - std::time::Duration::from_secs(60)),
+                (&new, std::time::SystemTime::now()),
+            ] {
+                util::fs::write_to_path(path, "0123456789")?;
+                std::fs::File::options()
+                    .write(true)
+                    .open(path)?
+                    .set_modified(modified)?;
+            }
+
+            // Over the limit by one file, the one read longest ago goes
+            super::trim_versions_cache(dir, &new, 20);
+            assert!(!older.exists());
+            assert!(old.exists());
+            assert!(new.exists());
+
+            // Files read in the last minute are kept even over the limit
+            super::trim_versions_cache(dir, &old, 0);
+            assert!(old.exists());
+            assert!(new.exists());
+
+            Ok(())
+        })
+    }
 }
@@ -1,13 +1,78 @@
 use crate::core::db::key_val::tree_db::TreeObjectChild;
 use crate::error::OxenError;
 use crate::model::{ContentHashable, NewCommit};
+use crate::util;
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::fmt;
 use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::path::Path;
+use std::str::FromStr;
+use utoipa::ToSchema;
 use xxhash_rust::xxh3::{xxh3_128, Xxh3};
 
+// Smaller version files are cheaper to read than to map
+const MMAP_THRESHOLD: u64 = 1024 * 1024;
+// Files are streamed through a buffer this size, one per thread so hashing many files in
+// parallel during add and status uses a bounded amount of memory
+const STREAM_BUFFER_SIZE: usize = 1024 * 1024;
+// BLAKE3 hashes files this large on all cores, a chunk of this size at a time
+const PARALLEL_HASH_THRESHOLD: u64 = 128 * 1024 * 1024;
+const PARALLEL_HASH_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+// Length of a BLAKE3 hash in hex, xxh3 hashes are at most 32
+const BLAKE3_HEX_LEN: usize = 64;
+
+/// How a repository hashes file contents, chosen at `oxen init` and kept in the repo config.
+/// xxh3 is fast, BLAKE3 is cryptographic for teams that need collision resistance.
+///
+/// Only file contents use it. Commit ids and the hashes of directory trees are always xxh3, they
+/// are computed from the content hashes so they still change whenever a file does.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Xxh3,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// The algorithm that produced a hash, so anyone holding a hash can verify it without
+    /// knowing which repository it came from
+    pub fn from_hash(hash: &str) -> HashAlgorithm {
+        if hash.len() == BLAKE3_HEX_LEN {
+            HashAlgorithm::Blake3
+        } else {
+            HashAlgorithm::Xxh3
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashAlgorithm::Xxh3 => write!(f, "xxh3"),
+            HashAlgorithm::Blake3 => write!(f, "blake3"),
+        }
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "xxh3" => Ok(HashAlgorithm::Xxh3),
+            "blake3" => Ok(HashAlgorithm::Blake3),
+            _ => Err(OxenError::basic_str(format!(
+                "Unknown hash algorithm '{s}', expected xxh3 or blake3"
+            ))),
+        }
+    }
+}
+
 pub fn hash_buffer(buffer: &[u8]) -> String {
     let val = xxh3_128(buffer);
     format!("{val:x}")
@@ -29,6 +94,7 @@ pub fn hash_buffer_128bit(buffer: &[u8]) -> u128 {
     xxh3_128(buffer)
 }
 
+/// The id of a commit, always xxh3 whatever the hash algorithm of the repository
 pub fn compute_commit_hash<E>(commit_data: &NewCommit, entries: &[E]) -> String
 where
     E: ContentHashable + std::fmt::Debug,
@@ -51,6 +117,7 @@ where
     format!("{val:x}")
 }
 
+// Always xxh3, like commit ids.
 // Need to hash on both path and hash - otherwise, vnode with same content under two different path hashes
 // (and many other examples) would overwrite node in objects dir since is hash-indexed
 pub fn compute_children_hash(children: &Vec<TreeObjectChild>) -> String {
@@ -67,14 +134,27 @@ pub fn compute_children_hash(children: &Vec<TreeObjectChild>) -> String {
     format!("{val:x}")
 }
 
-pub fn hash_file_contents_with_retry(path: &Path) -> Result<String, OxenError> {
+/// Hashes the uncompressed contents of a version file, whether or not it is stored compressed,
+/// with the same algorithm that produced `hash`
+pub fn hash_version_file_contents(version_path: &Path, hash: &str) -> Result<String, OxenError> {
+    if version_path.exists() {
+        return hash_mapped_file(version_path, HashAlgorithm::from_hash(hash));
+    }
+    let reader = util::fs::open_version_file(version_path)?;
+    hash_reader(reader, HashAlgorithm::from_hash(hash)).map_err(|_| {
+        OxenError::basic_str(format!("Could not read file for hashing {version_path:?}"))
+    })
+}
+
+/// Hashes a version file like `hash_version_file_contents`, retrying a few times
+pub fn hash_file_contents_with_retry(path: &Path, like: &str) -> Result<String, OxenError> {
     // Not sure why some tests were failing....the file didn't get written fast enough
     // So added this method to retry a few times
     let mut timeout = 1;
     let mut retries = 0;
     let total_retries = 5;
     loop {
-        match hash_file_contents(path) {
+        match hash_version_file_contents(path, like) {
             Ok(hash) => return Ok(hash),
             Err(err) => {
                 // sleep and try again
@@ -92,66 +172,136 @@ pub fn hash_file_contents_with_retry(path: &Path) -> Result<String, OxenError> {
 }
 
 pub fn hash_file_contents(path: &Path) -> Result<String, OxenError> {
-    // If file is < 1GB, one-shot hash for speed
-    // If file is > 1GB, stream hash to avoid memory overage issues
-    let file_size = std::fs::metadata(path)?.len();
+    hash_file_contents_with_algorithm(path, HashAlgorithm::Xxh3)
+}
 
-    if file_size < 1_000_000_000 {
-        hash_small_file_contents(path)
+/// Hashes a file with the same algorithm that produced `hash`, to check contents against it
+pub fn hash_file_contents_like(path: &Path, hash: &str) -> Result<String, OxenError> {
+    hash_file_contents_with_algorithm(path, HashAlgorithm::from_hash(hash))
+}
+
+pub fn hash_file_contents_with_algorithm(
+    path: &Path,
+    algorithm: HashAlgorithm,
+) -> Result<String, OxenError> {
+    let file = File::open(path).map_err(|err| {
+        OxenError::basic_str(format!(
+            "util::hasher::hash_file_contents Could not open file {path:?} {err:?}"
+        ))
+    })?;
+    let file_size = file.metadata()?.len();
+
+    let hash = if algorithm == HashAlgorithm::Blake3 && file_size >= PARALLEL_HASH_THRESHOLD {
+        hash_reader_parallel(file)
     } else {
-        hash_large_file_contents(path)
-    }
+        hash_reader(file, algorithm)
+    };
+    hash.map_err(|_| OxenError::basic_str(format!("Could not read file for hashing {path:?}")))
 }
 
-fn hash_small_file_contents(path: &Path) -> Result<String, OxenError> {
-    match File::open(path) {
-        Ok(file) => {
-            let mut reader = BufReader::new(file);
-            let mut buffer = Vec::new();
-            match reader.read_to_end(&mut buffer) {
-                Ok(_) => {
-                    let result = hash_buffer(&buffer);
-                    Ok(result)
-                }
-                Err(_) => {
-                    eprintln!("Could not read file for hashing {path:?}");
-                    Err(OxenError::basic_str("Could not read file for hashing"))
+// Version files are written once and never change after, so they are mapped instead of read. That
+// keeps memory flat when many large files are checked in parallel. Working files are always
+// streamed, a mapped file that is truncated while it is hashed kills the process with SIGBUS.
+fn hash_mapped_file(path: &Path, algorithm: HashAlgorithm) -> Result<String, OxenError> {
+    let file = File::open(path).map_err(|err| OxenError::file_error(path, err))?;
+    let file_size = file.metadata()?.len();
+    if file_size < MMAP_THRESHOLD {
+        return hash_file_contents_with_algorithm(path, algorithm);
+    }
+
+    match unsafe { Mmap::map(&file) } {
+        Ok(mmap) => Ok(match algorithm {
+            HashAlgorithm::Xxh3 => hash_buffer(&mmap),
+            HashAlgorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                if file_size >= PARALLEL_HASH_THRESHOLD {
+                    hasher.update_rayon(&mmap);
+                } else {
+                    hasher.update(&mmap);
                 }
+                hasher.finalize().to_hex().to_string()
             }
-        }
+        }),
         Err(err) => {
-            let err =
-                format!("util::hasher::hash_file_contents Could not open file {path:?} {err:?}");
-            Err(OxenError::basic_str(err))
+            log::debug!("Could not mmap {path:?} for hashing, streaming it: {err}");
+            hash_file_contents_with_algorithm(path, algorithm)
         }
     }
 }
 
-fn hash_large_file_contents(path: &Path) -> Result<String, OxenError> {
-    let file = File::open(path).map_err(|err| {
-        eprintln!("Could not open file {:?} due to {:?}", path, err);
-        OxenError::basic_str(format!("Could not open file {:?} due to {:?}", path, err))
-    })?;
+/// Hashes everything read through it, so a stream can be hashed while it is being stored
+pub struct HashingReader<R: Read> {
+    inner: R,
+    algorithm: HashAlgorithm,
+    xxh3: Xxh3,
+    blake3: blake3::Hasher,
+}
 
-    let mut reader = BufReader::new(file);
-    let mut hasher = Xxh3::new();
-    let mut buffer = [0; 4096];
+impl<R: Read> HashingReader<R> {
+    pub fn new(inner: R, algorithm: HashAlgorithm) -> HashingReader<R> {
+        HashingReader {
+            inner,
+            algorithm,
+            xxh3: Xxh3::new(),
+            blake3: blake3::Hasher::new(),
+        }
+    }
 
-    loop {
-        let count = reader.read(&mut buffer).map_err(|_| {
-            eprintln!("Could not read file for hashing {:?}", path);
-            OxenError::basic_str("Could not read file for hashing")
-        })?;
+    /// The hash of everything read so far
+    pub fn hash(&self) -> String {
+        match self.algorithm {
+            HashAlgorithm::Xxh3 => format!("{:x}", self.xxh3.digest128()),
+            HashAlgorithm::Blake3 => self.blake3.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        match self.algorithm {
+            HashAlgorithm::Xxh3 => self.xxh3.update(&buf[..count]),
+            HashAlgorithm::Blake3 => {
+                self.blake3.update(&buf[..count]);
+            }
+        }
+        Ok(count)
+    }
+}
+
+thread_local! {
+    static STREAM_BUFFER: RefCell<Vec<u8>> = RefCell::new(vec![0; STREAM_BUFFER_SIZE]);
+}
 
+fn hash_reader(reader: impl Read, algorithm: HashAlgorithm) -> std::io::Result<String> {
+    let mut reader = HashingReader::new(reader, algorithm);
+    STREAM_BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        while reader.read(&mut buffer)? > 0 {}
+        Ok(reader.hash())
+    })
+}
+
+// Hashes each chunk of a large file on all cores
+fn hash_reader_parallel(mut reader: impl Read) -> std::io::Result<String> {
+    let mut buffer = vec![0; PARALLEL_HASH_CHUNK_SIZE];
+    let mut hasher = blake3::Hasher::new();
+    loop {
+        // Fill the whole chunk so each update has enough work to split up
+        let mut count = 0;
+        while count < buffer.len() {
+            let read = reader.read(&mut buffer[count..])?;
+            if read == 0 {
+                break;
+            }
+            count += read;
+        }
         if count == 0 {
             break;
         }
-
-        hasher.update(&buffer[..count]);
+        hasher.update_rayon(&buffer[..count]);
     }
-
-    let result = hasher.digest128();
-    Ok(format!("{result:x}"))
+    Ok(hasher.finalize().to_hex().to_string())
 }
 
 pub fn hash_path<P: AsRef<Path>>(path: P) -> String {
@@ -161,3 +311,69 @@ pub fn hash_path<P: AsRef<Path>>(path: P) -> String {
 pub fn hash_pathbuf(path: &Path) -> String {
     hash_str(path.to_str().unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+    use crate::util::hasher::{self, HashAlgorithm};
+
+    #[test]
+    fn test_hash_file_contents_small_and_large() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let small = dir.join("small.txt");
+            util::fs::write_to_path(&small, "hello world")?;
+            assert_eq!(
+                hasher::hash_file_contents(&small)?,
+                hasher::hash_buffer(b"hello world")
+            );
+
+            // Bigger than the stream buffer
+            let contents = "0123456789abcdef".repeat(128 * 1024);
+            let large = dir.join("large.txt");
+            util::fs::write_to_path(&large, &contents)?;
+            assert_eq!(
+                hasher::hash_file_contents(&large)?,
+                hasher::hash_buffer(contents.as_bytes())
+            );
+            let blake3_hash =
+                hasher::hash_file_contents_with_algorithm(&large, HashAlgorithm::Blake3)?;
+            assert_eq!(
+                blake3_hash,
+                blake3::hash(contents.as_bytes()).to_hex().to_string()
+            );
+
+            // Hashes say which algorithm made them
+            assert_eq!(
+                HashAlgorithm::from_hash(&blake3_hash),
+                HashAlgorithm::Blake3
+            );
+            assert_eq!(
+                hasher::hash_file_contents_like(&large, &blake3_hash)?,
+                blake3_hash
+            );
+            // Version files are mapped instead of streamed, to the same hashes
+            assert_eq!(
+                hasher::hash_version_file_contents(&large, &blake3_hash)?,
+                blake3_hash
+            );
+            let xxh3_hash = hasher::hash_buffer(contents.as_bytes());
+            assert_eq!(
+                hasher::hash_version_file_contents(&large, &xxh3_hash)?,
+                xxh3_hash
+            );
+            assert_eq!(
+                HashAlgorithm::from_hash(&hasher::hash_file_contents(Path::new(&small))?),
+                HashAlgorithm::Xxh3
+            );
+
+            // Chunks hashed on all cores add up to the same hash
+            let reader = std::io::Cursor::new(contents.as_bytes());
+            assert_eq!(hasher::hash_reader_parallel(reader)?, blake3_hash);
+            Ok(())
+        })
+    }
+}
@@ -23,9 +23,11 @@ pub mod remote_staged_status;
 pub mod repository;
 pub mod revision;
 pub mod schema;
+pub mod search;
 pub mod sql_parse_error;
 pub mod status_message;
 pub mod tabular_diff_view;
+pub mod usage;
 pub mod version;
 pub mod workspaces;
 
@@ -44,6 +46,7 @@ pub use crate::view::json_data_frame_view::{
 };
 pub use crate::view::namespace::{ListNamespacesResponse, NamespaceResponse, NamespaceView};
 pub use crate::view::schema::ListSchemaResponse;
+pub use crate::view::usage::{StorageUsage, StorageUsageResponse};
 
 pub use crate::view::repository::{
     ListRepositoryResponse, RepositoryResolveResponse, RepositoryResponse, RepositoryView,
@@ -1,30 +1,31 @@
 use crate::model::{Commit, CommitStats};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::{Pagination, StatusMessage};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct CommitResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub commit: Commit,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct CommitStatsResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub stats: CommitStats,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct ListCommitResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub commits: Vec<Commit>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct CommitSyncStatusResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -32,7 +33,7 @@ pub struct CommitSyncStatusResponse {
     pub num_unsynced: usize,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct CommitTreeValidationResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -48,7 +49,7 @@ impl ListCommitResponse {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PaginatedCommits {
     #[serde(flatten)]
     pub status: StatusMessage,
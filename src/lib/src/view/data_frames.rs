@@ -1,30 +1,70 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use time::OffsetDateTime;
+
+use super::StatusMessage;
+
+use utoipa::ToSchema;
 
 pub mod columns;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct DataFramePayload {
     pub is_indexed: bool,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DataFrameColumnChange {
     pub operation: String,
     pub column_before: Option<ColumnChange>,
     pub column_after: Option<ColumnChange>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct ColumnChange {
     pub column_name: String,
     pub column_data_type: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+/// Counts of the rows touched by a bulk upsert or delete
+#[derive(Deserialize, Serialize, Debug, Clone, Default, ToSchema)]
+pub struct DataFrameBulkRowsSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub deleted: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct DataFrameBulkRowsResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub summary: DataFrameBulkRowsSummary,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DataFrameRowChange {
     pub row_id: String,
     pub operation: String,
     pub value: Value,
     pub new_value: Option<Value>,
 }
+
+/// One entry in the feed of edits made to a workspace data frame
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct DataFrameChange {
+    pub seq: i64,
+    pub row_id: String,
+    pub operation: String,
+    pub revision: Option<i64>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    pub value: Value,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct DataFrameChangesResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub changes: Vec<DataFrameChange>,
+    pub latest_seq: i64,
+}
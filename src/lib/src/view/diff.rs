@@ -3,17 +3,19 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::model::diff::diff_entry_status::DiffEntryStatus;
+use utoipa::ToSchema;
 
 use super::StatusMessage;
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct DirTreeDiffResponse {
     pub dirs: Vec<DirDiffTreeSummary>,
     #[serde(flatten)]
     pub status: StatusMessage,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DirDiffTreeSummary {
+    #[schema(value_type = String)]
     pub name: PathBuf,
     pub status: DiffEntryStatus,
     pub num_subdirs: usize,
@@ -21,8 +23,9 @@ pub struct DirDiffTreeSummary {
     pub children: Vec<DirDiffStatus>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct DirDiffStatus {
+    #[schema(value_type = String)]
     pub name: PathBuf,
     pub status: DiffEntryStatus,
 }
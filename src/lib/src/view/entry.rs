@@ -2,24 +2,25 @@ use std::path::PathBuf;
 
 use crate::model::{metadata::MetadataDir, Branch, CommitEntry, MetadataEntry, RemoteEntry};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::{Pagination, StatusMessage};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct EntryResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub entry: CommitEntry,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct RemoteEntryResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub entry: RemoteEntry,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct ResourceVersion {
     pub path: String,
     pub version: String,
@@ -34,7 +35,7 @@ impl ResourceVersion {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PaginatedEntries {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -45,14 +46,14 @@ pub struct PaginatedEntries {
     pub total_entries: usize,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PaginatedMetadataEntries {
     pub entries: Vec<MetadataEntry>,
     #[serde(flatten)]
     pub pagination: Pagination,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PaginatedMetadataEntriesResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -60,7 +61,7 @@ pub struct PaginatedMetadataEntriesResponse {
     pub entries: PaginatedMetadataEntries,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct PaginatedDirEntries {
     pub entries: Vec<MetadataEntry>,
     pub resource: Option<ResourceVersion>,
@@ -71,7 +72,7 @@ pub struct PaginatedDirEntries {
     pub total_entries: usize,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PaginatedDirEntriesResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -90,32 +91,33 @@ impl PaginatedDirEntriesResponse {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct BranchEntryVersion {
     pub branch: Branch,
     pub resource: ResourceVersion,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct CommitEntryVersion {
     pub commit: crate::model::Commit,
     pub resource: ResourceVersion,
     pub schema_hash: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PaginatedEntryVersions {
     pub versions: Vec<CommitEntryVersion>,
     #[serde(flatten)]
     pub pagination: Pagination,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PaginatedEntryVersionsResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     #[serde(flatten)]
     pub versions: PaginatedEntryVersions,
     pub branch: Branch,
+    #[schema(value_type = String)]
     pub path: PathBuf,
 }
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::model::MetadataEntry;
+use utoipa::ToSchema;
 
 use super::StatusMessage;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct MetadataEntryResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
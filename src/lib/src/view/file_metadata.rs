@@ -4,23 +4,26 @@ use serde::{Deserialize, Serialize};
 
 use super::{entry::ResourceVersion, StatusMessage};
 
-#[derive(Deserialize, Serialize, Debug)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct FileMetadata {
     pub size: u64,
     pub data_type: String,
     pub resource: ResourceVersion,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct FileMetadataResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub meta: FileMetadata,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct FilePathsResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
+    #[schema(value_type = Vec<String>)]
     pub paths: Vec<PathBuf>,
 }
@@ -2,14 +2,16 @@ use serde::{Deserialize, Serialize};
 
 use super::StatusMessage;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct HealthResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub disk_usage: DiskUsage,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct DiskUsage {
     pub total_gb: f64,
     pub used_gb: f64,
@@ -11,8 +11,9 @@ use crate::core::df::tabular;
 use crate::model::DataFrameSize;
 use crate::opts::PaginateOpts;
 use crate::{model::Schema, opts::DFOpts};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct JsonDataFrame {
     pub schema: Schema,
     pub view_schema: Schema,
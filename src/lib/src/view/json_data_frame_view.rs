@@ -20,14 +20,15 @@ use crate::opts::df_opts::DFOptsView;
 use crate::view::entry::ResourceVersion;
 use crate::view::Pagination;
 use crate::{model::Schema, opts::DFOpts};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct JsonDataFrameSource {
     pub schema: Schema,
     pub size: DataFrameSize,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct JsonDataFrameView {
     pub schema: Schema,
     pub size: DataFrameSize,
@@ -37,13 +38,13 @@ pub struct JsonDataFrameView {
     pub opts: DFOptsView,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct JsonDataFrameViews {
     pub source: JsonDataFrameSource,
     pub view: JsonDataFrameView,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct JsonDataFrameViewResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -53,7 +54,7 @@ pub struct JsonDataFrameViewResponse {
     pub derived_resource: Option<DerivedDFResource>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct WorkspaceJsonDataFrameViewResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -64,7 +65,7 @@ pub struct WorkspaceJsonDataFrameViewResponse {
     pub is_indexed: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct JsonDataFrameRowResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -75,9 +76,11 @@ pub struct JsonDataFrameRowResponse {
     pub derived_resource: Option<DerivedDFResource>,
     pub row_id: Option<String>,
     pub row_index: Option<usize>,
+    #[serde(default)]
+    pub row_revision: Option<i64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct JsonDataFrameColumnResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -87,7 +90,7 @@ pub struct JsonDataFrameColumnResponse {
     pub resource: Option<ResourceVersion>,
     pub derived_resource: Option<DerivedDFResource>,
 }
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DFResourceType {
     Compare,
@@ -95,7 +98,7 @@ pub enum DFResourceType {
     Query,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct DerivedDFResource {
     pub resource_id: String,
     pub path: String,
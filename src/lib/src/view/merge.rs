@@ -1,22 +1,23 @@
 use serde::{Deserialize, Serialize};
 
 use crate::model::Commit;
+use utoipa::ToSchema;
 
 use super::StatusMessage;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct MergeConflictFile {
     pub path: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct Mergeable {
     pub is_mergeable: bool,
     pub conflicts: Vec<MergeConflictFile>,
     pub commits: Vec<Commit>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct MergeableResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -24,7 +25,7 @@ pub struct MergeableResponse {
     pub mergeable: Mergeable,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct MergeSuccessResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -1,14 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+use utoipa::ToSchema;
+
 // This are the minimum fields we need to check if an oxen response is valid
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct OxenResponse {
     pub status: String,
     pub status_message: String,
     pub status_description: Option<String>,
     pub error: Option<ErrorResponse>,
 }
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct ErrorResponse {
     title: String,
     #[serde(rename = "type")]
@@ -9,19 +9,20 @@ use crate::{
     },
     util,
 };
+use utoipa::ToSchema;
 
 use super::{
     entry::ResourceVersion, json_data_frame_view::DerivedDFResource, JsonDataFrame,
     JsonDataFrameViews, PaginatedDirEntries, StatusMessage,
 };
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct DFIsEditableResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub is_editable: bool,
 }
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct DataFrameByResourceResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -32,14 +33,14 @@ pub struct DataFrameByResourceResponse {
     pub is_editable: bool,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct StagedFileModResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub modification: ModEntry,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct ListStagedFileModResponseRaw {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -51,27 +52,27 @@ pub struct ListStagedFileModResponseRaw {
     pub total_entries: usize,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct RemoteStagedStatus {
     pub added_dirs: SummarizedStagedDirStats,
     pub added_files: PaginatedDirEntries,
     pub modified_files: PaginatedDirEntries,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct RemoteStagedStatusResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub staged: RemoteStagedStatus,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct StagedDFModifications {
     pub added_rows: Option<JsonDataFrame>,
     // TODO: add other types
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct ListStagedFileModResponseDF {
     #[serde(flatten)]
     pub status: StatusMessage,
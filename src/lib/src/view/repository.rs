@@ -1,80 +1,85 @@
 use crate::model::{Commit, EntryDataType, RemoteRepository};
+use crate::util::hasher::HashAlgorithm;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::{DataTypeCount, StatusMessage};
 use std::str::FromStr;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct RepositoryView {
     pub namespace: String,
     pub name: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct RepositoryCreationView {
     pub namespace: String,
     pub name: String,
     pub latest_commit: Option<Commit>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct RepositoryDataTypesView {
     pub namespace: String,
     pub name: String,
     pub size: u64,
     pub data_types: Vec<DataTypeCount>,
+    // Servers that predate it leave it out, those repos hash with xxh3
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_algorithm: Option<HashAlgorithm>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct RepositoryResponse {
     pub status: String,
     pub status_message: String,
     pub repository: RepositoryView,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct RepositoryCreationResponse {
     pub status: String,
     pub status_message: String,
     pub repository: RepositoryCreationView,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct RepositoryDataTypesResponse {
     pub status: String,
     pub status_message: String,
     pub repository: RepositoryDataTypesView,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct ListRepositoryResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub repositories: Vec<RepositoryView>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct RepositoryResolveResponse {
     pub status: String,
     pub status_message: String,
     pub repository_api_url: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct RepositoryStatsResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub repository: RepositoryStatsView,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct DataTypeView {
     pub data_type: EntryDataType,
     pub data_size: u64,
     pub file_count: usize,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct RepositoryStatsView {
     pub data_size: u64,
     pub data_types: Vec<DataTypeView>,
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::model::ParsedResource;
+use utoipa::ToSchema;
 
 use super::StatusMessage;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct ParseResourceResponse {
     pub status: StatusMessage,
     pub resource: ParsedResource,
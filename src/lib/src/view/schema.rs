@@ -2,10 +2,11 @@ use serde::{Deserialize, Serialize};
 
 use super::entry::ResourceVersion;
 use crate::model::{Commit, Schema};
+use utoipa::ToSchema;
 
 use super::StatusMessage;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct SchemaWithPath {
     pub path: String,
     #[serde(flatten)]
@@ -18,7 +19,7 @@ impl SchemaWithPath {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct ListSchemaResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -28,7 +29,7 @@ pub struct ListSchemaResponse {
 }
 
 /* For getting schemas directly by hash - no path associated */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct SchemaResponse {
     pub status: StatusMessage,
     pub schema: Schema,
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Pagination, StatusMessage};
+use crate::model::SearchResult;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct SearchResultsResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub results: Vec<SearchResult>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct NearestNeighborsRequest {
+    pub column: String,
+    pub vector: Vec<f64>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct NearestNeighborsResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub results: Vec<SearchResult>,
+}
@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use super::StatusMessage;
 
-#[derive(Deserialize, Serialize, Debug)]
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct SQLParseError {
     #[serde(flatten)]
     pub status: StatusMessage,
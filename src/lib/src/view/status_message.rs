@@ -1,15 +1,16 @@
 use crate::constants::OXEN_VERSION;
 use crate::view;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct StatusMessage {
     pub status: String,
     pub status_message: String,
     pub oxen_version: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct StatusMessageDescription {
     pub status: String,
     pub status_message: String,
@@ -17,7 +18,7 @@ pub struct StatusMessageDescription {
     pub status_description: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct IsValidStatusMessage {
     pub status: String,
     pub status_message: String,
@@ -6,17 +6,18 @@ use crate::api;
 use crate::model::{CommitEntry, LocalRepository, Schema};
 use crate::opts::DFOpts;
 use crate::view::{JsonDataFrame, JsonDataFrameView};
+use utoipa::ToSchema;
 
 use crate::model::diff::tabular_diff_summary::{
     TabularDiffSummary, TabularDiffSummaryImpl, TabularDiffWrapper,
 };
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct TabularDiffView {
     pub tabular: TabularDiffViewImpl,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 pub struct TabularDiffViewImpl {
     #[serde(flatten)]
     pub summary: TabularDiffSummary,
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use super::StatusMessage;
+use crate::error::OxenError;
+use utoipa::ToSchema;
+
+/// Storage used by a repository or namespace, and its quota if one is set
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct StorageUsage {
+    pub bytes: u64,
+    pub files: u64,
+    pub max_bytes: Option<u64>,
+    pub max_files: Option<u64>,
+}
+
+impl StorageUsage {
+    /// Errors if adding `bytes` and `files` would go over the quota, `name` is what the quota
+    /// belongs to, ie "repository ox/data"
+    pub fn check(&self, name: &str, bytes: u64, files: u64) -> Result<(), OxenError> {
+        if let Some(max_bytes) = self.max_bytes {
+            if self.bytes.saturating_add(bytes) > max_bytes {
+                return Err(OxenError::quota_exceeded(format!(
+                    "Storage quota exceeded for {name}: adding {} to the {} in use would go over the {} limit",
+                    bytesize::ByteSize::b(bytes),
+                    bytesize::ByteSize::b(self.bytes),
+                    bytesize::ByteSize::b(max_bytes)
+                )));
+            }
+        }
+        if let Some(max_files) = self.max_files {
+            if self.files.saturating_add(files) > max_files {
+                return Err(OxenError::quota_exceeded(format!(
+                    "File quota exceeded for {name}: adding {files} files to the {} stored would go over the {max_files} file limit",
+                    self.files
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct StorageUsageResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub namespace: StorageUsage,
+    pub repository: Option<StorageUsage>,
+}
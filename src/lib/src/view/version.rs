@@ -1,6 +1,8 @@
 use super::StatusMessage;
 use serde::{Deserialize, Serialize};
-#[derive(Serialize, Deserialize, Debug)]
+
+use utoipa::ToSchema;
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct VersionResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
@@ -1,21 +1,35 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
 
 use time::OffsetDateTime;
 
 use super::StatusMessage;
-use crate::model::Commit;
+use crate::model::{Commit, Workspace};
+use utoipa::ToSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct NewWorkspace {
     pub workspace_id: String,
     pub branch_name: String,
     pub resource_path: Option<String>,
     pub entity_type: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub creator: Option<String>,
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RenameWorkspace {
+    pub name: String,
 }
 
 // HACK to get this to work with our hub where we don't keep parent_ids 🤦‍♂️
 // TODO: it should just be a Commit object
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct WorkspaceCommit {
     pub id: String,
     pub message: String,
@@ -39,22 +53,76 @@ impl From<WorkspaceCommit> for Commit {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct WorkspaceResponse {
     pub id: String,
     pub commit: WorkspaceCommit,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub branch_name: Option<String>,
+    #[serde(default)]
+    pub creator: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl From<Workspace> for WorkspaceResponse {
+    fn from(workspace: Workspace) -> Self {
+        WorkspaceResponse {
+            expires_at: workspace.expires_at(),
+            id: workspace.id,
+            commit: workspace.commit.into(),
+            name: workspace.name,
+            branch_name: workspace.branch_name,
+            creator: workspace.creator,
+            created_at: workspace.created_at,
+            updated_at: workspace.updated_at,
+        }
+    }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct WorkspaceResponseView {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub workspace: WorkspaceResponse,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct ListWorkspaceResponseView {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub workspaces: Vec<WorkspaceResponse>,
 }
+
+/// An edit that could not be replayed when moving a workspace onto a newer commit
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct WorkspaceRefreshConflict {
+    #[schema(value_type = String)]
+    pub path: PathBuf,
+    pub operation: String,
+    pub row_id: Option<String>,
+    pub column: Option<String>,
+    pub value: Option<Value>,
+    pub reason: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, ToSchema)]
+pub struct WorkspaceRefreshSummary {
+    pub replayed_rows: usize,
+    pub replayed_columns: usize,
+    pub conflicts: Vec<WorkspaceRefreshConflict>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct WorkspaceRefreshResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub workspace: WorkspaceResponse,
+    pub summary: WorkspaceRefreshSummary,
+}
@@ -0,0 +1,824 @@
+//! Repository maintenance run by operators with `oxen-server repo ...`, straight against the
+//! repositories in the sync dir
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use bytesize::ByteSize;
+use clap::{Arg, ArgMatches, Command};
+use liboxen::api;
+use liboxen::command::migrate::{
+    AddDirectoriesToCacheMigration, CacheDataFrameSizeMigration, CompressVersionFilesMigration,
+    CreateMerkleTreesMigration, Migrate, PropagateSchemasMigration, UpdateVersionFilesMigration,
+};
+use liboxen::constants::{
+    COMPRESSED_VERSION_EXTENSION, DATA_ARROW_FILE, ENCRYPTED_VERSION_EXTENSION, FILES_DIR, TMP_DIR,
+    VERSIONS_DIR, VERSION_FILE_NAME,
+};
+use liboxen::core::cache::commit_cacher;
+use liboxen::core::db::key_val::opts;
+use liboxen::core::encryption;
+use liboxen::error::OxenError;
+use liboxen::model::repository::repo_stats::RepoStats;
+use liboxen::model::{LocalRepository, RepoNew};
+use liboxen::util;
+use liboxen::view::StorageUsage;
+use rocksdb::DB;
+use serde::Serialize;
+
+// Version files this new are left alone by gc, they may belong to a push that has not
+// written its commit yet
+const GC_GRACE_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Debug)]
+pub struct RepoSummary {
+    pub namespace: String,
+    pub name: String,
+    pub bytes: u64,
+    pub files: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RepoAdminStats {
+    pub namespace: String,
+    pub name: String,
+    pub num_commits: usize,
+    pub num_branches: usize,
+    pub head_commit_id: Option<String>,
+    pub stats: RepoStats,
+    pub storage: StorageUsage,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub removed_files: u64,
+    pub removed_bytes: u64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FsckIssue {
+    pub commit_id: String,
+    pub path: String,
+    pub hash: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct FsckReport {
+    pub commits_checked: usize,
+    pub files_checked: usize,
+    /// Version files that no longer exist
+    pub missing: Vec<FsckIssue>,
+    /// Version files whose contents do not hash to the hash they are stored under
+    pub corrupt: Vec<FsckIssue>,
+}
+
+impl FsckReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+}
+
+/// Splits "namespace/name"
+pub fn parse_repo_id(repo_id: &str) -> Result<(String, String), OxenError> {
+    match repo_id.split_once('/') {
+        Some((namespace, name)) if !namespace.is_empty() && !name.is_empty() => {
+            Ok((namespace.to_string(), name.to_string()))
+        }
+        _ => Err(OxenError::basic_str(format!(
+            "Repository must be given as namespace/name, got '{repo_id}'"
+        ))),
+    }
+}
+
+pub fn get_repo(
+    sync_dir: &Path,
+    namespace: &str,
+    name: &str,
+) -> Result<LocalRepository, OxenError> {
+    api::local::repositories::get_by_namespace_and_name(sync_dir, namespace, name)?
+        .ok_or_else(|| OxenError::repo_not_found(RepoNew::from_namespace_name(namespace, name)))
+}
+
+/// Every repository on the server, or in one namespace
+pub fn list(sync_dir: &Path, namespace: Option<&str>) -> Result<Vec<RepoSummary>, OxenError> {
+    let namespaces = match namespace {
+        Some(namespace) => vec![namespace.to_string()],
+        None => api::local::repositories::list_namespaces(sync_dir)?,
+    };
+
+    let mut summaries = vec![];
+    for namespace in namespaces {
+        let namespace_dir = sync_dir.join(&namespace);
+        for repo in api::local::repositories::list_repos_in_namespace(&namespace_dir) {
+            let usage = api::local::quotas::repo_usage(&repo)?;
+            summaries.push(RepoSummary {
+                namespace: namespace.to_owned(),
+                name: repo.dirname(),
+                bytes: usage.bytes,
+                files: usage.files,
+            });
+        }
+    }
+    summaries.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    Ok(summaries)
+}
+
+pub fn stats(sync_dir: &Path, namespace: &str, name: &str) -> Result<RepoAdminStats, OxenError> {
+    let repo = get_repo(sync_dir, namespace, name)?;
+    let head_commit_id = api::local::commits::head_commit(&repo).ok().map(|c| c.id);
+    Ok(RepoAdminStats {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        num_commits: api::local::commits::list_all(&repo)?.len(),
+        num_branches: api::local::branches::list(&repo)?.len(),
+        head_commit_id,
+        stats: api::local::repositories::get_repo_stats(&repo),
+        storage: api::local::quotas::repo_usage(&repo)?,
+    })
+}
+
+pub fn delete(sync_dir: &Path, namespace: &str, name: &str) -> Result<(), OxenError> {
+    let repo = get_repo(sync_dir, namespace, name)?;
+    api::local::repositories::delete(repo)?;
+    Ok(())
+}
+
+pub fn rename(
+    sync_dir: &Path,
+    namespace: &str,
+    name: &str,
+    new_namespace: &str,
+    new_name: &str,
+) -> Result<LocalRepository, OxenError> {
+    if name == new_name {
+        api::local::repositories::transfer_namespace(sync_dir, name, namespace, new_namespace)
+    } else {
+        api::local::repositories::rename(sync_dir, namespace, name, new_namespace, new_name)
+    }
+}
+
+/// The server side migrations, by name
+pub fn migrations() -> Vec<Box<dyn Migrate>> {
+    vec![
+        Box::new(UpdateVersionFilesMigration),
+        Box::new(PropagateSchemasMigration),
+        Box::new(CacheDataFrameSizeMigration),
+        Box::new(CreateMerkleTreesMigration),
+        Box::new(AddDirectoriesToCacheMigration),
+        Box::new(CompressVersionFilesMigration),
+    ]
+}
+
+/// Runs a migration on one repository, or on every repository in the sync dir
+pub fn migrate(
+    sync_dir: &Path,
+    migration_name: &str,
+    repo: Option<&LocalRepository>,
+    down: bool,
+) -> Result<(), OxenError> {
+    let migration = migrations()
+        .into_iter()
+        .find(|m| m.name() == migration_name)
+        .ok_or_else(|| OxenError::basic_str(format!("Invalid migration: {migration_name}")))?;
+
+    let (path, all) = match repo {
+        Some(repo) => (repo.path.as_path(), false),
+        None => (sync_dir, true),
+    };
+    if down {
+        migration.down(path, all)
+    } else {
+        migration.up(path, all)
+    }
+}
+
+/// Recomputes the cached data of every commit, returns how many commits were processed
+pub fn reindex(repo: &LocalRepository) -> Result<usize, OxenError> {
+    let commits = api::local::commits::list_all(repo)?;
+    for commit in commits.iter() {
+        commit_cacher::run_all(repo, commit, true)?;
+    }
+    Ok(commits.len())
+}
+
+/// Hashes of every version file referenced by a commit, files and schemas
+fn referenced_hashes(repo: &LocalRepository) -> Result<HashSet<String>, OxenError> {
+    let mut hashes = HashSet::new();
+    for commit in api::local::commits::list_all(repo)? {
+        for entry in api::local::entries::list_all(repo, &commit)? {
+            hashes.insert(entry.hash);
+        }
+        for schema in api::local::schemas::list(repo, Some(&commit.id))?.into_values() {
+            hashes.insert(schema.hash);
+        }
+    }
+    Ok(hashes)
+}
+
+/// Deletes version files that no commit refers to, ie left behind by aborted pushes. Holds the
+/// repository lock so a commit cannot land between listing the references and deleting.
+pub fn gc(repo: &LocalRepository, dry_run: bool) -> Result<GcReport, OxenError> {
+    let mut lock_file = api::local::repositories::get_lock_file(repo)?;
+    let _mutex = api::local::repositories::get_exclusive_lock(&mut lock_file)?;
+
+    let referenced = referenced_hashes(repo)?;
+    let files_dir = util::fs::oxen_hidden_dir(&repo.path)
+        .join(VERSIONS_DIR)
+        .join(FILES_DIR);
+    let mut report = GcReport {
+        dry_run,
+        ..GcReport::default()
+    };
+    if !files_dir.exists() {
+        return Ok(report);
+    }
+
+    // Version dirs are files/{first two chars of the hash}/{rest of the hash}
+    for top_dir in std::fs::read_dir(&files_dir)? {
+        let top_dir = top_dir?.path();
+        if !top_dir.is_dir() {
+            continue;
+        }
+        for version_dir in std::fs::read_dir(&top_dir)? {
+            let version_dir = version_dir?.path();
+            let hash = format!("{}{}", file_name(&top_dir), file_name(&version_dir));
+            if referenced.contains(&hash) || is_recent(&version_dir) {
+                continue;
+            }
+
+            add_dir_usage(&version_dir, &mut report)?;
+            log::debug!("gc removing unreferenced version {:?}", version_dir);
+            if !dry_run {
+                util::fs::remove_dir_all(&version_dir)?;
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn add_dir_usage(dir: &Path, report: &mut GcReport) -> Result<(), OxenError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            add_dir_usage(&entry.path(), report)?;
+        } else {
+            report.removed_files += 1;
+            report.removed_bytes += metadata.len();
+        }
+    }
+    Ok(())
+}
+
+fn is_recent(path: &Path) -> bool {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_none_or(|age| age < GC_GRACE_PERIOD)
+}
+
+/// Checks that every version file referenced by a commit exists and has the contents it should
+pub fn fsck(repo: &LocalRepository) -> Result<FsckReport, OxenError> {
+    let mut report = FsckReport::default();
+    let mut checked: HashSet<String> = HashSet::new();
+    for commit in api::local::commits::list_all(repo)? {
+        report.commits_checked += 1;
+        for entry in api::local::entries::list_all(repo, &commit)? {
+            if !checked.insert(entry.hash.clone()) {
+                continue;
+            }
+            report.files_checked += 1;
+
+            let issue = FsckIssue {
+                commit_id: commit.id.to_owned(),
+                path: entry.path.to_string_lossy().to_string(),
+                hash: entry.hash.to_owned(),
+            };
+            let version_path = util::fs::version_path(repo, &entry);
+            if !util::fs::version_file_exists(&version_path) {
+                report.missing.push(issue);
+            } else if util::hasher::hash_version_file_contents(&version_path, &entry.hash)?
+                != entry.hash
+            {
+                report.corrupt.push(issue);
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct EncryptReport {
+    pub version_files: u64,
+    pub databases: u64,
+    pub values: u64,
+}
+
+/// Encrypts the repository with the key configured for it, what it already stores included.
+/// Running it again re-encrypts database values written before they were bound to their keys.
+/// A server that is already running remembers the repository as unencrypted, restart it after.
+pub fn encrypt(repo: &LocalRepository) -> Result<EncryptReport, OxenError> {
+    let mut lock_file = api::local::repositories::get_lock_file(repo)?;
+    let _mutex = api::local::repositories::get_exclusive_lock(&mut lock_file)?;
+
+    if !encryption::is_encrypted(&repo.path) {
+        encryption::enable(&repo.path)?;
+    }
+    let hidden_dir = util::fs::oxen_hidden_dir(&repo.path);
+    let versions_dir = hidden_dir.join(VERSIONS_DIR);
+    let mut report = EncryptReport::default();
+    encrypt_version_files(&versions_dir.join(FILES_DIR), &mut report)?;
+    encrypt_databases(&hidden_dir, &versions_dir, &mut report)?;
+
+    // Plain text copies of version files kept to read them faster
+    let cache_dir = hidden_dir.join(TMP_DIR).join(VERSIONS_DIR);
+    if cache_dir.exists() {
+        util::fs::remove_dir_all(&cache_dir)?;
+    }
+    Ok(report)
+}
+
+fn encrypt_version_files(dir: &Path, report: &mut EncryptReport) -> Result<(), OxenError> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            encrypt_version_files(&path, report)?;
+            continue;
+        }
+
+        let name = file_name(&path);
+        if name == DATA_ARROW_FILE {
+            util::fs::remove_file(&path)?;
+        } else if name.starts_with(VERSION_FILE_NAME)
+            && !util::fs::has_ext(&path, ENCRYPTED_VERSION_EXTENSION)
+        {
+            let version_path = if util::fs::has_ext(&path, COMPRESSED_VERSION_EXTENSION) {
+                path.with_extension("")
+            } else {
+                path
+            };
+            if util::fs::encrypt_version_file(&version_path)? {
+                report.version_files += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Every rocksdb database under .oxen has a CURRENT file
+fn encrypt_databases(
+    dir: &Path,
+    versions_dir: &Path,
+    report: &mut EncryptReport,
+) -> Result<(), OxenError> {
+    if dir.join("CURRENT").is_file() {
+        let db = DB::open(&opts::default(), dunce::simplified(dir))?;
+        report.databases += 1;
+        report.values += encryption::encrypt_db(&db)?;
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() && path != versions_dir {
+            encrypt_databases(&path, versions_dir, report)?;
+        }
+    }
+    Ok(())
+}
+
+fn repo_arg() -> Arg {
+    Arg::new("repo")
+        .help("Repository as namespace/name")
+        .required(true)
+        .action(clap::ArgAction::Set)
+}
+
+/// The `oxen-server repo` subcommand
+pub fn command() -> Command {
+    Command::new("repo")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .global(true)
+                .help("Print the result as JSON")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .subcommand(
+            Command::new("list")
+                .about("List repositories with their storage usage")
+                .arg(
+                    Arg::new("namespace")
+                        .long("namespace")
+                        .short('n')
+                        .help("Only list repositories in this namespace")
+                        .action(clap::ArgAction::Set),
+                ),
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Commits, branches, data types and storage of a repository")
+                .arg(repo_arg()),
+        )
+        .subcommand(
+            Command::new("delete")
+                .about("Delete a repository")
+                .arg(repo_arg()),
+        )
+        .subcommand(
+            Command::new("rename")
+                .about("Move a repository to a new namespace and/or name")
+                .arg(repo_arg())
+                .arg(
+                    Arg::new("new_repo")
+                        .help("New namespace/name")
+                        .required(true)
+                        .action(clap::ArgAction::Set),
+                ),
+        )
+        .subcommand(
+            Command::new("migrate")
+                .about("Run a migration on one repository, or on all of them")
+                .arg(
+                    Arg::new("migration")
+                        .help("Name of the migration")
+                        .required(true)
+                        .value_parser(
+                            migrations()
+                                .iter()
+                                .map(|m| m.name())
+                                .collect::<Vec<&'static str>>(),
+                        )
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("repo")
+                        .help("Repository as namespace/name, defaults to every repository")
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("down")
+                        .long("down")
+                        .help("Revert the migration instead of applying it")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("gc")
+                .about("Delete version files that no commit refers to")
+                .arg(repo_arg())
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Only report what would be deleted")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("reindex")
+                .about("Recompute the cached data of every commit")
+                .arg(repo_arg()),
+        )
+        .subcommand(
+            Command::new("fsck")
+                .about("Check that every committed file is stored intact")
+                .arg(repo_arg()),
+        )
+        .subcommand(
+            Command::new("encrypt")
+                .about("Encrypt the repository at rest with the key configured for it")
+                .arg(repo_arg()),
+        )
+}
+
+fn repo_id_from_args(args: &ArgMatches) -> Result<(String, String), OxenError> {
+    parse_repo_id(args.get_one::<String>("repo").expect("required"))
+}
+
+fn repo_from_args(sync_dir: &Path, args: &ArgMatches) -> Result<LocalRepository, OxenError> {
+    let (namespace, name) = repo_id_from_args(args)?;
+    get_repo(sync_dir, &namespace, &name)
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), OxenError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Runs `oxen-server repo ...`, printing the result as text or as JSON with `--json`
+pub fn run_command(sync_dir: &Path, matches: &ArgMatches) -> Result<(), OxenError> {
+    let json = matches.get_flag("json");
+    match matches.subcommand() {
+        Some(("list", args)) => {
+            let namespace = args.get_one::<String>("namespace").map(|n| n.as_str());
+            let repos = list(sync_dir, namespace)?;
+            if json {
+                return print_json(&repos);
+            }
+            for repo in repos {
+                println!(
+                    "{}/{}\t{}\t{} files",
+                    repo.namespace,
+                    repo.name,
+                    ByteSize::b(repo.bytes),
+                    repo.files
+                );
+            }
+        }
+        Some(("stats", args)) => {
+            let (namespace, name) = repo_id_from_args(args)?;
+            let stats = stats(sync_dir, &namespace, &name)?;
+            if json {
+                return print_json(&stats);
+            }
+            println!("{}/{}", stats.namespace, stats.name);
+            println!("  commits:  {}", stats.num_commits);
+            println!("  branches: {}", stats.num_branches);
+            if let Some(head) = &stats.head_commit_id {
+                println!("  head:     {head}");
+            }
+            println!(
+                "  storage:  {} in {} files",
+                ByteSize::b(stats.storage.bytes),
+                stats.storage.files
+            );
+            for data_type_stats in stats.stats.data_types.values() {
+                println!(
+                    "  {}: {} files, {}",
+                    data_type_stats.data_type,
+                    data_type_stats.file_count,
+                    ByteSize::b(data_type_stats.data_size)
+                );
+            }
+        }
+        Some(("delete", args)) => {
+            let (namespace, name) = repo_id_from_args(args)?;
+            delete(sync_dir, &namespace, &name)?;
+            if json {
+                return print_json(
+                    &serde_json::json!({ "deleted": format!("{namespace}/{name}") }),
+                );
+            }
+            println!("Deleted {namespace}/{name}");
+        }
+        Some(("rename", args)) => {
+            let (namespace, name) = repo_id_from_args(args)?;
+            let (new_namespace, new_name) =
+                parse_repo_id(args.get_one::<String>("new_repo").expect("required"))?;
+            let renamed = rename(sync_dir, &namespace, &name, &new_namespace, &new_name)?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "from": format!("{namespace}/{name}"),
+                    "to": format!("{new_namespace}/{new_name}"),
+                }));
+            }
+            println!("Moved to {:?}", renamed.path);
+        }
+        Some(("migrate", args)) => {
+            let migration = args.get_one::<String>("migration").expect("required");
+            let repo = match args.get_one::<String>("repo") {
+                Some(_) => Some(repo_from_args(sync_dir, args)?),
+                None => None,
+            };
+            let down = args.get_flag("down");
+            migrate(sync_dir, migration, repo.as_ref(), down)?;
+            if json {
+                return print_json(&serde_json::json!({
+                    "migration": migration,
+                    "direction": if down { "down" } else { "up" },
+                    "repo": args.get_one::<String>("repo"),
+                }));
+            }
+            println!("Ran migration {migration}");
+        }
+        Some(("gc", args)) => {
+            let repo = repo_from_args(sync_dir, args)?;
+            let report = gc(&repo, args.get_flag("dry-run"))?;
+            if json {
+                return print_json(&report);
+            }
+            let verb = if report.dry_run {
+                "Would remove"
+            } else {
+                "Removed"
+            };
+            println!(
+                "{verb} {} unreferenced files, {}",
+                report.removed_files,
+                ByteSize::b(report.removed_bytes)
+            );
+        }
+        Some(("reindex", args)) => {
+            let repo = repo_from_args(sync_dir, args)?;
+            let num_commits = reindex(&repo)?;
+            if json {
+                return print_json(&serde_json::json!({ "commits": num_commits }));
+            }
+            println!("Reindexed {num_commits} commits");
+        }
+        Some(("fsck", args)) => {
+            let repo = repo_from_args(sync_dir, args)?;
+            let report = fsck(&repo)?;
+            if json {
+                print_json(&report)?;
+            } else {
+                println!(
+                    "Checked {} files in {} commits",
+                    report.files_checked, report.commits_checked
+                );
+                for issue in report.missing.iter() {
+                    println!(
+                        "missing  {} {} ({})",
+                        issue.hash, issue.path, issue.commit_id
+                    );
+                }
+                for issue in report.corrupt.iter() {
+                    println!(
+                        "corrupt  {} {} ({})",
+                        issue.hash, issue.path, issue.commit_id
+                    );
+                }
+            }
+            if !report.is_ok() {
+                return Err(OxenError::basic_str(format!(
+                    "{} missing and {} corrupt version files",
+                    report.missing.len(),
+                    report.corrupt.len()
+                )));
+            }
+        }
+        Some(("encrypt", args)) => {
+            let repo = repo_from_args(sync_dir, args)?;
+            let report = encrypt(&repo)?;
+            if json {
+                return print_json(&report);
+            }
+            println!(
+                "Encrypted {} version files and {} values in {} databases",
+                report.version_files, report.values, report.databases
+            );
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use liboxen::api;
+    use liboxen::command;
+    use liboxen::constants::REFS_DIR;
+    use liboxen::core::db::key_val::opts;
+    use liboxen::core::encryption::{self, LocalKeyFile};
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::test;
+
+    #[test]
+    fn test_admin_list_stats_rename_and_delete() -> Result<(), OxenError> {
+        test::run_empty_sync_dir_test(|sync_dir| {
+            let repo = test::create_local_repo(sync_dir, "ox", "data")?;
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "hello world")?;
+            command::add(&repo, &hello_file)?;
+            command::commit(&repo, "adding hello")?;
+            test::create_local_repo(sync_dir, "bessie", "images")?;
+
+            let repos = super::list(sync_dir, None)?;
+            let names: Vec<String> = repos
+                .iter()
+                .map(|r| format!("{}/{}", r.namespace, r.name))
+                .collect();
+            assert_eq!(names, vec!["bessie/images", "ox/data"]);
+            assert_eq!(super::list(sync_dir, Some("ox"))?.len(), 1);
+
+            let stats = super::stats(sync_dir, "ox", "data")?;
+            assert_eq!(stats.num_commits, 2);
+            assert_eq!(stats.num_branches, 1);
+            assert!(stats.storage.files >= 1);
+
+            let renamed = super::rename(sync_dir, "ox", "data", "moo", "dataset")?;
+            assert_eq!(renamed.path, sync_dir.join("moo").join("dataset"));
+            assert!(super::get_repo(sync_dir, "ox", "data").is_err());
+
+            super::delete(sync_dir, "moo", "dataset")?;
+            assert!(!sync_dir.join("moo").join("dataset").exists());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_admin_fsck_and_gc() -> Result<(), OxenError> {
+        test::run_empty_sync_dir_test(|sync_dir| {
+            let repo = test::create_local_repo(sync_dir, "ox", "data")?;
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "hello world")?;
+            command::add(&repo, &hello_file)?;
+            let commit = command::commit(&repo, "adding hello")?;
+
+            let report = super::fsck(&repo)?;
+            assert!(report.is_ok());
+            assert_eq!(report.files_checked, 1);
+
+            // Referenced versions are kept, new unreferenced ones are in their grace period
+            let orphan = util::fs::version_dir_from_hash(&repo.path, String::from("abcdef0123"));
+            util::fs::create_dir_all(&orphan)?;
+            util::fs::write_to_path(orphan.join("data"), "orphan")?;
+            let report = super::gc(&repo, false)?;
+            assert_eq!(report.removed_files, 0);
+            assert!(orphan.exists());
+
+            // Break the version file
+            let entry = liboxen::api::local::entries::get_commit_entry(
+                &repo,
+                &commit,
+                std::path::Path::new("hello.txt"),
+            )?
+            .unwrap();
+            let version_path = util::fs::version_path(&repo, &entry);
+            util::fs::write_to_path(&version_path, "goodbye world")?;
+            let report = super::fsck(&repo)?;
+            assert_eq!(report.corrupt.len(), 1);
+            assert_eq!(report.corrupt[0].path, "hello.txt");
+
+            util::fs::remove_file(&version_path)?;
+            let report = super::fsck(&repo)?;
+            assert_eq!(report.missing.len(), 1);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_admin_encrypt() -> Result<(), OxenError> {
+        test::run_empty_sync_dir_test(|sync_dir| {
+            let repo = test::create_local_repo(sync_dir, "ox", "data")?;
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "hello world")?;
+            command::add(&repo, &hello_file)?;
+            let commit = command::commit(&repo, "adding hello")?;
+
+            let kms = LocalKeyFile::generate(&sync_dir.join("ox.key"))?;
+            encryption::register_kms(&repo.path, Arc::new(kms));
+            let report = super::encrypt(&repo)?;
+            assert_eq!(report.version_files, 1);
+            assert!(report.databases > 0);
+            assert!(report.values > 0);
+
+            let entry =
+                api::local::entries::get_commit_entry(&repo, &commit, Path::new("hello.txt"))?
+                    .unwrap();
+            let version_path = util::fs::version_path(&repo, &entry);
+            assert!(!version_path.exists());
+            assert!(util::fs::encrypted_version_path(&version_path).exists());
+
+            // Database keys are encrypted too
+            let refs_dir = util::fs::oxen_hidden_dir(&repo.path).join(REFS_DIR);
+            {
+                let refs_db = rocksdb::DB::open_for_read_only(&opts::default(), &refs_dir, false)?;
+                for item in refs_db.iterator(rocksdb::IteratorMode::Start) {
+                    let (key, value) = item?;
+                    assert!(encryption::is_encrypted_key(&key));
+                    assert!(!encryption::needs_encryption(&key, &value));
+                }
+            }
+
+            // Everything reads as before
+            assert_eq!(api::local::commits::head_commit(&repo)?.id, commit.id);
+            assert!(api::local::branches::get_by_name(&repo, "main")?.is_some());
+            let mut contents = String::new();
+            util::fs::open_version_file(&version_path)?.read_to_string(&mut contents)?;
+            assert_eq!(contents, "hello world");
+            assert!(super::fsck(&repo)?.is_ok());
+
+            // New commits are stored encrypted
+            let world_file = repo.path.join("world.txt");
+            util::fs::write_to_path(&world_file, "hello encrypted world")?;
+            command::add(&repo, &world_file)?;
+            let commit = command::commit(&repo, "adding world")?;
+            let entry =
+                api::local::entries::get_commit_entry(&repo, &commit, Path::new("world.txt"))?
+                    .unwrap();
+            let version_path = util::fs::version_path(&repo, &entry);
+            assert!(util::fs::version_file_is_encrypted(&version_path));
+            assert!(command::status(&repo)?.is_clean());
+            Ok(())
+        })
+    }
+}
@@ -5,11 +5,17 @@ use crate::queues::TaskQueue;
 pub struct OxenAppData {
     pub path: PathBuf,
     pub queue: TaskQueue,
+    // Seconds a workspace lives without being used when the client does not ask for a ttl
+    pub workspace_ttl_secs: Option<u64>,
 }
 
 impl OxenAppData {
     pub fn new(path: PathBuf, queue: TaskQueue) -> OxenAppData {
-        OxenAppData { path, queue }
+        OxenAppData {
+            path,
+            queue,
+            workspace_ttl_secs: None,
+        }
     }
 }
 
@@ -18,6 +24,7 @@ impl Clone for OxenAppData {
         OxenAppData {
             path: self.path.clone(),
             queue: self.queue.clone(),
+            workspace_ttl_secs: self.workspace_ttl_secs,
         }
     }
 }
@@ -0,0 +1,277 @@
+//! Append-only log of the requests that changed something on the server, kept as JSONL in
+//! `.oxen/audit.jsonl` under the sync dir. Entries are written by a single background thread so
+//! requests never wait on the file.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+
+use liboxen::api;
+use liboxen::error::OxenError;
+use liboxen::util;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+pub const AUDIT_LOG_FILENAME: &str = "audit.jsonl";
+
+// Size of the chunks the log is exported in
+const EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct AuditEntry {
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub request_id: Option<String>,
+    /// Email of the user the bearer token was issued to, None when no token was sent
+    pub user: Option<String>,
+    pub method: String,
+    /// Route pattern that matched, ie "/api/repos/{namespace}/{repo_name}/branches/{branch_name}"
+    pub route: String,
+    pub path: String,
+    pub namespace: Option<String>,
+    pub repo_name: Option<String>,
+    /// Branch named in the route
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Commit named in the route, otherwise the commit the branch pointed to right after the
+    /// request, so pushes, merges and workspace commits record what they produced
+    #[serde(default)]
+    pub commit_id: Option<String>,
+    pub status: u16,
+}
+
+/// Filters for reading back the log, everything is optional
+#[derive(Debug, Default)]
+pub struct AuditFilter {
+    pub namespace: Option<String>,
+    pub repo_name: Option<String>,
+    pub user: Option<String>,
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+impl AuditFilter {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        fn eq(filter: &Option<String>, value: &Option<String>) -> bool {
+            filter.is_none() || filter == value
+        }
+        eq(&self.namespace, &entry.namespace)
+            && eq(&self.repo_name, &entry.repo_name)
+            && eq(&self.user, &entry.user)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+    }
+}
+
+enum AuditMessage {
+    Append(PathBuf, Box<AuditEntry>),
+    Flush(Sender<()>),
+}
+
+pub fn log_path(sync_dir: &Path) -> PathBuf {
+    util::fs::oxen_hidden_dir(sync_dir).join(AUDIT_LOG_FILENAME)
+}
+
+pub fn now() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
+
+// The only writer of the log, so appends from concurrent requests never interleave
+fn writer() -> &'static Sender<AuditMessage> {
+    static WRITER: OnceLock<Sender<AuditMessage>> = OnceLock::new();
+    WRITER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for message in receiver {
+                match message {
+                    AuditMessage::Append(sync_dir, mut entry) => {
+                        fill_commit_id(&sync_dir, &mut entry);
+                        if let Err(err) = append(&sync_dir, &entry) {
+                            log::error!("could not write audit entry {:?}: {}", entry, err);
+                        }
+                    }
+                    AuditMessage::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+        sender
+    })
+}
+
+/// Queues the entry to be appended to the log
+pub fn record(sync_dir: &Path, entry: AuditEntry) {
+    let message = AuditMessage::Append(sync_dir.to_path_buf(), Box::new(entry));
+    if writer().send(message).is_err() {
+        log::error!("audit log writer is gone");
+    }
+}
+
+/// Waits until every entry recorded so far is in the log
+pub fn flush() {
+    let (done, wait) = mpsc::channel();
+    if writer().send(AuditMessage::Flush(done)).is_ok() {
+        let _ = wait.recv();
+    }
+}
+
+fn fill_commit_id(sync_dir: &Path, entry: &mut AuditEntry) {
+    if entry.commit_id.is_some() || !(200..300).contains(&entry.status) {
+        return;
+    }
+    let (Some(namespace), Some(repo_name), Some(branch)) =
+        (&entry.namespace, &entry.repo_name, &entry.branch)
+    else {
+        return;
+    };
+    let Ok(Some(repo)) =
+        api::local::repositories::get_by_namespace_and_name(sync_dir, namespace, repo_name)
+    else {
+        return;
+    };
+    if let Ok(Some(branch)) = api::local::branches::get_by_name(&repo, branch) {
+        entry.commit_id = Some(branch.commit_id);
+    }
+}
+
+fn append(sync_dir: &Path, entry: &AuditEntry) -> Result<(), OxenError> {
+    let path = log_path(sync_dir);
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    if let Some(parent) = path.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Reads the entries matching a filter a line at a time, oldest first
+pub struct AuditReader {
+    lines: Option<std::io::Lines<BufReader<File>>>,
+    filter: AuditFilter,
+}
+
+impl AuditReader {
+    pub fn new(sync_dir: &Path, filter: AuditFilter) -> Result<AuditReader, OxenError> {
+        let path = log_path(sync_dir);
+        let lines = if path.exists() {
+            Some(BufReader::new(File::open(&path)?).lines())
+        } else {
+            None
+        };
+        Ok(AuditReader { lines, filter })
+    }
+
+    /// The next matching entries as JSON lines, the same format they are stored in, about
+    /// 64kb at a time. None once the log has been read.
+    pub fn next_chunk(&mut self) -> Result<Option<String>, OxenError> {
+        let mut chunk = String::new();
+        while chunk.len() < EXPORT_CHUNK_SIZE {
+            let Some(entry) = self.next().transpose()? else {
+                break;
+            };
+            chunk.push_str(&serde_json::to_string(&entry)?);
+            chunk.push('\n');
+        }
+        Ok((!chunk.is_empty()).then_some(chunk))
+    }
+}
+
+impl Iterator for AuditReader {
+    type Item = Result<AuditEntry, OxenError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lines = self.lines.as_mut()?;
+        for line in lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err.into())),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            // A line cut short by a crash should not hide the rest of the log
+            match serde_json::from_str::<AuditEntry>(&line) {
+                Ok(entry) if self.filter.matches(&entry) => return Some(Ok(entry)),
+                Ok(_) => {}
+                Err(err) => log::warn!("skipping unreadable audit entry: {}", err),
+            }
+        }
+        None
+    }
+}
+
+/// Entries matching the filter, oldest first
+pub fn query(sync_dir: &Path, filter: AuditFilter) -> Result<Vec<AuditEntry>, OxenError> {
+    AuditReader::new(sync_dir, filter)?.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use liboxen::error::OxenError;
+
+    use super::{AuditEntry, AuditFilter, AuditReader};
+    use crate::test;
+
+    fn entry(timestamp: i64, user: &str, repo_name: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp,
+            request_id: None,
+            user: Some(user.to_string()),
+            method: String::from("DELETE"),
+            route: String::from("/api/repos/{namespace}/{repo_name}/branches/{branch_name}"),
+            path: format!("/api/repos/ox/{repo_name}/branches/dev"),
+            namespace: Some(String::from("ox")),
+            repo_name: Some(repo_name.to_string()),
+            branch: Some(String::from("dev")),
+            commit_id: None,
+            status: 200,
+        }
+    }
+
+    #[test]
+    fn test_audit_append_and_query() -> Result<(), OxenError> {
+        test::run_empty_sync_dir_test(|sync_dir| {
+            super::append(sync_dir, &entry(100, "ox@oxen.ai", "data"))?;
+            super::append(sync_dir, &entry(200, "bessie@oxen.ai", "data"))?;
+            super::append(sync_dir, &entry(300, "ox@oxen.ai", "images"))?;
+
+            let all = super::query(sync_dir, AuditFilter::default())?;
+            assert_eq!(all.len(), 3);
+            assert_eq!(all[0].timestamp, 100);
+
+            let by_user = super::query(
+                sync_dir,
+                AuditFilter {
+                    user: Some(String::from("ox@oxen.ai")),
+                    ..AuditFilter::default()
+                },
+            )?;
+            assert_eq!(by_user.len(), 2);
+
+            let by_repo_and_time = super::query(
+                sync_dir,
+                AuditFilter {
+                    namespace: Some(String::from("ox")),
+                    repo_name: Some(String::from("data")),
+                    since: Some(150),
+                    until: Some(250),
+                    ..AuditFilter::default()
+                },
+            )?;
+            assert_eq!(by_repo_and_time, vec![entry(200, "bessie@oxen.ai", "data")]);
+
+            let mut reader = AuditReader::new(sync_dir, AuditFilter::default())?;
+            let jsonl = reader.next_chunk()?.unwrap();
+            assert_eq!(jsonl.lines().count(), 3);
+            assert!(reader.next_chunk()?.is_none());
+            Ok(())
+        })
+    }
+}
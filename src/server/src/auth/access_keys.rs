@@ -15,6 +15,23 @@ pub struct JWTClaim {
     id: String,
     name: String,
     email: String,
+    // Admins can read the audit log and manage tasks, tokens issued before this are not admins
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    admin: bool,
+}
+
+impl JWTClaim {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.admin
+    }
 }
 
 pub struct AccessKeyManager {
@@ -71,10 +88,20 @@ impl AccessKeyManager {
     }
 
     pub fn create(&self, user: &User) -> Result<(User, String), OxenError> {
+        self.p_create(user, false)
+    }
+
+    /// Creates a token that can also use the admin endpoints
+    pub fn create_admin(&self, user: &User) -> Result<(User, String), OxenError> {
+        self.p_create(user, true)
+    }
+
+    fn p_create(&self, user: &User, admin: bool) -> Result<(User, String), OxenError> {
         let user_claims = JWTClaim {
             id: format!("{}", uuid::Uuid::new_v4()),
             name: user.name.to_owned(),
             email: user.email.to_owned(),
+            admin,
         };
 
         let secret_key = self.read_secret_key()?;
@@ -1,8 +1,16 @@
 use crate::app_data::OxenAppData;
 use crate::auth;
+use crate::errors::OxenHttpError;
+
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, OnceLock};
 
 use actix_web::dev::ServiceRequest;
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::{HttpMessage, HttpRequest};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
+use liboxen::util;
+use lru::LruCache;
 
 pub async fn validate(
     req: ServiceRequest,
@@ -24,3 +32,92 @@ pub async fn validate(
         )),
     }
 }
+
+/// Who is behind a request, worked out once and kept in the request extensions
+#[derive(Debug, Clone)]
+pub struct Identity {
+    /// Email of the user the bearer token was issued to, None without a valid token
+    pub user: Option<String>,
+    /// Whether the token was issued to an admin
+    pub admin: bool,
+    /// Hash of the access key when it is valid, the per key limits are tracked under it. Tokens
+    /// are hashed so they are not kept around in memory.
+    pub key: Option<String>,
+}
+
+// Valid tokens are never revoked, so once checked we remember the hash of a token and its email
+const MAX_CACHED_TOKENS: usize = 10_000;
+
+fn validated_tokens() -> &'static Mutex<LruCache<String, ValidatedUser>> {
+    static TOKENS: OnceLock<Mutex<LruCache<String, ValidatedUser>>> = OnceLock::new();
+    TOKENS.get_or_init(|| Mutex::new(LruCache::new(NonZeroUsize::new(MAX_CACHED_TOKENS).unwrap())))
+}
+
+/// The identity of the request, validating its bearer token the first time it is asked for.
+/// Tokens that are not valid count as no token, so making one up does not get a client a new
+/// allowance.
+pub fn identity(req: &HttpRequest) -> Identity {
+    if let Some(identity) = req.extensions().get::<Identity>() {
+        return identity.clone();
+    }
+
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let identity = match token.and_then(|token| validated_user(req, token)) {
+        Some((token_hash, user)) => Identity {
+            user: Some(user.email),
+            admin: user.admin,
+            key: Some(token_hash),
+        },
+        None => Identity {
+            user: None,
+            admin: false,
+            key: None,
+        },
+    };
+    req.extensions_mut().insert(identity.clone());
+    identity
+}
+
+/// Who a valid token was issued to
+#[derive(Debug, Clone)]
+struct ValidatedUser {
+    email: String,
+    admin: bool,
+}
+
+/// The hash of the token and the user it was issued to, if it is a token we issued
+fn validated_user(req: &HttpRequest, token: &str) -> Option<(String, ValidatedUser)> {
+    let token_hash = util::hasher::hash_str(token);
+    if let Some(user) = validated_tokens().lock().unwrap().get(&token_hash) {
+        return Some((token_hash, user.to_owned()));
+    }
+
+    let app_data = req.app_data::<OxenAppData>()?;
+    let keygen = auth::access_keys::AccessKeyManager::new_read_only(&app_data.path).ok()?;
+    if !keygen.token_is_valid(token) {
+        return None;
+    }
+    let claim = keygen.get_claim(token).ok()??;
+    let user = ValidatedUser {
+        email: claim.email().to_string(),
+        admin: claim.is_admin(),
+    };
+    validated_tokens()
+        .lock()
+        .unwrap()
+        .put(token_hash.to_owned(), user.to_owned());
+    Some((token_hash, user))
+}
+
+/// Admin endpoints need the token of an admin whether or not the server was started with auth
+pub fn require_admin(req: &HttpRequest) -> Result<(), OxenHttpError> {
+    if identity(req).admin {
+        Ok(())
+    } else {
+        Err(OxenHttpError::AdminRequired)
+    }
+}
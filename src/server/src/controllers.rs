@@ -1,4 +1,5 @@
 pub mod action;
+pub mod audit;
 pub mod branches;
 pub mod commits;
 pub mod data_frames;
@@ -9,11 +10,15 @@ pub mod file;
 pub mod health;
 pub mod merger;
 pub mod metadata;
+pub mod metrics;
 pub mod migrations;
 pub mod namespaces;
 pub mod not_found;
+pub mod openapi;
 pub mod repositories;
 pub mod revisions;
 pub mod schemas;
+pub mod search;
+pub mod tasks;
 pub mod version;
 pub mod workspaces;
@@ -2,9 +2,10 @@ use crate::{errors::OxenHttpError, params::path_param};
 use actix_web::{HttpRequest, HttpResponse};
 use liboxen::view::http::STATUS_SUCCESS;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize)]
-struct ActionResponse {
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ActionResponse {
     action: String,
     status: String,
     state: String,
@@ -0,0 +1,108 @@
+use crate::audit::{self, AuditEntry, AuditReader};
+use crate::auth::validator::require_admin;
+use crate::errors::OxenHttpError;
+use crate::params::{app_data, AuditQuery};
+
+use liboxen::error::OxenError;
+use liboxen::view::StatusMessage;
+use utoipa::ToSchema;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Serialize;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct AuditLogResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub entries: Vec<AuditEntry>,
+}
+
+/// Audit log entries, filtered with ?namespace=&repo_name=&user=&since=&until=. Admins only.
+pub async fn index(
+    req: HttpRequest,
+    query: web::Query<AuditQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    require_admin(&req)?;
+    let app_data = app_data(&req)?;
+    let sync_dir = app_data.path.clone();
+    let filter = query.to_filter();
+    let entries = web::block(move || audit::query(&sync_dir, filter)).await??;
+
+    Ok(HttpResponse::Ok().json(AuditLogResponse {
+        status: StatusMessage::resource_found(),
+        entries,
+    }))
+}
+
+/// Same as `index`, one JSON entry per line, streamed as the log is read. Admins only.
+pub async fn export(
+    req: HttpRequest,
+    query: web::Query<AuditQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    require_admin(&req)?;
+    let app_data = app_data(&req)?;
+    let reader = AuditReader::new(&app_data.path, query.to_filter())?;
+
+    let stream = futures_util::stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        let result = web::block(move || {
+            let chunk = reader.next_chunk();
+            (reader, chunk)
+        })
+        .await;
+        match result {
+            Ok((reader, Ok(Some(chunk)))) => Some((Ok(web::Bytes::from(chunk)), Some(reader))),
+            Ok((_, Ok(None))) => None,
+            Ok((_, Err(err))) => Some((Err(err), None)),
+            Err(err) => Some((Err(OxenError::basic_str(err.to_string())), None)),
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .insert_header((
+            "Content-Disposition",
+            "attachment; filename=\"audit.jsonl\"",
+        ))
+        .streaming(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::web;
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::controllers;
+    use crate::params::AuditQuery;
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_audit_requires_admin() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let query = || {
+            web::Query(AuditQuery {
+                namespace: None,
+                repo_name: None,
+                user: None,
+                since: None,
+                until: None,
+            })
+        };
+
+        let req = test::request(&sync_dir, test::init_queue(), "/api/audit");
+        let err = controllers::audit::index(req, query()).await.unwrap_err();
+        assert_eq!(
+            actix_web::ResponseError::status_code(&err),
+            StatusCode::FORBIDDEN
+        );
+
+        let req = test::admin_request(&sync_dir, test::init_queue(), "/api/audit")?;
+        let resp = controllers::audit::index(req, query()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        util::fs::remove_dir_all(sync_dir)?;
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ use liboxen::constants::VERSION_FILE_NAME;
 use liboxen::core::cache::cacher_status::CacherStatusType;
 use liboxen::core::cache::cachers::content_validator;
 use liboxen::core::cache::commit_cacher;
+use liboxen::core::encryption;
 use liboxen::core::index::CommitReader;
 use liboxen::core::index::CommitWriter;
 
@@ -20,6 +21,7 @@ use liboxen::model::commit::CommitWithBranchName;
 use liboxen::model::RepoNew;
 use liboxen::model::{Commit, LocalRepository};
 use liboxen::util;
+use liboxen::util::hasher::HashAlgorithm;
 use liboxen::view::branch::BranchName;
 use liboxen::view::commit::CommitSyncStatusResponse;
 use liboxen::view::commit::CommitTreeValidationResponse;
@@ -34,7 +36,7 @@ use os_path::OsPath;
 
 use crate::app_data::OxenAppData;
 use crate::errors::OxenHttpError;
-use crate::helpers::get_repo;
+use crate::helpers::{check_quota, get_repo, reserve_quota};
 use crate::params::parse_resource;
 use crate::params::PageNumQuery;
 use crate::params::{app_data, path_param};
@@ -416,6 +418,28 @@ pub async fn download_commits_db(
     Ok(HttpResponse::Ok().body(buffer))
 }
 
+// Clients cannot decrypt the databases of encrypted repositories, they get plain copies
+fn append_db_dir(
+    tar: &mut tar::Builder<GzEncoder<Vec<u8>>>,
+    repository: &LocalRepository,
+    tar_path: &Path,
+    dir: &Path,
+) -> Result<(), OxenError> {
+    if !encryption::is_encrypted(&repository.path) {
+        tar.append_dir_all(tar_path, dir)?;
+        return Ok(());
+    }
+
+    let plain_dir =
+        util::fs::decrypted_dir(&repository.path)?.join(uuid::Uuid::new_v4().to_string());
+    let result = encryption::copy_decrypted(dir, &plain_dir)
+        .and_then(|_| Ok(tar.append_dir_all(tar_path, &plain_dir)?));
+    if plain_dir.exists() {
+        util::fs::remove_dir_all(&plain_dir)?;
+    }
+    result
+}
+
 /// Take the commits db and compress it into a tarball buffer we can return
 fn compress_commits_db(repository: &LocalRepository) -> Result<Vec<u8>, OxenError> {
     // Tar and gzip the commit db directory
@@ -428,7 +452,7 @@ fn compress_commits_db(repository: &LocalRepository) -> Result<Vec<u8>, OxenErro
     let enc = GzEncoder::new(Vec::new(), Compression::default());
     let mut tar = tar::Builder::new(enc);
 
-    tar.append_dir_all(tar_subdir, commit_dir)?;
+    append_db_dir(&mut tar, repository, tar_subdir, &commit_dir)?;
     tar.finish()?;
 
     let buffer: Vec<u8> = tar.into_inner()?.finish()?;
@@ -446,7 +470,7 @@ fn compress_objects_db(repository: &LocalRepository) -> Result<Vec<u8>, OxenErro
     let enc = GzEncoder::new(Vec::new(), Compression::default());
     let mut tar = tar::Builder::new(enc);
 
-    tar.append_dir_all(tar_subdir, object_dir)?;
+    append_db_dir(&mut tar, repository, tar_subdir, &object_dir)?;
 
     tar.finish()?;
 
@@ -506,7 +530,7 @@ fn compress_commit(repository: &LocalRepository, commit: &Commit) -> Result<Vec<
         let full_path = commit_dir.join(dir);
         let tar_path = tar_subdir.join(dir);
         if full_path.exists() {
-            tar.append_dir_all(&tar_path, full_path)?;
+            append_db_dir(&mut tar, repository, &tar_path, &full_path)?;
         }
     }
 
@@ -634,7 +658,7 @@ pub async fn upload_chunk(
     let namespace = path_param(&req, "namespace")?;
     let name = path_param(&req, "repo_name")?;
     let commit_id = path_param(&req, "commit_id")?;
-    let repo = get_repo(&app_data.path, namespace, name)?;
+    let repo = get_repo(&app_data.path, &namespace, &name)?;
 
     let commit_reader = CommitReader::new(&repo)?;
 
@@ -649,6 +673,11 @@ pub async fn upload_chunk(
     let chunk_num = query.chunk_num;
     let total_chunks = query.total_chunks;
 
+    // Check the whole upload against the quota once, when it starts
+    if chunk_num == 0 {
+        reserve_quota(&app_data.path, &namespace, &name, size as u64, 1)?;
+    }
+
     log::debug!(
         "upload_chunk got chunk {chunk_num}/{total_chunks} of upload {id} of total size {size}"
     );
@@ -881,12 +910,19 @@ pub async fn can_push(
     let namespace = path_param(&req, "namespace")?;
     let name = path_param(&req, "repo_name")?;
     let client_head_id = path_param(&req, "commit_id")?;
-    let repo = get_repo(&app_data.path, namespace, name)?;
+    let repo = get_repo(&app_data.path, &namespace, &name)?;
     let server_head_id = query.get("remote_head").unwrap();
     let lca_id = query.get("lca").unwrap();
 
     log::debug!("in the new_can_push endpoint");
 
+    // The size of the push, so it can be turned away if it would go over quota. Older clients
+    // don't send it, their uploads are still checked as they are received.
+    if let (Some(bytes), Some(files)) = (size_param(&query, "bytes")?, size_param(&query, "files")?)
+    {
+        check_quota(&app_data.path, &namespace, &name, bytes, files)?;
+    }
+
     // Ensuring these commits exist on server
     let _server_head_commit = api::local::commits::get_by_id(&repo, server_head_id)?.ok_or(
         OxenError::revision_not_found(server_head_id.to_owned().into()),
@@ -924,6 +960,16 @@ pub async fn can_push(
     }
 }
 
+fn size_param(query: &HashMap<String, String>, name: &str) -> Result<Option<u64>, OxenHttpError> {
+    let Some(value) = query.get(name) else {
+        return Ok(None);
+    };
+    let size = value.parse::<u64>().map_err(|_| {
+        OxenHttpError::BadRequest(format!("Invalid query param `{name}`: {value}").into())
+    })?;
+    Ok(Some(size))
+}
+
 pub async fn root_commit(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
@@ -950,9 +996,7 @@ fn unpack_compressed_data(files: &[PathBuf], hidden_dir: &Path) -> Result<(), Ox
 
     // Unpack tarball to our hidden dir
     let mut archive = Archive::new(GzDecoder::new(&buffer[..]));
-    unpack_entry_tarball(hidden_dir, &mut archive);
-
-    Ok(())
+    unpack_entry_tarball(hidden_dir, &mut archive)
 }
 
 fn unpack_to_file(files: &[PathBuf], hidden_dir: &Path, filename: &str) -> Result<(), OxenError> {
@@ -974,6 +1018,24 @@ fn unpack_to_file(files: &[PathBuf], hidden_dir: &Path, filename: &str) -> Resul
         }
     }
 
+    // Compressed and encrypted as it is written if the repository is
+    if let Some(repo) = hidden_dir
+        .parent()
+        .and_then(|repo_path| LocalRepository::from_dir(repo_path).ok())
+    {
+        let mut reader: Box<dyn Read> = Box::new(std::io::empty());
+        for file in files.iter() {
+            let f = std::fs::File::open(file).map_err(|e| OxenError::file_open_error(file, e))?;
+            reader = Box::new(reader.chain(std::io::BufReader::new(f)));
+        }
+        return util::fs::write_version_file_from_reader(
+            &repo,
+            &mut reader,
+            &full_path,
+            repo.compresses_versions(),
+        );
+    }
+
     let mut outf = std::fs::File::create(&full_path)
         .map_err(|e| OxenError::file_create_error(&full_path, e))?;
 
@@ -997,6 +1059,7 @@ fn unpack_to_file(files: &[PathBuf], hidden_dir: &Path, filename: &str) -> Resul
             }
         }
     }
+    drop(outf);
     Ok(())
 }
 
@@ -1010,7 +1073,7 @@ pub async fn upload(
     let namespace = path_param(&req, "namespace")?;
     let name = path_param(&req, "repo_name")?;
     let commit_id = path_param(&req, "commit_id")?;
-    let repo = get_repo(&app_data.path, namespace, name)?;
+    let repo = get_repo(&app_data.path, &namespace, &name)?;
 
     // Match commit as either the provided commit id if it exists, or the head commit of the repo otherwise.
 
@@ -1034,6 +1097,7 @@ pub async fn upload(
         commit_id,
         ByteSize::b(total_size)
     );
+    reserve_quota(&app_data.path, &namespace, &name, total_size, 0)?;
 
     // Unpack in background thread because could take awhile
     // std::thread::spawn(move || {
@@ -1041,7 +1105,7 @@ pub async fn upload(
     log::debug!("Decompressing {} bytes to {:?}", bytes.len(), hidden_dir);
     // Unpack tarball to our hidden dir
     let mut archive = Archive::new(GzDecoder::new(&bytes[..]));
-    unpack_entry_tarball(&hidden_dir, &mut archive);
+    unpack_entry_tarball(&hidden_dir, &mut archive)?;
     // });
 
     Ok(HttpResponse::Ok().json(CommitResponse {
@@ -1153,7 +1217,7 @@ pub async fn complete_bulk(req: HttpRequest, body: String) -> Result<HttpRespons
                 commit.clone()
             );
 
-            queue.push(tasks::Task::PostPushComplete(task))
+            queue.push(tasks::Task::PostPushComplete(task))?;
         }
     }
 
@@ -1171,7 +1235,7 @@ pub async fn complete_bulk(req: HttpRequest, body: String) -> Result<HttpRespons
             repo: repo.clone(),
         };
 
-        queue.push(tasks::Task::PostPushComplete(task))
+        queue.push(tasks::Task::PostPushComplete(task))?;
     }
     Ok(HttpResponse::Ok().json(StatusMessage::resource_created()))
 }
@@ -1217,66 +1281,34 @@ fn unpack_tree_tarball(tmp_dir: &Path, archive: &mut Archive<GzDecoder<&[u8]>>)
     }
 }
 
-fn unpack_entry_tarball(hidden_dir: &Path, archive: &mut Archive<GzDecoder<&[u8]>>) {
+fn unpack_entry_tarball(
+    hidden_dir: &Path,
+    archive: &mut Archive<GzDecoder<&[u8]>>,
+) -> Result<(), OxenError> {
     // Unpack and compute HASH and save next to the file to speed up computation later
     // log::debug!("unpack_entry_tarball hidden_dir {:?}", hidden_dir);
-
-    match archive.entries() {
-        Ok(entries) => {
-            for file in entries {
-                if let Ok(mut file) = file {
-                    // Why hash now? To make sure everything synced properly
-                    // When we want to check is_synced, it is expensive to rehash everything
-                    // But since upload is network bound already, hashing here makes sense, and we will just
-                    // load the HASH file later
-                    let path = file.path().unwrap();
-                    let mut version_path = PathBuf::from(hidden_dir);
-                    // log::debug!("unpack_entry_tarball path {:?}", path);
-
-                    if path.starts_with("versions") && path.to_string_lossy().contains("files") {
-                        // Unpack version files to common name (data.extension) regardless of the name sent from the client
-                        let new_path = util::fs::replace_file_name_keep_extension(
-                            &path,
-                            VERSION_FILE_NAME.to_owned(),
-                        );
-                        version_path.push(new_path);
-                        // log::debug!("unpack_entry_tarball version_path {:?}", version_path);
-
-                        if let Some(parent) = version_path.parent() {
-                            if !parent.exists() {
-                                std::fs::create_dir_all(parent)
-                                    .expect("Could not create parent dir");
-                            }
-                        }
-                        file.unpack(&version_path).unwrap();
-                        // log::debug!("unpack_entry_tarball unpacked! {:?}", version_path);
-
-                        let hash_dir = version_path.parent().unwrap();
-                        let hash_file = hash_dir.join(HASH_FILE);
-                        let hash = util::hasher::hash_file_contents(&version_path).unwrap();
-                        util::fs::write_to_path(&hash_file, &hash)
-                            .expect("Could not write hash file");
-                    } else if path.starts_with(OBJECTS_DIR) {
-                        let temp_objects_dir = hidden_dir.join("tmp");
-                        if !temp_objects_dir.exists() {
-                            std::fs::create_dir_all(&temp_objects_dir).unwrap();
-                        }
-
-                        file.unpack_in(&temp_objects_dir).unwrap();
-                    } else {
-                        // For non-version files, use filename sent by client
-                        file.unpack_in(hidden_dir).unwrap();
-                    }
-                } else {
-                    log::error!("Could not unpack file in archive...");
-                }
-            }
+    let repo = hidden_dir
+        .parent()
+        .and_then(|repo_path| LocalRepository::from_dir(repo_path).ok());
+    // Databases are pushed in plain text, encrypted repositories unpack them aside and copy them
+    // in encrypted
+    let plain_dir = match &repo {
+        Some(repo) if encryption::is_encrypted(&repo.path) => {
+            let plain_dir =
+                util::fs::decrypted_dir(&repo.path)?.join(uuid::Uuid::new_v4().to_string());
+            // Entries are only unpacked into a directory that exists
+            util::fs::create_dir_all(&plain_dir)?;
+            Some(plain_dir)
         }
-        Err(err) => {
-            log::error!("Could not unpack entries from archive...");
-            log::error!("Err: {:?}", err);
+        _ => None,
+    };
+    let result = unpack_entries(hidden_dir, repo.as_ref(), plain_dir.as_deref(), archive);
+    if let Some(plain_dir) = &plain_dir {
+        if plain_dir.exists() {
+            util::fs::remove_dir_all(plain_dir)?;
         }
     }
+    result?;
     let tmp_objects_dir = hidden_dir.join("tmp").join(OBJECTS_DIR);
 
     // If this dir exists:
@@ -1284,13 +1316,92 @@ fn unpack_entry_tarball(hidden_dir: &Path, archive: &mut Archive<GzDecoder<&[u8]
         log::debug!("tmp objects dir exists, let's do some stuff");
 
         // merge_objects_dbs(hidden_dir.to_path_buf()).unwrap();
-        api::local::commits::merge_objects_dbs(&hidden_dir.join(OBJECTS_DIR), &tmp_objects_dir)
-            .unwrap();
+        api::local::commits::merge_objects_dbs(&hidden_dir.join(OBJECTS_DIR), &tmp_objects_dir)?;
 
-        std::fs::remove_dir_all(tmp_objects_dir.clone()).unwrap();
+        util::fs::remove_dir_all(&tmp_objects_dir)?;
     }
 
     log::debug!("Done decompressing.");
+    Ok(())
+}
+
+fn unpack_entries(
+    hidden_dir: &Path,
+    repo: Option<&LocalRepository>,
+    plain_dir: Option<&Path>,
+    archive: &mut Archive<GzDecoder<&[u8]>>,
+) -> Result<(), OxenError> {
+    for file in archive.entries()? {
+        let mut file = file?;
+        // Why hash now? To make sure everything synced properly
+        // When we want to check is_synced, it is expensive to rehash everything
+        // But since upload is network bound already, hashing here makes sense, and we will just
+        // load the HASH file later
+        let path = file.path()?.to_path_buf();
+        let mut version_path = PathBuf::from(hidden_dir);
+        // log::debug!("unpack_entry_tarball path {:?}", path);
+
+        if path.starts_with("versions") && path.to_string_lossy().contains("files") {
+            // Unpack version files to common name (data.extension) regardless of the name sent from the client
+            let new_path =
+                util::fs::replace_file_name_keep_extension(&path, VERSION_FILE_NAME.to_owned());
+            version_path.push(new_path);
+            // log::debug!("unpack_entry_tarball version_path {:?}", version_path);
+
+            if let Some(parent) = version_path.parent() {
+                util::fs::create_dir_all(parent)?;
+            }
+
+            let Some(hash_dir) = version_path.parent() else {
+                return Err(OxenError::file_has_no_parent(&version_path));
+            };
+            let hash_file = hash_dir.join(HASH_FILE);
+            // The version dirs are named after the client's hash, hash the way it did
+            let client_hash = format!(
+                "{}{}",
+                hash_dir
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .unwrap_or_default()
+                    .to_string_lossy(),
+                hash_dir.file_name().unwrap_or_default().to_string_lossy()
+            );
+            let hash = match repo {
+                // Compressed and encrypted as it is written if the repository is
+                Some(repo) => {
+                    let algorithm = HashAlgorithm::from_hash(&client_hash);
+                    let mut reader = util::hasher::HashingReader::new(&mut file, algorithm);
+                    util::fs::write_version_file_from_reader(
+                        repo,
+                        &mut reader,
+                        &version_path,
+                        repo.compresses_versions(),
+                    )?;
+                    reader.hash()
+                }
+                None => {
+                    file.unpack(&version_path)?;
+                    util::hasher::hash_file_contents_like(&version_path, &client_hash)?
+                }
+            };
+            util::fs::write_to_path(&hash_file, &hash)?;
+        } else if path.starts_with(OBJECTS_DIR) {
+            let temp_objects_dir = hidden_dir.join("tmp");
+            util::fs::create_dir_all(&temp_objects_dir)?;
+            file.unpack_in(&temp_objects_dir)?;
+        } else if let Some(plain_dir) = plain_dir {
+            file.unpack_in(plain_dir)?;
+        } else {
+            // For non-version files, use filename sent by client
+            file.unpack_in(hidden_dir)?;
+        }
+    }
+    if let Some(plain_dir) = plain_dir {
+        if plain_dir.exists() {
+            encryption::copy_encrypted(plain_dir, hidden_dir)?;
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1298,14 +1409,20 @@ mod tests {
 
     use actix_web::body::to_bytes;
     use actix_web::{web, App};
+    use flate2::read::GzDecoder;
     use flate2::write::GzEncoder;
     use flate2::Compression;
+    use std::io::Read;
     use std::path::Path;
+    use std::sync::Arc;
     use std::thread;
+    use tar::Archive;
 
     use liboxen::api;
     use liboxen::command;
-    use liboxen::constants::OXEN_HIDDEN_DIR;
+    use liboxen::constants::{HASH_FILE, OXEN_HIDDEN_DIR};
+    use liboxen::core::db::key_val::{kv_db, opts};
+    use liboxen::core::encryption::{self, LocalKeyFile};
     use liboxen::error::OxenError;
     use liboxen::util;
     use liboxen::view::{CommitResponse, ListCommitResponse};
@@ -1554,4 +1671,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_controllers_commits_unpack_into_encrypted_repo() -> Result<(), OxenError> {
+        test::run_empty_sync_dir_test(|sync_dir| {
+            let repo = test::create_local_repo(sync_dir, "ox", "secret")?;
+            let kms = LocalKeyFile::generate(&sync_dir.join("ox.key"))?;
+            encryption::register_kms(&repo.path, Arc::new(kms));
+            encryption::enable(&repo.path)?;
+
+            // What a client pushes, a version file and a plain database
+            let contents = "Hello encrypted";
+            let hash = util::hasher::hash_buffer(contents.as_bytes());
+            let version_dir = format!("versions/files/{}/{}", &hash[..2], &hash[2..]);
+            let client_dir = sync_dir.join("client");
+            let version_file = client_dir.join("hello.txt");
+            util::fs::create_dir_all(&client_dir)?;
+            util::fs::write_to_path(&version_file, contents)?;
+            let db_dir = client_dir.join("db");
+            {
+                let db = rocksdb::DB::open(&opts::default(), &db_dir)?;
+                db.put("hello.txt", "{}")?;
+            }
+
+            let enc = GzEncoder::new(Vec::new(), Compression::default());
+            let mut tar = tar::Builder::new(enc);
+            tar.append_path_with_name(&version_file, format!("{version_dir}/hello.txt"))?;
+            tar.append_dir_all("history/abc/db", &db_dir)?;
+            tar.finish()?;
+            let payload: Vec<u8> = tar.into_inner()?.finish()?;
+
+            let hidden_dir = util::fs::oxen_hidden_dir(&repo.path);
+            let mut archive = Archive::new(GzDecoder::new(&payload[..]));
+            super::unpack_entry_tarball(&hidden_dir, &mut archive)?;
+
+            // The version file is only stored encrypted, and hashed on the way
+            let version_path = hidden_dir.join(&version_dir).join("data.txt");
+            assert!(!version_path.exists());
+            assert!(util::fs::version_file_is_encrypted(&version_path));
+            let mut stored = String::new();
+            util::fs::open_version_file(&version_path)?.read_to_string(&mut stored)?;
+            assert_eq!(stored, contents);
+            let hash_file = hidden_dir.join(&version_dir).join(HASH_FILE);
+            assert_eq!(util::fs::read_from_path(hash_file)?, hash);
+
+            // The database is encrypted as it is received
+            let db_dir = hidden_dir.join("history").join("abc").join("db");
+            let db = rocksdb::DB::open_for_read_only(&opts::default(), &db_dir, false)?;
+            for item in db.iterator(rocksdb::IteratorMode::Start) {
+                let (key, value) = item?;
+                assert!(!encryption::needs_encryption(&key, &value));
+            }
+            assert_eq!(kv_db::get_bytes(&db, "hello.txt")?, Some(b"{}".to_vec()));
+
+            // Nothing plain is left behind
+            let decrypted_dir = util::fs::decrypted_dir(&repo.path)?;
+            assert!(util::fs::list_dirs_in_dir(&decrypted_dir)?.is_empty());
+            Ok(())
+        })
+    }
 }
@@ -1,5 +1,5 @@
 use crate::errors::OxenHttpError;
-use crate::helpers::get_repo;
+use crate::helpers::{accepted_df_encoding, df_json_response, df_response, get_repo};
 use crate::params::df_opts_query::{self, DFOptsQuery};
 use crate::params::{app_data, parse_resource, path_param};
 
@@ -8,6 +8,7 @@ use liboxen::constants;
 use liboxen::constants::DUCKDB_DF_TABLE_NAME;
 use liboxen::core::cache::cachers;
 use liboxen::core::db::data_frames::df_db;
+use liboxen::core::df::encoding::DataFrameEncoding;
 use liboxen::core::index::CommitEntryReader;
 use liboxen::error::{OxenError, PathBufError};
 use liboxen::model::{
@@ -59,6 +60,7 @@ pub async fn get(
 
     let mut opts = DFOpts::empty();
     opts = df_opts_query::parse_opts(&query, &mut opts);
+    let encoding = accepted_df_encoding(&req);
 
     let mut page_opts = PaginateOpts {
         page_num: constants::DEFAULT_PAGE_NUM,
@@ -91,6 +93,7 @@ pub async fn get(
         &page_opts,
         &data_frame_size,
         &entry,
+        encoding,
     );
     if let Ok(response) = handle_sql_result {
         return Ok(response);
@@ -101,9 +104,8 @@ pub async fn get(
         version: resource.version.to_string_lossy().into(),
     };
 
-    let height = if opts.slice.is_some() {
-        log::debug!("Scanning df with slice: {:?}", opts.slice);
-        let slice = opts.slice.as_ref().unwrap();
+    let height = if let Some(slice) = &opts.slice {
+        log::debug!("Scanning df with slice: {:?}", slice);
         let (_, end) = slice.split_once("..").unwrap();
 
         end.parse::<usize>().unwrap()
@@ -158,6 +160,15 @@ pub async fn get(
             };
 
             let total_pages = (view_height as f64 / page_opts.page_size as f64).ceil() as usize;
+            let pagination = Pagination {
+                page_number: page_opts.page_num,
+                page_size: page_opts.page_size,
+                total_pages,
+                total_entries: view_height,
+            };
+            if let Some(encoding) = encoding {
+                return Ok(df_response(df, encoding, Some(pagination)));
+            }
             log::debug!("here's our post-slice df {:?}", df);
 
             let mut slice_schema = Schema::from_polars(&df.schema());
@@ -178,12 +189,7 @@ pub async fn get(
                             width: df.width(),
                         },
                         data: JsonDataFrameView::json_from_df(&mut df),
-                        pagination: Pagination {
-                            page_number: page_opts.page_num,
-                            page_size: page_opts.page_size,
-                            total_pages,
-                            total_entries: view_height,
-                        },
+                        pagination,
                         opts: opts_view,
                     },
                 },
@@ -191,7 +197,7 @@ pub async fn get(
                 resource: Some(resource_version),
                 derived_resource: None,
             };
-            Ok(HttpResponse::Ok().json(response))
+            Ok(df_json_response(response))
         }
         Err(OxenError::SQLParseError(sql)) => {
             log::error!("Error parsing SQL: {}", sql);
@@ -204,6 +210,7 @@ pub async fn get(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_sql_querying(
     repo: &LocalRepository,
     commit: &Commit,
@@ -212,6 +219,7 @@ fn handle_sql_querying(
     page_opts: &PaginateOpts,
     data_frame_size: &DataFrameSize,
     entry: &CommitEntry,
+    encoding: Option<DataFrameEncoding>,
 ) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let mut workspace: Option<Workspace> = None;
 
@@ -256,6 +264,11 @@ fn handle_sql_querying(
 
         db_schema.update_metadata_from_schema(&og_schema);
 
+        if let Some(encoding) = encoding {
+            let df = tabular::paginate_df(df, page_opts)?;
+            return Ok(df_response(df, encoding, None));
+        }
+
         let json_df = format_sql_df_response(
             df,
             commit,
@@ -265,7 +278,7 @@ fn handle_sql_querying(
             &db_schema,
             data_frame_size,
         )?;
-        return Ok(HttpResponse::Ok().json(json_df));
+        return Ok(df_json_response(json_df));
     }
 
     Err(OxenHttpError::InternalServerError)
@@ -338,3 +351,68 @@ fn format_sql_df_response(
     };
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{web, App};
+
+    use liboxen::command;
+    use liboxen::core::df::encoding::{self, DataFrameEncoding};
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::app_data::OxenAppData;
+    use crate::controllers;
+    use crate::helpers::{PAGE_NUMBER_HEADER, TOTAL_ENTRIES_HEADER, TOTAL_PAGES_HEADER};
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_data_frames_get_arrow_page() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Name";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let path = repo.path.join("data.csv");
+        util::fs::write_to_path(&path, "id,label\n1,a\n2,b\n3,c\n4,d\n5,e\n")?;
+        command::add(&repo, &path)?;
+        command::commit(&repo, "adding data")?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/data_frames/{resource:.*}",
+                    web::get().to(controllers::data_frames::get),
+                ),
+        )
+        .await;
+        let uri = format!("/oxen/{namespace}/{name}/data_frames/main/data.csv?page=2&page_size=2");
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&uri)
+            .insert_header((header::ACCEPT, "application/vnd.apache.arrow.stream"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::VARY).unwrap(), "Accept");
+        assert_eq!(resp.headers().get(PAGE_NUMBER_HEADER).unwrap(), "2");
+        assert_eq!(resp.headers().get(TOTAL_PAGES_HEADER).unwrap(), "3");
+        assert_eq!(resp.headers().get(TOTAL_ENTRIES_HEADER).unwrap(), "5");
+        let bytes = actix_web::test::read_body(resp).await;
+        let df = encoding::decode(bytes.to_vec(), DataFrameEncoding::ArrowStream)?;
+        assert_eq!(df.height(), 2);
+
+        let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::VARY).unwrap(), "Accept");
+
+        util::fs::remove_dir_all(sync_dir)?;
+        Ok(())
+    }
+}
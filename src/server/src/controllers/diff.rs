@@ -14,7 +14,7 @@ use liboxen::model::diff::generic_diff_summary::GenericDiffSummary;
 use liboxen::model::diff::DiffResult;
 use liboxen::model::{Commit, DataFrameSize, LocalRepository, Schema};
 use liboxen::opts::df_opts::DFOptsView;
-use liboxen::opts::DFOpts;
+use liboxen::opts::{DFOpts, PaginateOpts};
 use liboxen::view::compare::{
     CompareCommits, CompareCommitsResponse, CompareDupes, CompareEntries, CompareEntryResponse,
     CompareTabular, CompareTabularResponse,
@@ -28,10 +28,10 @@ use liboxen::view::{
 };
 use liboxen::{api, constants, util};
 
-use crate::helpers::get_repo;
+use crate::helpers::{accepted_df_encoding, df_json_response, df_response, get_repo};
 use crate::params::{
     app_data, df_opts_query, parse_base_head, path_param, resolve_base_head, DFOptsQuery,
-    PageNumQuery,
+    DiffEntriesQuery, PageNumQuery,
 };
 
 pub async fn commits(
@@ -83,7 +83,7 @@ pub async fn commits(
 // TODO: Deprecate
 pub async fn entries(
     req: HttpRequest,
-    query: web::Query<PageNumQuery>,
+    query: web::Query<DiffEntriesQuery>,
 ) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
@@ -111,6 +111,7 @@ pub async fn entries(
         PathBuf::from(""),
         page,
         page_size,
+        query.renames.unwrap_or(false),
     )?;
 
     let entries = entries_diff.entries;
@@ -161,7 +162,7 @@ pub async fn dir_tree(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenH
 
 pub async fn dir_entries(
     req: HttpRequest,
-    query: web::Query<PageNumQuery>,
+    query: web::Query<DiffEntriesQuery>,
 ) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
@@ -191,6 +192,7 @@ pub async fn dir_entries(
         &head_commit,
         page,
         page_size,
+        query.renames.unwrap_or(false),
     )?;
 
     // For this view, exclude anything that isn't a direct child of the directory in question
@@ -490,6 +492,7 @@ pub async fn update_df_diff(
 
 pub async fn get_df_diff(
     req: HttpRequest,
+    query: web::Query<PageNumQuery>,
     body: String,
 ) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
@@ -536,6 +539,23 @@ pub async fn get_df_diff(
 
         match diff {
             DiffResult::Tabular(diff) => {
+                if let Some(encoding) = accepted_df_encoding(&req) {
+                    let page_opts = PaginateOpts {
+                        page_num: query.page.unwrap_or(constants::DEFAULT_PAGE_NUM),
+                        page_size: query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE),
+                    };
+                    let total_entries = diff.contents.height();
+                    let pagination = Pagination {
+                        page_number: page_opts.page_num,
+                        page_size: page_opts.page_size,
+                        total_pages: (total_entries as f64 / page_opts.page_size as f64).ceil()
+                            as usize,
+                        total_entries,
+                    };
+                    let df = tabular::paginate_df(diff.contents, &page_opts)?;
+                    return Ok(df_response(df, encoding, Some(pagination)));
+                }
+
                 if diff.summary.dupes.left > 0 || diff.summary.dupes.right > 0 {
                     let cdupes = CompareDupes::from_tabular_diff_dupes(&diff.summary.dupes);
                     messages.push(cdupes.to_message());
@@ -546,7 +566,7 @@ pub async fn get_df_diff(
                     dfs: CompareTabular::from(diff),
                     messages,
                 };
-                Ok(HttpResponse::Ok().json(view))
+                Ok(df_json_response(view))
             }
             _ => Err(OxenHttpError::NotFound),
         }
@@ -620,6 +640,15 @@ pub async fn get_derived_df(
             let mut paginated_df = tabular::transform(view_df, paginate_opts)?;
 
             let total_pages = (view_height as f64 / page_size as f64).ceil() as usize;
+            let pagination = Pagination {
+                page_number: page,
+                page_size,
+                total_pages,
+                total_entries: view_height,
+            };
+            if let Some(encoding) = accepted_df_encoding(&req) {
+                return Ok(df_response(paginated_df, encoding, Some(pagination)));
+            }
             let source_size = DataFrameSize {
                 width: full_width,
                 height: full_height,
@@ -652,14 +681,7 @@ pub async fn get_derived_df(
                     width: view_width,
                     height: view_height,
                 },
-                pagination: {
-                    Pagination {
-                        page_number: page,
-                        page_size,
-                        total_pages,
-                        total_entries: view_height,
-                    }
-                },
+                pagination,
                 opts: opts_view,
             };
 
@@ -680,7 +702,7 @@ pub async fn get_derived_df(
                 derived_resource: Some(derived_resource),
             };
 
-            Ok(HttpResponse::Ok().json(response))
+            Ok(df_json_response(response))
         }
         Err(OxenError::SQLParseError(sql)) => {
             log::error!("Error parsing SQL: {}", sql);
@@ -1,5 +1,5 @@
 use crate::errors::OxenHttpError;
-use crate::helpers::get_repo;
+use crate::helpers::{chunk_response, content_response, get_repo, Content};
 use crate::params::{app_data, parse_resource, path_param, PageNumQuery};
 
 use liboxen::constants::AVG_CHUNK_SIZE;
@@ -74,8 +74,16 @@ pub async fn download_data_from_version_paths(
             constants::VERSION_FILE_NAME.to_string(),
         );
 
+        let path_to_read = util::fs::readable_version_path(&repo, &path_to_read)?;
         if path_to_read.exists() {
-            tar.append_path_with_name(path_to_read, content_file)
+            // No timestamps, so the same list always builds the same tarball and ranges of it
+            // line up across requests
+            let mut file = File::open(&*path_to_read)?;
+            let mut header = tar::Header::new_gnu();
+            header.set_size(file.metadata()?.len());
+            header.set_mode(0o644);
+            header.set_mtime(0);
+            tar.append_data(&mut header, content_file, &mut file)
                 .unwrap();
         } else {
             log::error!(
@@ -88,7 +96,9 @@ pub async fn download_data_from_version_paths(
 
     tar.finish().unwrap();
     let buffer: Vec<u8> = tar.into_inner().unwrap().finish().unwrap();
-    Ok(HttpResponse::Ok().body(buffer))
+    // Version files never change, so the list identifies the tarball
+    let hash = util::hasher::hash_buffer(line_delimited_files.as_bytes());
+    content_response(&req, Content::Bytes(buffer.into()), &hash).await
 }
 
 /// Download a chunk of a larger file
@@ -110,16 +120,22 @@ pub async fn download_chunk(
         resource
     );
 
-    let version_path = util::fs::version_path_for_commit_id(&repo, &commit.id, &resource.path)?;
+    let entry = api::local::entries::get_commit_entry(&repo, &commit, &resource.path)?
+        .ok_or(OxenError::path_does_not_exist(&resource.path))?;
+    let version_path = util::fs::version_path(&repo, &entry);
+    let arrow_path = version_path
+        .parent()
+        .unwrap()
+        .join(constants::DATA_ARROW_FILE);
+    let content = if arrow_path.exists() {
+        Content::File(arrow_path)
+    } else {
+        Content::Version(version_path, entry.num_bytes)
+    };
     let chunk_start: u64 = query.chunk_start.unwrap_or(0);
     let chunk_size: u64 = query.chunk_size.unwrap_or(AVG_CHUNK_SIZE);
 
-    let mut f = File::open(version_path).unwrap();
-    f.seek(std::io::SeekFrom::Start(chunk_start)).unwrap();
-    let mut buffer = vec![0u8; chunk_size as usize];
-    f.read_exact(&mut buffer).unwrap();
-
-    Ok(HttpResponse::Ok().body(buffer))
+    chunk_response(&req, content, &entry.hash, chunk_start, chunk_size).await
 }
 
 pub async fn list_tabular(
@@ -1,5 +1,5 @@
 use crate::errors::OxenHttpError;
-use crate::helpers::get_repo;
+use crate::helpers::{content_response, get_repo, Content};
 use crate::params::{app_data, parse_resource, path_param};
 
 use liboxen::core::index::object_db_reader::get_object_reader;
@@ -8,10 +8,9 @@ use liboxen::model::metadata::metadata_image::ImgResize;
 use liboxen::model::CommitEntry;
 use liboxen::util;
 
-use actix_files::NamedFile;
 use actix_web::{http::header, web, HttpRequest, HttpResponse};
 
-/// Download file content
+/// Download file content, supports Range requests and conditional requests on the ETag
 pub async fn get(
     req: HttpRequest,
     query: web::Query<ImgResize>,
@@ -68,10 +67,15 @@ pub async fn get(
             img_resize.height,
         )?;
 
+        // Each size is its own version of the file
+        let width = img_resize.width.map(|w| w.to_string()).unwrap_or_default();
+        let height = img_resize.height.map(|h| h.to_string()).unwrap_or_default();
+        let hash = format!("{}-{width}x{height}", entry.hash);
+        let version_path = util::fs::readable_version_path(&repo, &version_path)?;
         util::fs::resize_cache_image(&version_path, &resized_path, img_resize)?;
 
         log::debug!("In the resize cache! {:?}", resized_path);
-        return Ok(NamedFile::open(resized_path)?.into_response(&req));
+        return content_response(&req, Content::File(resized_path), &hash).await;
 
         // log::debug!(
         //     "get_file_for_commit_id {:?}x{:?} for {:?} -> {:?}",
@@ -130,8 +134,14 @@ pub async fn get(
         version_path
     );
 
-    let file = NamedFile::open(version_path)?;
-    let mut response = file.into_response(&req);
+    let content = if util::fs::version_file_is_encrypted(&version_path) {
+        Content::Version(version_path, entry.num_bytes)
+    } else {
+        // Compressed versions are decompressed once into the versions cache, ranges read from it
+        let readable_path = util::fs::readable_version_path(&repo, &version_path)?;
+        Content::File(readable_path.to_path_buf())
+    };
+    let mut response = content_response(&req, content, &entry.hash).await?;
 
     response.headers_mut().insert(
         header::HeaderName::from_static("oxen-revision-id"),
@@ -140,3 +150,119 @@ pub async fn get(
 
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{web, App};
+
+    use liboxen::command;
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::app_data::OxenAppData;
+    use crate::controllers;
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_file_get_range_and_etag() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Name";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let path = repo.path.join("hello.txt");
+        util::fs::write_to_path(&path, "hello world")?;
+        command::add(&repo, &path)?;
+        command::commit(&repo, "adding hello")?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/file/{resource:.*}",
+                    web::get().to(controllers::file::get),
+                )
+                .route(
+                    "/oxen/{namespace}/{repo_name}/chunk/{resource:.*}",
+                    web::get().to(controllers::entries::download_chunk),
+                ),
+        )
+        .await;
+        let uri = format!("/oxen/{namespace}/{name}/file/main/hello.txt");
+
+        let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+        assert_eq!(resp.headers().get(header::ACCEPT_RANGES).unwrap(), "bytes");
+
+        // Same version, nothing to send
+        let req = actix_web::test::TestRequest::get()
+            .uri(&uri)
+            .insert_header((header::IF_NONE_MATCH, etag.clone()))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&uri)
+            .insert_header((header::RANGE, "bytes=6-"))
+            .insert_header((header::IF_RANGE, etag.clone()))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_RANGE).unwrap(),
+            "bytes 6-10/11"
+        );
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(bytes, "world");
+
+        // A range of another version gets the whole file
+        let req = actix_web::test::TestRequest::get()
+            .uri(&uri)
+            .insert_header((header::RANGE, "bytes=0-4"))
+            .insert_header((header::IF_RANGE, "\"not-the-hash\""))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(bytes, "hello world");
+
+        // Several ranges get the whole file
+        let req = actix_web::test::TestRequest::get()
+            .uri(&uri)
+            .insert_header((header::RANGE, "bytes=0-1,6-7"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(bytes, "hello world");
+
+        // The legacy chunk route is cut short at the end of the file and has the same ETag
+        let chunk_uri =
+            format!("/oxen/{namespace}/{name}/chunk/main/hello.txt?chunk_start=6&chunk_size=100");
+        let req = actix_web::test::TestRequest::get()
+            .uri(&chunk_uri)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::ETAG).unwrap(), etag);
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(bytes, "world");
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&uri)
+            .insert_header((header::RANGE, "bytes=100-200"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+
+        util::fs::remove_dir_all(sync_dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use crate::errors::OxenHttpError;
+use crate::metrics;
+use crate::params::app_data;
+
+use actix_web::{HttpRequest, HttpResponse};
+
+/// Prometheus scrape endpoint
+pub async fn index(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let body = metrics::render(&app_data.queue, &app_data.path);
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(body))
+}
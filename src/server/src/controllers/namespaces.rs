@@ -1,8 +1,10 @@
 use crate::errors::OxenHttpError;
-use crate::params::app_data;
+use crate::params::{app_data, path_param};
 
 use liboxen::api;
-use liboxen::view::{ListNamespacesResponse, NamespaceResponse, NamespaceView, StatusMessage};
+use liboxen::view::{
+    ListNamespacesResponse, NamespaceResponse, NamespaceView, StatusMessage, StorageUsageResponse,
+};
 
 use actix_web::{HttpRequest, HttpResponse, Result};
 
@@ -47,3 +49,19 @@ pub async fn show(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
         Err(OxenHttpError::BadRequest(msg.into()))
     }
 }
+
+/// Storage used by all the repositories in the namespace, with its quota
+pub async fn usage(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    if api::local::namespaces::get(&app_data.path, &namespace)?.is_none() {
+        return Err(OxenHttpError::NotFound);
+    }
+
+    let (namespace_usage, _) = api::local::quotas::usage(&app_data.path, &namespace, None)?;
+    Ok(HttpResponse::Ok().json(StorageUsageResponse {
+        status: StatusMessage::resource_found(),
+        namespace: namespace_usage,
+        repository: None,
+    }))
+}
@@ -0,0 +1,7 @@
+use crate::openapi;
+
+use actix_web::{HttpRequest, HttpResponse};
+
+pub async fn index(_req: HttpRequest) -> HttpResponse {
+    HttpResponse::Ok().json(openapi::spec())
+}
@@ -12,6 +12,7 @@ use liboxen::view::repository::{
 };
 use liboxen::view::{
     ListRepositoryResponse, NamespaceView, RepositoryResponse, RepositoryView, StatusMessage,
+    StorageUsageResponse,
 };
 
 use liboxen::model::RepoNew;
@@ -47,7 +48,7 @@ pub async fn show(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpE
     let name = path_param(&req, "repo_name")?;
 
     // Get the repository or return error
-    let _repository = get_repo(&app_data.path, &namespace, &name)?;
+    let repository = get_repo(&app_data.path, &namespace, &name)?;
 
     // Return the repository view
     Ok(HttpResponse::Ok().json(RepositoryDataTypesResponse {
@@ -60,6 +61,7 @@ pub async fn show(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpE
             // Hacking around it to not show in CLI unless you go through hub for now
             size: 0,
             data_types: vec![],
+            hash_algorithm: Some(repository.hash_algorithm()),
         },
     }))
 }
@@ -109,6 +111,22 @@ pub async fn stats(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttp
     }
 }
 
+/// Storage used by the repository and its namespace, with their quotas
+pub async fn usage(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let name = path_param(&req, "repo_name")?;
+    let _repo = get_repo(&app_data.path, &namespace, &name)?;
+
+    let (namespace_usage, repo_usage) =
+        api::local::quotas::usage(&app_data.path, &namespace, Some(&name))?;
+    Ok(HttpResponse::Ok().json(StorageUsageResponse {
+        status: StatusMessage::resource_found(),
+        namespace: namespace_usage,
+        repository: repo_usage,
+    }))
+}
+
 pub async fn create(
     req: HttpRequest,
     body: String,
@@ -264,8 +282,11 @@ mod tests {
     use liboxen::model::{Commit, RepoNew};
     use liboxen::util;
 
+    use liboxen::api::local::quotas::{Quota, QuotasConfig};
     use liboxen::view::http::STATUS_SUCCESS;
-    use liboxen::view::{ListRepositoryResponse, NamespaceView, RepositoryResponse};
+    use liboxen::view::{
+        ListRepositoryResponse, NamespaceView, RepositoryResponse, StorageUsageResponse,
+    };
     use time::OffsetDateTime;
 
     use crate::controllers;
@@ -344,6 +365,46 @@ mod tests {
         Ok(())
     }
 
+    #[actix_web::test]
+    async fn test_controllers_respositories_usage_with_quota() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Test-Namespace";
+        let name = "Testing-Usage";
+        test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let mut quotas = QuotasConfig::default();
+        quotas.repos.insert(
+            format!("{namespace}/{name}"),
+            Quota {
+                max_bytes: Some(1024),
+                max_files: None,
+            },
+        );
+        quotas.save(&sync_dir)?;
+
+        let uri = format!("/api/repos/{namespace}/{name}/usage");
+        let req = test::repo_request(&sync_dir, queue, &uri, namespace, name);
+
+        let resp = controllers::repositories::usage(req).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let text = std::str::from_utf8(&body).unwrap();
+        let usage: StorageUsageResponse = serde_json::from_str(text)?;
+        let repo_usage = usage.repository.unwrap();
+        assert_eq!(repo_usage.max_bytes, Some(1024));
+        assert_eq!(usage.namespace.max_bytes, None);
+
+        // A push bigger than the quota is turned away
+        let result = crate::helpers::check_quota(&sync_dir, namespace, name, 2048, 1);
+        assert!(result.is_err());
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
     #[actix_web::test]
     async fn test_controllers_respositories_create() -> Result<(), OxenError> {
         let sync_dir = test::get_sync_dir()?;
@@ -60,6 +60,7 @@ pub async fn list_or_get(req: HttpRequest) -> actix_web::Result<HttpResponse, Ox
                     &resource.path,
                 )? {
                     let version_path = util::fs::version_path(&repo, &entry);
+                    let version_path = util::fs::readable_version_path(&repo, &version_path)?;
                     log::debug!(
                         "No schemas found, trying to get from file {:?}",
                         resource.path
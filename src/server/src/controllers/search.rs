@@ -0,0 +1,149 @@
+use crate::errors::OxenHttpError;
+use crate::helpers::get_repo;
+use crate::params::{app_data, parse_resource, path_param, SearchQuery};
+
+use liboxen::opts::SearchOpts;
+use liboxen::view::search::{
+    NearestNeighborsRequest, NearestNeighborsResponse, SearchResultsResponse,
+};
+use liboxen::view::StatusMessage;
+use liboxen::{api, constants};
+
+use actix_web::{web, HttpRequest, HttpResponse};
+
+/// Full text search over the files in a revision, optionally restricted to a path
+pub async fn search(
+    req: HttpRequest,
+    query: web::Query<SearchQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
+    let resource = parse_resource(&req, &repo)?;
+    let commit = resource.commit.clone().ok_or(OxenHttpError::NotFound)?;
+
+    log::debug!(
+        "{} resource {namespace}/{repo_name}/{resource} q={}",
+        liboxen::current_function!(),
+        query.q
+    );
+
+    if query.q.trim().is_empty() {
+        return Err(OxenHttpError::BadRequest("Missing query param `q`".into()));
+    }
+
+    let opts = SearchOpts {
+        query: query.q.clone(),
+        revision: Some(commit.id.clone()),
+        path: Some(resource.path.clone()),
+        page_num: query.page.unwrap_or(constants::DEFAULT_PAGE_NUM),
+        page_size: query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE),
+        remote: false,
+    };
+    // Building the index on the first search of a commit can take a while
+    let (results, pagination) =
+        web::block(move || api::local::search::search(&repo, &commit, &opts)).await??;
+
+    Ok(HttpResponse::Ok().json(SearchResultsResponse {
+        status: StatusMessage::resource_found(),
+        results,
+        pagination,
+    }))
+}
+
+/// Nearest neighbor search over an embedding column in a revision
+pub async fn nearest(
+    req: HttpRequest,
+    body: web::Json<NearestNeighborsRequest>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
+    let resource = parse_resource(&req, &repo)?;
+    let commit = resource.commit.clone().ok_or(OxenHttpError::NotFound)?;
+
+    if body.vector.is_empty() {
+        return Err(OxenHttpError::BadRequest(
+            "Missing `vector` to search".into(),
+        ));
+    }
+
+    let limit = body.limit.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+    let body = body.into_inner();
+    let results = web::block(move || {
+        api::local::search::nearest(
+            &repo,
+            &commit,
+            &body.column,
+            &body.vector,
+            &resource.path,
+            limit,
+        )
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(NearestNeighborsResponse {
+        status: StatusMessage::resource_found(),
+        results,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{web, App};
+
+    use liboxen::command;
+    use liboxen::error::OxenError;
+    use liboxen::util;
+    use liboxen::view::search::SearchResultsResponse;
+
+    use crate::app_data::OxenAppData;
+    use crate::controllers;
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_search_revision() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Name";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let readme = repo.path.join("README.md");
+        util::fs::write_to_path(&readme, "Hello world\nGoodbye world\n")?;
+        command::add(&repo, &readme)?;
+        let commit = command::commit(&repo, "adding readme")?;
+
+        let uri = format!(
+            "/oxen/{namespace}/{name}/search/{}?q=hello%20world",
+            commit.id
+        );
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/search/{resource:.*}",
+                    web::get().to(controllers::search::search),
+                ),
+        )
+        .await;
+
+        let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = std::str::from_utf8(&bytes).unwrap();
+        let response: SearchResultsResponse = serde_json::from_str(body)?;
+
+        assert_eq!(response.pagination.total_entries, 1);
+        assert_eq!(response.results[0].text, "Hello world");
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::auth::validator::require_admin;
+use crate::errors::OxenHttpError;
+use crate::params::{app_data, path_param, TasksQuery};
+use crate::queues::{QueuedTask, TaskStatus};
+use crate::tasks::{self, NewTask};
+
+use liboxen::view::StatusMessage;
+use utoipa::ToSchema;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Serialize;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct TaskResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub task: QueuedTask,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ListTasksResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub tasks: Vec<QueuedTask>,
+}
+
+// Tasks can touch any repository, so every endpoint here needs an admin token
+
+/// List the background tasks, optionally filtered with ?status=queued|running|retrying|succeeded|dead
+pub async fn list(
+    req: HttpRequest,
+    query: web::Query<TasksQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    require_admin(&req)?;
+    let app_data = app_data(&req)?;
+    let status = match &query.status {
+        Some(status) => Some(
+            status
+                .parse::<TaskStatus>()
+                .map_err(|err| OxenHttpError::BadRequest(err.to_string().into()))?,
+        ),
+        None => None,
+    };
+
+    let tasks = app_data.queue.list(status)?;
+    Ok(HttpResponse::Ok().json(ListTasksResponse {
+        status: StatusMessage::resource_found(),
+        tasks,
+    }))
+}
+
+pub async fn show(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    require_admin(&req)?;
+    let app_data = app_data(&req)?;
+    let task_id = path_param(&req, "task_id")?;
+
+    let task = app_data
+        .queue
+        .get(&task_id)?
+        .ok_or(OxenHttpError::NotFound)?;
+    Ok(HttpResponse::Ok().json(TaskResponse {
+        status: StatusMessage::resource_found(),
+        task,
+    }))
+}
+
+/// Queue one of the registered tasks by name
+pub async fn create(
+    req: HttpRequest,
+    body: String,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    require_admin(&req)?;
+    let app_data = app_data(&req)?;
+
+    let data: Result<NewTask, serde_json::Error> = serde_json::from_str(&body);
+    let data = match data {
+        Ok(data) => data,
+        Err(err) => {
+            log::error!("Unable to parse body. Err: {}\n{}", err, body);
+            return Ok(HttpResponse::BadRequest().json(StatusMessage::error(err.to_string())));
+        }
+    };
+
+    let task = tasks::from_params(&app_data.path, &data)?;
+    let mut queue = app_data.queue.clone();
+    let task_id = queue.push(task)?;
+    let task = queue.get(&task_id)?.ok_or(OxenHttpError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(TaskResponse {
+        status: StatusMessage::resource_created(),
+        task,
+    }))
+}
+
+/// Run a finished, retrying or dead task again with a fresh set of attempts
+pub async fn retry(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    require_admin(&req)?;
+    let app_data = app_data(&req)?;
+    let task_id = path_param(&req, "task_id")?;
+
+    let mut queue = app_data.queue.clone();
+    let task = queue.retry(&task_id)?.ok_or(OxenHttpError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(TaskResponse {
+        status: StatusMessage::resource_updated(),
+        task,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::to_bytes;
+    use actix_web::http;
+    use actix_web::web;
+
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::controllers;
+    use crate::params::TasksQuery;
+    use crate::queues::{InMemoryTaskQueue, TaskQueue};
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_tasks_create_list_and_retry() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Tasks";
+        test::create_local_repo(&sync_dir, namespace, name)?;
+
+        // Only admins can queue tasks
+        let req = test::request(&sync_dir, queue.clone(), "/api/tasks");
+        let body =
+            format!(r#"{{"name": "gc", "namespace": "{namespace}", "repo_name": "{name}"}}"#);
+        assert!(controllers::tasks::create(req, body).await.is_err());
+        assert!(queue.list(None)?.is_empty());
+
+        // Unknown tasks are rejected
+        let req = test::admin_request(&sync_dir, queue.clone(), "/api/tasks")?;
+        let body = String::from(r#"{"name": "defrag"}"#);
+        assert!(controllers::tasks::create(req, body).await.is_err());
+
+        let req = test::admin_request(&sync_dir, queue.clone(), "/api/tasks")?;
+        let body =
+            format!(r#"{{"name": "gc", "namespace": "{namespace}", "repo_name": "{name}"}}"#);
+        let resp = controllers::tasks::create(req, body).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["task"]["name"], "gc");
+        assert_eq!(body["task"]["status"], "queued");
+        let task_id = body["task"]["id"].as_str().unwrap().to_string();
+
+        // Run it the way the poller would
+        let mut worker_queue = queue.clone();
+        let task = worker_queue.reserve()?.task.unwrap();
+        assert_eq!(task.id, task_id);
+        worker_queue.complete(&task.id)?;
+
+        let req = test::admin_request(&sync_dir, queue.clone(), "/api/tasks?status=succeeded")?;
+        let query = web::Query::<TasksQuery>::from_query("status=succeeded").unwrap();
+        let resp = controllers::tasks::list(req, query).await.unwrap();
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["tasks"].as_array().unwrap().len(), 1);
+
+        let uri = format!("/api/tasks/{task_id}/retry");
+        let req =
+            test::admin_request_with_param(&sync_dir, queue.clone(), &uri, "task_id", task_id)?;
+        let resp = controllers::tasks::retry(req).await.unwrap();
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["task"]["status"], "queued");
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
@@ -1,16 +1,22 @@
+use crate::auth;
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
-use crate::params::{app_data, path_param};
+use crate::params::{app_data, path_param, WorkspacesQuery};
+use crate::tasks::cleanup_workspaces::CleanupWorkspaces;
+use crate::tasks::Task;
 
 use liboxen::core::cache::commit_cacher;
 
 use liboxen::error::OxenError;
 use liboxen::model::NewCommitBody;
-use liboxen::view::workspaces::{ListWorkspaceResponseView, NewWorkspace, WorkspaceResponse};
+use liboxen::view::workspaces::{
+    ListWorkspaceResponseView, NewWorkspace, RenameWorkspace, WorkspaceRefreshResponse,
+    WorkspaceResponse,
+};
 use liboxen::view::{CommitResponse, StatusMessage, WorkspaceResponseView};
 use liboxen::{api, core::index};
 
-use actix_web::{HttpRequest, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 
 pub mod changes;
 pub mod data_frames;
@@ -44,41 +50,42 @@ pub async fn get_or_create(
     if let Ok(workspace) = index::workspaces::get(&repo, &workspace_id) {
         return Ok(HttpResponse::Ok().json(WorkspaceResponseView {
             status: StatusMessage::resource_created(),
-            workspace: WorkspaceResponse {
-                id: workspace_id,
-                commit: workspace.commit.into(),
-            },
+            workspace: workspace.into(),
         }));
     }
 
     let commit = api::local::commits::get_by_id(&repo, &branch.commit_id)?.unwrap();
 
     // Get or create the workspace
-    index::workspaces::create(&repo, &commit, &workspace_id, true)?;
+    let mut workspace = index::workspaces::create(&repo, &commit, &workspace_id, true)?;
+    workspace.name = data.name;
+    workspace.branch_name = Some(branch.name);
+    // The user behind the token, the creator in the body can only be trusted without auth
+    workspace.creator = auth::validator::identity(&req).user.or(data.creator);
+    workspace.ttl_secs = data.ttl_secs.or(app_data.workspace_ttl_secs);
+    workspace.save()?;
 
     Ok(HttpResponse::Ok().json(WorkspaceResponseView {
         status: StatusMessage::resource_created(),
-        workspace: WorkspaceResponse {
-            id: workspace_id,
-            commit: commit.into(),
-        },
+        workspace: workspace.into(),
     }))
 }
 
-pub async fn list(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+pub async fn list(
+    req: HttpRequest,
+    query: web::Query<WorkspacesQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
 
     let repo = get_repo(&app_data.path, namespace, repo_name)?;
     log::debug!("workspaces::list got repo: {:?}", repo.path);
-    let workspaces = index::workspaces::list(&repo)?;
+    let workspaces =
+        index::workspaces::list_by(&repo, query.branch.as_deref(), query.creator.as_deref())?;
     let workspace_views = workspaces
-        .iter()
-        .map(|workspace| WorkspaceResponse {
-            id: workspace.id.clone(),
-            commit: workspace.commit.clone().into(),
-        })
+        .into_iter()
+        .map(WorkspaceResponse::from)
         .collect();
 
     Ok(HttpResponse::Ok().json(ListWorkspaceResponseView {
@@ -100,13 +107,77 @@ pub async fn delete(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHtt
 
     Ok(HttpResponse::Ok().json(WorkspaceResponseView {
         status: StatusMessage::resource_created(),
-        workspace: WorkspaceResponse {
-            id: workspace_id,
-            commit: workspace.commit.into(),
-        },
+        workspace: workspace.into(),
     }))
 }
 
+pub async fn rename(
+    req: HttpRequest,
+    body: String,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+
+    let data: Result<RenameWorkspace, serde_json::Error> = serde_json::from_str(&body);
+    let data = match data {
+        Ok(data) => data,
+        Err(err) => {
+            log::error!("Unable to parse body. Err: {}\n{}", err, body);
+            return Ok(HttpResponse::BadRequest().json(StatusMessage::error(err.to_string())));
+        }
+    };
+
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let workspace = index::workspaces::get(&repo, &workspace_id)?;
+    let workspace = index::workspaces::rename(&workspace, &data.name)?;
+
+    Ok(HttpResponse::Ok().json(WorkspaceResponseView {
+        status: StatusMessage::resource_updated(),
+        workspace: workspace.into(),
+    }))
+}
+
+/// Move the workspace onto the head of the branch, replaying its staged edits
+pub async fn refresh(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let branch_name = path_param(&req, "branch")?;
+
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let workspace = index::workspaces::get(&repo, &workspace_id)?;
+    let (workspace, summary) = index::workspaces::refresh(&workspace, &branch_name)?;
+
+    log::debug!(
+        "workspace::refresh {} onto {} replayed {} rows with {} conflicts",
+        workspace_id,
+        branch_name,
+        summary.replayed_rows,
+        summary.conflicts.len()
+    );
+
+    Ok(HttpResponse::Ok().json(WorkspaceRefreshResponse {
+        status: StatusMessage::resource_updated(),
+        workspace: workspace.into(),
+        summary,
+    }))
+}
+
+/// Queue a sweep of the expired workspaces across all repositories
+pub async fn cleanup(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let mut queue = app_data.queue.clone();
+    queue.push(Task::CleanupWorkspaces(CleanupWorkspaces {
+        sync_dir: app_data.path.clone(),
+    }))?;
+
+    Ok(HttpResponse::Ok().json(StatusMessage::resource_found()))
+}
+
 pub async fn commit(req: HttpRequest, body: String) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
 
@@ -1,22 +1,24 @@
 use std::path::PathBuf;
 
 use crate::errors::OxenHttpError;
-use crate::helpers::get_repo;
-use crate::params::{app_data, df_opts_query, path_param, DFOptsQuery, PageNumQuery};
+use crate::helpers::{accepted_df_encoding, df_json_response, df_response, get_repo};
+use crate::params::{
+    app_data, df_opts_query, path_param, BulkRowsQuery, ChangesQuery, DFOptsQuery, PageNumQuery,
+};
 
 use actix_web::{web, HttpRequest, HttpResponse};
 use liboxen::constants::TABLE_NAME;
 use liboxen::core::db::data_frames::{df_db, workspace_df_db};
 use liboxen::error::OxenError;
-use liboxen::model::Schema;
+use liboxen::model::{BulkFileFormat, Schema};
 use liboxen::opts::DFOpts;
 use liboxen::util::paginate;
-use liboxen::view::data_frames::DataFramePayload;
+use liboxen::view::data_frames::{DataFrameChangesResponse, DataFramePayload};
 use liboxen::view::entry::ResourceVersion;
 use liboxen::view::entry::{PaginatedMetadataEntries, PaginatedMetadataEntriesResponse};
 use liboxen::view::json_data_frame_view::WorkspaceJsonDataFrameViewResponse;
-use liboxen::view::{JsonDataFrameViewResponse, JsonDataFrameViews, StatusMessage};
-use liboxen::{api, constants, core::index};
+use liboxen::view::{JsonDataFrameViewResponse, JsonDataFrameViews, Pagination, StatusMessage};
+use liboxen::{api, constants, core::index, util};
 
 pub mod columns;
 pub mod rows;
@@ -52,7 +54,7 @@ pub async fn get_by_resource(
             is_indexed,
         };
 
-        return Ok(HttpResponse::Ok().json(response));
+        return Ok(df_json_response(response));
     }
 
     let staged_db_path =
@@ -63,6 +65,16 @@ pub async fn get_by_resource(
     let count = index::workspaces::data_frames::count(&workspace, &file_path)?;
 
     let df = index::workspaces::data_frames::query(&workspace, &file_path, &opts)?;
+    if let Some(encoding) = accepted_df_encoding(&req) {
+        let page_size = opts.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+        let pagination = Pagination {
+            page_number: opts.page.unwrap_or(constants::DEFAULT_PAGE_NUM),
+            page_size,
+            total_pages: (count as f64 / page_size as f64).ceil() as usize,
+            total_entries: count,
+        };
+        return Ok(df_response(df, encoding, Some(pagination)));
+    }
 
     let mut df_schema = df_db::get_schema(&conn, TABLE_NAME)?;
 
@@ -94,7 +106,74 @@ pub async fn get_by_resource(
         is_indexed,
     };
 
-    Ok(HttpResponse::Ok().json(response))
+    Ok(df_json_response(response))
+}
+
+/// Download the current state of the workspace data frame, including the diff status of each row
+pub async fn export(
+    req: HttpRequest,
+    query: web::Query<BulkRowsQuery>,
+) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let workspace = index::workspaces::get(&repo, workspace_id)?;
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+
+    if !index::workspaces::data_frames::is_indexed(&workspace, &file_path)? {
+        return Err(OxenHttpError::DatasetNotIndexed(file_path.into()));
+    }
+
+    // Default to the format of the data frame itself
+    let format = match query.format {
+        Some(format) => format,
+        None => BulkFileFormat::from_path(&file_path)?,
+    };
+
+    let export_path =
+        index::workspaces::data_frames::bulk_file_path(&workspace, &file_path, format);
+    index::workspaces::data_frames::export(&workspace, &file_path, &export_path)?;
+    let mime_type = util::fs::file_mime_type(&export_path);
+    let bytes = std::fs::read(&export_path);
+    util::fs::remove_file(&export_path)?;
+
+    Ok(HttpResponse::Ok().content_type(mime_type).body(bytes?))
+}
+
+/// Poll for the row edits made after the `since` sequence number
+pub async fn changes(
+    req: HttpRequest,
+    query: web::Query<ChangesQuery>,
+) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let workspace = index::workspaces::get(&repo, workspace_id)?;
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+
+    if !index::workspaces::data_frames::is_indexed(&workspace, &file_path)? {
+        return Err(OxenHttpError::DatasetNotIndexed(file_path.into()));
+    }
+
+    let since = query.since.unwrap_or(0);
+    let page_size = query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+    let changes = index::workspaces::data_frames::change_feed::list_since(
+        &workspace, &file_path, since, page_size,
+    )?;
+    let latest_seq =
+        index::workspaces::data_frames::change_feed::latest_seq(&workspace, &file_path)?;
+
+    Ok(HttpResponse::Ok().json(DataFrameChangesResponse {
+        status: StatusMessage::resource_found(),
+        changes,
+        latest_seq,
+    }))
 }
 
 pub async fn get_by_branch(
@@ -1,16 +1,22 @@
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
-use crate::params::{app_data, path_param};
+use crate::params::{app_data, path_param, BulkRowsQuery};
 
-use actix_web::{web::Bytes, HttpRequest, HttpResponse};
+use actix_web::{http::header, web, web::Bytes, HttpRequest, HttpResponse};
+use futures_util::stream::StreamExt as _;
+use liboxen::constants::OXEN_ROW_REVISION_COL;
+use liboxen::core::df::tabular;
 use liboxen::error::OxenError;
 use liboxen::model::Schema;
+use liboxen::model::Workspace;
 use liboxen::opts::DFOpts;
+use liboxen::view::data_frames::DataFrameBulkRowsResponse;
 use liboxen::view::json_data_frame_view::{JsonDataFrameRowResponse, JsonDataFrameSource};
 use liboxen::view::{JsonDataFrameView, JsonDataFrameViews, StatusMessage};
-use liboxen::{api, core::index};
+use liboxen::{api, core::index, util};
 
 pub async fn create(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
@@ -52,6 +58,7 @@ pub async fn create(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
     let row_df = index::workspaces::data_frames::rows::add(&workspace, &file_path, data)?;
     let row_id: Option<String> = index::workspaces::data_frames::rows::get_row_id(&row_df)?;
     let row_index: Option<usize> = index::workspaces::data_frames::rows::get_row_idx(&row_df)?;
+    let row_revision = index::workspaces::data_frames::rows::get_row_revision(&row_df)?;
 
     let opts = DFOpts::empty();
     let row_schema = Schema::from_polars(&row_df.schema().clone());
@@ -72,6 +79,7 @@ pub async fn create(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
         resource: None,
         row_id,
         row_index,
+        row_revision,
     };
 
     Ok(HttpResponse::Ok().json(response))
@@ -93,6 +101,7 @@ pub async fn get(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
 
     let row_id = index::workspaces::data_frames::rows::get_row_id(&row_df)?;
     let row_index = index::workspaces::data_frames::rows::get_row_idx(&row_df)?;
+    let row_revision = index::workspaces::data_frames::rows::get_row_revision(&row_df)?;
 
     let opts = DFOpts::empty();
     let row_schema = Schema::from_polars(&row_df.schema().clone());
@@ -111,6 +120,7 @@ pub async fn get(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
         resource: None,
         row_id,
         row_index,
+        row_revision,
     };
 
     Ok(HttpResponse::Ok().json(response))
@@ -136,12 +146,19 @@ pub async fn update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
     // If the json has an outer property of "data", serialize the inner object
     let json_value: serde_json::Value = serde_json::from_str(&data)?;
     // TODO why do we allow both?
-    let data = if let Some(data_obj) = json_value.get("data") {
-        data_obj
+    let mut data = if let Some(data_obj) = json_value.get("data") {
+        data_obj.clone()
     } else {
-        &json_value
+        json_value
     };
 
+    // A revision in the If-Match header makes this a conditional update
+    if let Some(revision) = if_match_revision(&req)? {
+        if let Some(obj) = data.as_object_mut() {
+            obj.insert(OXEN_ROW_REVISION_COL.to_string(), revision.into());
+        }
+    }
+
     // Assumes the workspace is already created
     let workspace = index::workspaces::get(&repo, &workspace_id)?;
     log::debug!(
@@ -153,9 +170,10 @@ pub async fn update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
     );
 
     let modified_row =
-        index::workspaces::data_frames::rows::update(&workspace, &file_path, &row_id, data)?;
+        index::workspaces::data_frames::rows::update(&workspace, &file_path, &row_id, &data)?;
 
     let row_index = index::workspaces::data_frames::rows::get_row_idx(&modified_row)?;
+    let row_revision = index::workspaces::data_frames::rows::get_row_revision(&modified_row)?;
     let row_id = index::workspaces::data_frames::rows::get_row_id(&modified_row)?;
 
     let diff = index::workspaces::data_frames::rows::get_row_diff(&workspace, &file_path)?;
@@ -174,6 +192,7 @@ pub async fn update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
         resource: None,
         row_id,
         row_index,
+        row_revision,
     }))
 }
 
@@ -190,7 +209,11 @@ pub async fn delete(req: HttpRequest, _bytes: Bytes) -> Result<HttpResponse, Oxe
     let file_path = PathBuf::from(path_param(&req, "path")?);
     let workspace = index::workspaces::get(&repo, workspace_id)?;
 
-    let df = index::workspaces::data_frames::rows::delete(&workspace, &file_path, &row_id)?;
+    let revision = if_match_revision(&req)?;
+    let df = index::workspaces::data_frames::rows::delete_at_revision(
+        &workspace, &file_path, &row_id, revision,
+    )?;
+    let row_revision = index::workspaces::data_frames::rows::get_row_revision(&df)?;
     let diff = index::workspaces::data_frames::rows::get_row_diff(&workspace, &file_path)?;
 
     let schema = Schema::from_polars(&df.schema());
@@ -206,6 +229,7 @@ pub async fn delete(req: HttpRequest, _bytes: Bytes) -> Result<HttpResponse, Oxe
         resource: None,
         row_id: None,
         row_index: None,
+        row_revision,
     }))
 }
 
@@ -228,6 +252,7 @@ pub async fn restore(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
     let restored_row = index::workspaces::data_frames::rows::restore(&workspace, &entry, row_id)?;
 
     let row_index = index::workspaces::data_frames::rows::get_row_idx(&restored_row)?;
+    let row_revision = index::workspaces::data_frames::rows::get_row_revision(&restored_row)?;
     let row_id = index::workspaces::data_frames::rows::get_row_id(&restored_row)?;
 
     let diff = index::workspaces::data_frames::rows::get_row_diff(&workspace, &file_path)?;
@@ -246,5 +271,123 @@ pub async fn restore(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
         resource: None,
         row_id,
         row_index,
+        row_revision,
+    }))
+}
+
+/// Parse the row revision out of an If-Match header, quoted like an etag or not
+fn if_match_revision(req: &HttpRequest) -> Result<Option<i64>, OxenHttpError> {
+    let Some(value) = req.headers().get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    let revision = value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse::<i64>()
+        .map_err(|_| {
+            OxenHttpError::BadRequest(format!("Invalid If-Match revision '{value}'").into())
+        })?;
+    Ok(Some(revision))
+}
+
+/// Upsert all the rows of the uploaded file, matched on the `keys` columns
+pub async fn upsert(
+    req: HttpRequest,
+    query: web::Query<BulkRowsQuery>,
+    body: web::Payload,
+) -> Result<HttpResponse, OxenHttpError> {
+    let (workspace, file_path, df) = bulk_rows_from_request(&req, &query, body).await?;
+    let keys = query.key_columns();
+
+    log::info!(
+        "upsert {} rows in {:?} on keys {:?} in workspace {}",
+        df.height(),
+        file_path,
+        keys,
+        workspace.id
+    );
+
+    let summary = index::workspaces::data_frames::rows::upsert(&workspace, &file_path, &df, &keys)?;
+    Ok(HttpResponse::Ok().json(DataFrameBulkRowsResponse {
+        status: StatusMessage::resource_updated(),
+        summary,
+    }))
+}
+
+/// Delete all the rows matching the `keys` columns of the uploaded file
+pub async fn delete_many(
+    req: HttpRequest,
+    query: web::Query<BulkRowsQuery>,
+    body: web::Payload,
+) -> Result<HttpResponse, OxenHttpError> {
+    let (workspace, file_path, df) = bulk_rows_from_request(&req, &query, body).await?;
+    let keys = query.key_columns();
+
+    log::info!(
+        "delete {} rows in {:?} on keys {:?} in workspace {}",
+        df.height(),
+        file_path,
+        keys,
+        workspace.id
+    );
+
+    let summary =
+        index::workspaces::data_frames::rows::delete_many(&workspace, &file_path, &df, &keys)?;
+    Ok(HttpResponse::Ok().json(DataFrameBulkRowsResponse {
+        status: StatusMessage::resource_deleted(),
+        summary,
     }))
 }
+
+async fn bulk_rows_from_request(
+    req: &HttpRequest,
+    query: &BulkRowsQuery,
+    mut body: web::Payload,
+) -> Result<(Workspace, PathBuf, polars::frame::DataFrame), OxenHttpError> {
+    let app_data = app_data(req)?;
+    let namespace = path_param(req, "namespace")?;
+    let repo_name = path_param(req, "repo_name")?;
+    let workspace_id = path_param(req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let file_path = PathBuf::from(path_param(req, "path")?);
+    let workspace = index::workspaces::get(&repo, workspace_id)?;
+
+    if !index::workspaces::data_frames::is_indexed(&workspace, &file_path)? {
+        return Err(OxenHttpError::DatasetNotIndexed(file_path.into()));
+    }
+
+    let Some(format) = query.format else {
+        return Err(OxenHttpError::BadRequest(
+            "Missing `format` of the uploaded file".into(),
+        ));
+    };
+
+    // Stream the upload to disk so we can use the same readers as committed files
+    let upload_path =
+        index::workspaces::data_frames::bulk_file_path(&workspace, &file_path, format);
+    if let Some(parent) = upload_path.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    let df = match write_payload(&upload_path, &mut body).await {
+        Ok(()) => tabular::read_df(&upload_path, DFOpts::empty()).map_err(OxenHttpError::from),
+        Err(err) => Err(err),
+    };
+    if upload_path.exists() {
+        util::fs::remove_file(&upload_path)?;
+    }
+
+    Ok((workspace, file_path, df?))
+}
+
+async fn write_payload(path: &Path, body: &mut web::Payload) -> Result<(), OxenHttpError> {
+    let mut file = util::fs::file_create(path)?;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|err| OxenHttpError::BadRequest(err.to_string().into()))?;
+        file.write_all(&chunk)?;
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::errors::OxenHttpError;
-use crate::helpers::get_repo;
+use crate::helpers::{get_repo, reserve_quota};
 use crate::params::{app_data, path_param};
 
 use actix_files::NamedFile;
@@ -10,6 +10,7 @@ use liboxen::model::Workspace;
 use liboxen::util;
 use liboxen::view::{FilePathsResponse, StatusMessage};
 
+use actix_web::http::header::CONTENT_LENGTH;
 use actix_web::{web, HttpRequest, HttpResponse};
 
 use actix_multipart::Multipart;
@@ -57,11 +58,20 @@ pub async fn add(req: HttpRequest, payload: Multipart) -> Result<HttpResponse, O
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
     let workspace_id = path_param(&req, "workspace_id")?;
-    let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+    let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
     let directory = PathBuf::from(path_param(&req, "path")?);
 
     let workspace = index::workspaces::get(&repo, &workspace_id)?;
 
+    // The files will be stored on commit, so turn them away now if they would go over quota
+    let upload_size = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(0);
+    reserve_quota(&app_data.path, &namespace, &repo_name, upload_size, 1)?;
+
     log::debug!("add_file directory {:?}", directory);
 
     let files = save_parts(&workspace, &directory, payload).await?;
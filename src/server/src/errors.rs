@@ -1,3 +1,4 @@
+use actix_web::http::header;
 use actix_web::{error, http::StatusCode, HttpResponse};
 use derive_more::{Display, Error};
 use liboxen::constants;
@@ -25,6 +26,10 @@ pub enum OxenHttpError {
     DatasetAlreadyIndexed(PathBufError),
     UpdateRequired(StringError),
     WorkspaceBehind(Branch),
+    RepoReadOnly(StringError),
+    AdminRequired,
+    /// Seconds the client should wait before trying again
+    TooManyRequests(#[error(not(source))] u64),
 
     // Translate OxenError to OxenHttpError
     InternalOxenError(OxenError),
@@ -66,6 +71,12 @@ impl From<actix_web::Error> for OxenHttpError {
     }
 }
 
+impl From<error::BlockingError> for OxenHttpError {
+    fn from(error: error::BlockingError) -> Self {
+        OxenHttpError::ActixError(error.into())
+    }
+}
+
 impl From<serde_json::Error> for OxenHttpError {
     fn from(error: serde_json::Error) -> Self {
         OxenHttpError::SerdeError(error)
@@ -134,7 +145,7 @@ impl error::ResponseError for OxenHttpError {
                     "error": {
                         "type": MSG_CONFLICT,
                         "title": "Workspace is behind",
-                        "detail": format!("This workspace is behind on branch '{}'", branch.name)
+                        "detail": format!("This workspace is behind on branch '{}', refresh it onto the latest commit and try again", branch.name)
                     },
                     "status": STATUS_ERROR,
                     "status_message": MSG_CONFLICT,
@@ -142,6 +153,47 @@ impl error::ResponseError for OxenHttpError {
 
                 HttpResponse::NotFound().json(error_json)
             }
+            OxenHttpError::RepoReadOnly(repo) => {
+                let error_json = json!({
+                    "error": {
+                        "type": "read_only",
+                        "title": "Repository is read only",
+                        "detail": format!("Repository '{}' is a read only mirror, push to the source server instead", repo)
+                    },
+                    "status": STATUS_ERROR,
+                    "status_message": MSG_BAD_REQUEST,
+                });
+
+                HttpResponse::Forbidden().json(error_json)
+            }
+            OxenHttpError::AdminRequired => {
+                let error_json = json!({
+                    "error": {
+                        "type": "admin_required",
+                        "title": "Admin access required",
+                        "detail": "Send the bearer token of a user added with `oxen-server add-user --admin`"
+                    },
+                    "status": STATUS_ERROR,
+                    "status_message": MSG_BAD_REQUEST,
+                });
+
+                HttpResponse::Forbidden().json(error_json)
+            }
+            OxenHttpError::TooManyRequests(retry_after) => {
+                let error_json = json!({
+                    "error": {
+                        "type": "rate_limited",
+                        "title": "Too many requests",
+                        "detail": format!("Rate limit exceeded, retry after {} seconds", retry_after)
+                    },
+                    "status": STATUS_ERROR,
+                    "status_message": MSG_BAD_REQUEST,
+                });
+
+                HttpResponse::TooManyRequests()
+                    .insert_header((header::RETRY_AFTER, retry_after.to_string()))
+                    .json(error_json)
+            }
             OxenHttpError::DatasetAlreadyIndexed(path) => {
                 let error_json = json!({
                     "error": {
@@ -265,6 +317,36 @@ impl error::ResponseError for OxenHttpError {
 
                         HttpResponse::NotFound().json(error_json)
                     }
+                    OxenError::QuotaExceeded(msg) => {
+                        log::debug!("{msg}");
+
+                        let error_json = json!({
+                            "error": {
+                                "type": "quota_exceeded",
+                                "title": "Storage quota exceeded",
+                                "detail": format!("{}", msg)
+                            },
+                            "status": STATUS_ERROR,
+                            "status_message": MSG_BAD_REQUEST,
+                        });
+
+                        HttpResponse::PayloadTooLarge().json(error_json)
+                    }
+                    OxenError::DataFrameRowConflict(msg) => {
+                        log::debug!("{msg}");
+
+                        let error_json = json!({
+                            "error": {
+                                "type": MSG_CONFLICT,
+                                "title": "Row revision conflict",
+                                "detail": format!("{}", msg)
+                            },
+                            "status": STATUS_ERROR,
+                            "status_message": MSG_CONFLICT,
+                        });
+
+                        HttpResponse::Conflict().json(error_json)
+                    }
                     OxenError::CommitEntryNotFound(msg) => {
                         log::error!("{msg}");
 
@@ -390,6 +472,9 @@ impl error::ResponseError for OxenHttpError {
             OxenHttpError::NotFound => StatusCode::NOT_FOUND,
             OxenHttpError::NotQueryable => StatusCode::BAD_REQUEST,
             OxenHttpError::WorkspaceBehind(_) => StatusCode::CONFLICT,
+            OxenHttpError::RepoReadOnly(_) => StatusCode::FORBIDDEN,
+            OxenHttpError::AdminRequired => StatusCode::FORBIDDEN,
+            OxenHttpError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             OxenHttpError::DatasetNotIndexed(_) => StatusCode::BAD_REQUEST,
             OxenHttpError::DatasetAlreadyIndexed(_) => StatusCode::BAD_REQUEST,
             OxenHttpError::UpdateRequired(_) => StatusCode::UPGRADE_REQUIRED,
@@ -401,6 +486,8 @@ impl error::ResponseError for OxenHttpError {
                 OxenError::RepoNotFound(_) => StatusCode::NOT_FOUND,
                 OxenError::RevisionNotFound(_) => StatusCode::NOT_FOUND,
                 OxenError::InvalidSchema(_) => StatusCode::BAD_REQUEST,
+                OxenError::DataFrameRowConflict(_) => StatusCode::CONFLICT,
+                OxenError::QuotaExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
                 _ => StatusCode::INTERNAL_SERVER_ERROR,
             },
         }
@@ -1,12 +1,34 @@
-use std::path::Path;
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 
+use actix_files::HttpRange;
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse};
 use liboxen::api;
 use liboxen::constants::DEFAULT_REDIS_URL;
+use liboxen::core::df::encoding::{self, DataFrameEncoding};
 use liboxen::error::OxenError;
 use liboxen::model::{LocalRepository, RepoNew};
+use liboxen::util;
+use liboxen::view::Pagination;
+use polars::frame::DataFrame;
+use serde::Serialize;
 
 use crate::errors::OxenHttpError;
 
+// Size of the reads when streaming a file back
+const STREAM_BUFFER_SIZE: u64 = 64 * 1024;
+// Rows per record batch or row group when streaming a data frame
+const DF_BATCH_SIZE: usize = 10_000;
+// Encoded chunks of a data frame that can wait to be sent before encoding pauses
+const DF_STREAM_BUFFER: usize = 16;
+
+pub const PAGE_NUMBER_HEADER: &str = "oxen-page-number";
+pub const PAGE_SIZE_HEADER: &str = "oxen-page-size";
+pub const TOTAL_PAGES_HEADER: &str = "oxen-total-pages";
+pub const TOTAL_ENTRIES_HEADER: &str = "oxen-total-entries";
+
 pub fn get_repo(
     path: &Path,
     namespace: impl AsRef<str>,
@@ -19,15 +41,330 @@ pub fn get_repo(
     )
 }
 
+/// Errors if storing `bytes` more in `files` more files would put the repo or its namespace over
+/// quota
+pub fn check_quota(
+    sync_dir: &Path,
+    namespace: impl AsRef<str>,
+    name: impl AsRef<str>,
+    bytes: u64,
+    files: u64,
+) -> Result<(), OxenHttpError> {
+    api::local::quotas::check(sync_dir, namespace.as_ref(), name.as_ref(), bytes, files)?;
+    Ok(())
+}
+
+/// Like `check_quota` for an upload that is about to be stored, which then counts in the usage
+/// of the repo until its version files are walked again
+pub fn reserve_quota(
+    sync_dir: &Path,
+    namespace: impl AsRef<str>,
+    name: impl AsRef<str>,
+    bytes: u64,
+    files: u64,
+) -> Result<(), OxenHttpError> {
+    api::local::quotas::reserve(sync_dir, namespace.as_ref(), name.as_ref(), bytes, files)?;
+    Ok(())
+}
+
 pub fn get_redis_connection() -> Result<r2d2::Pool<redis::Client>, OxenError> {
     let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| DEFAULT_REDIS_URL.to_string());
     let redis_client = redis::Client::open(redis_url)?;
 
     // First, ping redis to see if available - builder retries infinitely and spews error messages
     let mut test_conn = redis_client.get_connection()?;
-    redis::cmd("ECHO").arg("test").query(&mut test_conn)?;
+    redis::cmd("ECHO").arg("test").query::<()>(&mut test_conn)?;
 
     // If echo test didn't error, init the builder
     let pool = r2d2::Pool::builder().build(redis_client)?;
     Ok(pool)
 }
+
+/// Where the body of a `content_response` is read from
+pub enum Content {
+    /// A plain file on disk
+    File(PathBuf),
+    /// A version file however it is stored and its plain size. Encrypted versions only decrypt
+    /// the segments a range covers and are never written out in plain.
+    Version(PathBuf, u64),
+    /// Bytes built in memory
+    Bytes(web::Bytes),
+}
+
+impl Content {
+    fn size(&self) -> Result<u64, OxenError> {
+        match self {
+            Content::File(path) => Ok(util::fs::metadata(path)?.len()),
+            Content::Version(_, size) => Ok(*size),
+            Content::Bytes(bytes) => Ok(bytes.len() as u64),
+        }
+    }
+
+    fn mime_type(&self) -> Result<String, OxenError> {
+        match self {
+            Content::File(path) => Ok(util::fs::file_mime_type(path)),
+            Content::Version(path, _) => util::fs::version_file_mime_type(path),
+            Content::Bytes(_) => Ok(String::from("application/octet-stream")),
+        }
+    }
+
+    /// A reader of the contents from `offset`
+    fn open_at(self, offset: u64) -> Result<Box<dyn Read + Send>, OxenError> {
+        match self {
+            Content::File(path) => {
+                let mut file =
+                    File::open(&path).map_err(|err| OxenError::file_error(&path, err))?;
+                file.seek(SeekFrom::Start(offset))?;
+                Ok(Box::new(file))
+            }
+            Content::Version(path, _) => util::fs::open_version_file_at(&path, offset),
+            Content::Bytes(bytes) => {
+                let mut cursor = Cursor::new(bytes);
+                cursor.set_position(offset);
+                Ok(Box::new(cursor))
+            }
+        }
+    }
+}
+
+/// Serve content identified by `hash`, ie a version file from `CommitEntry::hash`. The hash is
+/// the strong ETag, so `If-None-Match` gets a 304 and a single `Range` gets a 206, unless an
+/// `If-Range` names another version. Reads happen on the blocking thread pool.
+pub async fn content_response(
+    req: &HttpRequest,
+    content: Content,
+    hash: &str,
+) -> Result<HttpResponse, OxenHttpError> {
+    let etag = format!("\"{hash}\"");
+    if if_none_match(req, &etag) {
+        return Ok(not_modified(etag));
+    }
+
+    let (size, mime_type, content) = describe(content).await?;
+    let range = req
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .filter(|_| if_range(req, &etag));
+    let Some(range) = range else {
+        let body = stream_content(content, 0, size).await?;
+        return Ok(HttpResponse::Ok()
+            .content_type(mime_type)
+            .insert_header((header::ETAG, etag))
+            .insert_header((header::ACCEPT_RANGES, "bytes"))
+            .no_chunking(size)
+            .streaming(body));
+    };
+
+    let range = match HttpRange::parse(range, size) {
+        Ok(ranges) if ranges.len() == 1 => ranges[0],
+        // Several ranges would need a multipart/byteranges body, send the whole file instead
+        Ok(_) => {
+            let body = stream_content(content, 0, size).await?;
+            return Ok(HttpResponse::Ok()
+                .content_type(mime_type)
+                .insert_header((header::ETAG, etag))
+                .insert_header((header::ACCEPT_RANGES, "bytes"))
+                .no_chunking(size)
+                .streaming(body));
+        }
+        Err(_) => {
+            return Ok(HttpResponse::RangeNotSatisfiable()
+                .insert_header((header::CONTENT_RANGE, format!("bytes */{size}")))
+                .finish());
+        }
+    };
+
+    let body = stream_content(content, range.start, range.length).await?;
+    Ok(HttpResponse::PartialContent()
+        .content_type(mime_type)
+        .insert_header((header::ETAG, etag))
+        .insert_header((header::ACCEPT_RANGES, "bytes"))
+        .insert_header((
+            header::CONTENT_RANGE,
+            format!(
+                "bytes {}-{}/{size}",
+                range.start,
+                range.start + range.length - 1
+            ),
+        ))
+        .no_chunking(range.length)
+        .streaming(body))
+}
+
+/// Serve `size` bytes of content from `start` like the legacy `/chunk` route does, as a 200 cut
+/// short at the end of the content. A `Range` header is served like `content_response` instead.
+pub async fn chunk_response(
+    req: &HttpRequest,
+    content: Content,
+    hash: &str,
+    start: u64,
+    size: u64,
+) -> Result<HttpResponse, OxenHttpError> {
+    if req.headers().contains_key(header::RANGE) {
+        return content_response(req, content, hash).await;
+    }
+
+    let etag = format!("\"{hash}\"");
+    if if_none_match(req, &etag) {
+        return Ok(not_modified(etag));
+    }
+
+    let (total, mime_type, content) = describe(content).await?;
+    let start = start.min(total);
+    let length = size.min(total - start);
+    let body = stream_content(content, start, length).await?;
+    Ok(HttpResponse::Ok()
+        .content_type(mime_type)
+        .insert_header((header::ETAG, etag))
+        .insert_header((header::ACCEPT_RANGES, "bytes"))
+        .no_chunking(length)
+        .streaming(body))
+}
+
+/// The binary data frame encoding asked for in the Accept header, None when the client wants JSON
+pub fn accepted_df_encoding(req: &HttpRequest) -> Option<DataFrameEncoding> {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .and_then(DataFrameEncoding::from_accept)
+}
+
+/// The data frame as an Arrow IPC stream or Parquet, encoded on the blocking thread pool and
+/// streamed `DF_BATCH_SIZE` rows at a time. Binary bodies have nowhere to put the pagination of
+/// the JSON responses, so it is sent in the `oxen-page-*` and `oxen-total-*` headers.
+pub fn df_response(
+    df: DataFrame,
+    encoding: DataFrameEncoding,
+    pagination: Option<Pagination>,
+) -> HttpResponse {
+    let (sender, receiver) = tokio::sync::mpsc::channel(DF_STREAM_BUFFER);
+    actix_web::rt::task::spawn_blocking(move || {
+        let mut writer =
+            BufWriter::with_capacity(STREAM_BUFFER_SIZE as usize, ChannelWriter(sender.clone()));
+        let result = encoding::encode_to(&df, encoding, &mut writer, DF_BATCH_SIZE)
+            .and_then(|_| writer.flush().map_err(OxenError::from));
+        if let Err(err) = result {
+            log::error!("df_response could not encode data frame: {err}");
+            let _ = sender.blocking_send(Err(std::io::Error::other(err.to_string())));
+        }
+    });
+    let body = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|item| (item, receiver))
+    });
+
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(encoding.mime_type())
+        .insert_header((header::VARY, "Accept"));
+    if let Some(pagination) = pagination {
+        response
+            .insert_header((PAGE_NUMBER_HEADER, pagination.page_number))
+            .insert_header((PAGE_SIZE_HEADER, pagination.page_size))
+            .insert_header((TOTAL_PAGES_HEADER, pagination.total_pages))
+            .insert_header((TOTAL_ENTRIES_HEADER, pagination.total_entries));
+    }
+    response.streaming(body)
+}
+
+/// JSON for a route that can also send a data frame in a binary encoding, so caches keep the
+/// two apart
+pub fn df_json_response(body: impl Serialize) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((header::VARY, "Accept"))
+        .json(body)
+}
+
+// Sends what is written to it on to a response body
+struct ChannelWriter(tokio::sync::mpsc::Sender<std::io::Result<web::Bytes>>);
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .blocking_send(Ok(web::Bytes::copy_from_slice(buf)))
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Whether the client already has this version, weak comparison as RFC 9110 asks for
+fn if_none_match(req: &HttpRequest, etag: &str) -> bool {
+    let Some(value) = req.headers().get(header::IF_NONE_MATCH) else {
+        return false;
+    };
+    let Ok(value) = value.to_str() else {
+        return false;
+    };
+    value
+        .split(',')
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+/// Whether a Range should be honored, only if the If-Range is missing or is our strong ETag.
+/// We never send Last-Modified, so an If-Range date cannot match.
+fn if_range(req: &HttpRequest, etag: &str) -> bool {
+    match req.headers().get(header::IF_RANGE) {
+        Some(value) => value
+            .to_str()
+            .map(|tag| tag.trim() == etag)
+            .unwrap_or(false),
+        None => true,
+    }
+}
+
+fn not_modified(etag: String) -> HttpResponse {
+    HttpResponse::NotModified()
+        .insert_header((header::ETAG, etag))
+        .finish()
+}
+
+/// The size and mime type of the content, read off the async runtime
+async fn describe(content: Content) -> Result<(u64, String, Content), OxenHttpError> {
+    Ok(web::block(move || -> Result<_, OxenError> {
+        Ok((content.size()?, content.mime_type()?, content))
+    })
+    .await??)
+}
+
+/// Streams `length` bytes of the content from `start`, opening it and every read happen on the
+/// blocking thread pool
+async fn stream_content(
+    content: Content,
+    start: u64,
+    length: u64,
+) -> Result<impl futures_util::Stream<Item = Result<web::Bytes, OxenHttpError>>, OxenHttpError> {
+    let reader = web::block(move || content.open_at(start)).await??;
+    Ok(futures_util::stream::unfold(
+        (reader, length),
+        |(mut reader, remaining)| async move {
+            if remaining == 0 {
+                return None;
+            }
+            let read = web::block(move || {
+                let mut buffer = vec![0u8; remaining.min(STREAM_BUFFER_SIZE) as usize];
+                let count = reader.read(&mut buffer)?;
+                buffer.truncate(count);
+                Ok::<_, std::io::Error>((buffer, reader))
+            })
+            .await;
+            match read {
+                Ok(Ok((buffer, _))) if buffer.is_empty() => None,
+                Ok(Ok((buffer, reader))) => {
+                    let count = buffer.len() as u64;
+                    Some((Ok(web::Bytes::from(buffer)), (reader, remaining - count)))
+                }
+                Ok(Err(err)) => Some((Err(err.into()), (empty_reader(), 0))),
+                Err(err) => Some((Err(err.into()), (empty_reader(), 0))),
+            }
+        },
+    ))
+}
+
+fn empty_reader() -> Box<dyn Read + Send> {
+    Box::new(std::io::empty())
+}
@@ -3,15 +3,21 @@ use dotenv::from_filename;
 use liboxen::config::UserConfig;
 use liboxen::model::User;
 
+pub mod admin;
 pub mod app_data;
+pub mod audit;
 pub mod auth;
 pub mod controllers;
 pub mod errors;
 pub mod helpers;
+pub mod metrics;
 pub mod middleware;
+pub mod mirror;
+pub mod openapi;
 pub mod params;
 pub mod queue_poller;
 pub mod queues;
+pub mod rate_limit;
 pub mod routes;
 pub mod services;
 pub mod tasks;
@@ -29,6 +35,7 @@ use env_logger::Env;
 
 use std::env;
 use std::io::Write;
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 
 const VERSION: &str = liboxen::constants::OXEN_VERSION;
@@ -38,8 +45,19 @@ const ADD_USER_USAGE: &str =
 
 const START_SERVER_USAGE: &str = "Usage: `oxen-server start -i 0.0.0.0 -p 3000`";
 
+const MIRROR_USAGE: &str = "Usage: `oxen-server mirror -s hub.example.com --read-only`";
+
+const REPO_USAGE: &str =
+    "Usage: `oxen-server repo [list|stats|delete|rename|migrate|gc|reindex|fsck] --json`";
+
+const INVALID_INTERVAL_MSG: &str = "Interval must be a number of seconds";
+
 const INVALID_PORT_MSG: &str = "Port must a valid number between 0-65535";
 
+const INVALID_LIMIT_MSG: &str = "Limits must be positive numbers";
+const INVALID_TRUSTED_PROXY_MSG: &str = "Trusted proxies must be ip addresses";
+const INVALID_WORKSPACE_TTL_MSG: &str = "Workspace ttl must be a positive number of seconds";
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
@@ -51,11 +69,15 @@ async fn main() -> std::io::Result<()> {
 
     env_logger::Builder::from_env(Env::default().default_filter_or("info,debug"))
         .format(|buf, record| {
+            let request_id = middleware::current_request_id()
+                .map(|id| format!(" [{id}]"))
+                .unwrap_or_default();
             writeln!(
                 buf,
-                "{} [{}] - {}: {}",
+                "{} [{}]{} - {}: {}",
                 chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f"),
                 record.level(),
+                request_id,
                 record.target(),
                 record.args()
             )
@@ -102,6 +124,36 @@ async fn main() -> std::io::Result<()> {
                         .short('a')
                         .help("Start the server with token-based authentication enforced")
                         .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("max-requests-per-second")
+                        .long("max-requests-per-second")
+                        .help("Requests per second allowed for each access key and for each ip")
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("max-bytes-per-second")
+                        .long("max-bytes-per-second")
+                        .help("Bytes per second uploaded and downloaded by each access key or ip")
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("max-concurrent-transfers")
+                        .long("max-concurrent-transfers")
+                        .help("File uploads and downloads each access key or ip can run at once")
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("trusted-proxy")
+                        .long("trusted-proxy")
+                        .help("Ip of a reverse proxy in front of the server, requests from it are limited by the client ip it forwards. Can be given more than once")
+                        .action(clap::ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("workspace-ttl")
+                        .long("workspace-ttl")
+                        .help("Seconds a workspace is kept without being used, unless it was created with its own ttl")
+                        .action(clap::ArgAction::Set),
                 ),
         )
         .subcommand(
@@ -123,6 +175,12 @@ async fn main() -> std::io::Result<()> {
                         .required(true)
                         .action(clap::ArgAction::Set),
                 )
+                .arg(
+                    Arg::new("admin")
+                        .long("admin")
+                        .help("The token can also read the audit log and manage background tasks")
+                        .action(clap::ArgAction::SetTrue),
+                )
                 .arg(
                     Arg::new("output")
                         .long("output")
@@ -132,7 +190,49 @@ async fn main() -> std::io::Result<()> {
                         .help("Where to write the output config file to give to the user")
                         .action(clap::ArgAction::Set),
                 ),
-        );
+        )
+        .subcommand(
+            Command::new("mirror")
+                .about(MIRROR_USAGE)
+                .arg(
+                    Arg::new("source")
+                        .long("source")
+                        .short('s')
+                        .help(
+                            "Host of the oxen-server to mirror, ie hub.example.com or 0.0.0.0:3000",
+                        )
+                        .required(true)
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("namespace")
+                        .long("namespace")
+                        .short('n')
+                        .help("Only mirror this namespace, can be given more than once")
+                        .action(clap::ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .short('t')
+                        .default_value("300")
+                        .help("Seconds to wait between syncs")
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("read-only")
+                        .long("read-only")
+                        .help("Reject pushes to the mirrored repositories")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("once")
+                        .long("once")
+                        .help("Sync once and exit instead of following the source")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(admin::command().about(REPO_USAGE));
     let matches = command.get_matches();
 
     match matches.subcommand() {
@@ -147,44 +247,84 @@ async fn main() -> std::io::Result<()> {
                     println!("Running on {host}:{port}");
                     println!("Syncing to directory: {sync_dir}");
                     let enable_auth = sub_matches.get_flag("auth");
+                    let rate_limits = rate_limit::RateLimitConfig {
+                        requests_per_second: sub_matches
+                            .get_one::<String>("max-requests-per-second")
+                            .map(|v| {
+                                v.parse::<f64>()
+                                    .ok()
+                                    .filter(|v| *v > 0.0)
+                                    .expect(INVALID_LIMIT_MSG)
+                            }),
+                        bytes_per_second: sub_matches
+                            .get_one::<String>("max-bytes-per-second")
+                            .map(|v| {
+                                v.parse::<u64>()
+                                    .ok()
+                                    .filter(|v| *v > 0)
+                                    .expect(INVALID_LIMIT_MSG)
+                            }),
+                        max_concurrent_transfers: sub_matches
+                            .get_one::<String>("max-concurrent-transfers")
+                            .map(|v| {
+                                v.parse::<usize>()
+                                    .ok()
+                                    .filter(|v| *v > 0)
+                                    .expect(INVALID_LIMIT_MSG)
+                            }),
+                        trusted_proxies: sub_matches
+                            .get_many::<String>("trusted-proxy")
+                            .unwrap_or_default()
+                            .map(|v| v.parse::<IpAddr>().expect(INVALID_TRUSTED_PROXY_MSG))
+                            .collect(),
+                    };
+                    // Shared by all the workers so a client cannot get around it by
+                    // landing on another one
+                    let limiter = rate_limit::RateLimiter::new(rate_limits);
 
                     log::debug!("initializing queue");
                     let queue = queue_poller::init_queue();
                     log::debug!("initialized queue");
-                    let data = app_data::OxenAppData::new(PathBuf::from(sync_dir), queue.clone());
+                    let mut data =
+                        app_data::OxenAppData::new(PathBuf::from(&sync_dir), queue.clone());
+                    data.workspace_ttl_secs =
+                        sub_matches.get_one::<String>("workspace-ttl").map(|v| {
+                            v.parse::<u64>()
+                                .ok()
+                                .filter(|v| *v > 0)
+                                .expect(INVALID_WORKSPACE_TTL_MSG)
+                        });
+                    let usage_sync_dir = PathBuf::from(&sync_dir);
+                    tokio::task::spawn_blocking(move || {
+                        metrics::load_repo_disk_usage(&usage_sync_dir)
+                    });
                     // Poll for post-commit tasks in background
                     log::debug!("initialized app data, spawning polling worker");
+                    let cleanup_queue = queue.clone();
                     tokio::spawn(async move { queue_poller::poll_queue(queue.clone()).await });
+                    tokio::spawn(async move {
+                        queue_poller::schedule_workspace_cleanup(
+                            cleanup_queue,
+                            PathBuf::from(sync_dir),
+                        )
+                        .await
+                    });
 
                     HttpServer::new(move || {
                         App::new()
                             .app_data(data.clone())
-                            .route("/api/version", web::get().to(controllers::version::index))
-                            .route(
-                                "/api/min_version",
-                                web::get().to(controllers::version::min_version),
-                            )
-                            .route("/api/health", web::get().to(controllers::health::index))
-                            .route(
-                                "/api/namespaces",
-                                web::get().to(controllers::namespaces::index),
-                            )
-                            .route(
-                                "/api/namespaces/{namespace}",
-                                web::get().to(controllers::namespaces::show),
-                            )
-                            .route(
-                                "/api/migrations/{migration_tstamp}",
-                                web::get().to(controllers::migrations::list_unmigrated),
-                            )
+                            .route("/metrics", web::get().to(controllers::metrics::index))
                             .wrap(Condition::new(
                                 enable_auth,
                                 HttpAuthentication::bearer(auth::validator::validate),
                             ))
-                            .service(web::scope("/api/repos").configure(routes::config))
+                            .service(routes::api().into_scope())
                             .default_service(web::route().to(controllers::not_found::index))
+                            .wrap(middleware::RateLimit::new(limiter.clone()))
+                            .wrap(middleware::AuditLog)
                             .wrap(Logger::default())
                             .wrap(Logger::new("user agent is %a %{User-Agent}i"))
+                            .wrap(middleware::RequestTracing)
                     })
                     .bind((host.to_owned(), port))?
                     .run()
@@ -210,7 +350,12 @@ async fn main() -> std::io::Result<()> {
                             name: name.to_string(),
                             email: email.to_string(),
                         };
-                        match keygen.create(&new_user) {
+                        let created = if sub_matches.get_flag("admin") {
+                            keygen.create_admin(&new_user)
+                        } else {
+                            keygen.create(&new_user)
+                        };
+                        match created {
                             Ok((user, token)) => {
                                 let cfg = UserConfig::from_user(&user);
                                 match cfg.save(Path::new(output)) {
@@ -235,6 +380,44 @@ async fn main() -> std::io::Result<()> {
 
             Ok(())
         }
+        Some(("mirror", sub_matches)) => {
+            let opts = mirror::MirrorOpts {
+                source: sub_matches
+                    .get_one::<String>("source")
+                    .expect("required")
+                    .to_owned(),
+                namespaces: sub_matches
+                    .get_many::<String>("namespace")
+                    .map(|n| n.cloned().collect())
+                    .unwrap_or_default(),
+                read_only: sub_matches.get_flag("read-only"),
+            };
+            let path = Path::new(&sync_dir);
+            println!("🐂 v{VERSION}");
+            println!("Mirroring {} to directory: {sync_dir}", opts.source);
+
+            if sub_matches.get_flag("once") {
+                if let Err(err) = mirror::sync(path, &opts).await {
+                    eprintln!("Err: {err}");
+                }
+            } else {
+                let interval = sub_matches
+                    .get_one::<String>("interval")
+                    .expect("has default")
+                    .parse::<u64>()
+                    .expect(INVALID_INTERVAL_MSG);
+                mirror::run(path, &opts, std::time::Duration::from_secs(interval)).await;
+            }
+
+            Ok(())
+        }
+        Some(("repo", sub_matches)) => {
+            if let Err(err) = admin::run_command(Path::new(&sync_dir), sub_matches) {
+                eprintln!("Err: {err}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
         _ => unreachable!(), // If all subcommands are defined above, anything else is unreachabe!()
     }
 }
@@ -0,0 +1,325 @@
+//! Process wide metrics for oxen-server, exported in the Prometheus text format on /metrics
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use liboxen::api;
+use liboxen::core::cache::cachers::repo_size;
+use liboxen::core::cache::commit_cacher;
+
+use crate::queues::{TaskQueue, TaskStatus};
+
+// Upper bounds of the histogram buckets, in seconds
+const REQUEST_DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+const TASK_DURATION_BUCKETS: [f64; 8] = [0.1, 1.0, 5.0, 15.0, 60.0, 300.0, 900.0, 3600.0];
+
+#[derive(Default)]
+struct Histogram {
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, buckets: &[f64], value: f64) {
+        if self.counts.is_empty() {
+            self.counts = vec![0; buckets.len()];
+        }
+        for (i, bound) in buckets.iter().enumerate() {
+            if value <= *bound {
+                self.counts[i] += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str, buckets: &[f64]) {
+        for (bound, count) in buckets.iter().zip(self.counts.iter()) {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    // (method, route, status) -> count
+    requests: BTreeMap<(String, String, u16), u64>,
+    // (method, route) -> seconds
+    request_durations: BTreeMap<(String, String), Histogram>,
+    // (service, direction) -> bytes
+    bytes: BTreeMap<(String, String), u64>,
+    // (task, outcome) -> seconds
+    task_durations: BTreeMap<(String, String), Histogram>,
+    // (namespace, repo) -> bytes, as of startup
+    repo_disk_usage: BTreeMap<(String, String), u64>,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Registry::default()))
+}
+
+/// Count a finished request against the route pattern it matched
+pub fn record_request(method: &str, route: &str, status: u16, seconds: f64) {
+    let mut registry = registry().lock().unwrap();
+    *registry
+        .requests
+        .entry((method.to_string(), route.to_string(), status))
+        .or_default() += 1;
+    registry
+        .request_durations
+        .entry((method.to_string(), route.to_string()))
+        .or_default()
+        .observe(&REQUEST_DURATION_BUCKETS, seconds);
+}
+
+/// Count bytes received ("in") or sent ("out") by one of the data transfer services
+pub fn record_bytes(service: &str, direction: &str, bytes: u64) {
+    let mut registry = registry().lock().unwrap();
+    *registry
+        .bytes
+        .entry((service.to_string(), direction.to_string()))
+        .or_default() += bytes;
+}
+
+/// Time a background task took, with an outcome of "succeeded" or "failed"
+pub fn record_task(name: &str, outcome: &str, seconds: f64) {
+    let mut registry = registry().lock().unwrap();
+    registry
+        .task_durations
+        .entry((name.to_string(), outcome.to_string()))
+        .or_default()
+        .observe(&TASK_DURATION_BUCKETS, seconds);
+}
+
+/// Read the cached size of every repository once, at startup. Later the sizes come from the
+/// commits cached by this process, so a scrape never has to walk the repositories.
+pub fn load_repo_disk_usage(sync_dir: &Path) {
+    let namespaces = match api::local::repositories::list_namespaces(sync_dir) {
+        Ok(namespaces) => namespaces,
+        Err(err) => {
+            log::error!("metrics could not list namespaces: {:?}", err);
+            return;
+        }
+    };
+    let mut usage = BTreeMap::new();
+    for namespace in namespaces {
+        let namespace_path = sync_dir.join(&namespace);
+        for repo in api::local::repositories::list_repos_in_namespace(&namespace_path) {
+            if let Ok(size) = api::local::namespaces::get_storage_for_repo(&repo) {
+                usage.insert((namespace.to_owned(), repo.dirname()), size);
+            }
+        }
+    }
+    registry().lock().unwrap().repo_disk_usage = usage;
+}
+
+/// Render all the metrics, reading the queue depth as of now
+pub fn render(queue: &TaskQueue, sync_dir: &Path) -> String {
+    let mut out = String::new();
+    {
+        let registry = registry().lock().unwrap();
+
+        header(
+            &mut out,
+            "oxen_http_requests_total",
+            "counter",
+            "HTTP requests by route and status",
+        );
+        for ((method, route, status), count) in registry.requests.iter() {
+            let _ = writeln!(
+                out,
+                "oxen_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                escape(method),
+                escape(route),
+                status,
+                count
+            );
+        }
+
+        header(
+            &mut out,
+            "oxen_http_request_duration_seconds",
+            "histogram",
+            "HTTP request latency by route",
+        );
+        for ((method, route), histogram) in registry.request_durations.iter() {
+            let labels = format!("method=\"{}\",route=\"{}\"", escape(method), escape(route));
+            histogram.render(
+                &mut out,
+                "oxen_http_request_duration_seconds",
+                &labels,
+                &REQUEST_DURATION_BUCKETS,
+            );
+        }
+
+        header(
+            &mut out,
+            "oxen_transfer_bytes_total",
+            "counter",
+            "Bytes moved by the transfer, chunk and versions services",
+        );
+        for ((service, direction), bytes) in registry.bytes.iter() {
+            let _ = writeln!(
+                out,
+                "oxen_transfer_bytes_total{{service=\"{}\",direction=\"{}\"}} {}",
+                escape(service),
+                escape(direction),
+                bytes
+            );
+        }
+
+        header(
+            &mut out,
+            "oxen_task_duration_seconds",
+            "histogram",
+            "Background task run time by task and outcome",
+        );
+        for ((name, outcome), histogram) in registry.task_durations.iter() {
+            let labels = format!("task=\"{}\",outcome=\"{}\"", escape(name), escape(outcome));
+            histogram.render(
+                &mut out,
+                "oxen_task_duration_seconds",
+                &labels,
+                &TASK_DURATION_BUCKETS,
+            );
+        }
+    }
+
+    header(
+        &mut out,
+        "oxen_task_queue_depth",
+        "gauge",
+        "Tasks in the queue by status",
+    );
+    match queue.list(None) {
+        Ok(tasks) => {
+            for status in [
+                TaskStatus::Queued,
+                TaskStatus::Running,
+                TaskStatus::Retrying,
+                TaskStatus::Dead,
+            ] {
+                let depth = tasks.iter().filter(|t| t.status == status).count();
+                let _ = writeln!(out, "oxen_task_queue_depth{{status=\"{status}\"}} {depth}");
+            }
+        }
+        Err(err) => log::error!("metrics could not list tasks: {:?}", err),
+    }
+
+    header(
+        &mut out,
+        "oxen_cacher_runs_total",
+        "counter",
+        "Commit cacher runs by cacher and outcome",
+    );
+    for (name, runs) in commit_cacher::cacher_runs() {
+        let name = escape(&name);
+        let _ = writeln!(
+            out,
+            "oxen_cacher_runs_total{{cacher=\"{name}\",outcome=\"succeeded\"}} {}",
+            runs.succeeded
+        );
+        let _ = writeln!(
+            out,
+            "oxen_cacher_runs_total{{cacher=\"{name}\",outcome=\"failed\"}} {}",
+            runs.failed
+        );
+    }
+
+    header(
+        &mut out,
+        "oxen_repo_disk_usage_bytes",
+        "gauge",
+        "Size of each repository as of its latest commit",
+    );
+    let mut usage = registry().lock().unwrap().repo_disk_usage.clone();
+    for (path, size) in repo_size::repo_sizes() {
+        let Ok(relative) = path.strip_prefix(sync_dir) else {
+            continue;
+        };
+        let mut components = relative.iter().map(|c| c.to_string_lossy().to_string());
+        if let (Some(namespace), Some(name), None) =
+            (components.next(), components.next(), components.next())
+        {
+            usage.insert((namespace, name), size);
+        }
+    }
+    for ((namespace, name), size) in usage {
+        // Deleted or renamed since
+        if !sync_dir.join(&namespace).join(&name).exists() {
+            continue;
+        }
+        let _ = writeln!(
+            out,
+            "oxen_repo_disk_usage_bytes{{namespace=\"{}\",repo=\"{}\"}} {}",
+            escape(&namespace),
+            escape(&name),
+            size
+        );
+    }
+
+    out
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::queues::{InMemoryTaskQueue, TaskQueue};
+    use crate::test;
+
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    #[test]
+    fn test_metrics_render_prometheus_text() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        test::create_local_repo(&sync_dir, "Testing-Namespace", "Testing-Metrics")?;
+        let queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+
+        super::record_request("GET", "/api/repos/{namespace}/{repo_name}", 200, 0.02);
+        super::record_bytes("versions", "in", 1024);
+        super::record_task("gc", "succeeded", 2.0);
+
+        super::load_repo_disk_usage(&sync_dir);
+
+        let text = super::render(&queue, &sync_dir);
+        assert!(text.contains("# TYPE oxen_http_requests_total counter"));
+        assert!(text.contains(
+            "oxen_http_requests_total{method=\"GET\",route=\"/api/repos/{namespace}/{repo_name}\",status=\"200\"}"
+        ));
+        assert!(text.contains(
+            "oxen_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/repos/{namespace}/{repo_name}\",le=\"0.025\"}"
+        ));
+        assert!(text.contains("oxen_transfer_bytes_total{service=\"versions\",direction=\"in\"}"));
+        assert!(
+            text.contains("oxen_task_duration_seconds_count{task=\"gc\",outcome=\"succeeded\"}")
+        );
+        assert!(text.contains("oxen_task_queue_depth{status=\"queued\"} 0"));
+        assert!(text.contains(
+            "oxen_repo_disk_usage_bytes{namespace=\"Testing-Namespace\",repo=\"Testing-Metrics\"}"
+        ));
+
+        util::fs::remove_dir_all(sync_dir)?;
+        Ok(())
+    }
+}
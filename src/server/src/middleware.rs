@@ -1 +1,691 @@
+use std::cell::Cell;
+use std::future::{ready, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
 
+use actix_http::BoxedPayloadStream;
+use actix_web::body::{BodySize, BoxBody, MessageBody};
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue, CONTENT_LENGTH};
+use actix_web::http::Method;
+use actix_web::web::Bytes;
+use actix_web::{Error, HttpMessage};
+use futures_util::future::LocalBoxFuture;
+use futures_util::StreamExt;
+use liboxen::model::LocalRepository;
+
+use crate::app_data::OxenAppData;
+use crate::audit::{self, AuditEntry};
+use crate::auth;
+use crate::errors::OxenHttpError;
+use crate::metrics;
+use crate::rate_limit::{self, RateLimiter, TransferPermit};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// Services that move repository data, their traffic is counted in bytes
+const TRANSFER_SERVICES: [&str; 3] = ["transfer", "chunk", "versions"];
+
+// Routes under a repository that take a POST only because the query is too big for a query
+// string, a read only mirror still serves them
+const READ_ONLY_POSTS: [&str; 2] = ["/search/nearest/", "/compare/data_frames/"];
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Id of the request being handled on this task, for tagging log lines
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Tags every request with an id, taken from the X-Request-Id header or generated, that is
+/// echoed back on the response and included in the logs, and records the request metrics.
+pub struct RequestTracing;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTracing
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = RequestTracingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestTracingMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestTracingMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTracingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .filter(|id| is_valid_request_id(id))
+            .map(String::from)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let method = req.method().to_string();
+        // Count the body as it is read, chunked uploads have no Content-Length
+        let bytes_in = Rc::new(Cell::new(0u64));
+        let counter = bytes_in.clone();
+        let payload = req.take_payload().inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                counter.set(counter.get() + chunk.len() as u64);
+            }
+        });
+        req.set_payload(Payload::from(Box::pin(payload) as BoxedPayloadStream));
+        let service = self.service.clone();
+        let start = Instant::now();
+
+        Box::pin(REQUEST_ID.scope(request_id.clone(), async move {
+            let result = service.call(req).await;
+            let seconds = start.elapsed().as_secs_f64();
+
+            let mut res = match result {
+                Ok(res) => res.map_into_boxed_body(),
+                Err(err) => {
+                    let status = err.as_response_error().status_code().as_u16();
+                    metrics::record_request(&method, "unmatched", status, seconds);
+                    return Err(err);
+                }
+            };
+
+            let route = res
+                .request()
+                .match_pattern()
+                .unwrap_or_else(|| String::from("unmatched"));
+            metrics::record_request(&method, &route, res.status().as_u16(), seconds);
+            if let Some(service) = transfer_service(&route) {
+                metrics::record_bytes(service, "in", bytes_in.get());
+                res = res.map_body(|_, body| BoxBody::new(CountedBody { body, service }));
+            }
+
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+            Ok(res)
+        }))
+    }
+}
+
+/// Rejects requests that would write to a repository that is a read only mirror. Wraps the
+/// `/{namespace}/{repo_name}` routes.
+pub struct ReadOnlyMirror;
+
+impl<S, B> Transform<S, ServiceRequest> for ReadOnlyMirror
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = ReadOnlyMirrorMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ReadOnlyMirrorMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct ReadOnlyMirrorMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ReadOnlyMirrorMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        if is_write(req.method()) && !is_read_only_post(&req) {
+            if let Some(repo) = read_only_repo(&req) {
+                let res = req.error_response(OxenHttpError::RepoReadOnly(repo.into()));
+                return Box::pin(ready(Ok(res)));
+            }
+        }
+        let service = self.service.clone();
+        Box::pin(async move { Ok(service.call(req).await?.map_into_boxed_body()) })
+    }
+}
+
+/// Appends every request that could change something on the server to the audit log, with the
+/// user behind the bearer token. Wraps the whole app, inside `RequestTracing`.
+pub struct AuditLog;
+
+impl<S, B> Transform<S, ServiceRequest> for AuditLog
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = AuditLogMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditLogMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct AuditLogMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuditLogMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let sync_dir = match req.app_data::<OxenAppData>() {
+            Some(app_data) if is_write(req.method()) => app_data.path.clone(),
+            _ => return Box::pin(async move { service.call(req).await }),
+        };
+        let user = auth::validator::identity(req.request()).user;
+        let method = req.method().to_string();
+        let path = req.path().to_string();
+
+        Box::pin(async move {
+            let result = service.call(req).await;
+
+            let mut entry = AuditEntry {
+                timestamp: audit::now(),
+                request_id: current_request_id(),
+                user,
+                method,
+                route: String::from("unmatched"),
+                path,
+                namespace: None,
+                repo_name: None,
+                branch: None,
+                commit_id: None,
+                status: 0,
+            };
+            match &result {
+                Ok(res) => {
+                    let request = res.request();
+                    if let Some(route) = request.match_pattern() {
+                        entry.route = route;
+                    }
+                    let params = request.match_info();
+                    entry.namespace = params.get("namespace").map(String::from);
+                    entry.repo_name = params.get("repo_name").map(String::from);
+                    entry.branch = params
+                        .get("branch_name")
+                        .or_else(|| params.get("branch"))
+                        .map(String::from);
+                    entry.commit_id = params.get("commit_id").map(String::from);
+                    entry.status = res.status().as_u16();
+                }
+                Err(err) => {
+                    entry.status = err.as_response_error().status_code().as_u16();
+                }
+            }
+            audit::record(&sync_dir, entry);
+
+            result
+        })
+    }
+}
+
+/// Turns clients over their request, bytes or concurrent transfer limits away with a 429 and a
+/// Retry-After. Wraps the whole app, inside `AuditLog` so rejected writes are still audited.
+pub struct RateLimit {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimit {
+    pub fn new(limiter: Arc<RateLimiter>) -> RateLimit {
+        RateLimit { limiter }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            limiter: self.limiter.clone(),
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        if self.limiter.is_unlimited() {
+            return Box::pin(async move { Ok(service.call(req).await?.map_into_boxed_body()) });
+        }
+
+        let clients = self.limiter.clients(req.request());
+        let permit = match self
+            .limiter
+            .acquire(&clients, rate_limit::is_transfer(req.path()))
+        {
+            Ok(permit) => permit,
+            Err(retry_after) => {
+                log::warn!(
+                    "rate limited {} {} for {}s",
+                    req.method(),
+                    req.path(),
+                    retry_after
+                );
+                let res = req.error_response(OxenHttpError::TooManyRequests(retry_after));
+                return Box::pin(ready(Ok(res)));
+            }
+        };
+        let bytes_in = req
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
+        self.limiter.record_bytes(&clients, bytes_in);
+
+        let limiter = self.limiter.clone();
+        Box::pin(async move {
+            let res = service.call(req).await?.map_into_boxed_body();
+            Ok(res.map_body(|_, body| {
+                BoxBody::new(MeteredBody {
+                    body,
+                    limiter,
+                    clients,
+                    _permit: permit,
+                })
+            }))
+        })
+    }
+}
+
+/// Response body that counts the bytes sent against the clients' limits as they go out, and
+/// holds on to their transfer slots until the download is done
+struct MeteredBody {
+    body: BoxBody,
+    limiter: Arc<RateLimiter>,
+    clients: Vec<String>,
+    _permit: Option<TransferPermit>,
+}
+
+impl MessageBody for MeteredBody {
+    type Error = <BoxBody as MessageBody>::Error;
+
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.body).poll_next(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+            this.limiter.record_bytes(&this.clients, chunk.len() as u64);
+        }
+        poll
+    }
+}
+
+/// Response body that counts the bytes sent by a transfer service as they go out
+struct CountedBody {
+    body: BoxBody,
+    service: &'static str,
+}
+
+impl MessageBody for CountedBody {
+    type Error = <BoxBody as MessageBody>::Error;
+
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.body).poll_next(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+            metrics::record_bytes(this.service, "out", chunk.len() as u64);
+        }
+        poll
+    }
+}
+
+fn is_write(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+// A POST that only reads, judged by the path after the repository scope, ie "/search/nearest/.."
+fn is_read_only_post(req: &ServiceRequest) -> bool {
+    let rest = req.match_info().unprocessed();
+    req.method() == Method::POST && READ_ONLY_POSTS.iter().any(|route| rest.starts_with(route))
+}
+
+/// "namespace/repo_name" if the request targets a read only repository
+fn read_only_repo(req: &ServiceRequest) -> Option<String> {
+    let app_data = req.app_data::<OxenAppData>()?;
+    let namespace = req.match_info().get("namespace")?;
+    let repo_name = req.match_info().get("repo_name")?;
+    let repo = LocalRepository::from_dir(&app_data.path.join(namespace).join(repo_name)).ok()?;
+    repo.is_read_only()
+        .then(|| format!("{namespace}/{repo_name}"))
+}
+
+// Don't let clients put arbitrary text in our logs
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+/// The data transfer service a route pattern belongs to, from the segment after the repo name
+fn transfer_service(route: &str) -> Option<&'static str> {
+    let rest = route.strip_prefix("/api/repos/{namespace}/{repo_name}/")?;
+    let segment = rest.split('/').next()?;
+    TRANSFER_SERVICES.into_iter().find(|s| *s == segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::header::AUTHORIZATION;
+    use actix_web::{test, web, App, HttpResponse};
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use super::*;
+    use crate::test as test_helpers;
+
+    #[actix_web::test]
+    async fn test_request_tracing_sets_request_id() {
+        let app = test::init_service(App::new().wrap(RequestTracing).route(
+            "/api/repos/{namespace}/{repo_name}/versions",
+            web::post().to(|| async {
+                let id = current_request_id().unwrap_or_default();
+                HttpResponse::Ok().body(id)
+            }),
+        ))
+        .await;
+
+        // A valid id from the client is kept and visible to the handler
+        let req = test::TestRequest::post()
+            .uri("/api/repos/ox/data/versions")
+            .insert_header((REQUEST_ID_HEADER, "abc-123"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(REQUEST_ID_HEADER).unwrap(), "abc-123");
+        let body = test::read_body(res).await;
+        assert_eq!(body, "abc-123");
+
+        // Otherwise we make one up
+        let req = test::TestRequest::post()
+            .uri("/api/repos/ox/data/versions")
+            .insert_header((REQUEST_ID_HEADER, "not valid"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let id = res
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+    }
+
+    #[actix_web::test]
+    async fn test_request_tracing_counts_unsized_transfers() {
+        let app = test::init_service(App::new().wrap(RequestTracing).route(
+            "/api/repos/{namespace}/{repo_name}/chunk/{resource:.*}",
+            web::post().to(|body: Bytes| async move {
+                let chunks = futures_util::stream::iter(vec![Ok::<_, Error>(body)]);
+                HttpResponse::Ok().streaming(chunks)
+            }),
+        ))
+        .await;
+
+        // No Content-Length on the way in or out
+        let req = test::TestRequest::post()
+            .uri("/api/repos/ox/data/chunk/main/data.txt")
+            .set_payload("hello")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(test::read_body(res).await, "hello");
+
+        let queue = crate::queues::TaskQueue::InMemory(crate::queues::InMemoryTaskQueue::new());
+        let text = metrics::render(&queue, std::path::Path::new(""));
+        assert!(text.contains("oxen_transfer_bytes_total{service=\"chunk\",direction=\"in\"} 5"));
+        assert!(text.contains("oxen_transfer_bytes_total{service=\"chunk\",direction=\"out\"} 5"));
+    }
+
+    #[actix_web::test]
+    async fn test_read_only_mirror_rejects_writes() -> Result<(), OxenError> {
+        let sync_dir = test_helpers::get_sync_dir()?;
+        let repo =
+            test_helpers::create_local_repo(&sync_dir, "Testing-Namespace", "Testing-Mirror")?;
+        repo.write_is_read_only(true)?;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(
+                    sync_dir.to_owned(),
+                    test_helpers::init_queue(),
+                ))
+                .service(
+                    web::scope("/api/repos/{namespace}/{repo_name}")
+                        .wrap(ReadOnlyMirror)
+                        .route("/commits", web::get().to(HttpResponse::Ok))
+                        .route("/commits", web::post().to(HttpResponse::Ok))
+                        .route(
+                            "/search/nearest/{resource:.*}",
+                            web::post().to(HttpResponse::Ok),
+                        ),
+                ),
+        )
+        .await;
+
+        let uri = "/api/repos/Testing-Namespace/Testing-Mirror/commits";
+        let res = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+
+        let res = test::call_service(&app, test::TestRequest::post().uri(uri).to_request()).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+        // Searching only reads
+        let nearest_uri = "/api/repos/Testing-Namespace/Testing-Mirror/search/nearest/main/a.jsonl";
+        let req = test::TestRequest::post().uri(nearest_uri).to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+
+        // Once it is no longer a mirror we can push again
+        repo.write_is_read_only(false)?;
+        let res = test::call_service(&app, test::TestRequest::post().uri(uri).to_request()).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+
+        util::fs::remove_dir_all(sync_dir)?;
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_audit_log_records_writes() -> Result<(), OxenError> {
+        let sync_dir = test_helpers::get_sync_dir()?;
+        let keygen = crate::auth::access_keys::AccessKeyManager::new(&sync_dir)?;
+        let (_user, token) = keygen.create(&liboxen::model::User {
+            name: String::from("Ox"),
+            email: String::from("ox@oxen.ai"),
+        })?;
+        // Close the keys db so the token is on disk when the middleware reads it
+        drop(keygen);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(
+                    sync_dir.to_owned(),
+                    test_helpers::init_queue(),
+                ))
+                .wrap(AuditLog)
+                .route(
+                    "/api/repos/{namespace}/{repo_name}/branches/{branch_name}",
+                    web::get().to(HttpResponse::Ok),
+                )
+                .route(
+                    "/api/repos/{namespace}/{repo_name}/branches/{branch_name}",
+                    web::delete().to(HttpResponse::Ok),
+                ),
+        )
+        .await;
+
+        let uri = "/api/repos/ox/data/branches/dev";
+        test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        let req = test::TestRequest::delete()
+            .uri(uri)
+            .insert_header((AUTHORIZATION, format!("Bearer {token}")))
+            .to_request();
+        test::call_service(&app, req).await;
+
+        // Reads are not audited
+        audit::flush();
+        let entries = audit::query(&sync_dir, audit::AuditFilter::default())?;
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.method, "DELETE");
+        assert_eq!(
+            entry.route,
+            "/api/repos/{namespace}/{repo_name}/branches/{branch_name}"
+        );
+        assert_eq!(entry.namespace.as_deref(), Some("ox"));
+        assert_eq!(entry.repo_name.as_deref(), Some("data"));
+        assert_eq!(entry.user.as_deref(), Some("ox@oxen.ai"));
+        assert_eq!(entry.branch.as_deref(), Some("dev"));
+        assert_eq!(entry.status, 200);
+
+        util::fs::remove_dir_all(sync_dir)?;
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_rate_limit_returns_retry_after() {
+        let limiter = RateLimiter::new(crate::rate_limit::RateLimitConfig {
+            requests_per_second: Some(1.0),
+            ..Default::default()
+        });
+        let app = test::init_service(App::new().wrap(RateLimit::new(limiter)).route(
+            "/api/repos/{namespace}/{repo_name}/branches",
+            web::get().to(HttpResponse::Ok),
+        ))
+        .await;
+
+        let uri = "/api/repos/ox/data/branches";
+        let res = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+
+        let res = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            res.headers()
+                .get(actix_web::http::header::RETRY_AFTER)
+                .unwrap(),
+            "1"
+        );
+
+        // Making up a token does not get around it, it only counts once it is validated
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header((AUTHORIZATION, "Bearer made-up-token"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::TOO_MANY_REQUESTS);
+
+        // Someone else is not held back by it
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_transfer_service_from_route() {
+        assert_eq!(
+            transfer_service("/api/repos/{namespace}/{repo_name}/versions"),
+            Some("versions")
+        );
+        assert_eq!(
+            transfer_service("/api/repos/{namespace}/{repo_name}/chunk/{resource:.*}"),
+            Some("chunk")
+        );
+        assert_eq!(
+            transfer_service("/api/repos/{namespace}/{repo_name}/branches"),
+            None
+        );
+    }
+}
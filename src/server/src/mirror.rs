@@ -0,0 +1,130 @@
+//! Follow the repositories on another oxen-server, to run a read replica of it
+
+use std::path::Path;
+use std::time::Duration;
+
+use liboxen::api;
+use liboxen::constants::{DEFAULT_BRANCH_NAME, DEFAULT_REMOTE_NAME};
+use liboxen::core::cache::commit_cacher;
+use liboxen::core::index::{EntryIndexer, RefWriter};
+use liboxen::error::OxenError;
+use liboxen::model::{LocalRepository, Remote};
+use liboxen::util;
+
+pub struct MirrorOpts {
+    /// Host of the server we are mirroring, ie "hub.example.com" or "0.0.0.0:3000"
+    pub source: String,
+    /// Only mirror these namespaces, all of them if empty
+    pub namespaces: Vec<String>,
+    /// Reject pushes to the mirrored repositories
+    pub read_only: bool,
+}
+
+/// Sync every repository from the source, then again every `interval`
+pub async fn run(sync_dir: &Path, opts: &MirrorOpts, interval: Duration) {
+    loop {
+        if let Err(err) = sync(sync_dir, opts).await {
+            log::error!("mirror of {} failed: {}", opts.source, err);
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// One pass over all the repositories on the source. A namespace or repository that fails to
+/// sync is logged and picked up again on the next pass.
+pub async fn sync(sync_dir: &Path, opts: &MirrorOpts) -> Result<(), OxenError> {
+    let namespaces = if opts.namespaces.is_empty() {
+        api::remote::repositories::list_namespaces(&opts.source).await?
+    } else {
+        opts.namespaces.clone()
+    };
+
+    for namespace in namespaces {
+        let repos =
+            match api::remote::repositories::list_in_namespace(&opts.source, &namespace).await {
+                Ok(repos) => repos,
+                Err(err) => {
+                    log::error!("mirror could not list namespace {}: {}", namespace, err);
+                    continue;
+                }
+            };
+        for repo in repos {
+            match sync_repo(sync_dir, opts, &namespace, &repo.name).await {
+                Ok(0) => log::debug!("mirror {}/{} up to date", namespace, repo.name),
+                Ok(n) => log::info!("mirror {}/{} synced {} commits", namespace, repo.name, n),
+                Err(err) => log::error!("mirror {}/{} failed: {}", namespace, repo.name, err),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Pull the new commits, refs and version files of one repository, returns how many commits
+/// were synced
+pub async fn sync_repo(
+    sync_dir: &Path,
+    opts: &MirrorOpts,
+    namespace: &str,
+    name: &str,
+) -> Result<usize, OxenError> {
+    let url = api::endpoint::remote_url_from_namespace_name(&opts.source, namespace, name);
+    let remote = Remote {
+        name: String::from(DEFAULT_REMOTE_NAME),
+        url: url.to_owned(),
+    };
+    let remote_repo = api::remote::repositories::get_by_remote(&remote)
+        .await?
+        .ok_or_else(|| OxenError::remote_repo_not_found(&url))?;
+
+    let repo_dir = sync_dir.join(namespace).join(name);
+    let is_new = !util::fs::config_filepath(&repo_dir).exists();
+    let repo = if is_new {
+        util::fs::create_dir_all(util::fs::oxen_hidden_dir(&repo_dir))?;
+        let repo = LocalRepository::from_remote(remote_repo.clone(), &repo_dir)?;
+        repo.save_default()?;
+        repo
+    } else {
+        let repo = LocalRepository::from_dir(&repo_dir)?;
+        // Never overwrite a repository that was pushed to this server directly
+        if repo.get_remote(DEFAULT_REMOTE_NAME).map(|r| r.url) != Some(url.to_owned()) {
+            return Err(OxenError::basic_str(format!(
+                "Repository {namespace}/{name} exists and is not a mirror of {url}"
+            )));
+        }
+        repo
+    };
+    repo.write_is_read_only(opts.read_only)?;
+
+    let remote_branches = api::remote::branches::list(&remote_repo).await?;
+    let indexer = EntryIndexer::new(&repo)?;
+    let mut n_synced = 0;
+    for branch in remote_branches.iter() {
+        let commits = indexer.mirror_branch(&remote_repo, branch).await?;
+        for commit in commits.iter() {
+            commit_cacher::run_all(&repo, commit, false)?;
+        }
+        n_synced += commits.len();
+    }
+
+    // Drop the branches that were deleted on the source
+    for branch in api::local::branches::list(&repo)? {
+        if !remote_branches.iter().any(|b| b.name == branch.name) {
+            // Fails for the HEAD branch, which we keep until the source picks a new default
+            if let Err(err) = api::local::branches::force_delete(&repo, &branch.name) {
+                log::warn!("mirror could not delete branch {}: {}", branch.name, err);
+            }
+        }
+    }
+
+    if is_new {
+        let head = remote_branches
+            .iter()
+            .find(|b| b.name == DEFAULT_BRANCH_NAME)
+            .or(remote_branches.first());
+        if let Some(head) = head {
+            RefWriter::new(&repo)?.set_head(&head.name);
+        }
+    }
+
+    Ok(n_synced)
+}
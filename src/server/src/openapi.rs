@@ -0,0 +1,545 @@
+//! OpenAPI 3 document for the server, served on /api/openapi.json
+//!
+//! Routes are added through `ApiScope`, which keeps an `Operation` with the method, summary,
+//! response and request body of each route next to the actix route it registers. The document
+//! is built from those operations, so a route can't be served without being documented, and the
+//! schemas come from the view types with utoipa's `#[derive(ToSchema)]`.
+
+use actix_web::dev::HttpServiceFactory;
+use actix_web::http::Method;
+use actix_web::{guard, web, FromRequest, Handler, Responder, Scope};
+use liboxen::constants::OXEN_VERSION;
+use liboxen::core::df::encoding::{ARROW_STREAM_MIME_TYPE, PARQUET_MIME_TYPE};
+use liboxen::view::StatusMessage;
+use serde_json::{json, Map, Value};
+use utoipa::openapi::schema::Schema;
+use utoipa::openapi::RefOr;
+use utoipa::ToSchema;
+
+use crate::routes;
+
+type SchemaFn = fn(&mut Components) -> Value;
+
+/// Registers a route or a nested scope on the scope being built
+type Register = Box<dyn FnOnce(&mut web::ServiceConfig)>;
+
+/// The named schemas of the document, `components/schemas`
+#[derive(Default)]
+struct Components {
+    schemas: Map<String, Value>,
+}
+
+impl Components {
+    /// A `$ref` to the schema of `T`, adding it and the schemas it refers to. Arrays, primitives
+    /// and any JSON are inlined instead
+    fn reference<T: ToSchema>(&mut self) -> Value {
+        let mut schemas = vec![];
+        T::schemas(&mut schemas);
+        for (name, schema) in schemas {
+            self.insert(name, schema);
+        }
+
+        let schema = T::schema();
+        if !is_named(&schema) {
+            return to_json(schema);
+        }
+        let name = T::name().into_owned();
+        self.insert(name.clone(), schema);
+        json!({ "$ref": format!("#/components/schemas/{name}") })
+    }
+
+    fn insert(&mut self, name: String, schema: RefOr<Schema>) {
+        let schema = to_json(schema);
+        match self.schemas.get(&name) {
+            Some(existing) if *existing != schema => {
+                panic!("two different types use the schema name {name}")
+            }
+            Some(_) => {}
+            None => {
+                self.schemas.insert(name, schema);
+            }
+        }
+    }
+}
+
+/// Structs, enums and compositions get a name, the schemas of `Vec`, `String` or `Value` don't
+fn is_named(schema: &RefOr<Schema>) -> bool {
+    match schema {
+        RefOr::Ref(_) => false,
+        RefOr::T(Schema::Object(object)) => {
+            !object.properties.is_empty() || object.enum_values.is_some()
+        }
+        RefOr::T(Schema::Array(_)) => false,
+        RefOr::T(_) => true,
+    }
+}
+
+fn to_json(schema: RefOr<Schema>) -> Value {
+    serde_json::to_value(schema).expect("OpenAPI schemas serialize to JSON")
+}
+
+/// What a route responds with, or takes as its request body
+#[derive(Clone, Copy)]
+enum Content {
+    Json(SchemaFn),
+    /// A page of rows as JSON, or all of them as Arrow or Parquet if the Accept header asks
+    DataFrame(SchemaFn),
+    Binary,
+    Text,
+    Multipart,
+}
+
+/// Documents a single route
+pub struct Operation {
+    method: Method,
+    path: String,
+    summary: &'static str,
+    response: Content,
+    body: Option<Content>,
+}
+
+impl Operation {
+    fn new(method: Method, summary: &'static str) -> Operation {
+        Operation {
+            method,
+            path: String::new(),
+            summary,
+            response: Content::Json(Components::reference::<StatusMessage>),
+            body: None,
+        }
+    }
+
+    pub fn get(summary: &'static str) -> Operation {
+        Operation::new(Method::GET, summary)
+    }
+
+    pub fn post(summary: &'static str) -> Operation {
+        Operation::new(Method::POST, summary)
+    }
+
+    pub fn put(summary: &'static str) -> Operation {
+        Operation::new(Method::PUT, summary)
+    }
+
+    pub fn patch(summary: &'static str) -> Operation {
+        Operation::new(Method::PATCH, summary)
+    }
+
+    pub fn delete(summary: &'static str) -> Operation {
+        Operation::new(Method::DELETE, summary)
+    }
+
+    /// Responds with `T` as JSON, a plain `StatusMessage` if not set
+    pub fn json<T: ToSchema>(mut self) -> Operation {
+        self.response = Content::Json(Components::reference::<T>);
+        self
+    }
+
+    /// Responds with a page of a data frame as `T`, or the rows in the format the client accepts
+    pub fn data_frame<T: ToSchema>(mut self) -> Operation {
+        self.response = Content::DataFrame(Components::reference::<T>);
+        self
+    }
+
+    pub fn binary(mut self) -> Operation {
+        self.response = Content::Binary;
+        self
+    }
+
+    pub fn text(mut self) -> Operation {
+        self.response = Content::Text;
+        self
+    }
+
+    /// Takes `T` as a JSON request body
+    pub fn body<T: ToSchema>(mut self) -> Operation {
+        self.body = Some(Content::Json(Components::reference::<T>));
+        self
+    }
+
+    pub fn binary_body(mut self) -> Operation {
+        self.body = Some(Content::Binary);
+        self
+    }
+
+    pub fn multipart_body(mut self) -> Operation {
+        self.body = Some(Content::Multipart);
+        self
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Path relative to the scope the operation was added to, with the route regexes removed
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn to_json(&self, path: &str, components: &mut Components) -> Value {
+        let parameters: Vec<Value> = path_params(path)
+            .into_iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            })
+            .collect();
+
+        let status = components.reference::<StatusMessage>();
+        let mut operation = json!({
+            "summary": self.summary,
+            "tags": [tag(path)],
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "description": "OK",
+                    "content": content(self.response, components),
+                },
+                "default": {
+                    "description": "Error",
+                    "content": { "application/json": { "schema": status } },
+                },
+            },
+        });
+        if let Some(body) = self.body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": content(body, components),
+            });
+        }
+        operation
+    }
+}
+
+/// A `web::scope` that documents the routes added to it
+pub struct ApiScope {
+    path: String,
+    operations: Vec<Operation>,
+    services: Vec<Register>,
+}
+
+impl ApiScope {
+    pub fn new(path: &str) -> ApiScope {
+        ApiScope {
+            path: path.to_string(),
+            operations: vec![],
+            services: vec![],
+        }
+    }
+
+    /// Serve `operation` on `path` with `handler`
+    pub fn route<F, Args>(self, path: &str, operation: Operation, handler: F) -> ApiScope
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        let route = web::method(operation.method.clone()).to(handler);
+        let path_owned = path.to_string();
+        self.add(path, operation, move |cfg| {
+            cfg.route(&path_owned, route);
+        })
+    }
+
+    /// Like `route`, on a resource named for `HttpRequest::url_for`. The method guard goes on the
+    /// resource so other methods on the same path fall through to their own routes
+    pub fn named_route<F, Args>(
+        self,
+        path: &str,
+        name: &str,
+        operation: Operation,
+        handler: F,
+    ) -> ApiScope
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        let resource = web::resource(path)
+            .name(name)
+            .guard(guard::Method(operation.method.clone()))
+            .to(handler);
+        self.add(path, operation, move |cfg| {
+            cfg.service(resource);
+        })
+    }
+
+    /// Nest the routes of another scope
+    pub fn service(self, nested: ApiScope) -> ApiScope {
+        self.service_with(nested, |scope| scope)
+    }
+
+    /// Nest another scope, after `wrap` applies its middleware
+    pub fn service_with<W, S>(mut self, nested: ApiScope, wrap: W) -> ApiScope
+    where
+        W: FnOnce(Scope) -> S + 'static,
+        S: HttpServiceFactory + 'static,
+    {
+        let ApiScope {
+            path,
+            operations,
+            services,
+        } = nested;
+        for mut operation in operations {
+            operation.path = format!("{path}{}", operation.path);
+            self.operations.push(operation);
+        }
+        self.services.push(Box::new(move |cfg| {
+            cfg.service(wrap(into_scope(&path, services)));
+        }));
+        self
+    }
+
+    pub fn operations(&self) -> impl Iterator<Item = (String, &Operation)> {
+        self.operations
+            .iter()
+            .map(|operation| (format!("{}{}", self.path, operation.path), operation))
+    }
+
+    pub fn into_scope(self) -> Scope {
+        into_scope(&self.path, self.services)
+    }
+
+    fn add(
+        mut self,
+        path: &str,
+        mut operation: Operation,
+        service: impl FnOnce(&mut web::ServiceConfig) + 'static,
+    ) -> ApiScope {
+        operation.path = strip_regexes(path);
+        self.operations.push(operation);
+        self.services.push(Box::new(service));
+        self
+    }
+}
+
+fn into_scope(path: &str, services: Vec<Register>) -> Scope {
+    services
+        .into_iter()
+        .fold(web::scope(path), |scope, service| scope.configure(service))
+}
+
+/// The whole OpenAPI document
+pub fn spec() -> Value {
+    let mut components = Components::default();
+    let mut paths = Map::new();
+    for (path, operation) in routes::api().operations() {
+        let item = paths.entry(path.to_owned()).or_insert_with(|| json!({}));
+        item[operation.method.as_str().to_lowercase()] = operation.to_json(&path, &mut components);
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Oxen Server API",
+            "version": OXEN_VERSION,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": components.schemas,
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer" },
+            },
+        },
+        "security": [{ "bearerAuth": [] }],
+    })
+}
+
+fn content(content: Content, components: &mut Components) -> Value {
+    let binary = json!({ "schema": { "type": "string", "format": "binary" } });
+    match content {
+        Content::Json(schema) => json!({
+            "application/json": { "schema": schema(components) },
+        }),
+        Content::DataFrame(schema) => json!({
+            "application/json": { "schema": schema(components) },
+            ARROW_STREAM_MIME_TYPE: binary,
+            PARQUET_MIME_TYPE: binary,
+        }),
+        Content::Binary => json!({ "application/octet-stream": binary }),
+        Content::Text => json!({ "text/plain": { "schema": { "type": "string" } } }),
+        Content::Multipart => json!({
+            "multipart/form-data": {
+                "schema": {
+                    "type": "object",
+                    "properties": {
+                        "file[]": {
+                            "type": "array",
+                            "items": { "type": "string", "format": "binary" },
+                        },
+                    },
+                },
+            },
+        }),
+    }
+}
+
+/// Drop the regexes from the params of an actix path, "{branch_name:.*}" => "{branch_name}"
+fn strip_regexes(path: &str) -> String {
+    let mut stripped = String::new();
+    let mut in_regex = false;
+    for c in path.chars() {
+        match c {
+            ':' if stripped.rfind('{') > stripped.rfind('}') => in_regex = true,
+            '}' => {
+                in_regex = false;
+                stripped.push(c);
+            }
+            _ if !in_regex => stripped.push(c),
+            _ => {}
+        }
+    }
+    // actix adds the leading slash to patterns like "{column_name}/resource"
+    if !stripped.is_empty() && !stripped.starts_with('/') {
+        stripped.insert(0, '/');
+    }
+    stripped
+}
+
+/// Names of the `{params}` in a path
+fn path_params(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split('}').next())
+        .collect()
+}
+
+/// Group operations by the service they belong to, ie "branches"
+fn tag(path: &str) -> &str {
+    let rest = path
+        .strip_prefix("/api/repos/{namespace}/{repo_name}")
+        .or_else(|| path.strip_prefix("/api"))
+        .unwrap_or(path);
+    match rest.split('/').nth(1) {
+        Some("") | None => "repositories",
+        Some("repos") => "repositories",
+        Some(segment) => segment,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::app_data::OxenAppData;
+    use crate::routes;
+
+    #[test]
+    fn test_openapi_schemas_from_views() {
+        let spec = super::spec();
+        assert_eq!(spec["openapi"], "3.1.0");
+
+        let schemas = &spec["components"]["schemas"];
+        // StatusMessage is flattened into the response, next to its own fields
+        let branches = &schemas["ListBranchesResponse"]["allOf"];
+        assert_eq!(branches[0]["$ref"], "#/components/schemas/StatusMessage");
+        assert_eq!(branches[1]["properties"]["branches"]["type"], "array");
+        assert_eq!(
+            branches[1]["properties"]["branches"]["items"]["$ref"],
+            "#/components/schemas/Branch"
+        );
+        assert_eq!(
+            schemas["StatusMessage"]["properties"]["status"]["type"],
+            "string"
+        );
+        assert_eq!(
+            schemas["Branch"]["properties"]["is_head"]["type"],
+            "boolean"
+        );
+        let paginated = &schemas["PaginatedCommits"]["allOf"];
+        assert_eq!(paginated[1]["$ref"], "#/components/schemas/Pagination");
+        assert_eq!(
+            schemas["Pagination"]["properties"]["total_pages"]["type"],
+            "integer"
+        );
+
+        let branch = &spec["paths"]["/api/repos/{namespace}/{repo_name}/branches/{branch_name}"];
+        assert_eq!(branch["get"]["parameters"].as_array().unwrap().len(), 3);
+        assert_eq!(branch["get"]["tags"][0], "branches");
+        assert_eq!(
+            branch["put"]["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/BranchUpdate"
+        );
+
+        // Every referenced schema exists
+        let text = spec.to_string();
+        for reference in text.split("#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.get(name).is_some(), "missing schema {name}");
+        }
+    }
+
+    #[test]
+    fn test_openapi_operations_are_unique() {
+        let api = routes::api();
+        let mut seen = HashSet::new();
+        for (path, operation) in api.operations() {
+            assert!(
+                seen.insert((operation.method().clone(), path.to_owned())),
+                "{} {path} is registered twice",
+                operation.method()
+            );
+        }
+        assert!(seen.len() > 100, "only found {} operations", seen.len());
+    }
+
+    #[actix_web::test]
+    async fn test_openapi_operations_are_served() -> Result<(), OxenError> {
+        let sync_dir = crate::test::get_sync_dir()?;
+        let queue = crate::test::init_queue();
+        let app = init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.to_path_buf(), queue))
+                .service(routes::api().into_scope())
+                .default_service(web::to(HttpResponse::ImATeapot)),
+        )
+        .await;
+
+        // Fill in the path params, the repository doesn't exist so the handlers return early
+        for (path, operation) in routes::api().operations() {
+            let uri = path
+                .split('/')
+                .map(|segment| {
+                    if segment.starts_with('{') {
+                        "x"
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            let req = TestRequest::default()
+                .method(operation.method().clone())
+                .uri(&uri)
+                .to_request();
+            let resp = call_service(&app, req).await;
+            let status = resp.status();
+            assert!(
+                status != StatusCode::IM_A_TEAPOT && status != StatusCode::METHOD_NOT_ALLOWED,
+                "{} {path} is documented but not served, got {status}",
+                operation.method()
+            );
+            // Handlers say what they did not find, a route that didn't match has no body
+            if status == StatusCode::NOT_FOUND {
+                assert!(
+                    !read_body(resp).await.is_empty(),
+                    "{} {path} is documented but not routed",
+                    operation.method()
+                );
+            }
+        }
+
+        util::fs::remove_dir_all(sync_dir)?;
+        Ok(())
+    }
+}
@@ -15,12 +15,33 @@ use crate::errors::OxenHttpError;
 pub mod aggregate_query;
 pub use aggregate_query::AggregateQuery;
 
+pub mod audit_query;
+pub use audit_query::AuditQuery;
+
 pub mod page_num_query;
 pub use page_num_query::PageNumQuery;
 
+pub mod diff_entries_query;
+pub use diff_entries_query::DiffEntriesQuery;
+
 pub mod df_opts_query;
 pub use df_opts_query::DFOptsQuery;
 
+pub mod bulk_rows_query;
+pub use bulk_rows_query::BulkRowsQuery;
+
+pub mod search_query;
+pub use search_query::SearchQuery;
+
+pub mod changes_query;
+pub use changes_query::ChangesQuery;
+
+pub mod tasks_query;
+pub use tasks_query::TasksQuery;
+
+pub mod workspaces_query;
+pub use workspaces_query::WorkspacesQuery;
+
 pub fn app_data(req: &HttpRequest) -> Result<&OxenAppData, OxenHttpError> {
     log::debug!(
         "Get user agent from app data (app_data) {:?}",
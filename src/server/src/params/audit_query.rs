@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+use crate::audit::AuditFilter;
+
+#[derive(Deserialize, Debug)]
+pub struct AuditQuery {
+    pub namespace: Option<String>,
+    pub repo_name: Option<String>,
+    pub user: Option<String>,
+    /// Unix timestamps in seconds, inclusive
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+impl AuditQuery {
+    pub fn to_filter(&self) -> AuditFilter {
+        AuditFilter {
+            namespace: self.namespace.clone(),
+            repo_name: self.repo_name.clone(),
+            user: self.user.clone(),
+            since: self.since,
+            until: self.until,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use liboxen::model::BulkFileFormat;
+use serde::Deserialize;
+
+/// Query params for importing or exporting data frame rows as a file
+#[derive(Deserialize, Debug)]
+pub struct BulkRowsQuery {
+    /// Comma separated list of the columns to match rows on
+    pub keys: Option<String>,
+    /// Format of the uploaded or exported file, one of csv, tsv, parquet, jsonl or arrow
+    pub format: Option<BulkFileFormat>,
+}
+
+impl BulkRowsQuery {
+    pub fn key_columns(&self) -> Vec<String> {
+        self.keys
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect()
+    }
+}
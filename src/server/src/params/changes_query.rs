@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct ChangesQuery {
+    pub since: Option<i64>,
+    pub page_size: Option<usize>,
+}
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct DiffEntriesQuery {
+    pub page: Option<usize>,
+    pub page_size: Option<usize>,
+    // Pair removed and added files with the same contents up as renamed, off for clients
+    // that predate the renamed status
+    pub renames: Option<bool>,
+}
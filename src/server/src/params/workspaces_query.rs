@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct WorkspacesQuery {
+    pub branch: Option<String>,
+    pub creator: Option<String>,
+}
@@ -1,63 +1,181 @@
-use liboxen::core::cache::cacher_status::CacherStatus;
-use liboxen::core::cache::commit_cacher;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 use tokio::time::sleep;
 
 use crate::helpers::get_redis_connection;
-use crate::queues::{InMemoryTaskQueue, RedisTaskQueue, TaskQueue};
+use crate::metrics;
+use crate::queues::{
+    InMemoryTaskQueue, QueuedTask, RedisTaskQueue, TaskQueue, TaskStatus, VISIBILITY_TIMEOUT_SECS,
+};
+use crate::tasks::cleanup_workspaces::CleanupWorkspaces;
 use crate::tasks::{Runnable, Task};
 
-pub async fn poll_queue(mut queue: TaskQueue) {
-    log::debug!("Starting queue poller");
+/// How often expired workspaces are swept from the sync dir
+const WORKSPACE_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How often a running task pushes back its visibility timeout
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(VISIBILITY_TIMEOUT_SECS as u64 / 3);
+
+/// Upper bound on the default number of tasks run at once, override with OXEN_TASK_WORKERS
+const MAX_DEFAULT_TASK_WORKERS: usize = 4;
+
+fn num_task_workers() -> usize {
+    if let Ok(num_workers) = std::env::var("OXEN_TASK_WORKERS") {
+        if let Ok(num_workers) = num_workers.parse::<usize>() {
+            return num_workers.max(1);
+        }
+    }
+
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(MAX_DEFAULT_TASK_WORKERS)
+}
+
+pub async fn poll_queue(queue: TaskQueue) {
+    let num_workers = num_task_workers();
+    log::debug!("Starting queue poller with {} workers", num_workers);
+    let workers = Arc::new(Semaphore::new(num_workers));
     loop {
-        match queue.pop() {
-            Some(task) => {
-                log::debug!("Got queue item: {:?}", task);
+        // Only take a task off the queue once there is a worker free to run it
+        let Ok(permit) = workers.clone().acquire_owned().await else {
+            log::error!("Task worker pool closed, stopping queue poller");
+            return;
+        };
+
+        let mut reserve_queue = queue.clone();
+        let reserved = tokio::task::spawn_blocking(move || {
+            reserve_queue.reserve().map(|reservation| {
+                for task in reservation.dead {
+                    log::error!(
+                        "Task {} {} timed out and moved to dead letters",
+                        task.name,
+                        task.id
+                    );
+                    task.task
+                        .on_dead(task.last_error.as_deref().unwrap_or_default());
+                }
+                reservation.task
+            })
+        })
+        .await;
+        match reserved {
+            Ok(Ok(Some(task))) => {
+                log::debug!("Got queue item: {} {}", task.name, task.id);
 
                 // to ensure we don't block the poller, we run the task in an OS thread.
+                let queue = queue.clone();
                 tokio::task::spawn_blocking(move || {
-                    let result = std::panic::catch_unwind(|| {
-                        task.run();
-                    });
-                    if let Err(e) = result {
-                        log::error!("Error or panic processing task {:?}", e);
-                        // Handle task failure
-                        // Set the task to failed
-                        match task {
-                            Task::PostPushComplete(post_push_complete) => {
-                                let repo = post_push_complete.repo;
-                                let commit = post_push_complete.commit;
-
-                                match commit_cacher::set_all_cachers_status(
-                                    &repo,
-                                    &commit,
-                                    CacherStatus::failed("Panic in task execution"),
-                                ) {
-                                    Ok(_) => log::debug!("Set all cachers to failed status"),
-                                    Err(e) => log::error!(
-                                        "Error setting all cachers to failed status: {:?}",
-                                        e
-                                    ),
-                                }
-                            }
-                        }
-                    }
+                    run_task(queue, task);
+                    drop(permit);
                 });
             }
-            None => {
-                // log::debug!("No queue items found, sleeping");
+            Ok(Ok(None)) => {
+                drop(permit);
+                sleep(Duration::from_millis(1000)).await;
+            }
+            Ok(Err(err)) => {
+                drop(permit);
+                log::error!("Could not reserve task from queue: {:?}", err);
+                sleep(Duration::from_millis(1000)).await;
+            }
+            Err(err) => {
+                drop(permit);
+                log::error!("Queue poller could not reserve task: {:?}", err);
                 sleep(Duration::from_millis(1000)).await;
             }
         }
     }
 }
 
+fn run_task(mut queue: TaskQueue, task: QueuedTask) {
+    let start = Instant::now();
+    let (done, heartbeat) = start_heartbeat(queue.clone(), task.id.clone());
+    let result = std::panic::catch_unwind(|| task.task.run());
+    drop(done);
+    let _ = heartbeat.join();
+    let outcome = if matches!(result, Ok(Ok(_))) {
+        "succeeded"
+    } else {
+        "failed"
+    };
+    metrics::record_task(&task.name, outcome, start.elapsed().as_secs_f64());
+
+    let error = match result {
+        Ok(Ok(_)) => {
+            if let Err(err) = queue.complete(&task.id) {
+                log::error!("Could not mark task {} complete: {:?}", task.id, err);
+            }
+            return;
+        }
+        Ok(Err(err)) => format!("{err}"),
+        Err(e) => {
+            log::error!("Panic processing task {} {:?}", task.id, e);
+            String::from("Panic in task execution")
+        }
+    };
+
+    log::error!(
+        "Task {} {} failed on attempt {}/{}: {}",
+        task.name,
+        task.id,
+        task.attempts,
+        task.max_attempts,
+        error
+    );
+    match queue.fail(&task.id, &error) {
+        Ok(Some(failed)) if failed.status == TaskStatus::Dead => {
+            log::error!("Task {} {} moved to dead letters", task.name, task.id);
+            task.task.on_dead(&error);
+        }
+        Ok(_) => {}
+        Err(err) => log::error!("Could not mark task {} failed: {:?}", task.id, err),
+    }
+}
+
+// Keep the task hidden from other workers until the returned sender is dropped
+fn start_heartbeat(
+    mut queue: TaskQueue,
+    id: String,
+) -> (mpsc::Sender<()>, std::thread::JoinHandle<()>) {
+    let (done, stop) = mpsc::channel::<()>();
+    let handle = std::thread::spawn(move || {
+        while let Err(RecvTimeoutError::Timeout) = stop.recv_timeout(HEARTBEAT_INTERVAL) {
+            if let Err(err) = queue.heartbeat(&id) {
+                log::error!("Could not send heartbeat for task {}: {:?}", id, err);
+            }
+        }
+    });
+    (done, handle)
+}
+
+/// Periodically queue a task that removes the expired workspaces
+pub async fn schedule_workspace_cleanup(mut queue: TaskQueue, sync_dir: PathBuf) {
+    loop {
+        if let Err(err) = queue.push(Task::CleanupWorkspaces(CleanupWorkspaces {
+            sync_dir: sync_dir.clone(),
+        })) {
+            log::error!("Could not queue workspace cleanup: {:?}", err);
+        }
+        sleep(WORKSPACE_CLEANUP_INTERVAL).await;
+    }
+}
+
 // If redis connection is available, use redis queue, else in-memory
 pub fn init_queue() -> TaskQueue {
     match get_redis_connection() {
         Ok(pool) => {
             println!("connecting to redis established, initializing queue");
-            TaskQueue::Redis(RedisTaskQueue { pool })
+            let mut queue = RedisTaskQueue { pool };
+            match queue.migrate_legacy_queue() {
+                Ok(0) => {}
+                Ok(n) => log::info!("Moved {} tasks from the legacy queue", n),
+                Err(err) => log::error!("Could not migrate the legacy queue: {:?}", err),
+            }
+            TaskQueue::Redis(queue)
         }
         Err(_) => {
             println!("Failed to connect to Redis. Falling back to in-memory queue.");
@@ -1,10 +1,203 @@
-use std::sync::Mutex;
-use std::{collections::VecDeque, sync::Arc};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
-use crate::tasks::post_push_complete::PostPushComplete;
-use crate::tasks::Task;
 use liboxen::constants::COMMIT_QUEUE_NAME;
+use liboxen::error::OxenError;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::tasks::post_push_complete::PostPushComplete;
+use crate::tasks::{Runnable, Task};
+use utoipa::ToSchema;
+
+/// How long a reserved task stays invisible to other workers before it is handed out again,
+/// unless the worker running it sends a heartbeat
+pub const VISIBILITY_TIMEOUT_SECS: i64 = 30 * 60;
+/// The first retry waits this long, doubling on every attempt after
+const RETRY_BACKOFF_BASE_SECS: i64 = 10;
+const RETRY_BACKOFF_MAX_SECS: i64 = 60 * 60;
+/// Finished tasks are kept around this long so they can be inspected
+const FINISHED_TASK_RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+/// Error recorded on a task that was still running on its last attempt when it timed out
+const TIMED_OUT_ERROR: &str = "Task timed out on its last attempt";
+
+// Redis keys: task records by id, the ready list, and sorted sets of ids scored by when they are due
+const TASKS_KEY: &str = "oxen:tasks";
+const READY_KEY: &str = "oxen:tasks:ready";
+const PROCESSING_KEY: &str = "oxen:tasks:processing";
+const DELAYED_KEY: &str = "oxen:tasks:delayed";
+const FINISHED_KEY: &str = "oxen:tasks:finished";
+const DEAD_KEY: &str = "oxen:tasks:dead";
+
+// Pop the next ready id and mark it as processing in one step, so a crash can't lose it
+const RESERVE_SCRIPT: &str = r"
+local id = redis.call('RPOP', KEYS[1])
+if id then
+    redis.call('ZADD', KEYS[2], ARGV[1], id)
+end
+return id
+";
+
+// Move every id that is due in the sorted set KEYS[1] back onto the ready list KEYS[2]
+const REQUEUE_DUE_SCRIPT: &str = r"
+local ids = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+for _, id in ipairs(ids) do
+    redis.call('ZREM', KEYS[1], id)
+    redis.call('LPUSH', KEYS[2], id)
+end
+return #ids
+";
+
+// Drop the records of tasks that finished before ARGV[1]
+const PRUNE_FINISHED_SCRIPT: &str = r"
+local ids = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+for _, id in ipairs(ids) do
+    redis.call('ZREM', KEYS[1], id)
+    redis.call('HDEL', KEYS[2], id)
+end
+return #ids
+";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Queued,
+    Running,
+    Retrying,
+    Succeeded,
+    Dead,
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TaskStatus::Queued => "queued",
+            TaskStatus::Running => "running",
+            TaskStatus::Retrying => "retrying",
+            TaskStatus::Succeeded => "succeeded",
+            TaskStatus::Dead => "dead",
+        };
+        write!(f, "{s}")
+    }
+}
 
+impl FromStr for TaskStatus {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(TaskStatus::Queued),
+            "running" => Ok(TaskStatus::Running),
+            "retrying" => Ok(TaskStatus::Retrying),
+            "succeeded" => Ok(TaskStatus::Succeeded),
+            "dead" => Ok(TaskStatus::Dead),
+            _ => Err(OxenError::basic_str(format!("Unknown task status: {s}"))),
+        }
+    }
+}
+
+/// A task along with its bookkeeping in the queue. Timestamps are unix seconds.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct QueuedTask {
+    pub id: String,
+    pub name: String,
+    pub task: Task,
+    pub status: TaskStatus,
+    pub attempts: u32,
+    pub max_attempts: u32,
+    pub enqueued_at: i64,
+    pub updated_at: i64,
+    /// When a running task times out, or when a retrying task may run again
+    pub run_at: i64,
+    pub last_error: Option<String>,
+}
+
+impl QueuedTask {
+    fn new(task: Task) -> QueuedTask {
+        let now = now_secs();
+        QueuedTask {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: task.name().to_string(),
+            max_attempts: task.max_attempts(),
+            task,
+            status: TaskStatus::Queued,
+            attempts: 0,
+            enqueued_at: now,
+            updated_at: now,
+            run_at: now,
+            last_error: None,
+        }
+    }
+
+    fn start(&mut self, now: i64) {
+        self.status = TaskStatus::Running;
+        self.attempts += 1;
+        self.updated_at = now;
+        self.run_at = now + VISIBILITY_TIMEOUT_SECS;
+    }
+
+    fn extend(&mut self, now: i64) {
+        self.updated_at = now;
+        self.run_at = now + VISIBILITY_TIMEOUT_SECS;
+    }
+
+    fn succeed(&mut self, now: i64) {
+        self.status = TaskStatus::Succeeded;
+        self.updated_at = now;
+        self.last_error = None;
+    }
+
+    /// Schedule the next attempt, or give up once the attempts are used up
+    fn fail(&mut self, now: i64, error: &str) {
+        self.updated_at = now;
+        self.last_error = Some(error.to_string());
+        if self.attempts >= self.max_attempts {
+            self.status = TaskStatus::Dead;
+        } else {
+            self.status = TaskStatus::Retrying;
+            self.run_at = now + retry_backoff_secs(self.attempts);
+        }
+    }
+
+    fn requeue(&mut self, now: i64) {
+        self.status = TaskStatus::Queued;
+        self.attempts = 0;
+        self.updated_at = now;
+        self.run_at = now;
+        self.last_error = None;
+    }
+
+    fn is_pending(&self) -> bool {
+        matches!(self.status, TaskStatus::Queued | TaskStatus::Running)
+    }
+}
+
+/// What `reserve` found
+#[derive(Debug, Default)]
+pub struct Reservation {
+    /// The task to run next
+    pub task: Option<QueuedTask>,
+    /// Tasks that went to the dead letters because they timed out on their last attempt
+    pub dead: Vec<QueuedTask>,
+}
+
+fn now_secs() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
+
+pub fn retry_backoff_secs(attempts: u32) -> i64 {
+    let exp = attempts.saturating_sub(1).min(16);
+    (RETRY_BACKOFF_BASE_SECS << exp).min(RETRY_BACKOFF_MAX_SECS)
+}
+
+/// Reliable queue of background tasks.
+///
+/// Workers `reserve` a task, which hides it for the visibility timeout, then report back with
+/// `complete` or `fail`. Workers send a `heartbeat` while a task runs to keep it hidden, a task
+/// that is never reported on is handed out again once the timeout passes. Failed tasks are retried with exponential backoff until they run out of attempts,
+/// then they land on the dead letter list where they can be inspected and re-run.
 #[derive(Clone)]
 pub enum TaskQueue {
     InMemory(InMemoryTaskQueue),
@@ -12,18 +205,83 @@ pub enum TaskQueue {
 }
 
 impl TaskQueue {
-    pub fn push(&mut self, task: Task) {
+    /// Add a task to the back of the queue, returning its id
+    pub fn push(&mut self, task: Task) -> Result<String, OxenError> {
+        let record = QueuedTask::new(task);
+        log::debug!("Queueing task {} {}", record.name, record.id);
+        let id = record.id.clone();
+        match self {
+            TaskQueue::InMemory(queue) => queue.push(record),
+            TaskQueue::Redis(queue) => queue.push(record)?,
+        }
+        Ok(id)
+    }
+
+    /// Take the next task that is ready to run, if any. Tasks that timed out on their last
+    /// attempt are moved to the dead letters on the way and handed back, so the caller can run
+    /// their `on_dead` hook.
+    pub fn reserve(&mut self) -> Result<Reservation, OxenError> {
+        match self {
+            TaskQueue::InMemory(queue) => Ok(queue.reserve()),
+            TaskQueue::Redis(queue) => queue.reserve(),
+        }
+    }
+
+    /// Push back the visibility timeout of a running task, so a long task is not handed out twice
+    pub fn heartbeat(&mut self, id: &str) -> Result<(), OxenError> {
+        match self {
+            TaskQueue::InMemory(queue) => {
+                queue.heartbeat(id);
+                Ok(())
+            }
+            TaskQueue::Redis(queue) => queue.heartbeat(id),
+        }
+    }
+
+    pub fn complete(&mut self, id: &str) -> Result<(), OxenError> {
+        match self {
+            TaskQueue::InMemory(queue) => {
+                queue.complete(id);
+                Ok(())
+            }
+            TaskQueue::Redis(queue) => queue.complete(id),
+        }
+    }
+
+    /// Record a failed attempt, returns the task with its new status
+    pub fn fail(&mut self, id: &str, error: &str) -> Result<Option<QueuedTask>, OxenError> {
+        match self {
+            TaskQueue::InMemory(queue) => Ok(queue.fail(id, error)),
+            TaskQueue::Redis(queue) => queue.fail(id, error),
+        }
+    }
+
+    /// Put a retrying, dead or finished task back on the queue with a fresh set of attempts
+    pub fn retry(&mut self, id: &str) -> Result<Option<QueuedTask>, OxenError> {
         match self {
-            TaskQueue::InMemory(queue) => queue.push(task),
-            TaskQueue::Redis(queue) => queue.push(task),
+            TaskQueue::InMemory(queue) => queue.retry(id),
+            TaskQueue::Redis(queue) => queue.retry(id),
         }
     }
 
-    pub fn pop(&mut self) -> Option<Task> {
+    pub fn get(&self, id: &str) -> Result<Option<QueuedTask>, OxenError> {
         match self {
-            TaskQueue::InMemory(queue) => queue.pop(),
-            TaskQueue::Redis(queue) => queue.pop(),
+            TaskQueue::InMemory(queue) => Ok(queue.get(id)),
+            TaskQueue::Redis(queue) => queue.get(id),
+        }
+    }
+
+    /// List the tasks the queue knows about, most recent first
+    pub fn list(&self, status: Option<TaskStatus>) -> Result<Vec<QueuedTask>, OxenError> {
+        let mut tasks = match self {
+            TaskQueue::InMemory(queue) => queue.list(),
+            TaskQueue::Redis(queue) => queue.list()?,
+        };
+        if let Some(status) = status {
+            tasks.retain(|t| t.status == status);
         }
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.enqueued_at));
+        Ok(tasks)
     }
 }
 
@@ -37,62 +295,502 @@ impl RedisTaskQueue {
         RedisTaskQueue { pool }
     }
 
-    fn push(&mut self, task: Task) {
-        let mut conn = self.pool.get().unwrap();
+    /// Move the tasks left on the queue of older servers, which only ran post push tasks, onto
+    /// this queue so they are not lost on upgrade
+    pub fn migrate_legacy_queue(&mut self) -> Result<usize, OxenError> {
+        let mut conn = self.pool.get()?;
+        let mut migrated = 0;
+        loop {
+            let data: Option<Vec<u8>> = redis::cmd("RPOP")
+                .arg(COMMIT_QUEUE_NAME)
+                .query(&mut *conn)?;
+            let Some(data) = data else {
+                break;
+            };
+            match bincode::deserialize::<PostPushComplete>(&data) {
+                Ok(task) => {
+                    self.push(QueuedTask::new(Task::PostPushComplete(task)))?;
+                    migrated += 1;
+                }
+                Err(err) => log::error!("Dropping legacy task that could not be read: {:?}", err),
+            }
+        }
+        Ok(migrated)
+    }
+
+    fn push(&mut self, record: QueuedTask) -> Result<(), OxenError> {
+        let mut conn = self.pool.get()?;
+        redis::pipe()
+            .atomic()
+            .hset(TASKS_KEY, &record.id, serialize(&record)?)
+            .ignore()
+            .lpush(READY_KEY, &record.id)
+            .ignore()
+            .query::<()>(&mut *conn)?;
+        Ok(())
+    }
+
+    fn reserve(&mut self) -> Result<Reservation, OxenError> {
+        let mut conn = self.pool.get()?;
+        let now = now_secs();
+
+        // Tasks whose visibility timeout or backoff ran out are ready again
+        for key in [PROCESSING_KEY, DELAYED_KEY] {
+            let requeued: usize = redis::Script::new(REQUEUE_DUE_SCRIPT)
+                .key(key)
+                .key(READY_KEY)
+                .arg(now)
+                .invoke(&mut *conn)?;
+            if requeued > 0 {
+                log::debug!("Requeued {} tasks from {}", requeued, key);
+            }
+        }
+
+        let mut reservation = Reservation::default();
+        loop {
+            let id: Option<String> = redis::Script::new(RESERVE_SCRIPT)
+                .key(READY_KEY)
+                .key(PROCESSING_KEY)
+                .arg(now + VISIBILITY_TIMEOUT_SECS)
+                .invoke(&mut *conn)?;
+            let Some(id) = id else {
+                return Ok(reservation);
+            };
+
+            let Some(mut record) = load(&mut conn, &id)? else {
+                log::error!("Task {} was queued without a record, dropping it", id);
+                redis::cmd("ZREM")
+                    .arg(PROCESSING_KEY)
+                    .arg(&id)
+                    .query::<()>(&mut *conn)?;
+                continue;
+            };
+
+            // A task that keeps timing out, say by crashing the worker, is not retried forever
+            if record.attempts >= record.max_attempts {
+                record.fail(now, TIMED_OUT_ERROR);
+                redis::pipe()
+                    .atomic()
+                    .zrem(PROCESSING_KEY, &id)
+                    .ignore()
+                    .lpush(DEAD_KEY, &id)
+                    .ignore()
+                    .hset(TASKS_KEY, &id, serialize(&record)?)
+                    .ignore()
+                    .query::<()>(&mut *conn)?;
+                reservation.dead.push(record);
+                continue;
+            }
+
+            record.start(now);
+            save(&mut conn, &record)?;
+            reservation.task = Some(record);
+            return Ok(reservation);
+        }
+    }
+
+    fn heartbeat(&mut self, id: &str) -> Result<(), OxenError> {
+        let mut conn = self.pool.get()?;
+        let Some(mut record) = load(&mut conn, id)? else {
+            return Ok(());
+        };
+        if record.status != TaskStatus::Running {
+            return Ok(());
+        }
+        record.extend(now_secs());
+        // XX only updates the score if the task did not time out and get requeued already
+        redis::pipe()
+            .atomic()
+            .cmd("ZADD")
+            .arg(PROCESSING_KEY)
+            .arg("XX")
+            .arg(record.run_at)
+            .arg(id)
+            .ignore()
+            .hset(TASKS_KEY, id, serialize(&record)?)
+            .ignore()
+            .query::<()>(&mut *conn)?;
+        Ok(())
+    }
+
+    fn complete(&mut self, id: &str) -> Result<(), OxenError> {
+        let mut conn = self.pool.get()?;
+        let Some(mut record) = load(&mut conn, id)? else {
+            return Ok(());
+        };
+        let now = now_secs();
+        record.succeed(now);
+        redis::pipe()
+            .atomic()
+            .zrem(PROCESSING_KEY, id)
+            .ignore()
+            .zadd(FINISHED_KEY, id, now)
+            .ignore()
+            .hset(TASKS_KEY, id, serialize(&record)?)
+            .ignore()
+            .query::<()>(&mut *conn)?;
+
+        let _: usize = redis::Script::new(PRUNE_FINISHED_SCRIPT)
+            .key(FINISHED_KEY)
+            .key(TASKS_KEY)
+            .arg(now - FINISHED_TASK_RETENTION_SECS)
+            .invoke(&mut *conn)?;
+        Ok(())
+    }
+
+    fn fail(&mut self, id: &str, error: &str) -> Result<Option<QueuedTask>, OxenError> {
+        let mut conn = self.pool.get()?;
+        let Some(mut record) = load(&mut conn, id)? else {
+            return Ok(None);
+        };
+        record.fail(now_secs(), error);
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().zrem(PROCESSING_KEY, id).ignore();
+        if record.status == TaskStatus::Dead {
+            pipe.lpush(DEAD_KEY, id).ignore();
+        } else {
+            pipe.zadd(DELAYED_KEY, id, record.run_at).ignore();
+        }
+        pipe.hset(TASKS_KEY, id, serialize(&record)?)
+            .ignore()
+            .query::<()>(&mut *conn)?;
+        Ok(Some(record))
+    }
 
-        let data: Vec<u8> = match task {
-            Task::PostPushComplete(task) => bincode::serialize(&task).unwrap(),
+    fn retry(&mut self, id: &str) -> Result<Option<QueuedTask>, OxenError> {
+        let mut conn = self.pool.get()?;
+        let Some(mut record) = load(&mut conn, id)? else {
+            return Ok(None);
         };
+        if record.is_pending() {
+            return Err(OxenError::basic_str(format!(
+                "Task {id} is already {}",
+                record.status
+            )));
+        }
+
+        record.requeue(now_secs());
+        redis::pipe()
+            .atomic()
+            .lrem(DEAD_KEY, 0, id)
+            .ignore()
+            .zrem(DELAYED_KEY, id)
+            .ignore()
+            .zrem(FINISHED_KEY, id)
+            .ignore()
+            .hset(TASKS_KEY, id, serialize(&record)?)
+            .ignore()
+            .lpush(READY_KEY, id)
+            .ignore()
+            .query::<()>(&mut *conn)?;
+        Ok(Some(record))
+    }
 
-        let _: isize = redis::cmd("LPUSH")
-            .arg(COMMIT_QUEUE_NAME)
-            .arg(data)
-            .query(&mut conn)
-            .unwrap();
-    }
-
-    fn pop(&mut self) -> Option<Task> {
-        let mut conn = self.pool.get().unwrap();
-        let outcome: Option<Vec<u8>> = redis::cmd("LPOP")
-            .arg(COMMIT_QUEUE_NAME)
-            .query(&mut conn)
-            .unwrap();
-
-        match outcome {
-            Some(data) => {
-                // TODO: Support multiple task types
-                let task: PostPushComplete = bincode::deserialize(&data).unwrap();
-                Some(Task::PostPushComplete(task))
+    fn get(&self, id: &str) -> Result<Option<QueuedTask>, OxenError> {
+        let mut conn = self.pool.get()?;
+        load(&mut conn, id)
+    }
+
+    fn list(&self) -> Result<Vec<QueuedTask>, OxenError> {
+        let mut conn = self.pool.get()?;
+        let values: Vec<Vec<u8>> = redis::cmd("HVALS").arg(TASKS_KEY).query(&mut *conn)?;
+        let mut tasks = vec![];
+        for value in values {
+            match bincode::deserialize(&value) {
+                Ok(task) => tasks.push(task),
+                Err(err) => log::error!("Could not deserialize task record: {:?}", err),
             }
-            None => None,
         }
+        Ok(tasks)
     }
 }
 
+fn serialize(record: &QueuedTask) -> Result<Vec<u8>, OxenError> {
+    bincode::serialize(record)
+        .map_err(|e| OxenError::basic_str(format!("Could not serialize task: {e}")))
+}
+
+fn load(conn: &mut redis::Connection, id: &str) -> Result<Option<QueuedTask>, OxenError> {
+    let data: Option<Vec<u8>> = redis::cmd("HGET").arg(TASKS_KEY).arg(id).query(conn)?;
+    match data {
+        Some(data) => {
+            let record = bincode::deserialize(&data)
+                .map_err(|e| OxenError::basic_str(format!("Could not deserialize task: {e}")))?;
+            Ok(Some(record))
+        }
+        None => Ok(None),
+    }
+}
+
+fn save(conn: &mut redis::Connection, record: &QueuedTask) -> Result<(), OxenError> {
+    redis::cmd("HSET")
+        .arg(TASKS_KEY)
+        .arg(&record.id)
+        .arg(serialize(record)?)
+        .query::<()>(conn)?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct InMemoryState {
+    tasks: HashMap<String, QueuedTask>,
+    ready: VecDeque<String>,
+}
+
+/// Local stand-in for the redis queue, with the same semantics but nothing survives a restart
 #[derive(Clone)]
 pub struct InMemoryTaskQueue {
-    queue: Arc<Mutex<VecDeque<Task>>>,
+    state: Arc<Mutex<InMemoryState>>,
 }
 
 impl InMemoryTaskQueue {
     pub fn new() -> Self {
         InMemoryTaskQueue {
-            queue: Arc::new(Mutex::new(VecDeque::new())),
+            state: Arc::new(Mutex::new(InMemoryState::default())),
         }
     }
 
-    fn push(&mut self, task: Task) {
-        let mut queue = self.queue.lock().unwrap();
-        queue.push_back(task);
+    fn push(&mut self, record: QueuedTask) {
+        let mut state = self.state.lock().unwrap();
+        state.ready.push_back(record.id.clone());
+        state.tasks.insert(record.id.clone(), record);
     }
 
-    fn pop(&mut self) -> Option<Task> {
-        let mut queue = self.queue.lock().unwrap();
-        queue.pop_front()
+    fn reserve(&mut self) -> Reservation {
+        let mut state = self.state.lock().unwrap();
+        let now = now_secs();
+
+        let mut due: Vec<&mut QueuedTask> = state
+            .tasks
+            .values_mut()
+            .filter(|t| {
+                matches!(t.status, TaskStatus::Running | TaskStatus::Retrying) && t.run_at <= now
+            })
+            .collect();
+        due.sort_by_key(|t| t.run_at);
+        let mut reservation = Reservation::default();
+        let mut requeued = vec![];
+        for task in due {
+            if task.status == TaskStatus::Running && task.attempts >= task.max_attempts {
+                task.fail(now, TIMED_OUT_ERROR);
+                reservation.dead.push(task.clone());
+            } else {
+                task.status = TaskStatus::Queued;
+                requeued.push(task.id.clone());
+            }
+        }
+        state.ready.extend(requeued);
+
+        while let Some(id) = state.ready.pop_front() {
+            if let Some(task) = state.tasks.get_mut(&id) {
+                task.start(now);
+                reservation.task = Some(task.clone());
+                break;
+            }
+        }
+        reservation
+    }
+
+    fn heartbeat(&mut self, id: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(task) = state.tasks.get_mut(id) {
+            if task.status == TaskStatus::Running {
+                task.extend(now_secs());
+            }
+        }
+    }
+
+    fn complete(&mut self, id: &str) {
+        let mut state = self.state.lock().unwrap();
+        let now = now_secs();
+        if let Some(task) = state.tasks.get_mut(id) {
+            task.succeed(now);
+        }
+        state.tasks.retain(|_, t| {
+            t.status != TaskStatus::Succeeded || t.updated_at >= now - FINISHED_TASK_RETENTION_SECS
+        });
+    }
+
+    fn fail(&mut self, id: &str, error: &str) -> Option<QueuedTask> {
+        let mut state = self.state.lock().unwrap();
+        let task = state.tasks.get_mut(id)?;
+        task.fail(now_secs(), error);
+        Some(task.clone())
+    }
+
+    fn retry(&mut self, id: &str) -> Result<Option<QueuedTask>, OxenError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(task) = state.tasks.get_mut(id) else {
+            return Ok(None);
+        };
+        if task.is_pending() {
+            return Err(OxenError::basic_str(format!(
+                "Task {id} is already {}",
+                task.status
+            )));
+        }
+        task.requeue(now_secs());
+        let task = task.clone();
+        state.ready.push_back(task.id.clone());
+        Ok(Some(task))
+    }
+
+    fn get(&self, id: &str) -> Option<QueuedTask> {
+        let state = self.state.lock().unwrap();
+        state.tasks.get(id).cloned()
+    }
+
+    fn list(&self) -> Vec<QueuedTask> {
+        let state = self.state.lock().unwrap();
+        state.tasks.values().cloned().collect()
     }
 }
+
 impl Default for InMemoryTaskQueue {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use liboxen::error::OxenError;
+    use liboxen::model::LocalRepository;
+
+    use super::*;
+    use crate::tasks::cleanup_workspaces::CleanupWorkspaces;
+    use crate::tasks::reindex::Reindex;
+
+    fn reindex_task() -> Result<Task, OxenError> {
+        Ok(Task::Reindex(Reindex {
+            repo: LocalRepository::new(Path::new("data/test/runs/queue"))?,
+            commit_id: None,
+        }))
+    }
+
+    // Pretend the backoff of the task already ran out
+    fn end_backoff(queue: &TaskQueue, id: &str) {
+        if let TaskQueue::InMemory(queue) = queue {
+            let mut state = queue.state.lock().unwrap();
+            state.tasks.get_mut(id).unwrap().run_at = 0;
+        }
+    }
+
+    #[test]
+    fn test_in_memory_queue_retries_then_dead_letters() -> Result<(), OxenError> {
+        let mut queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        let id = queue.push(reindex_task()?)?;
+
+        let task = queue.reserve()?.task.unwrap();
+        assert_eq!(task.id, id);
+        assert_eq!(task.status, TaskStatus::Running);
+        assert_eq!(task.attempts, 1);
+        // Reserved tasks are hidden from other workers
+        assert!(queue.reserve()?.task.is_none());
+
+        let task = queue.fail(&id, "disk full")?.unwrap();
+        assert_eq!(task.status, TaskStatus::Retrying);
+        assert_eq!(task.last_error, Some(String::from("disk full")));
+        assert!(task.run_at >= task.updated_at + retry_backoff_secs(1));
+        // Still backing off
+        assert!(queue.reserve()?.task.is_none());
+
+        // Use up the rest of the attempts
+        let mut task = task;
+        while task.status != TaskStatus::Dead {
+            end_backoff(&queue, &id);
+            assert_eq!(queue.reserve()?.task.unwrap().id, id);
+            task = queue.fail(&id, "disk full")?.unwrap();
+        }
+        assert_eq!(task.attempts, task.max_attempts);
+        end_backoff(&queue, &id);
+        assert!(queue.reserve()?.task.is_none());
+        assert_eq!(queue.list(Some(TaskStatus::Dead))?.len(), 1);
+
+        // An operator can re-run it from the dead letter list
+        let task = queue.retry(&id)?.unwrap();
+        assert_eq!(task.status, TaskStatus::Queued);
+        assert_eq!(task.attempts, 0);
+        let task = queue.reserve()?.task.unwrap();
+        queue.complete(&task.id)?;
+        assert_eq!(queue.get(&id)?.unwrap().status, TaskStatus::Succeeded);
+        assert!(queue.retry(&id)?.is_some());
+        assert!(queue.retry(&id).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_memory_queue_is_fifo() -> Result<(), OxenError> {
+        let mut queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        let first = queue.push(reindex_task()?)?;
+        let second = queue.push(Task::CleanupWorkspaces(CleanupWorkspaces {
+            sync_dir: PathBuf::from("data/test/runs/queue"),
+        }))?;
+
+        assert_eq!(queue.reserve()?.task.unwrap().id, first);
+        let task = queue.reserve()?.task.unwrap();
+        assert_eq!(task.id, second);
+        assert_eq!(task.name, "cleanup_workspaces");
+        assert_eq!(task.max_attempts, 1);
+        assert_eq!(queue.list(None)?.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_memory_queue_heartbeat_keeps_task_hidden() -> Result<(), OxenError> {
+        let mut queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        let id = queue.push(reindex_task()?)?;
+        queue.reserve()?.task.unwrap();
+
+        // Pretend the task has been running for almost the whole timeout
+        end_backoff(&queue, &id);
+        queue.heartbeat(&id)?;
+        let task = queue.get(&id)?.unwrap();
+        assert_eq!(task.status, TaskStatus::Running);
+        assert!(task.run_at >= task.updated_at + VISIBILITY_TIMEOUT_SECS);
+        assert!(queue.reserve()?.task.is_none());
+
+        // Heartbeats from a worker that already reported back do nothing
+        queue.complete(&id)?;
+        queue.heartbeat(&id)?;
+        assert_eq!(queue.get(&id)?.unwrap().status, TaskStatus::Succeeded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_memory_queue_hands_back_tasks_that_time_out() -> Result<(), OxenError> {
+        let mut queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        let id = queue.push(Task::CleanupWorkspaces(CleanupWorkspaces {
+            sync_dir: PathBuf::from("data/test/runs/queue"),
+        }))?;
+        let next = queue.push(reindex_task()?)?;
+        queue.reserve()?.task.unwrap();
+
+        // The worker running its only attempt went away, the next ready task is still handed out
+        end_backoff(&queue, &id);
+        let reservation = queue.reserve()?;
+        assert_eq!(reservation.task.unwrap().id, next);
+        assert_eq!(reservation.dead.len(), 1);
+        assert_eq!(reservation.dead[0].id, id);
+        assert_eq!(reservation.dead[0].status, TaskStatus::Dead);
+        assert_eq!(
+            reservation.dead[0].last_error.as_deref(),
+            Some(TIMED_OUT_ERROR)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_backoff_is_capped() {
+        assert_eq!(retry_backoff_secs(1), RETRY_BACKOFF_BASE_SECS);
+        assert_eq!(retry_backoff_secs(2), RETRY_BACKOFF_BASE_SECS * 2);
+        assert_eq!(retry_backoff_secs(100), RETRY_BACKOFF_MAX_SECS);
+    }
+}
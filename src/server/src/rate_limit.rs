@@ -0,0 +1,365 @@
+//! Per client limits on requests, bytes and concurrent transfers, so one runaway client cannot
+//! saturate the server for everyone else. Requests count against the access key they send as a
+//! bearer token once the token is validated, and against the ip address they come from. Both
+//! have to be within their limits for a request to go through.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use actix_web::HttpRequest;
+
+use crate::auth;
+
+// Path segments after /api/repos/{namespace}/{repo_name} that move file contents
+const TRANSFER_SEGMENTS: [&str; 4] = ["file", "chunk", "versions", "transfer"];
+
+// Forget about idle clients once we are tracking this many
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitConfig {
+    pub requests_per_second: Option<f64>,
+    pub bytes_per_second: Option<u64>,
+    pub max_concurrent_transfers: Option<usize>,
+    /// Reverse proxies whose requests are limited by the client ip they forward, instead of
+    /// everyone behind the proxy sharing its ip
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimitConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.requests_per_second.is_none()
+            && self.bytes_per_second.is_none()
+            && self.max_concurrent_transfers.is_none()
+    }
+}
+
+/// Token bucket holding up to one second worth of allowance, and at least one request. Bytes are
+/// only known once they have been sent, so the bytes bucket can go negative and the client waits
+/// until it is paid back.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn full(rate: f64) -> Bucket {
+        Bucket {
+            tokens: Bucket::capacity(rate),
+            updated: Instant::now(),
+        }
+    }
+
+    fn capacity(rate: f64) -> f64 {
+        rate.max(1.0)
+    }
+
+    fn refill(&mut self, rate: f64) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(Bucket::capacity(rate));
+        self.updated = now;
+    }
+
+    /// Seconds until the bucket holds `amount`
+    fn wait_for(&self, amount: f64, rate: f64) -> u64 {
+        ((amount - self.tokens) / rate).ceil().max(1.0) as u64
+    }
+}
+
+#[derive(Debug, Default)]
+struct ClientState {
+    requests: Option<Bucket>,
+    bytes: Option<Bucket>,
+    transfers: usize,
+}
+
+impl ClientState {
+    fn is_idle(&self, config: &RateLimitConfig) -> bool {
+        let is_full = |bucket: &Option<Bucket>, rate: Option<f64>| match (bucket, rate) {
+            (Some(bucket), Some(rate)) => {
+                bucket.tokens + bucket.updated.elapsed().as_secs_f64() * rate
+                    >= Bucket::capacity(rate)
+            }
+            _ => true,
+        };
+        self.transfers == 0
+            && is_full(&self.requests, config.requests_per_second)
+            && is_full(&self.bytes, config.bytes_per_second.map(|b| b as f64))
+    }
+}
+
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    clients: Mutex<HashMap<String, ClientState>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Arc<RateLimiter> {
+        Arc::new(RateLimiter {
+            config,
+            clients: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.config.is_unlimited()
+    }
+
+    /// The clients a request counts against: its access key when it sends a valid one, and the
+    /// ip address it comes from
+    pub fn clients(&self, req: &HttpRequest) -> Vec<String> {
+        let mut clients = vec![];
+        if let Some(key) = auth::validator::identity(req).key {
+            clients.push(format!("key:{key}"));
+        }
+        clients.push(format!("ip:{}", self.client_ip(req)));
+        clients
+    }
+
+    // The peer, or the client a trusted proxy forwarded the request for
+    fn client_ip(&self, req: &HttpRequest) -> String {
+        let peer = req.peer_addr().map(|addr| addr.ip());
+        if peer.is_some_and(|ip| self.config.trusted_proxies.contains(&ip)) {
+            if let Some(addr) = req.connection_info().realip_remote_addr() {
+                // Forwarded addresses may come with a port, the client is the same on any port
+                if let Ok(addr) = addr.parse::<SocketAddr>() {
+                    return addr.ip().to_string();
+                }
+                return addr.to_string();
+            }
+        }
+        match peer {
+            Some(ip) => ip.to_string(),
+            None => String::from("unknown"),
+        }
+    }
+
+    /// Lets a request from `clients` through, holding one of the transfer slots of each until the
+    /// permit is dropped if it is a transfer. Otherwise returns the seconds to wait before trying
+    /// again.
+    pub fn acquire(
+        self: &Arc<Self>,
+        clients: &[String],
+        is_transfer: bool,
+    ) -> Result<Option<TransferPermit>, u64> {
+        let mut states = self.clients.lock().unwrap();
+        if states.len() >= MAX_TRACKED_CLIENTS {
+            states.retain(|_, state| !state.is_idle(&self.config));
+        }
+
+        // Check everything before taking anything, a rejected request is free
+        let mut retry_after = 0;
+        for client in clients {
+            let state = states.entry(client.to_string()).or_default();
+            if let Some(rate) = self.config.requests_per_second {
+                let bucket = state.requests.get_or_insert_with(|| Bucket::full(rate));
+                bucket.refill(rate);
+                if bucket.tokens < 1.0 {
+                    retry_after = retry_after.max(bucket.wait_for(1.0, rate));
+                }
+            }
+            if let Some(rate) = self.config.bytes_per_second {
+                let rate = rate as f64;
+                let bucket = state.bytes.get_or_insert_with(|| Bucket::full(rate));
+                bucket.refill(rate);
+                if bucket.tokens < 0.0 {
+                    retry_after = retry_after.max(bucket.wait_for(0.0, rate));
+                }
+            }
+            if let Some(max) = self.config.max_concurrent_transfers {
+                if is_transfer && state.transfers >= max {
+                    retry_after = retry_after.max(1);
+                }
+            }
+        }
+        if retry_after > 0 {
+            return Err(retry_after);
+        }
+
+        let holds_transfer = is_transfer && self.config.max_concurrent_transfers.is_some();
+        for client in clients {
+            let state = states.entry(client.to_string()).or_default();
+            if let Some(bucket) = state.requests.as_mut() {
+                bucket.tokens -= 1.0;
+            }
+            if holds_transfer {
+                state.transfers += 1;
+            }
+        }
+        if !holds_transfer {
+            return Ok(None);
+        }
+        Ok(Some(TransferPermit {
+            limiter: self.clone(),
+            clients: clients.to_vec(),
+        }))
+    }
+
+    /// Counts bytes sent or received against the bytes per second of each of the clients
+    pub fn record_bytes(&self, clients: &[String], bytes: u64) {
+        let Some(rate) = self.config.bytes_per_second else {
+            return;
+        };
+        let rate = rate as f64;
+        let mut states = self.clients.lock().unwrap();
+        for client in clients {
+            let state = states.entry(client.to_string()).or_default();
+            let bucket = state.bytes.get_or_insert_with(|| Bucket::full(rate));
+            bucket.refill(rate);
+            bucket.tokens -= bytes as f64;
+        }
+    }
+
+    fn release(&self, clients: &[String]) {
+        let mut states = self.clients.lock().unwrap();
+        for client in clients {
+            if let Some(state) = states.get_mut(client) {
+                state.transfers = state.transfers.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// One of the concurrent transfer slots of each client of a request, given back when dropped
+pub struct TransferPermit {
+    limiter: Arc<RateLimiter>,
+    clients: Vec<String>,
+}
+
+impl Drop for TransferPermit {
+    fn drop(&mut self) {
+        self.limiter.release(&self.clients);
+    }
+}
+
+/// Whether a request path is an upload or download of file contents
+pub fn is_transfer(path: &str) -> bool {
+    // "", "api", "repos", namespace, repo_name, service
+    path.starts_with("/api/repos/")
+        && path
+            .split('/')
+            .nth(5)
+            .is_some_and(|segment| TRANSFER_SEGMENTS.contains(&segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::{RateLimitConfig, RateLimiter};
+
+    fn clients(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rate_limit_requests_and_bytes() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_second: Some(2.0),
+            bytes_per_second: Some(100),
+            max_concurrent_transfers: None,
+            ..RateLimitConfig::default()
+        });
+
+        assert!(limiter.acquire(&clients(&["ip:127.0.0.1"]), false).is_ok());
+        assert!(limiter.acquire(&clients(&["ip:127.0.0.1"]), false).is_ok());
+        assert_eq!(
+            limiter.acquire(&clients(&["ip:127.0.0.1"]), false).err(),
+            Some(1)
+        );
+        // Other clients have their own allowance
+        assert!(limiter.acquire(&clients(&["ip:10.0.0.1"]), false).is_ok());
+
+        limiter.record_bytes(&clients(&["ip:10.0.0.1"]), 350);
+        assert_eq!(
+            limiter.acquire(&clients(&["ip:10.0.0.1"]), false).err(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_concurrent_transfers() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            max_concurrent_transfers: Some(1),
+            ..RateLimitConfig::default()
+        });
+
+        let permit = limiter.acquire(&clients(&["key:abc"]), true).unwrap();
+        assert!(permit.is_some());
+        assert_eq!(limiter.acquire(&clients(&["key:abc"]), true).err(), Some(1));
+        // Only transfers count
+        assert!(limiter
+            .acquire(&clients(&["key:abc"]), false)
+            .unwrap()
+            .is_none());
+
+        drop(permit);
+        assert!(limiter.acquire(&clients(&["key:abc"]), true).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_key_and_ip() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_second: Some(1.0),
+            ..RateLimitConfig::default()
+        });
+
+        assert!(limiter
+            .acquire(&clients(&["key:abc", "ip:10.0.0.1"]), false)
+            .is_ok());
+        // Another key from the same ip is held back by the ip
+        assert!(limiter
+            .acquire(&clients(&["key:def", "ip:10.0.0.1"]), false)
+            .is_err());
+        // The same key from another ip is held back by the key
+        assert!(limiter
+            .acquire(&clients(&["key:abc", "ip:10.0.0.2"]), false)
+            .is_err());
+        // Nothing was taken from the ip that let it through
+        assert!(limiter
+            .acquire(&clients(&["key:ghi", "ip:10.0.0.2"]), false)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_client_ip_behind_trusted_proxy() {
+        let proxy = "10.0.0.1".parse().unwrap();
+        let limiter = RateLimiter::new(RateLimitConfig {
+            trusted_proxies: vec![proxy],
+            ..RateLimitConfig::default()
+        });
+
+        let req = TestRequest::default()
+            .peer_addr("10.0.0.1:4000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "203.0.113.7"))
+            .to_http_request();
+        assert_eq!(limiter.clients(&req), clients(&["ip:203.0.113.7"]));
+
+        // Anyone else can't pick their own ip
+        let req = TestRequest::default()
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "203.0.113.7"))
+            .to_http_request();
+        assert_eq!(limiter.clients(&req), clients(&["ip:10.0.0.2"]));
+
+        // Nothing forwarded, the proxy itself
+        let req = TestRequest::default()
+            .peer_addr("10.0.0.1:4000".parse().unwrap())
+            .to_http_request();
+        assert_eq!(limiter.clients(&req), clients(&["ip:10.0.0.1"]));
+    }
+
+    #[test]
+    fn test_is_transfer() {
+        assert!(super::is_transfer("/api/repos/ox/data/versions"));
+        assert!(super::is_transfer(
+            "/api/repos/ox/data/file/main/images/cat.jpg"
+        ));
+        assert!(!super::is_transfer("/api/repos/ox/data/branches"));
+        assert!(!super::is_transfer("/api/namespaces/ox/data/versions"));
+    }
+}
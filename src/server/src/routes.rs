@@ -1,46 +1,159 @@
 use super::controllers;
 
-use actix_web::web;
+use liboxen::model::RepoNew;
+use liboxen::view::repository::{RepositoryCreationResponse, RepositoryDataTypesResponse};
+use liboxen::view::version::VersionResponse;
+use liboxen::view::{
+    HealthResponse, ListNamespacesResponse, ListRepositoryResponse, NamespaceResponse,
+    StorageUsageResponse,
+};
+use serde_json::Value;
 
+use crate::controllers::audit::AuditLogResponse;
+use crate::controllers::tasks::{ListTasksResponse, TaskResponse};
+use crate::middleware::ReadOnlyMirror;
+use crate::openapi::{ApiScope, Operation};
 use crate::services;
+use crate::tasks::NewTask;
 
-pub fn config(cfg: &mut web::ServiceConfig) {
-    // Create Repository
-    cfg.route("", web::post().to(controllers::repositories::create))
+/// Every route under /api, these are served and documented in the openapi spec
+pub fn api() -> ApiScope {
+    ApiScope::new("/api")
+        .route(
+            "/version",
+            Operation::get("Check that the server is up"),
+            controllers::version::index,
+        )
+        .route(
+            "/min_version",
+            Operation::get("Oldest client version the server accepts").json::<VersionResponse>(),
+            controllers::version::min_version,
+        )
+        .route(
+            "/health",
+            Operation::get("Disk usage of the server").json::<HealthResponse>(),
+            controllers::health::index,
+        )
+        .route(
+            "/openapi.json",
+            Operation::get("This document").json::<Value>(),
+            controllers::openapi::index,
+        )
+        .route(
+            "/audit",
+            Operation::get("Read the audit log").json::<AuditLogResponse>(),
+            controllers::audit::index,
+        )
+        .route(
+            "/audit/export",
+            Operation::get("Export the audit log, one JSON entry per line").text(),
+            controllers::audit::export,
+        )
+        .route(
+            "/namespaces",
+            Operation::get("List namespaces").json::<ListNamespacesResponse>(),
+            controllers::namespaces::index,
+        )
+        .route(
+            "/namespaces/{namespace}",
+            Operation::get("Get a namespace").json::<NamespaceResponse>(),
+            controllers::namespaces::show,
+        )
+        .route(
+            "/namespaces/{namespace}/usage",
+            Operation::get("Disk usage of a namespace").json::<StorageUsageResponse>(),
+            controllers::namespaces::usage,
+        )
+        .route(
+            "/workspaces/cleanup",
+            Operation::post("Delete expired workspaces"),
+            controllers::workspaces::cleanup,
+        )
+        .route(
+            "/tasks",
+            Operation::get("List background tasks").json::<ListTasksResponse>(),
+            controllers::tasks::list,
+        )
+        .route(
+            "/tasks",
+            Operation::post("Queue a background task")
+                .body::<NewTask>()
+                .json::<TaskResponse>(),
+            controllers::tasks::create,
+        )
+        .route(
+            "/tasks/{task_id}",
+            Operation::get("Get a background task").json::<TaskResponse>(),
+            controllers::tasks::show,
+        )
+        .route(
+            "/tasks/{task_id}/retry",
+            Operation::post("Queue a dead task again").json::<TaskResponse>(),
+            controllers::tasks::retry,
+        )
+        .route(
+            "/migrations/{migration_tstamp}",
+            Operation::get("List the repositories a migration has not run on")
+                .json::<ListRepositoryResponse>(),
+            controllers::migrations::list_unmigrated,
+        )
+        .service(repos())
+}
+
+pub fn repos() -> ApiScope {
+    ApiScope::new("/repos")
+        // Create Repository
+        .route(
+            "",
+            Operation::post("Create a repository")
+                .body::<RepoNew>()
+                .json::<RepositoryCreationResponse>(),
+            controllers::repositories::create,
+        )
         // List Repositories in a Namespace
         .route(
             "/{namespace}",
-            web::get().to(controllers::repositories::index),
+            Operation::get("List the repositories in a namespace").json::<ListRepositoryResponse>(),
+            controllers::repositories::index,
         )
-        // Get/Delete Repository
-        .service(
-            web::resource("/{namespace}/{repo_name}")
-                // we give the resource a name here so it can be used with HttpRequest.url_for
-                .name("repo_root")
-                .route(web::get().to(controllers::repositories::show))
-                .route(web::delete().to(controllers::repositories::delete)),
+        .service_with(repo(), |scope| scope.wrap(ReadOnlyMirror))
+}
+
+/// Routes of a single repository
+pub fn repo() -> ApiScope {
+    ApiScope::new("/{namespace}/{repo_name}")
+        // we give the resource a name here so it can be used with HttpRequest.url_for
+        .named_route(
+            "",
+            "repo_root",
+            Operation::get("Get a repository").json::<RepositoryDataTypesResponse>(),
+            controllers::repositories::show,
+        )
+        .route(
+            "",
+            Operation::delete("Delete a repository"),
+            controllers::repositories::delete,
         )
         // Repository Services
-        .service(
-            web::scope("/{namespace}/{repo_name}")
-                .service(services::action())
-                .service(services::branches())
-                .service(services::chunk())
-                .service(services::commits())
-                .service(services::commits_db())
-                .service(services::compare())
-                .service(services::data_frames())
-                .service(services::dir())
-                .service(services::file())
-                .service(services::merge())
-                .service(services::meta())
-                .service(services::objects_db())
-                .service(services::revisions())
-                .service(services::schemas())
-                .service(services::stats())
-                .service(services::tabular())
-                .service(services::transfer())
-                .service(services::versions())
-                .service(services::workspace()),
-        );
+        .service(services::action())
+        .service(services::branches())
+        .service(services::chunk())
+        .service(services::commits())
+        .service(services::commits_db())
+        .service(services::compare())
+        .service(services::data_frames())
+        .service(services::dir())
+        .service(services::file())
+        .service(services::merge())
+        .service(services::meta())
+        .service(services::objects_db())
+        .service(services::revisions())
+        .service(services::schemas())
+        .service(services::search())
+        .service(services::stats())
+        .service(services::tabular())
+        .service(services::transfer())
+        .service(services::usage())
+        .service(services::versions())
+        .service(services::workspace())
 }
@@ -12,9 +12,11 @@ pub mod meta;
 pub mod objects_db;
 pub mod revisions;
 pub mod schemas;
+pub mod search;
 pub mod stats;
 pub mod tabular;
 pub mod transfer;
+pub mod usage;
 pub mod versions;
 pub mod workspaces;
 
@@ -32,8 +34,10 @@ pub use meta::meta;
 pub use objects_db::objects_db;
 pub use revisions::revisions;
 pub use schemas::schemas;
+pub use search::search;
 pub use stats::stats;
 pub use tabular::tabular;
 pub use transfer::transfer;
+pub use usage::usage;
 pub use versions::versions;
 pub use workspaces::workspace;
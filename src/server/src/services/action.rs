@@ -1,24 +1,27 @@
-use actix_web::web;
-use actix_web::Scope;
-
 use crate::controllers;
+use crate::controllers::action::ActionResponse;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn action() -> Scope {
-    web::scope("/action")
+pub fn action() -> ApiScope {
+    ApiScope::new("/action")
         .route(
             "/completed/{action}",
-            web::get().to(controllers::action::completed),
+            Operation::get("Mark an action completed").json::<ActionResponse>(),
+            controllers::action::completed,
         )
         .route(
             "/started/{action}",
-            web::get().to(controllers::action::started),
+            Operation::get("Mark an action started").json::<ActionResponse>(),
+            controllers::action::started,
         )
         .route(
             "/completed/{action}",
-            web::post().to(controllers::action::completed),
+            Operation::post("Mark an action completed").json::<ActionResponse>(),
+            controllers::action::completed,
         )
         .route(
             "/started/{action}",
-            web::post().to(controllers::action::started),
+            Operation::post("Mark an action started").json::<ActionResponse>(),
+            controllers::action::started,
         )
 }
@@ -1,49 +1,75 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::branch::{
+    BranchLockResponse, BranchNewFromExisting, BranchRemoteMerge, BranchResponse, BranchUpdate,
+    ListBranchesResponse,
+};
+use liboxen::view::{CommitResponse, PaginatedEntryVersionsResponse};
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn branches() -> Scope {
-    web::scope("/branches")
-        .route("", web::get().to(controllers::branches::index))
+pub fn branches() -> ApiScope {
+    ApiScope::new("/branches")
         .route(
             "",
-            web::post().to(controllers::branches::create_from_or_get),
+            Operation::get("List branches").json::<ListBranchesResponse>(),
+            controllers::branches::index,
+        )
+        .route(
+            "",
+            Operation::post("Create a branch, or get it if it exists")
+                .body::<BranchNewFromExisting>()
+                .json::<BranchResponse>(),
+            controllers::branches::create_from_or_get,
         )
         .route(
             "/{branch_name:.*}/lock",
-            web::post().to(controllers::branches::lock),
+            Operation::post("Lock a branch").json::<BranchLockResponse>(),
+            controllers::branches::lock,
         )
         .route(
             "/{branch_name:.*}/versions/{path:.*}",
-            web::get().to(controllers::branches::list_entry_versions),
+            Operation::get("List the versions of a file on a branch")
+                .json::<PaginatedEntryVersionsResponse>(),
+            controllers::branches::list_entry_versions,
         )
         .route(
             "/{branch_name}/latest_synced_commit",
-            web::get().to(controllers::branches::latest_synced_commit),
+            Operation::get("Latest commit on a branch with all its data synced")
+                .json::<CommitResponse>(),
+            controllers::branches::latest_synced_commit,
         )
         .route(
             "/{branch_name:.*}/lock",
-            web::get().to(controllers::branches::is_locked),
+            Operation::get("Check if a branch is locked").json::<BranchLockResponse>(),
+            controllers::branches::is_locked,
         )
         .route(
             "/{branch_name:.*}/unlock",
-            web::post().to(controllers::branches::unlock),
+            Operation::post("Unlock a branch").json::<BranchLockResponse>(),
+            controllers::branches::unlock,
         )
         .route(
             "/{branch_name:.*}/merge",
-            web::put().to(controllers::branches::maybe_create_merge),
+            Operation::put("Merge a pushed commit into a branch")
+                .body::<BranchRemoteMerge>()
+                .json::<CommitResponse>(),
+            controllers::branches::maybe_create_merge,
         )
         .route(
             "/{branch_name:.*}",
-            web::get().to(controllers::branches::show),
+            Operation::get("Get a branch").json::<BranchResponse>(),
+            controllers::branches::show,
         )
         .route(
             "/{branch_name:.*}",
-            web::delete().to(controllers::branches::delete),
+            Operation::delete("Delete a branch").json::<BranchResponse>(),
+            controllers::branches::delete,
         )
         .route(
             "/{branch_name:.*}",
-            web::put().to(controllers::branches::update),
+            Operation::put("Point a branch at a commit")
+                .body::<BranchUpdate>()
+                .json::<BranchResponse>(),
+            controllers::branches::update,
         )
 }
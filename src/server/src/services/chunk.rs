@@ -1,11 +1,10 @@
-use actix_web::web;
-use actix_web::Scope;
-
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn chunk() -> Scope {
-    web::scope("/chunk").route(
+pub fn chunk() -> ApiScope {
+    ApiScope::new("/chunk").route(
         "/{resource:.*}",
-        web::get().to(controllers::entries::download_chunk),
+        Operation::get("Download a chunk of a file").binary(),
+        controllers::entries::download_chunk,
     )
 }
@@ -1,62 +1,112 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::model::commit::CommitWithBranchName;
+use liboxen::model::Commit;
+use liboxen::view::commit::{CommitSyncStatusResponse, CommitTreeValidationResponse};
+use liboxen::view::{CommitResponse, IsValidStatusMessage, ListCommitResponse, PaginatedCommits};
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn commits() -> Scope {
-    web::scope("/commits")
-        .route("", web::get().to(controllers::commits::index))
-        .route("", web::post().to(controllers::commits::create))
-        .route("/bulk", web::post().to(controllers::commits::create_bulk))
-        .route("/root", web::get().to(controllers::commits::root_commit))
+pub fn commits() -> ApiScope {
+    ApiScope::new("/commits")
+        .route(
+            "",
+            Operation::get("List the commits on the default branch").json::<ListCommitResponse>(),
+            controllers::commits::index,
+        )
+        .route(
+            "",
+            Operation::post("Create a commit on a branch")
+                .body::<CommitWithBranchName>()
+                .json::<CommitResponse>(),
+            controllers::commits::create,
+        )
+        .route(
+            "/bulk",
+            Operation::post("Create many commits")
+                .body::<Vec<CommitWithBranchName>>()
+                .json::<ListCommitResponse>(),
+            controllers::commits::create_bulk,
+        )
+        .route(
+            "/root",
+            Operation::get("Get the first commit").json::<CommitResponse>(),
+            controllers::commits::root_commit,
+        )
         .route(
             "/complete",
-            web::post().to(controllers::commits::complete_bulk),
+            Operation::post("Mark pushed commits complete").body::<Vec<Commit>>(),
+            controllers::commits::complete_bulk,
         )
         .route(
             "/{commit_id}/db_status",
-            web::get().to(controllers::commits::commits_db_status),
+            Operation::get("Commits that are missing their commit db").json::<ListCommitResponse>(),
+            controllers::commits::commits_db_status,
         )
         .route(
             "/{commit_id}/entries_status",
-            web::get().to(controllers::commits::entries_status),
+            Operation::get("Commits that are missing their entries").json::<ListCommitResponse>(),
+            controllers::commits::entries_status,
+        )
+        .route(
+            "/all",
+            Operation::get("List all commits, a page at a time").json::<PaginatedCommits>(),
+            controllers::commits::list_all,
         )
-        .route("/all", web::get().to(controllers::commits::list_all))
         .route(
             "/{commit_id}/latest_synced",
-            web::get().to(controllers::commits::latest_synced),
+            Operation::get("Latest commit before this one with all its data synced")
+                .json::<CommitSyncStatusResponse>(),
+            controllers::commits::latest_synced,
+        )
+        .route(
+            "/{commit_id}",
+            Operation::get("Get a commit").json::<CommitResponse>(),
+            controllers::commits::show,
         )
-        .route("/{commit_id}", web::get().to(controllers::commits::show))
         .route(
             "/{commit_id}/data",
-            web::post().to(controllers::commits::upload),
+            Operation::post("Upload the data of a commit as a tarball")
+                .binary_body()
+                .json::<CommitResponse>(),
+            controllers::commits::upload,
         )
         .route(
             "/{commit_id}/can_push",
-            web::get().to(controllers::commits::can_push),
+            Operation::get("Check whether a push fits in the quota")
+                .json::<CommitTreeValidationResponse>(),
+            controllers::commits::can_push,
         )
         .route(
             "/{commit_id}/complete",
-            web::post().to(controllers::commits::complete),
+            Operation::post("Mark a pushed commit complete"),
+            controllers::commits::complete,
         )
         .route(
             "/{commit_id}/upload_chunk",
-            web::post().to(controllers::commits::upload_chunk),
+            Operation::post("Upload a chunk of a commit tarball")
+                .binary_body()
+                .json::<CommitResponse>(),
+            controllers::commits::upload_chunk,
         )
         .route(
             "/history/{resource:.*}",
-            web::get().to(controllers::commits::commit_history),
+            Operation::get("List the commits that touched a path").json::<PaginatedCommits>(),
+            controllers::commits::commit_history,
         )
         .route(
             "/{commit_or_branch:.*}/parents",
-            web::get().to(controllers::commits::parents),
+            Operation::get("List the parents of a commit").json::<ListCommitResponse>(),
+            controllers::commits::parents,
         )
         .route(
             "/{commit_or_branch:.*}/is_synced",
-            web::get().to(controllers::commits::is_synced),
+            Operation::get("Check whether all the data of a commit is synced")
+                .json::<IsValidStatusMessage>(),
+            controllers::commits::is_synced,
         )
         .route(
             "/{commit_or_branch:.*}/commit_db",
-            web::get().to(controllers::commits::download_commit_entries_db),
+            Operation::get("Download the entries db of a commit as a tarball").binary(),
+            controllers::commits::download_commit_entries_db,
         )
 }
@@ -1,8 +1,10 @@
-use actix_web::web;
-use actix_web::Scope;
-
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn commits_db() -> Scope {
-    web::scope("/commits_db").route("", web::get().to(controllers::commits::download_commits_db))
+pub fn commits_db() -> ApiScope {
+    ApiScope::new("/commits_db").route(
+        "",
+        Operation::get("Download the commits db as a tarball").binary(),
+        controllers::commits::download_commits_db,
+    )
 }
@@ -1,48 +1,70 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::compare::{
+    CompareCommitsResponse, CompareEntryResponse, CompareTabularResponse, TabularCompareBody,
+};
+use liboxen::view::diff::DirTreeDiffResponse;
+use liboxen::view::{CompareEntriesResponse, JsonDataFrameViewResponse};
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn compare() -> Scope {
-    web::scope("/compare")
+pub fn compare() -> ApiScope {
+    ApiScope::new("/compare")
         .route(
             "/commits/{base_head:.*}",
-            web::get().to(controllers::diff::commits),
+            Operation::get("List the commits between base..head").json::<CompareCommitsResponse>(),
+            controllers::diff::commits,
         )
         .route(
             "/dir_tree/{base_head:.*}",
-            web::get().to(controllers::diff::dir_tree),
+            Operation::get("Directories changed between base..head").json::<DirTreeDiffResponse>(),
+            controllers::diff::dir_tree,
         )
         .route(
             "/entries/{base_head:.*}/dir/{dir:.*}",
-            web::get().to(controllers::diff::dir_entries),
+            Operation::get("Entries changed in a directory between base..head")
+                .json::<CompareEntriesResponse>(),
+            controllers::diff::dir_entries,
         )
         .route(
             "/entries/{base_head:.*}",
-            web::get().to(controllers::diff::entries),
+            Operation::get("Entries changed between base..head").json::<CompareEntriesResponse>(),
+            controllers::diff::entries,
         )
         .route(
             "/file/{base_head:.*}",
-            web::get().to(controllers::diff::file),
+            Operation::get("Diff a file between base..head").json::<CompareEntryResponse>(),
+            controllers::diff::file,
         )
         .route(
             "/data_frames/{compare_id}/{path}/{base_head:.*}",
-            web::get().to(controllers::diff::get_derived_df),
+            Operation::get("Get the rows of a data frame comparison")
+                .data_frame::<JsonDataFrameViewResponse>(),
+            controllers::diff::get_derived_df,
         )
         .route(
             "/data_frames/{compare_id}",
-            web::post().to(controllers::diff::get_df_diff),
+            Operation::post("Get a data frame comparison")
+                .body::<TabularCompareBody>()
+                .data_frame::<CompareTabularResponse>(),
+            controllers::diff::get_df_diff,
         )
         .route(
             "/data_frames/{compare_id}",
-            web::put().to(controllers::diff::update_df_diff),
+            Operation::put("Update a data frame comparison")
+                .body::<TabularCompareBody>()
+                .json::<CompareTabularResponse>(),
+            controllers::diff::update_df_diff,
         )
         .route(
             "/data_frames",
-            web::post().to(controllers::diff::create_df_diff),
+            Operation::post("Compare two data frames")
+                .body::<TabularCompareBody>()
+                .json::<CompareTabularResponse>(),
+            controllers::diff::create_df_diff,
         )
         .route(
             "/data_frames/{compare_id}",
-            web::delete().to(controllers::diff::delete_df_diff),
+            Operation::delete("Delete a data frame comparison"),
+            controllers::diff::delete_df_diff,
         )
 }
@@ -1,16 +1,19 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::JsonDataFrameViewResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn data_frames() -> Scope {
-    web::scope("/data_frames")
+pub fn data_frames() -> ApiScope {
+    ApiScope::new("/data_frames")
         .route(
             "/index/{resource:.*}",
-            web::post().to(controllers::data_frames::index),
+            Operation::post("Index a data frame for querying"),
+            controllers::data_frames::index,
         )
         .route(
             "/{resource:.*}",
-            web::get().to(controllers::data_frames::get),
+            Operation::get("Get the rows of a data frame")
+                .data_frame::<JsonDataFrameViewResponse>(),
+            controllers::data_frames::get,
         )
 }
@@ -1,8 +1,12 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::PaginatedDirEntriesResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn dir() -> Scope {
-    web::scope("/dir").route("/{resource:.*}", web::get().to(controllers::dir::get))
+pub fn dir() -> ApiScope {
+    ApiScope::new("/dir").route(
+        "/{resource:.*}",
+        Operation::get("List the entries of a directory").json::<PaginatedDirEntriesResponse>(),
+        controllers::dir::get,
+    )
 }
@@ -1,8 +1,10 @@
-use actix_web::web;
-use actix_web::Scope;
-
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn file() -> Scope {
-    web::scope("/file").route("/{resource:.*}", web::get().to(controllers::file::get))
+pub fn file() -> ApiScope {
+    ApiScope::new("/file").route(
+        "/{resource:.*}",
+        Operation::get("Download a file").binary(),
+        controllers::file::get,
+    )
 }
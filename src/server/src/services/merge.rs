@@ -1,13 +1,19 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::merge::{MergeSuccessResponse, MergeableResponse};
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn merge() -> Scope {
-    web::scope("/merge")
-        .route("/{base_head:.*}", web::get().to(controllers::merger::show))
+pub fn merge() -> ApiScope {
+    ApiScope::new("/merge")
         .route(
             "/{base_head:.*}",
-            web::post().to(controllers::merger::merge),
+            Operation::get("Check whether head merges cleanly into base")
+                .json::<MergeableResponse>(),
+            controllers::merger::show,
+        )
+        .route(
+            "/{base_head:.*}",
+            Operation::post("Merge head into base").json::<MergeSuccessResponse>(),
+            controllers::merger::merge,
         )
 }
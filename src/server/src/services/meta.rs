@@ -1,13 +1,19 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::{JsonDataFrameViewResponse, MetadataEntryResponse};
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn meta() -> Scope {
-    web::scope("/meta")
+pub fn meta() -> ApiScope {
+    ApiScope::new("/meta")
         .route(
             "/agg/dir/{resource:.*}",
-            web::get().to(controllers::metadata::agg_dir),
+            Operation::get("Aggregate the metadata of a directory")
+                .json::<JsonDataFrameViewResponse>(),
+            controllers::metadata::agg_dir,
+        )
+        .route(
+            "/{resource:.*}",
+            Operation::get("Get the metadata of a file").json::<MetadataEntryResponse>(),
+            controllers::metadata::file,
         )
-        .route("/{resource:.*}", web::get().to(controllers::metadata::file))
 }
@@ -1,8 +1,10 @@
-use actix_web::web;
-use actix_web::Scope;
-
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn objects_db() -> Scope {
-    web::scope("/objects_db").route("", web::get().to(controllers::commits::download_objects_db))
+pub fn objects_db() -> ApiScope {
+    ApiScope::new("/objects_db").route(
+        "",
+        Operation::get("Download the objects db as a tarball").binary(),
+        controllers::commits::download_objects_db,
+    )
 }
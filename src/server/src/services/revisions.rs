@@ -1,8 +1,13 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::ParseResourceResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn revisions() -> Scope {
-    web::scope("/revisions").route("/{resource:.*}", web::get().to(controllers::revisions::get))
+pub fn revisions() -> ApiScope {
+    ApiScope::new("/revisions").route(
+        "/{resource:.*}",
+        Operation::get("Split a path into its revision and file path")
+            .json::<ParseResourceResponse>(),
+        controllers::revisions::get,
+    )
 }
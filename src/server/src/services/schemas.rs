@@ -1,16 +1,19 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::schema::SchemaResponse;
+use liboxen::view::ListSchemaResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn schemas() -> Scope {
-    web::scope("/schemas")
+pub fn schemas() -> ApiScope {
+    ApiScope::new("/schemas")
         .route(
             "/hash/{hash}",
-            web::get().to(controllers::schemas::get_by_hash),
+            Operation::get("Get a schema by its hash").json::<SchemaResponse>(),
+            controllers::schemas::get_by_hash,
         )
         .route(
             "/{resource:.*}",
-            web::get().to(controllers::schemas::list_or_get),
+            Operation::get("List the schemas under a path").json::<ListSchemaResponse>(),
+            controllers::schemas::list_or_get,
         )
 }
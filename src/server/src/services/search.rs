@@ -0,0 +1,22 @@
+use liboxen::view::search::{
+    NearestNeighborsRequest, NearestNeighborsResponse, SearchResultsResponse,
+};
+
+use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
+
+pub fn search() -> ApiScope {
+    ApiScope::new("/search")
+        .route(
+            "/nearest/{resource:.*}",
+            Operation::post("Find the rows with the nearest embeddings")
+                .body::<NearestNeighborsRequest>()
+                .json::<NearestNeighborsResponse>(),
+            controllers::search::nearest,
+        )
+        .route(
+            "/{resource:.*}",
+            Operation::get("Search the files of a commit").json::<SearchResultsResponse>(),
+            controllers::search::search,
+        )
+}
@@ -1,8 +1,12 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::repository::RepositoryStatsResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn stats() -> Scope {
-    web::scope("/stats").route("", web::get().to(controllers::repositories::stats))
+pub fn stats() -> ApiScope {
+    ApiScope::new("/stats").route(
+        "",
+        Operation::get("Size and data types of a repository").json::<RepositoryStatsResponse>(),
+        controllers::repositories::stats,
+    )
 }
@@ -1,11 +1,13 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::entry::PaginatedMetadataEntriesResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn tabular() -> Scope {
-    web::scope("/tabular").route(
+pub fn tabular() -> ApiScope {
+    ApiScope::new("/tabular").route(
         "/{commit_or_branch:.*}",
-        web::get().to(controllers::entries::list_tabular),
+        Operation::get("List the tabular files of a commit")
+            .json::<PaginatedMetadataEntriesResponse>(),
+        controllers::entries::list_tabular,
     )
 }
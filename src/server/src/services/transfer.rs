@@ -1,11 +1,14 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::{NamespaceView, RepositoryResponse};
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn transfer() -> Scope {
-    web::scope("/transfer").route(
+pub fn transfer() -> ApiScope {
+    ApiScope::new("/transfer").route(
         "",
-        web::patch().to(controllers::repositories::transfer_namespace),
+        Operation::patch("Move a repository to another namespace")
+            .body::<NamespaceView>()
+            .json::<RepositoryResponse>(),
+        controllers::repositories::transfer_namespace,
     )
 }
@@ -0,0 +1,12 @@
+use liboxen::view::StorageUsageResponse;
+
+use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
+
+pub fn usage() -> ApiScope {
+    ApiScope::new("/usage").route(
+        "",
+        Operation::get("Disk usage of a repository").json::<StorageUsageResponse>(),
+        controllers::repositories::usage,
+    )
+}
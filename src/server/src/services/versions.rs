@@ -1,11 +1,12 @@
-use actix_web::web;
-use actix_web::Scope;
-
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn versions() -> Scope {
-    web::scope("/versions").route(
+pub fn versions() -> ApiScope {
+    ApiScope::new("/versions").route(
         "",
-        web::get().to(controllers::entries::download_data_from_version_paths),
+        Operation::get("Download version files as a tarball, the body lists their paths")
+            .binary_body()
+            .binary(),
+        controllers::entries::download_data_from_version_paths,
     )
 }
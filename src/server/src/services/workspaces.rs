@@ -1,40 +1,84 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::model::NewCommitBody;
+use liboxen::view::workspaces::{
+    ListWorkspaceResponseView, NewWorkspace, RenameWorkspace, WorkspaceRefreshResponse,
+};
+use liboxen::view::{
+    CommitResponse, FilePathsResponse, RemoteStagedStatusResponse, WorkspaceResponseView,
+};
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
 pub mod data_frames;
 
-pub fn workspace() -> Scope {
-    web::scope("/workspaces")
-        .route("", web::put().to(controllers::workspaces::get_or_create))
-        .route("", web::get().to(controllers::workspaces::list))
+pub fn workspace() -> ApiScope {
+    ApiScope::new("/workspaces")
+        .route(
+            "",
+            Operation::put("Create a workspace, or get it if it exists")
+                .body::<NewWorkspace>()
+                .json::<WorkspaceResponseView>(),
+            controllers::workspaces::get_or_create,
+        )
+        .route(
+            "",
+            Operation::get("List workspaces").json::<ListWorkspaceResponseView>(),
+            controllers::workspaces::list,
+        )
         .service(
-            web::scope("/{workspace_id}")
-                .route("", web::delete().to(controllers::workspaces::delete))
+            ApiScope::new("/{workspace_id}")
+                .route(
+                    "",
+                    Operation::delete("Delete a workspace").json::<WorkspaceResponseView>(),
+                    controllers::workspaces::delete,
+                )
+                .route(
+                    "",
+                    Operation::put("Rename a workspace")
+                        .body::<RenameWorkspace>()
+                        .json::<WorkspaceResponseView>(),
+                    controllers::workspaces::rename,
+                )
                 .route(
                     "/changes/{path:.*}",
-                    web::get().to(controllers::workspaces::changes::list),
+                    Operation::get("List the changes staged in a workspace")
+                        .json::<RemoteStagedStatusResponse>(),
+                    controllers::workspaces::changes::list,
                 )
                 .route(
                     "/changes/{path:.*}",
-                    web::delete().to(controllers::workspaces::files::delete),
+                    Operation::delete("Unstage a file"),
+                    controllers::workspaces::files::delete,
                 )
                 .route(
                     "/files/{path:.*}",
-                    web::get().to(controllers::workspaces::files::get),
+                    Operation::get("Download a file from a workspace").binary(),
+                    controllers::workspaces::files::get,
                 )
                 .route(
                     "/files/{path:.*}",
-                    web::post().to(controllers::workspaces::files::add),
+                    Operation::post("Add files to a workspace")
+                        .multipart_body()
+                        .json::<FilePathsResponse>(),
+                    controllers::workspaces::files::add,
                 )
                 .route(
                     "/files/{path:.*}",
-                    web::delete().to(controllers::workspaces::files::delete),
+                    Operation::delete("Remove a file from a workspace"),
+                    controllers::workspaces::files::delete,
                 )
                 .route(
                     "/commit/{branch:.*}",
-                    web::post().to(controllers::workspaces::commit),
+                    Operation::post("Commit a workspace to a branch")
+                        .body::<NewCommitBody>()
+                        .json::<CommitResponse>(),
+                    controllers::workspaces::commit,
+                )
+                .route(
+                    "/refresh/{branch:.*}",
+                    Operation::post("Rebase a workspace on the head of a branch")
+                        .json::<WorkspaceRefreshResponse>(),
+                    controllers::workspaces::refresh,
                 )
                 .service(data_frames::data_frames()),
         )
@@ -1,32 +1,54 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::data_frames::{DataFrameChangesResponse, DataFramePayload};
+use liboxen::view::entry::PaginatedMetadataEntriesResponse;
+use liboxen::view::json_data_frame_view::WorkspaceJsonDataFrameViewResponse;
+use liboxen::view::JsonDataFrameViewResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
 pub mod columns;
 pub mod rows;
 
-pub fn data_frames() -> Scope {
-    web::scope("/data_frames")
+pub fn data_frames() -> ApiScope {
+    ApiScope::new("/data_frames")
         .route(
             "/branch/{branch:.*}",
-            web::get().to(controllers::workspaces::data_frames::get_by_branch),
+            Operation::get("List the data frames indexed in a workspace")
+                .json::<PaginatedMetadataEntriesResponse>(),
+            controllers::workspaces::data_frames::get_by_branch,
         )
         .route(
             "/resource/{path:.*}",
-            web::get().to(controllers::workspaces::data_frames::get_by_resource),
+            Operation::get("Get the rows of a data frame in a workspace")
+                .data_frame::<WorkspaceJsonDataFrameViewResponse>(),
+            controllers::workspaces::data_frames::get_by_resource,
+        )
+        .route(
+            "/export/{path:.*}",
+            Operation::get("Download a data frame with the changes in a workspace").binary(),
+            controllers::workspaces::data_frames::export,
+        )
+        .route(
+            "/changes/{path:.*}",
+            Operation::get("List the row and column changes to a data frame")
+                .json::<DataFrameChangesResponse>(),
+            controllers::workspaces::data_frames::changes,
         )
         .route(
             "/diff/{path:.*}",
-            web::get().to(controllers::workspaces::data_frames::diff),
+            Operation::get("Diff a data frame against the workspace commit")
+                .json::<JsonDataFrameViewResponse>(),
+            controllers::workspaces::data_frames::diff,
         )
         .route(
             "/resource/{path:.*}",
-            web::put().to(controllers::workspaces::data_frames::put),
+            Operation::put("Index or unindex a data frame for editing").body::<DataFramePayload>(),
+            controllers::workspaces::data_frames::put,
         )
         .route(
             "/resource/{path:.*}",
-            web::delete().to(controllers::workspaces::data_frames::delete),
+            Operation::delete("Drop the changes to a data frame"),
+            controllers::workspaces::data_frames::delete,
         )
         .service(rows::rows())
         .service(columns::columns())
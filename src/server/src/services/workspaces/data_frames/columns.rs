@@ -1,25 +1,34 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::data_frames::columns::{ColumnToUpdate, NewColumn};
+use liboxen::view::json_data_frame_view::JsonDataFrameColumnResponse;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn columns() -> Scope {
-    web::scope("/columns")
+pub fn columns() -> ApiScope {
+    ApiScope::new("/columns")
         .route(
             "/resource/{path:.*}",
-            web::post().to(controllers::workspaces::data_frames::columns::create),
+            Operation::post("Add a column")
+                .body::<NewColumn>()
+                .json::<JsonDataFrameColumnResponse>(),
+            controllers::workspaces::data_frames::columns::create,
         )
         .route(
             "{column_name:.*}/resource/{path:.*}",
-            web::delete().to(controllers::workspaces::data_frames::columns::delete),
+            Operation::delete("Delete a column").json::<JsonDataFrameColumnResponse>(),
+            controllers::workspaces::data_frames::columns::delete,
         )
         .route(
             "{column_name:.*}/resource/{path:.*}",
-            web::put().to(controllers::workspaces::data_frames::columns::update),
+            Operation::put("Rename or retype a column")
+                .body::<ColumnToUpdate>()
+                .json::<JsonDataFrameColumnResponse>(),
+            controllers::workspaces::data_frames::columns::update,
         )
         .route(
             "/{column_name:.*}/restore/{path:.*}",
-            web::post().to(controllers::workspaces::data_frames::columns::restore),
+            Operation::post("Undo the changes to a column").json::<JsonDataFrameColumnResponse>(),
+            controllers::workspaces::data_frames::columns::restore,
         )
     // .route(
     //     "/{row_id}/resource/{path:.*}",
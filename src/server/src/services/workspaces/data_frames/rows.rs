@@ -1,28 +1,53 @@
-use actix_web::web;
-use actix_web::Scope;
+use liboxen::view::data_frames::DataFrameBulkRowsResponse;
+use liboxen::view::json_data_frame_view::JsonDataFrameRowResponse;
+use serde_json::Value;
 
 use crate::controllers;
+use crate::openapi::{ApiScope, Operation};
 
-pub fn rows() -> Scope {
-    web::scope("/rows")
+pub fn rows() -> ApiScope {
+    ApiScope::new("/rows")
         .route(
             "/{row_id}/restore/{path:.*}",
-            web::post().to(controllers::workspaces::data_frames::rows::restore),
+            Operation::post("Undo the changes to a row").json::<JsonDataFrameRowResponse>(),
+            controllers::workspaces::data_frames::rows::restore,
         )
         .route(
             "/resource/{path:.*}",
-            web::post().to(controllers::workspaces::data_frames::rows::create),
+            Operation::post("Add a row")
+                .body::<Value>()
+                .json::<JsonDataFrameRowResponse>(),
+            controllers::workspaces::data_frames::rows::create,
+        )
+        .route(
+            "/upsert/resource/{path:.*}",
+            Operation::post("Upsert the rows of an uploaded file, matched on the key columns")
+                .binary_body()
+                .json::<DataFrameBulkRowsResponse>(),
+            controllers::workspaces::data_frames::rows::upsert,
+        )
+        .route(
+            "/delete/resource/{path:.*}",
+            Operation::post("Delete the rows matching the key columns of an uploaded file")
+                .binary_body()
+                .json::<DataFrameBulkRowsResponse>(),
+            controllers::workspaces::data_frames::rows::delete_many,
         )
         .route(
             "/{row_id}/resource/{path:.*}",
-            web::put().to(controllers::workspaces::data_frames::rows::update),
+            Operation::put("Update a row")
+                .body::<Value>()
+                .json::<JsonDataFrameRowResponse>(),
+            controllers::workspaces::data_frames::rows::update,
         )
         .route(
             "/{row_id}/resource/{path:.*}",
-            web::delete().to(controllers::workspaces::data_frames::rows::delete),
+            Operation::delete("Delete a row").json::<JsonDataFrameRowResponse>(),
+            controllers::workspaces::data_frames::rows::delete,
         )
         .route(
             "/{row_id}/resource/{path:.*}",
-            web::get().to(controllers::workspaces::data_frames::rows::get),
+            Operation::get("Get a row").json::<JsonDataFrameRowResponse>(),
+            controllers::workspaces::data_frames::rows::get,
         )
 }
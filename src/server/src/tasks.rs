@@ -1,18 +1,120 @@
+pub mod cleanup_workspaces;
+pub mod gc;
 pub mod post_push_complete;
+pub mod reindex;
+pub mod validate;
+
+use std::path::Path;
+
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::OxenHttpError;
+use crate::helpers::get_repo;
+use utoipa::ToSchema;
+
+/// Attempts a task gets before it is moved to the dead letter list
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
 
 pub trait Runnable {
-    fn run(&self);
+    /// Name the task is registered and listed under
+    fn name(&self) -> &'static str;
+
+    fn run(&self) -> Result<(), OxenError>;
+
+    fn max_attempts(&self) -> u32 {
+        DEFAULT_MAX_ATTEMPTS
+    }
+
+    /// Called once the task has failed for the last time
+    fn on_dead(&self, _error: &str) {}
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub enum Task {
     PostPushComplete(post_push_complete::PostPushComplete),
+    CleanupWorkspaces(cleanup_workspaces::CleanupWorkspaces),
+    GarbageCollect(gc::GarbageCollect),
+    Reindex(reindex::Reindex),
+    Validate(validate::Validate),
 }
 
-impl Runnable for Task {
-    fn run(&self) {
+impl Task {
+    fn runnable(&self) -> &dyn Runnable {
         match self {
-            Task::PostPushComplete(task) => task.run(),
+            Task::PostPushComplete(task) => task,
+            Task::CleanupWorkspaces(task) => task,
+            Task::GarbageCollect(task) => task,
+            Task::Reindex(task) => task,
+            Task::Validate(task) => task,
         }
     }
 }
+
+impl Runnable for Task {
+    fn name(&self) -> &'static str {
+        self.runnable().name()
+    }
+
+    fn run(&self) -> Result<(), OxenError> {
+        self.runnable().run()
+    }
+
+    fn max_attempts(&self) -> u32 {
+        self.runnable().max_attempts()
+    }
+
+    fn on_dead(&self, error: &str) {
+        self.runnable().on_dead(error)
+    }
+}
+
+/// Body of a request to queue a task by name
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct NewTask {
+    pub name: String,
+    pub namespace: Option<String>,
+    pub repo_name: Option<String>,
+    pub commit_id: Option<String>,
+}
+
+/// Builds a task from its registered name, so it can be queued through the api
+type TaskBuilder = fn(&Path, &NewTask) -> Result<Task, OxenHttpError>;
+
+/// The tasks that can be queued by name. Add an entry here to expose a new kind of background job.
+pub const REGISTRY: [(&str, TaskBuilder); 5] = [
+    (post_push_complete::NAME, post_push_complete::from_params),
+    (cleanup_workspaces::NAME, cleanup_workspaces::from_params),
+    (gc::NAME, gc::from_params),
+    (reindex::NAME, reindex::from_params),
+    (validate::NAME, validate::from_params),
+];
+
+/// Look up the task registered under `params.name` and build it
+pub fn from_params(sync_dir: &Path, params: &NewTask) -> Result<Task, OxenHttpError> {
+    let Some((_, builder)) = REGISTRY.iter().find(|(name, _)| *name == params.name) else {
+        let names: Vec<&str> = REGISTRY.iter().map(|(name, _)| *name).collect();
+        return Err(OxenHttpError::BadRequest(
+            format!(
+                "Unknown task '{}', expected one of: {}",
+                params.name,
+                names.join(", ")
+            )
+            .into(),
+        ));
+    };
+    builder(sync_dir, params)
+}
+
+pub fn repo_from_params(
+    sync_dir: &Path,
+    params: &NewTask,
+) -> Result<LocalRepository, OxenHttpError> {
+    match (&params.namespace, &params.repo_name) {
+        (Some(namespace), Some(repo_name)) => get_repo(sync_dir, namespace, repo_name),
+        _ => Err(OxenHttpError::BadRequest(
+            format!("namespace and repo_name are required for {}", params.name).into(),
+        )),
+    }
+}
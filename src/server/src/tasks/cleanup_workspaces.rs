@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+
+use liboxen::api;
+use liboxen::core::index;
+use liboxen::error::OxenError;
+use serde::{Deserialize, Serialize};
+
+use super::{NewTask, Runnable, Task};
+use crate::errors::OxenHttpError;
+use utoipa::ToSchema;
+
+pub const NAME: &str = "cleanup_workspaces";
+
+/// Removes the expired workspaces of every repository in the sync dir
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CleanupWorkspaces {
+    #[schema(value_type = String)]
+    pub sync_dir: PathBuf,
+}
+
+pub fn from_params(sync_dir: &Path, _params: &NewTask) -> Result<Task, OxenHttpError> {
+    Ok(Task::CleanupWorkspaces(CleanupWorkspaces {
+        sync_dir: sync_dir.to_path_buf(),
+    }))
+}
+
+impl Runnable for CleanupWorkspaces {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    // It is scheduled every hour anyways
+    fn max_attempts(&self) -> u32 {
+        1
+    }
+
+    fn run(&self) -> Result<(), OxenError> {
+        log::debug!("Cleaning up expired workspaces in {:?}", self.sync_dir);
+        let namespaces = api::local::repositories::list_namespaces(&self.sync_dir)?;
+
+        for namespace in namespaces {
+            let namespace_path = self.sync_dir.join(namespace);
+            for repo in api::local::repositories::list_repos_in_namespace(&namespace_path) {
+                match index::workspaces::cleanup_expired(&repo) {
+                    Ok(removed) => {
+                        if !removed.is_empty() {
+                            log::info!(
+                                "Removed {} expired workspaces from {:?}",
+                                removed.len(),
+                                repo.path
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Could not clean up workspaces in {:?}: {:?}", repo.path, e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
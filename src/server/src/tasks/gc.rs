@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use liboxen::constants::{CACHE_DIR, COMPARES_DIR};
+use liboxen::core::index;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::util;
+use serde::{Deserialize, Serialize};
+
+use super::{repo_from_params, NewTask, Runnable, Task};
+use crate::errors::OxenHttpError;
+use utoipa::ToSchema;
+
+pub const NAME: &str = "gc";
+
+/// Frees up space in a repository: expired workspaces and cached comparisons,
+/// which are rebuilt on demand
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct GarbageCollect {
+    pub repo: LocalRepository,
+}
+
+pub fn from_params(sync_dir: &Path, params: &NewTask) -> Result<Task, OxenHttpError> {
+    let repo = repo_from_params(sync_dir, params)?;
+    Ok(Task::GarbageCollect(GarbageCollect { repo }))
+}
+
+impl Runnable for GarbageCollect {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn run(&self) -> Result<(), OxenError> {
+        log::debug!("Collecting garbage in {:?}", self.repo.path);
+        let removed = index::workspaces::cleanup_expired(&self.repo)?;
+        log::debug!("gc removed {} expired workspaces", removed.len());
+
+        let compares_dir = util::fs::oxen_hidden_dir(&self.repo.path)
+            .join(CACHE_DIR)
+            .join(COMPARES_DIR);
+        if compares_dir.exists() {
+            log::debug!("gc removing cached compares {:?}", compares_dir);
+            util::fs::remove_dir_all(&compares_dir)?;
+        }
+        Ok(())
+    }
+}
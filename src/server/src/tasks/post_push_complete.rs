@@ -1,43 +1,78 @@
+use std::path::Path;
+
+use liboxen::api;
+use liboxen::core::cache::cacher_status::CacherStatus;
+use liboxen::error::OxenError;
 use liboxen::{
     core::cache::commit_cacher,
     model::{Commit, LocalRepository},
 };
 use serde::{Deserialize, Serialize};
 
-use super::Runnable;
+use super::{repo_from_params, NewTask, Runnable, Task};
+use crate::errors::OxenHttpError;
+use utoipa::ToSchema;
+
+pub const NAME: &str = "post_push_complete";
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct PostPushComplete {
     pub commit: Commit,
     pub repo: LocalRepository,
 }
 
+pub fn from_params(sync_dir: &Path, params: &NewTask) -> Result<Task, OxenHttpError> {
+    let repo = repo_from_params(sync_dir, params)?;
+    let commit_id = params
+        .commit_id
+        .as_ref()
+        .ok_or(OxenHttpError::BadRequest("commit_id is required".into()))?;
+    let commit = api::local::commits::get_by_id(&repo, commit_id)?
+        .ok_or(OxenError::revision_not_found(commit_id.to_owned().into()))?;
+    Ok(Task::PostPushComplete(PostPushComplete { commit, repo }))
+}
+
 impl Runnable for PostPushComplete {
-    fn run(&self) {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn run(&self) -> Result<(), OxenError> {
         log::debug!(
-            "Running cachers for commit {:?} on repo {:?} from redis queue",
+            "Running cachers for commit {:?} on repo {:?} from queue",
             self.commit.id,
             &self.repo.path
         );
-        // sleep to debug
-        println!("Here is the commit id: {}", self.commit.id);
         let force = false;
         match commit_cacher::run_all(&self.repo, &self.commit, force) {
             Ok(_) => {
                 log::debug!(
-                    "Cachers ran successfully for commit {:?} on repo {:?} from redis queue",
+                    "Cachers ran successfully for commit {:?} on repo {:?} from queue",
                     self.commit.id,
                     &self.repo.path
                 );
+                Ok(())
             }
             Err(e) => {
                 log::error!(
-                    "Cachers failed to run for commit {:?} on repo {:?} from redis queue",
+                    "Cachers failed to run for commit {:?} on repo {:?} from queue",
                     self.commit.id,
                     &self.repo.path
                 );
                 log::error!("Error: {:?}", e);
+                Err(e)
             }
         }
     }
+
+    fn on_dead(&self, error: &str) {
+        match commit_cacher::set_all_cachers_status(
+            &self.repo,
+            &self.commit,
+            CacherStatus::failed(error),
+        ) {
+            Ok(_) => log::debug!("Set all cachers to failed status"),
+            Err(e) => log::error!("Error setting all cachers to failed status: {:?}", e),
+        }
+    }
 }
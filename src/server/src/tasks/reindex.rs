@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use liboxen::api;
+use liboxen::core::cache::commit_cacher;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use serde::{Deserialize, Serialize};
+
+use super::{repo_from_params, NewTask, Runnable, Task};
+use crate::errors::OxenHttpError;
+use utoipa::ToSchema;
+
+pub const NAME: &str = "reindex";
+
+/// Recomputes the cached data for one commit, or every commit of the repository if none is given
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Reindex {
+    pub repo: LocalRepository,
+    pub commit_id: Option<String>,
+}
+
+pub fn from_params(sync_dir: &Path, params: &NewTask) -> Result<Task, OxenHttpError> {
+    let repo = repo_from_params(sync_dir, params)?;
+    Ok(Task::Reindex(Reindex {
+        repo,
+        commit_id: params.commit_id.clone(),
+    }))
+}
+
+impl Runnable for Reindex {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn run(&self) -> Result<(), OxenError> {
+        let commits = match &self.commit_id {
+            Some(commit_id) => vec![api::local::commits::get_by_id(&self.repo, commit_id)?
+                .ok_or(OxenError::revision_not_found(commit_id.to_owned().into()))?],
+            None => api::local::commits::list_all(&self.repo)?,
+        };
+
+        let force = true;
+        for commit in commits {
+            log::debug!("Reindexing commit {} in {:?}", commit.id, self.repo.path);
+            commit_cacher::run_all(&self.repo, &commit, force)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use liboxen::api;
+use liboxen::core::index::commit_validator;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use serde::{Deserialize, Serialize};
+
+use super::{repo_from_params, NewTask, Runnable, Task};
+use crate::errors::OxenHttpError;
+use utoipa::ToSchema;
+
+pub const NAME: &str = "validate";
+
+/// Checks the merkle tree of every commit in the repository against its content
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Validate {
+    pub repo: LocalRepository,
+}
+
+pub fn from_params(sync_dir: &Path, params: &NewTask) -> Result<Task, OxenHttpError> {
+    let repo = repo_from_params(sync_dir, params)?;
+    Ok(Task::Validate(Validate { repo }))
+}
+
+impl Runnable for Validate {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    // Running it again won't change the answer
+    fn max_attempts(&self) -> u32 {
+        1
+    }
+
+    fn run(&self) -> Result<(), OxenError> {
+        let mut invalid = vec![];
+        for commit in api::local::commits::list_all(&self.repo)? {
+            if !commit_validator::validate_tree_hash(&self.repo, &commit)? {
+                log::error!("Commit {} in {:?} is not valid", commit.id, self.repo.path);
+                invalid.push(commit.id);
+            }
+        }
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(OxenError::basic_str(format!(
+                "{} commits failed validation: {}",
+                invalid.len(),
+                invalid.join(", ")
+            )))
+        }
+    }
+}
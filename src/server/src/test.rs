@@ -1,12 +1,14 @@
 use crate::app_data::OxenAppData;
+use crate::auth::access_keys::AccessKeyManager;
 use crate::helpers;
 use crate::queues::{InMemoryTaskQueue, RedisTaskQueue, TaskQueue};
 
 use liboxen::command;
 use liboxen::error::OxenError;
-use liboxen::model::LocalRepository;
+use liboxen::model::{LocalRepository, User};
 use liboxen::util;
 
+use actix_web::http::header::AUTHORIZATION;
 use env_logger::Env;
 use serde::Serialize;
 use std::borrow::Cow;
@@ -82,6 +84,43 @@ pub fn request(sync_dir: &Path, queue: TaskQueue, uri: &str) -> actix_web::HttpR
         .to_http_request()
 }
 
+fn admin_token(sync_dir: &Path) -> Result<String, OxenError> {
+    let keygen = AccessKeyManager::new(sync_dir)?;
+    let (_user, token) = keygen.create_admin(&User {
+        name: String::from("Admin"),
+        email: String::from("admin@oxen.ai"),
+    })?;
+    Ok(token)
+}
+
+/// A request with the bearer token of a new admin user, for the admin endpoints
+pub fn admin_request(
+    sync_dir: &Path,
+    queue: TaskQueue,
+    uri: &str,
+) -> Result<actix_web::HttpRequest, OxenError> {
+    let token = admin_token(sync_dir)?;
+    Ok(actix_web::test::TestRequest::with_uri(uri)
+        .app_data(OxenAppData::new(sync_dir.to_path_buf(), queue))
+        .insert_header((AUTHORIZATION, format!("Bearer {token}")))
+        .to_http_request())
+}
+
+pub fn admin_request_with_param(
+    sync_dir: &Path,
+    queue: TaskQueue,
+    uri: &str,
+    key: impl Into<Cow<'static, str>>,
+    val: impl Into<Cow<'static, str>>,
+) -> Result<actix_web::HttpRequest, OxenError> {
+    let token = admin_token(sync_dir)?;
+    Ok(actix_web::test::TestRequest::with_uri(uri)
+        .app_data(OxenAppData::new(sync_dir.to_path_buf(), queue))
+        .insert_header((AUTHORIZATION, format!("Bearer {token}")))
+        .param(key, val)
+        .to_http_request())
+}
+
 pub fn namespace_request(
     sync_dir: &Path,
     queue: TaskQueue,